@@ -0,0 +1,138 @@
+//! Roles and identities of API callers, deciding which fields they may read and which
+//! services they may modify
+
+use async_graphql::{Context, Enum};
+use aureacore::registry::{Actor, ServiceRegistry};
+use axum::http::{header, HeaderMap};
+
+/// Role of the caller of a GraphQL request
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// May read the catalog, except for sensitive metadata
+    Public,
+    /// May read everything
+    Internal,
+}
+
+/// Decides the role and identity of callers from the bearer token they send
+///
+/// Without any token configured, every caller is internal. Identified callers are
+/// internal, and their changes are checked against the catalog's access control lists.
+#[derive(Debug, Clone, Default)]
+pub struct AccessPolicy {
+    internal_tokens: Vec<String>,
+    /// Principals by the token identifying them
+    principal_tokens: Vec<(String, String)>,
+}
+
+impl AccessPolicy {
+    /// Creates a policy treating every caller as internal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treats callers sending `token` as internal, and callers without it as public
+    pub fn with_internal_token(mut self, token: impl Into<String>) -> Self {
+        self.internal_tokens.push(token.into());
+        self
+    }
+
+    /// Identifies callers sending `token` as `principal`
+    pub fn with_principal_token(
+        mut self,
+        principal: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        self.principal_tokens.push((token.into(), principal.into()));
+        self
+    }
+
+    /// Decides the role of a caller from the `Authorization` header of its request
+    pub fn role_for(&self, headers: &HeaderMap) -> Role {
+        if self.internal_tokens.is_empty() && self.principal_tokens.is_empty() {
+            return Role::Internal;
+        }
+        match bearer_token(headers) {
+            Some(token) if self.internal_tokens.iter().any(|t| constant_time_eq(t, token)) => {
+                Role::Internal
+            }
+            _ if self.principal_for(headers).is_some() => Role::Internal,
+            _ => Role::Public,
+        }
+    }
+
+    /// Decides whom a caller makes changes on behalf of, anonymous unless identified
+    pub fn actor_for(&self, headers: &HeaderMap) -> Actor {
+        Actor::from_principal(self.principal_for(headers).map(str::to_string))
+    }
+
+    /// Gets the principal identified by the bearer token of a request
+    fn principal_for(&self, headers: &HeaderMap) -> Option<&str> {
+        let token = bearer_token(headers)?;
+        self.principal_tokens
+            .iter()
+            .find(|(t, _)| constant_time_eq(t, token))
+            .map(|(_, principal)| principal.as_str())
+    }
+}
+
+/// Gets the bearer token sent in the `Authorization` header
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Gets the role of the caller of a request
+///
+/// Requests executed without a role, e.g. by embedding applications, are internal.
+pub(crate) fn caller_role(ctx: &Context<'_>) -> Role {
+    ctx.data_opt::<Role>().copied().unwrap_or(Role::Internal)
+}
+
+/// Runs a change to the catalog on behalf of the caller of a request
+///
+/// Requests executed without an actor, e.g. by embedding applications, act as the
+/// registry itself.
+pub(crate) fn as_caller<T>(
+    ctx: &Context<'_>,
+    registry: &mut ServiceRegistry,
+    f: impl FnOnce(&mut ServiceRegistry) -> aureacore::Result<T>,
+) -> aureacore::Result<T> {
+    let actor = ctx.data_opt::<Actor>().cloned().unwrap_or_default();
+    registry.acting_as(actor, f)
+}
+
+/// Compares tokens in time independent of where they differ
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_role_for() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(AccessPolicy::new().role_for(&headers), Role::Internal);
+
+        let policy = AccessPolicy::new().with_internal_token("s3cret");
+        assert_eq!(policy.role_for(&headers), Role::Public);
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer wrong"));
+        assert_eq!(policy.role_for(&headers), Role::Public);
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("s3cret"));
+        assert_eq!(policy.role_for(&headers), Role::Public);
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer s3cret"));
+        assert_eq!(policy.role_for(&headers), Role::Internal);
+        assert_eq!(policy.actor_for(&headers), Actor::Anonymous);
+
+        let policy = policy.with_principal_token("alice", "t0ken");
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer t0ken"));
+        assert_eq!(policy.role_for(&headers), Role::Internal);
+        assert_eq!(policy.actor_for(&headers), Actor::Principal("alice".to_string()));
+    }
+}
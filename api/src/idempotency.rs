@@ -0,0 +1,201 @@
+//! Idempotency keys letting clients retry mutations without applying them twice
+//!
+//! Mutations sent with an `Idempotency-Key` header are executed once; retries with the
+//! same key and request are answered with the stored response and an
+//! `Idempotent-Replayed: true` header. Reusing a key for a different request, or by a
+//! different caller, fails with `422 Unprocessable Entity`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_graphql::parser::types::OperationType;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use tokio::sync::OnceCell;
+
+/// Header carrying the idempotency key of a request
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header marking responses replayed from an earlier request
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// How long outcomes are kept by default
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Number of keys kept by default
+pub const DEFAULT_IDEMPOTENCY_CAPACITY: usize = 10_000;
+
+/// Outcome of a request, once it completed
+type Slot = Arc<OnceCell<serde_json::Value>>;
+
+struct Entry {
+    fingerprint: u64,
+    stored_at: Instant,
+    slot: Slot,
+}
+
+/// Recent outcomes of mutations, by idempotency key
+///
+/// Clones share the stored outcomes.
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL, DEFAULT_IDEMPOTENCY_CAPACITY)
+    }
+}
+
+impl IdempotencyStore {
+    /// Creates a store keeping up to `capacity` outcomes for `ttl` each
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self { entries: Arc::default(), ttl, capacity: capacity.max(1) }
+    }
+
+    /// Runs a GraphQL request, replaying the outcome of an earlier mutation sent with the
+    /// same idempotency key
+    ///
+    /// Queries, and requests without a key, are always executed. Concurrent retries wait
+    /// for the first one to complete. Requests failing with a status code are not
+    /// stored, so they can be retried.
+    pub async fn respond<F, Fut>(
+        &self,
+        headers: &HeaderMap,
+        request: async_graphql::Request,
+        execute: F,
+    ) -> Result<Response, StatusCode>
+    where
+        F: FnOnce(async_graphql::Request) -> Fut,
+        Fut: Future<Output = Result<async_graphql::Response, StatusCode>>,
+    {
+        let key = headers.get(IDEMPOTENCY_KEY_HEADER).and_then(|v| v.to_str().ok());
+        let Some(key) = key.filter(|_| is_mutation(&request)) else {
+            return Ok(Json(execute(request).await?).into_response());
+        };
+
+        let slot = self.slot(key, fingerprint(headers, &request))?;
+        let mut executed = false;
+        let outcome = slot
+            .get_or_try_init(|| async {
+                executed = true;
+                let response = execute(request).await?;
+                serde_json::to_value(&response).map_err(|e| {
+                    tracing::error!("Failed to encode response: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })
+            })
+            .await?;
+        let mut response = Json(outcome.clone()).into_response();
+        if !executed {
+            response.headers_mut().insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        }
+        Ok(response)
+    }
+
+    /// Gets the slot of a key, checking it is reused for the same request
+    fn slot(&self, key: &str, fingerprint: u64) -> Result<Slot, StatusCode> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        entries.retain(|_, entry| now.duration_since(entry.stored_at) < self.ttl);
+        if let Some(entry) = entries.get(key) {
+            if entry.fingerprint != fingerprint {
+                return Err(StatusCode::UNPROCESSABLE_ENTITY);
+            }
+            return Ok(entry.slot.clone());
+        }
+
+        if entries.len() >= self.capacity {
+            let oldest = entries.iter().min_by_key(|(_, entry)| entry.stored_at);
+            if let Some(oldest) = oldest.map(|(key, _)| key.clone()) {
+                entries.remove(&oldest);
+            }
+        }
+        let slot = Slot::default();
+        entries.insert(key.to_string(), Entry { fingerprint, stored_at: now, slot: slot.clone() });
+        Ok(slot)
+    }
+}
+
+/// Checks whether a request may mutate the catalog
+///
+/// Persisted queries are sent without their text and may be mutations.
+pub(crate) fn is_mutation(request: &async_graphql::Request) -> bool {
+    request.query.is_empty()
+        || async_graphql::parser::parse_query(&request.query).is_ok_and(|document| {
+            document.operations.iter().any(|(_, op)| op.node.ty == OperationType::Mutation)
+        })
+}
+
+/// Identifies a request and its caller
+fn fingerprint(headers: &HeaderMap, request: &async_graphql::Request) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    headers.get(header::AUTHORIZATION).map(|v| v.as_bytes()).hash(&mut hasher);
+    request.query.hash(&mut hasher);
+    request.operation_name.hash(&mut hasher);
+    serde_json::to_string(&request.variables).unwrap_or_default().hash(&mut hasher);
+    serde_json::to_string(&request.extensions).unwrap_or_default().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replays_mutations() {
+        let store = IdempotencyStore::new(Duration::from_secs(60), 2);
+        let calls = AtomicUsize::new(0);
+        let run = |headers: HeaderMap, query: &str| {
+            let request = async_graphql::Request::new(query);
+            let calls = &calls;
+            let store = store.clone();
+            async move {
+                store
+                    .respond(&headers, request, |_| async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst);
+                        Ok(async_graphql::Response::new(async_graphql::Value::from(n as i32)))
+                    })
+                    .await
+            }
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("ci-1"));
+
+        let first = run(headers.clone(), "mutation { a }").await.unwrap();
+        assert!(first.headers().get(REPLAYED_HEADER).is_none());
+        let retry = run(headers.clone(), "mutation { a }").await.unwrap();
+        assert_eq!(retry.headers()[REPLAYED_HEADER], "true");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Queries and requests without a key are always executed
+        run(headers.clone(), "{ a }").await.unwrap();
+        run(HeaderMap::new(), "mutation { a }").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let err = run(headers.clone(), "mutation { b }").await.unwrap_err();
+        assert_eq!(err, StatusCode::UNPROCESSABLE_ENTITY);
+        let mut other_caller = headers.clone();
+        other_caller.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer other"));
+        assert!(run(other_caller, "mutation { a }").await.is_err());
+
+        // The oldest key is evicted beyond capacity
+        for key in ["ci-2", "ci-3"] {
+            let mut headers = HeaderMap::new();
+            headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static(key));
+            run(headers, "mutation { a }").await.unwrap();
+        }
+        let evicted = run(headers, "mutation { a }").await.unwrap();
+        assert!(evicted.headers().get(REPLAYED_HEADER).is_none());
+    }
+}
@@ -1,7 +1,927 @@
 //! API layer for AureaCore service catalog
 
-use async_graphql::{EmptySubscription, Object, Schema};
-use aureacore_core::Service;
+pub mod access;
+pub mod idempotency;
+pub mod limits;
+pub mod scalars;
+pub mod security;
+pub mod server;
+pub mod tenant;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub use access::{AccessPolicy, Role};
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Json, Object, Schema, SimpleObject,
+    Subscription as GraphQLSubscription,
+};
+use aureacore::error::AureaCoreError;
+use aureacore::registry::{
+    ActiveRules, BatchSyncReport, CancellationToken, CommitInfo,
+    EndpointConsumer as RegistryEndpointConsumer, FlagUsage, FreezeWindow as RegistryFreezeWindow,
+    GraphExport, GraphLevel as RegistryGraphLevel, GraphQuery, ImpactInfo, OncallContact,
+    ProgressEvent, ProgressReporter, QueryMatch as RegistryQueryMatch, Service, ServiceRegistry,
+    ServiceState as RegistryServiceState, ServiceStatus as RegistryServiceStatus, SharedRegistry,
+    StatusTransition, ValidationSummary as RegistryValidationSummary,
+};
+use aureacore::reports::{
+    cost_rollup, CostDimension as RegistryCostDimension, CostGroup as RegistryCostGroup,
+};
+use aureacore::scheduler::{JobStatus, JobStatuses};
+use aureacore::schema::{
+    redact_metadata, FlagPlatform as RegistryFlagPlatform, ResourceKind as RegistryResourceKind,
+    ResourceSchema,
+};
+use aureacore::Interaction as RegistryInteraction;
+use chrono::{DateTime, Utc};
+pub use idempotency::IdempotencyStore;
+pub use limits::QueryLimits;
+pub use scalars::{SemVer, VersionConstraint};
+pub use security::{ServerSecurity, TlsConfig};
+
+use crate::access::{as_caller, caller_role};
+
+/// GraphQL schema type for the service catalog
+pub type ApiSchema = Schema<Query, Mutation, Subscription>;
+
+/// Converts a registry error into a GraphQL error carrying its error code
+fn api_error(err: AureaCoreError) -> async_graphql::Error {
+    let code = err.code();
+    async_graphql::Error::new(err.to_string()).extend_with(|_, ext| ext.set("code", code))
+}
+
+/// Creates the error of a request the caller may not make
+fn forbidden(message: String) -> async_graphql::Error {
+    async_graphql::Error::new(message).extend_with(|_, ext| ext.set("code", "FORBIDDEN"))
+}
+
+/// Fields of a definition holding cost data, which only internal callers may read
+const COST_FIELDS: [&str; 2] = ["cost_center", "monthly_cost_estimate"];
+
+/// Removes cost data from a raw definition, returning whether it held any
+fn redact_cost(definition: &mut serde_json::Value) -> bool {
+    let Some(object) = definition.as_object_mut() else {
+        return false;
+    };
+    let mut redacted = false;
+    for field in COST_FIELDS {
+        redacted |= object.remove(field).is_some();
+    }
+    redacted
+}
+
+/// A service registered in the catalog
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct ServiceInfo {
+    /// Stable machine ID of the service, kept when the service is renamed
+    pub id: Option<String>,
+    /// Name of the service
+    pub name: String,
+    /// Version declared in the service definition, null if it isn't a semantic version
+    pub version: Option<SemVer>,
+    /// Version as declared in the service definition, e.g. a calendar version
+    pub declared_version: Option<String>,
+    /// Description from the service definition
+    pub description: Option<String>,
+    /// Namespace of the service
+    pub namespace: Option<String>,
+    /// System the service belongs to
+    pub system: Option<String>,
+    /// Domain the service belongs to
+    pub domain: Option<String>,
+    /// Cost center the service's spending is booked to
+    #[graphql(skip)]
+    pub cost_center: Option<String>,
+    /// Estimated monthly cost of running the service
+    #[graphql(skip)]
+    pub monthly_cost_estimate: Option<f64>,
+    /// Current state of the service
+    #[graphql(deprecation = "Use `status.state`")]
+    pub state: String,
+    /// Error message of the current status, if any
+    pub error_message: Option<String>,
+    /// Warnings of the current status
+    pub warnings: Vec<String>,
+    /// Whether the contract tests passed when last verified, if they ran
+    pub contracts_passed: Option<bool>,
+    /// Last time the status was computed
+    pub last_checked: DateTime<Utc>,
+    /// Current status of the service
+    pub status: ServiceStatus,
+    /// Services this service depends on
+    pub dependencies: Vec<DependencyInfo>,
+}
+
+/// A dependency declared in a service definition
+#[derive(SimpleObject)]
+pub struct DependencyInfo {
+    /// Name of the service depended upon
+    pub service: String,
+    /// Versions of the service accepted, null if unconstrained or not a semver requirement
+    pub version_constraint: Option<VersionConstraint>,
+    /// Whether the dependency is required
+    pub required: bool,
+}
+
+/// State of a service
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ServiceState {
+    /// Service is active and running
+    Active,
+    /// Service is inactive or stopped
+    Inactive,
+    /// Service configuration is being validated
+    Validating,
+    /// Service is in an error state
+    Error,
+    /// Service is archived: out of the active catalog, kept for reference
+    Archived,
+}
+
+impl From<&RegistryServiceState> for ServiceState {
+    fn from(state: &RegistryServiceState) -> Self {
+        match state {
+            RegistryServiceState::Active => ServiceState::Active,
+            RegistryServiceState::Inactive => ServiceState::Inactive,
+            RegistryServiceState::Validating => ServiceState::Validating,
+            RegistryServiceState::Error => ServiceState::Error,
+            RegistryServiceState::Archived => ServiceState::Archived,
+        }
+    }
+}
+
+/// Health of a service as of its last validation
+#[derive(SimpleObject)]
+pub struct ServiceStatus {
+    /// Current state of the service
+    pub state: ServiceState,
+    /// Error message, if the service is in error
+    pub error_message: Option<String>,
+    /// Warnings found by the last validation
+    pub warnings: Vec<String>,
+    /// Whether the contract tests passed when last verified, if they ran
+    pub contracts_passed: Option<bool>,
+    /// Last time the status was computed
+    pub last_checked: DateTime<Utc>,
+    /// Last time the service was loaded or registered
+    pub last_updated: DateTime<Utc>,
+}
+
+impl ServiceStatus {
+    fn new(status: &RegistryServiceStatus, last_updated: DateTime<Utc>) -> Self {
+        Self {
+            state: (&status.state).into(),
+            error_message: status.error_message.clone(),
+            warnings: status.warnings.clone(),
+            contracts_passed: status.contracts.as_ref().map(|contracts| contracts.passed),
+            last_checked: status.last_checked,
+            last_updated,
+        }
+    }
+}
+
+impl ServiceInfo {
+    /// Describes a service of a registry, naming the services now providing aliased
+    /// dependencies
+    fn new(service: &Service, registry: &ServiceRegistry) -> Self {
+        let definition = service.definition();
+        Self {
+            id: service.id().map(|id| id.to_string()),
+            name: service.name.clone(),
+            version: definition.as_ref().and_then(|d| d.version.parse().ok()),
+            declared_version: definition.as_ref().map(|d| d.version.clone()),
+            description: definition.as_ref().and_then(|d| d.description.clone()),
+            namespace: service.config.namespace.clone(),
+            system: definition.as_ref().and_then(|d| d.system.clone()),
+            domain: definition.as_ref().and_then(|d| d.domain.clone()),
+            cost_center: definition.as_ref().and_then(|d| d.cost_center.clone()),
+            monthly_cost_estimate: definition.as_ref().and_then(|d| d.monthly_cost_estimate),
+            state: service.status.state.to_string(),
+            error_message: service.status.error_message.clone(),
+            warnings: service.status.warnings.clone(),
+            contracts_passed: service.status.contracts.as_ref().map(|contracts| contracts.passed),
+            last_checked: service.status.last_checked,
+            status: ServiceStatus::new(&service.status, service.last_updated),
+            dependencies: registry
+                .canonical_dependencies(service)
+                .into_iter()
+                .map(|dependency| DependencyInfo {
+                    version_constraint: dependency
+                        .version_constraint
+                        .as_deref()
+                        .and_then(|c| c.parse().ok()),
+                    service: dependency.service,
+                    required: dependency.required,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[ComplexObject]
+impl ServiceInfo {
+    /// Cost center the service's spending is booked to, null for public callers
+    #[graphql(name = "costCenter")]
+    async fn resolve_cost_center(&self, ctx: &Context<'_>) -> Option<String> {
+        self.cost_center.clone().filter(|_| caller_role(ctx) == Role::Internal)
+    }
+
+    /// Estimated monthly cost of running the service, null for public callers
+    #[graphql(name = "monthlyCostEstimate")]
+    async fn resolve_monthly_cost_estimate(&self, ctx: &Context<'_>) -> Option<f64> {
+        self.monthly_cost_estimate.filter(|_| caller_role(ctx) == Role::Internal)
+    }
+
+    /// Definition file of the service as currently stored on disk
+    ///
+    /// Public callers may only read it if it holds no sensitive metadata or cost data.
+    async fn raw_config(&self, ctx: &Context<'_>) -> async_graphql::Result<String> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let service = registry.get_service(&self.name).map_err(api_error)?;
+        if caller_role(ctx) == Role::Public {
+            let mut definition = service.schema_data.clone().unwrap_or_default();
+            if !redact_metadata(&mut definition, registry.sensitive_metadata()).is_empty() {
+                return Err(forbidden(format!(
+                    "The definition of '{}' holds sensitive metadata",
+                    self.name
+                )));
+            }
+            if redact_cost(&mut definition) {
+                return Err(forbidden(format!(
+                    "The definition of '{}' holds cost data",
+                    self.name
+                )));
+            }
+        }
+        service.raw_definition().map_err(api_error)
+    }
+
+    /// Parsed definition the validator last saw, if it was loaded
+    ///
+    /// Sensitive metadata and cost data are left out for public callers.
+    async fn rendered_schema(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<Json<serde_json::Value>>> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let service = registry.get_service(&self.name).map_err(api_error)?;
+        let mut definition = service.schema_data.clone();
+        if let Some(definition) = definition.as_mut().filter(|_| caller_role(ctx) == Role::Public) {
+            redact_metadata(definition, registry.sensitive_metadata());
+            redact_cost(definition);
+        }
+        Ok(definition.map(Json))
+    }
+
+    /// Metadata of the service, without sensitive keys for public callers
+    async fn metadata(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<Json<serde_json::Value>>> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let service = registry.get_service(&self.name).map_err(api_error)?;
+        let Some(mut definition) = service.schema_data.clone() else {
+            return Ok(None);
+        };
+        if caller_role(ctx) == Role::Public {
+            redact_metadata(&mut definition, registry.sensitive_metadata());
+        }
+        Ok(definition.get("metadata").cloned().map(Json))
+    }
+
+    /// Last commit that changed the definition, to route validation failures to its author
+    async fn last_modified(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<CommitSummary>> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let commit = registry.last_modified_by(&self.name).map_err(api_error)?;
+        Ok(commit.map(Into::into))
+    }
+}
+
+/// A commit in the catalog repository
+#[derive(SimpleObject)]
+pub struct CommitSummary {
+    /// Full commit id
+    pub id: String,
+    /// First line of the commit message
+    pub summary: String,
+    /// Name of the commit author
+    pub author: String,
+    /// Email address of the commit author
+    pub email: String,
+    /// Commit timestamp
+    pub time: DateTime<Utc>,
+}
+
+impl From<CommitInfo> for CommitSummary {
+    fn from(commit: CommitInfo) -> Self {
+        Self {
+            id: commit.id,
+            summary: commit.summary,
+            author: commit.author,
+            email: commit.email,
+            time: commit.time,
+        }
+    }
+}
+
+/// A recorded change of a service's status
+#[derive(SimpleObject)]
+pub struct StatusTransitionInfo {
+    /// State the service moved into
+    pub state: String,
+    /// When the transition was recorded
+    pub timestamp: DateTime<Utc>,
+    /// What caused the status to be recomputed
+    pub trigger: String,
+    /// Error message of the new status, if any
+    pub error_message: Option<String>,
+}
+
+impl From<StatusTransition> for StatusTransitionInfo {
+    fn from(transition: StatusTransition) -> Self {
+        Self {
+            state: transition.state.to_string(),
+            timestamp: transition.timestamp,
+            trigger: transition.trigger.to_string(),
+            error_message: transition.error_message,
+        }
+    }
+}
+
+/// Granularity of a dependency graph
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GraphLevel {
+    /// One node per service
+    Service,
+    /// One node per system
+    System,
+}
+
+impl From<GraphLevel> for RegistryGraphLevel {
+    fn from(level: GraphLevel) -> Self {
+        match level {
+            GraphLevel::Service => RegistryGraphLevel::Service,
+            GraphLevel::System => RegistryGraphLevel::System,
+        }
+    }
+}
+
+/// What to group services by in a cost rollup
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum CostDimension {
+    /// The owner of the service
+    Team,
+    /// The system the service belongs to
+    System,
+    /// The namespace the service is registered in
+    Namespace,
+}
+
+impl From<CostDimension> for RegistryCostDimension {
+    fn from(dimension: CostDimension) -> Self {
+        match dimension {
+            CostDimension::Team => RegistryCostDimension::Team,
+            CostDimension::System => RegistryCostDimension::System,
+            CostDimension::Namespace => RegistryCostDimension::Namespace,
+        }
+    }
+}
+
+/// Estimated monthly cost of the services sharing a team, system or namespace
+#[derive(SimpleObject)]
+pub struct CostGroup {
+    /// Team, system or namespace, or `unassigned`
+    pub key: String,
+    /// Sum of the monthly cost estimates of the services
+    pub monthly_cost: f64,
+    /// Services in the group
+    pub services: Vec<String>,
+    /// Cost centers the services book to
+    pub cost_centers: Vec<String>,
+    /// Services in the group without a cost estimate
+    pub unestimated: Vec<String>,
+}
+
+impl From<RegistryCostGroup> for CostGroup {
+    fn from(group: RegistryCostGroup) -> Self {
+        Self {
+            key: group.key,
+            monthly_cost: group.monthly_cost,
+            services: group.services,
+            cost_centers: group.cost_centers,
+            unestimated: group.unestimated,
+        }
+    }
+}
+
+/// Kind of interaction along a dependency
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum Interaction {
+    /// Synchronous request/response call
+    SyncCall,
+    /// Asynchronous event or message
+    AsyncEvent,
+    /// Reads data owned by the dependency
+    DataRead,
+    /// Writes data owned by the dependency
+    DataWrite,
+}
+
+impl From<Interaction> for RegistryInteraction {
+    fn from(interaction: Interaction) -> Self {
+        match interaction {
+            Interaction::SyncCall => RegistryInteraction::SyncCall,
+            Interaction::AsyncEvent => RegistryInteraction::AsyncEvent,
+            Interaction::DataRead => RegistryInteraction::DataRead,
+            Interaction::DataWrite => RegistryInteraction::DataWrite,
+        }
+    }
+}
+
+impl From<RegistryInteraction> for Interaction {
+    fn from(interaction: RegistryInteraction) -> Self {
+        match interaction {
+            RegistryInteraction::SyncCall => Interaction::SyncCall,
+            RegistryInteraction::AsyncEvent => Interaction::AsyncEvent,
+            RegistryInteraction::DataRead => Interaction::DataRead,
+            RegistryInteraction::DataWrite => Interaction::DataWrite,
+        }
+    }
+}
+
+/// Kind of resource
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ResourceKind {
+    /// Relational or document database
+    Database,
+    /// Message queue or event stream
+    Queue,
+    /// Cache such as Redis or Memcached
+    Cache,
+    /// Object storage bucket
+    Bucket,
+}
+
+impl From<RegistryResourceKind> for ResourceKind {
+    fn from(kind: RegistryResourceKind) -> Self {
+        match kind {
+            RegistryResourceKind::Database => ResourceKind::Database,
+            RegistryResourceKind::Queue => ResourceKind::Queue,
+            RegistryResourceKind::Cache => ResourceKind::Cache,
+            RegistryResourceKind::Bucket => ResourceKind::Bucket,
+        }
+    }
+}
+
+/// A database, queue or other resource services depend on
+#[derive(SimpleObject)]
+pub struct Resource {
+    /// Name of the resource
+    pub name: String,
+    /// Kind of resource
+    pub kind: ResourceKind,
+    /// Description of the resource
+    pub description: Option<String>,
+    /// Owner of the resource
+    pub owner: Option<String>,
+    /// System the resource belongs to
+    pub system: Option<String>,
+    /// Technology backing the resource
+    pub engine: Option<String>,
+}
+
+impl From<&ResourceSchema> for Resource {
+    fn from(resource: &ResourceSchema) -> Self {
+        Self {
+            name: resource.name.clone(),
+            kind: resource.kind.into(),
+            description: resource.description.clone(),
+            owner: resource.owner.clone(),
+            system: resource.system.clone(),
+            engine: resource.engine.clone(),
+        }
+    }
+}
+
+/// A node of the dependency graph
+#[derive(SimpleObject)]
+pub struct GraphNode {
+    /// Service name, or system name for system-level graphs
+    pub id: String,
+    /// System the node belongs to
+    pub system: Option<String>,
+    /// Domain the node belongs to
+    pub domain: Option<String>,
+    /// Services represented by the node
+    pub services: Vec<String>,
+    /// Kind of resource, if the node is a resource rather than services
+    pub resource: Option<ResourceKind>,
+}
+
+/// A dependency between two graph nodes
+#[derive(SimpleObject)]
+pub struct GraphEdge {
+    /// Dependent node
+    pub from: String,
+    /// Node depended upon
+    pub to: String,
+    /// Whether any underlying service dependency is required
+    pub required: bool,
+    /// Kinds of interaction of the underlying service dependencies
+    pub interactions: Vec<Interaction>,
+}
+
+/// Dependency graph of the catalog
+#[derive(SimpleObject)]
+pub struct DependencyGraph {
+    /// Nodes sorted by id
+    pub nodes: Vec<GraphNode>,
+    /// Edges sorted by source and target
+    pub edges: Vec<GraphEdge>,
+}
+
+impl From<GraphExport> for DependencyGraph {
+    fn from(export: GraphExport) -> Self {
+        Self {
+            nodes: export
+                .nodes
+                .into_iter()
+                .map(|n| GraphNode {
+                    id: n.id,
+                    system: n.system,
+                    domain: n.domain,
+                    services: n.services,
+                    resource: n.resource.map(Into::into),
+                })
+                .collect(),
+            edges: export
+                .edges
+                .into_iter()
+                .map(|e| GraphEdge {
+                    from: e.from,
+                    to: e.to,
+                    required: e.required,
+                    interactions: e.interactions.into_iter().map(Into::into).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A service reached by a graph query
+#[derive(SimpleObject)]
+pub struct QueryMatch {
+    /// Name of the service
+    pub service: String,
+    /// Number of edges between the start service and this one
+    pub depth: usize,
+    /// Services on the way from the start service, both ends included
+    pub path: Vec<String>,
+}
+
+impl From<RegistryQueryMatch> for QueryMatch {
+    fn from(found: RegistryQueryMatch) -> Self {
+        Self { service: found.service, depth: found.depth, path: found.path }
+    }
+}
+
+/// A service affected by a change to another service
+#[derive(SimpleObject)]
+pub struct ImpactedService {
+    /// Name of the affected service
+    pub service: String,
+    /// Whether the affected service requires the changed one
+    pub required: bool,
+    /// Services from the changed one to the affected one, both ends included
+    pub path: Vec<String>,
+}
+
+impl From<ImpactInfo> for ImpactedService {
+    fn from(impact: ImpactInfo) -> Self {
+        Self {
+            service: impact.service_name,
+            required: impact.is_required,
+            path: impact.impact_path,
+        }
+    }
+}
+
+/// A service consuming an endpoint of another service
+#[derive(SimpleObject)]
+pub struct EndpointConsumer {
+    /// Consuming service
+    pub consumer: String,
+    /// Service providing the endpoint
+    pub provider: String,
+    /// Name of the endpoint
+    pub endpoint: String,
+    /// Path or address of the endpoint, if the provider still declares it
+    pub path: Option<String>,
+    /// API version the consumer is pinned to
+    pub version: String,
+    /// Whether the consumer requires the provider
+    pub required: bool,
+}
+
+impl From<RegistryEndpointConsumer> for EndpointConsumer {
+    fn from(consumer: RegistryEndpointConsumer) -> Self {
+        Self {
+            consumer: consumer.consumer,
+            provider: consumer.provider,
+            endpoint: consumer.endpoint,
+            path: consumer.path,
+            version: consumer.version,
+            required: consumer.required,
+        }
+    }
+}
+
+/// Platform hosting a feature flag
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FlagPlatform {
+    /// LaunchDarkly
+    LaunchDarkly,
+    /// Unleash
+    Unleash,
+}
+
+impl From<RegistryFlagPlatform> for FlagPlatform {
+    fn from(platform: RegistryFlagPlatform) -> Self {
+        match platform {
+            RegistryFlagPlatform::LaunchDarkly => FlagPlatform::LaunchDarkly,
+            RegistryFlagPlatform::Unleash => FlagPlatform::Unleash,
+        }
+    }
+}
+
+impl From<FlagPlatform> for RegistryFlagPlatform {
+    fn from(platform: FlagPlatform) -> Self {
+        match platform {
+            FlagPlatform::LaunchDarkly => RegistryFlagPlatform::LaunchDarkly,
+            FlagPlatform::Unleash => RegistryFlagPlatform::Unleash,
+        }
+    }
+}
+
+/// A service gated by a feature flag
+#[derive(SimpleObject)]
+pub struct GatedService {
+    /// Service declaring the flag
+    pub service: String,
+    /// Platform hosting the flag
+    pub provider: FlagPlatform,
+    /// Key of the flag
+    pub key: String,
+    /// Project the flag belongs to
+    pub project: String,
+    /// What the flag gates in the service
+    pub description: Option<String>,
+}
+
+impl From<FlagUsage> for GatedService {
+    fn from(usage: FlagUsage) -> Self {
+        Self {
+            service: usage.service,
+            provider: usage.flag.provider.into(),
+            project: usage.flag.project().to_string(),
+            key: usage.flag.key,
+            description: usage.flag.description,
+        }
+    }
+}
+
+/// A responder currently on call for a service
+#[derive(SimpleObject)]
+pub struct OncallResponder {
+    /// Display name of the responder
+    pub name: String,
+    /// Email address of the responder
+    pub email: Option<String>,
+    /// Escalation level the responder is on call for, 1 being the first responder
+    pub escalation_level: u32,
+}
+
+impl From<OncallContact> for OncallResponder {
+    fn from(contact: OncallContact) -> Self {
+        Self {
+            name: contact.name,
+            email: contact.email,
+            escalation_level: contact.escalation_level,
+        }
+    }
+}
+
+/// A freeze window blocking changes
+#[derive(SimpleObject)]
+pub struct FreezeWindow {
+    /// Name of the window
+    pub name: String,
+    /// Cron-like schedule of frozen minutes, in UTC
+    pub schedule: String,
+    /// Namespaces affected by the freeze, all namespaces if empty
+    pub namespaces: Vec<String>,
+    /// Service tiers affected by the freeze, all tiers if empty
+    pub tiers: Vec<String>,
+    /// Why changes are frozen
+    pub reason: Option<String>,
+}
+
+impl From<&RegistryFreezeWindow> for FreezeWindow {
+    fn from(window: &RegistryFreezeWindow) -> Self {
+        Self {
+            name: window.name.clone(),
+            schedule: window.schedule.to_string(),
+            namespaces: window.namespaces.clone(),
+            tiers: window.tiers.clone(),
+            reason: window.reason.clone(),
+        }
+    }
+}
+
+/// Current freeze status of a service
+#[derive(SimpleObject)]
+pub struct FreezeStatus {
+    /// Whether changes to the service are currently frozen
+    pub frozen: bool,
+    /// Freeze windows currently in effect for the service
+    pub windows: Vec<FreezeWindow>,
+}
+
+/// A service that failed validation
+#[derive(SimpleObject)]
+pub struct ValidationFailure {
+    /// Name of the service
+    pub service: String,
+    /// Validation error message
+    pub message: String,
+}
+
+/// Warnings reported for a service during validation
+#[derive(SimpleObject)]
+pub struct ValidationWarnings {
+    /// Name of the service, or `system` for catalog-wide warnings
+    pub service: String,
+    /// Warning messages
+    pub messages: Vec<String>,
+}
+
+/// Result of a full catalog validation
+#[derive(SimpleObject)]
+pub struct ValidationSummary {
+    /// Services that validated successfully
+    pub successful: Vec<String>,
+    /// Services that failed validation
+    pub failed: Vec<ValidationFailure>,
+    /// Warnings grouped by service
+    pub warnings: Vec<ValidationWarnings>,
+    /// Warnings suppressed by their services, grouped by service
+    pub suppressed: Vec<ValidationWarnings>,
+    /// Whether validation stopped early because it was cancelled or timed out
+    pub timed_out: bool,
+    /// Services left unvalidated because validation stopped early
+    pub skipped: Vec<String>,
+    /// When the validation ran
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&RegistryValidationSummary> for ValidationSummary {
+    fn from(summary: &RegistryValidationSummary) -> Self {
+        let grouped = |by_service: &HashMap<String, Vec<String>>| {
+            let mut grouped: Vec<_> = by_service
+                .iter()
+                .map(|(service, messages)| ValidationWarnings {
+                    service: service.clone(),
+                    messages: messages.clone(),
+                })
+                .collect();
+            grouped.sort_by(|a, b| a.service.cmp(&b.service));
+            grouped
+        };
+
+        Self {
+            successful: summary.successful.clone(),
+            failed: summary
+                .failed
+                .iter()
+                .map(|(service, message)| ValidationFailure {
+                    service: service.clone(),
+                    message: message.clone(),
+                })
+                .collect(),
+            warnings: grouped(&summary.warnings),
+            suppressed: grouped(&summary.suppressed),
+            timed_out: summary.timed_out,
+            skipped: summary.skipped.clone(),
+            timestamp: summary.timestamp,
+        }
+    }
+}
+
+/// A service to upsert in a batch sync
+#[derive(InputObject)]
+pub struct ServiceInput {
+    /// Name of the service
+    pub name: String,
+    /// Catalog config of the service as JSON
+    pub config: String,
+}
+
+/// Outcome of a batch sync
+#[derive(SimpleObject)]
+pub struct SyncResult {
+    /// Services that were not registered before
+    pub added: Vec<String>,
+    /// Registered services whose config was replaced
+    pub updated: Vec<String>,
+    /// Services removed because they were absent from the input
+    pub removed: Vec<String>,
+    /// Validation of the synced services and their dependents
+    pub validation: ValidationSummary,
+}
+
+impl From<BatchSyncReport> for SyncResult {
+    fn from(report: BatchSyncReport) -> Self {
+        Self {
+            validation: (&report.validation).into(),
+            added: report.added,
+            updated: report.updated,
+            removed: report.removed,
+        }
+    }
+}
+
+/// Outcome of revalidating a service and the services its change may impact
+#[derive(SimpleObject)]
+pub struct Revalidation {
+    /// The revalidated service, with its new status
+    pub service: ServiceInfo,
+    /// Services revalidated, sorted by name
+    pub revalidated: Vec<String>,
+    /// Validation of the revalidated services
+    pub validation: ValidationSummary,
+}
+
+/// Version of the rules and policies the registry validates with
+#[derive(SimpleObject)]
+pub struct RuleSetInfo {
+    /// Digest of the rule and policy files
+    pub version: String,
+    /// When the rule set was applied
+    pub loaded_at: DateTime<Utc>,
+}
+
+impl From<&ActiveRules> for RuleSetInfo {
+    fn from(rules: &ActiveRules) -> Self {
+        Self { version: rules.version.clone(), loaded_at: rules.loaded_at }
+    }
+}
+
+/// Last-run status of a periodic job of the server
+#[derive(SimpleObject)]
+pub struct ScheduledJob {
+    /// Name of the job, e.g. `sync`
+    pub name: String,
+    /// Milliseconds between runs, before jitter
+    pub interval_ms: u64,
+    /// Whether a run is in progress
+    pub running: bool,
+    /// When the last run started
+    pub last_started: Option<DateTime<Utc>>,
+    /// When the last run finished
+    pub last_finished: Option<DateTime<Utc>>,
+    /// How many milliseconds the last finished run took
+    pub last_duration_ms: Option<u64>,
+    /// Error of the last finished run, if it failed
+    pub last_error: Option<String>,
+    /// Number of finished runs
+    pub runs: u64,
+    /// Number of finished runs that failed
+    pub failures: u64,
+    /// Number of runs skipped because the previous run was still in progress
+    pub skipped: u64,
+}
+
+impl From<JobStatus> for ScheduledJob {
+    fn from(status: JobStatus) -> Self {
+        Self {
+            name: status.name,
+            interval_ms: status.interval.as_millis() as u64,
+            running: status.running,
+            last_started: status.last_started,
+            last_finished: status.last_finished,
+            last_duration_ms: status.last_duration.map(|d| d.as_millis() as u64),
+            last_error: status.last_error,
+            runs: status.runs,
+            failures: status.failures,
+            skipped: status.skipped,
+        }
+    }
+}
 
 /// GraphQL Query root
 pub struct Query;
@@ -9,15 +929,247 @@ pub struct Query;
 #[Object]
 impl Query {
     /// Get a service by name
-    async fn service(&self, name: String) -> Option<Service> {
-        // This is just a placeholder implementation
-        Some(Service::new(name, "0.1.0"))
+    async fn service(&self, ctx: &Context<'_>, name: String) -> Option<ServiceInfo> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.get_service(&name).ok().map(|service| ServiceInfo::new(service, &registry))
+    }
+
+    /// Get a service by its stable machine ID
+    async fn service_by_id(&self, ctx: &Context<'_>, id: String) -> Option<ServiceInfo> {
+        let id = id.parse().ok()?;
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.get_service_by_id(id).ok().map(|service| ServiceInfo::new(service, &registry))
+    }
+
+    /// List all services, optionally only those whose version satisfies a constraint
+    async fn services(
+        &self,
+        ctx: &Context<'_>,
+        version_matching: Option<VersionConstraint>,
+    ) -> Vec<ServiceInfo> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let mut names = registry.list_services().unwrap_or_default();
+        names.sort();
+        names
+            .iter()
+            .filter_map(|name| registry.get_service(name).ok())
+            .map(|service| ServiceInfo::new(service, &registry))
+            .filter(|service| match (&version_matching, &service.version) {
+                (Some(constraint), Some(version)) => constraint.matches(version),
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .collect()
+    }
+
+    /// Services whose constraint on `service` would reject it at `version`
+    async fn dependents_rejecting(
+        &self,
+        ctx: &Context<'_>,
+        service: String,
+        version: SemVer,
+    ) -> Vec<String> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let mut names = registry.list_services().unwrap_or_default();
+        names.sort();
+        names
+            .into_iter()
+            .filter(|name| {
+                let Ok(dependent) = registry.get_service(name) else {
+                    return false;
+                };
+                let provider = registry.resolve_alias(&service);
+                registry.canonical_dependencies(dependent).iter().any(|dependency| {
+                    dependency.service == provider
+                        && dependency
+                            .version_constraint
+                            .as_deref()
+                            .and_then(|c| c.parse::<VersionConstraint>().ok())
+                            .is_some_and(|constraint| !constraint.matches(&version))
+                })
+            })
+            .collect()
+    }
+
+    /// List archived services
+    async fn archived_services(&self, ctx: &Context<'_>) -> Vec<ServiceInfo> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry
+            .list_archived_services()
+            .iter()
+            .filter_map(|name| registry.get_archived_service(name).ok())
+            .map(|service| ServiceInfo::new(service, &registry))
+            .collect()
+    }
+
+    /// Dependency graph at service or system level, optionally only with edges of the
+    /// given interaction kinds
+    async fn dependency_graph(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "GraphLevel::Service")] level: GraphLevel,
+        #[graphql(default)] interactions: Vec<Interaction>,
+    ) -> DependencyGraph {
+        let interactions: Vec<RegistryInteraction> =
+            interactions.into_iter().map(Into::into).collect();
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.export_graph_of(level.into(), &interactions).into()
+    }
+
+    /// Estimated monthly cost of the catalog by team, system or namespace, most expensive first
+    ///
+    /// Only internal callers may read cost data.
+    async fn cost_rollup(
+        &self,
+        ctx: &Context<'_>,
+        by: CostDimension,
+    ) -> async_graphql::Result<Vec<CostGroup>> {
+        if caller_role(ctx) == Role::Public {
+            return Err(forbidden("Cost data is only available to internal callers".to_string()));
+        }
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let rollup = cost_rollup(&registry, by.into()).map_err(api_error)?;
+        Ok(rollup.groups.into_iter().map(Into::into).collect())
+    }
+
+    /// Run a graph query such as `deps(checkout, depth<=2, required_only)`
+    async fn graph_query(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+    ) -> async_graphql::Result<Vec<QueryMatch>> {
+        let query: GraphQuery = query.parse().map_err(api_error)?;
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let matches = registry.query_graph(&query).map_err(api_error)?;
+        Ok(matches.into_iter().map(Into::into).collect())
+    }
+
+    /// Databases, queues and other resources services depend on
+    async fn resources(&self, ctx: &Context<'_>) -> Vec<Resource> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.list_resources().into_iter().map(Into::into).collect()
+    }
+
+    /// Services affected by a change to, or outage of, the given service or resource
+    async fn impact(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        env: Option<String>,
+    ) -> async_graphql::Result<Vec<ImpactedService>> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let impacts = match env {
+            Some(env) => registry.get_detailed_impact_in_environment(&name, &env),
+            None => registry.get_detailed_impact(&name),
+        }
+        .map_err(api_error)?;
+        Ok(impacts.into_iter().map(Into::into).collect())
+    }
+
+    /// Services consuming an endpoint at the given path, of any provider
+    async fn who_calls(&self, ctx: &Context<'_>, endpoint_path: String) -> Vec<EndpointConsumer> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.who_calls(&endpoint_path).into_iter().map(Into::into).collect()
+    }
+
+    /// Services consuming an endpoint of the given service, by endpoint name or path
+    async fn who_depends_on_endpoint(
+        &self,
+        ctx: &Context<'_>,
+        service: String,
+        endpoint: String,
+    ) -> async_graphql::Result<Vec<EndpointConsumer>> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let consumers = registry.who_depends_on_endpoint(&service, &endpoint).map_err(api_error)?;
+        Ok(consumers.into_iter().map(Into::into).collect())
+    }
+
+    /// Services gated by a feature flag, optionally only on one platform
+    async fn services_gated_by(
+        &self,
+        ctx: &Context<'_>,
+        flag: String,
+        provider: Option<FlagPlatform>,
+    ) -> Vec<GatedService> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let usages = registry.services_gated_by(&flag, provider.map(Into::into));
+        usages.into_iter().map(Into::into).collect()
+    }
+
+    /// Responders currently on call for a service, resolved on its on-call platform
+    ///
+    /// Only internal callers may look up responders.
+    async fn who_is_oncall_for(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Vec<OncallResponder>> {
+        if caller_role(ctx) == Role::Public {
+            return Err(forbidden(
+                "On-call responders are only available to internal callers".to_string(),
+            ));
+        }
+        // The platform is asked without holding on to the registry
+        let (provider, schedule_id) = {
+            let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+            registry.oncall_schedule(&name).map_err(api_error)?
+        };
+        let contacts = provider.current_oncall(&schedule_id).await.map_err(api_error)?;
+        Ok(contacts.into_iter().map(Into::into).collect())
+    }
+
+    /// Freeze windows currently in effect for any service
+    async fn active_freezes(&self, ctx: &Context<'_>) -> Vec<FreezeWindow> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.freeze_calendar().active_at(Utc::now()).into_iter().map(Into::into).collect()
+    }
+
+    /// Whether changes to a service are currently frozen, and by which windows
+    async fn freeze_status(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<FreezeStatus> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let windows: Vec<FreezeWindow> =
+            registry.freeze_status(&name).map_err(api_error)?.into_iter().map(Into::into).collect();
+        Ok(FreezeStatus { frozen: !windows.is_empty(), windows })
+    }
+
+    /// Content hash identifying the catalog, suitable as an entity tag
+    async fn catalog_fingerprint(&self, ctx: &Context<'_>) -> async_graphql::Result<String> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.fingerprint().map_err(api_error)
+    }
+
+    /// Summary of the most recent full validation
+    async fn last_validation(&self, ctx: &Context<'_>) -> Option<ValidationSummary> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.last_validation_summary().map(ValidationSummary::from)
+    }
+
+    /// Status transitions of a service, oldest first
+    async fn validation_history(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Vec<StatusTransitionInfo>> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let history = registry.validation_history(&name).map_err(api_error)?;
+        Ok(history.into_iter().map(Into::into).collect())
     }
 
-    /// List all services
-    async fn services(&self) -> Vec<Service> {
-        // This is just a placeholder implementation
-        vec![Service::new("example-service", "1.0.0").with_description("An example service")]
+    /// Rule set in effect, if rules were loaded from the repository
+    async fn active_rules(&self, ctx: &Context<'_>) -> Option<RuleSetInfo> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.active_rules().map(RuleSetInfo::from)
+    }
+
+    /// Periodic jobs of the server and the outcome of their last runs
+    async fn scheduled_jobs(&self, ctx: &Context<'_>) -> Vec<ScheduledJob> {
+        ctx.data_opt::<JobStatuses>()
+            .map(|jobs| jobs.all().into_iter().map(Into::into).collect())
+            .unwrap_or_default()
     }
 }
 
@@ -26,36 +1178,284 @@ pub struct Mutation;
 
 #[Object]
 impl Mutation {
-    /// Create a new service
-    async fn create_service(
+    /// Register a service from its catalog config, optionally overriding freeze windows
+    async fn register_service(
         &self,
+        ctx: &Context<'_>,
         name: String,
-        version: String,
-        description: Option<String>,
-    ) -> Service {
-        // This is just a placeholder implementation
-        let mut service = Service::new(name, version);
-        if let Some(desc) = description {
-            service = service.with_description(desc);
+        config: String,
+        freeze_override: Option<String>,
+    ) -> async_graphql::Result<ServiceInfo> {
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        as_caller(ctx, &mut registry, |registry| match freeze_override {
+            Some(token) => {
+                registry.overriding_freeze(&token, |r| r.register_service(&name, &config))
+            }
+            None => registry.register_service(&name, &config),
+        })
+        .map_err(api_error)?;
+        Ok(ServiceInfo::new(registry.get_service(&name).map_err(api_error)?, &registry))
+    }
+
+    /// Move a service out of the active catalog into the archive
+    async fn archive_service(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<ServiceInfo> {
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        as_caller(ctx, &mut registry, |r| r.archive_service(&name)).map_err(api_error)?;
+        Ok(ServiceInfo::new(registry.get_archived_service(&name).map_err(api_error)?, &registry))
+    }
+
+    /// Rename a service, keeping its ID
+    ///
+    /// Services still depending on the old name need their dependencies updated.
+    async fn rename_service(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        new_name: String,
+    ) -> async_graphql::Result<ServiceInfo> {
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        as_caller(ctx, &mut registry, |r| r.rename_service(&name, &new_name)).map_err(api_error)?;
+        Ok(ServiceInfo::new(registry.get_service(&new_name).map_err(api_error)?, &registry))
+    }
+
+    /// Restore an archived service into the active catalog
+    async fn unarchive_service(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<ServiceInfo> {
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        as_caller(ctx, &mut registry, |r| r.unarchive_service(&name)).map_err(api_error)?;
+        Ok(ServiceInfo::new(registry.get_service(&name).map_err(api_error)?, &registry))
+    }
+
+    /// Upsert many services at once, optionally removing services absent from the input
+    ///
+    /// The input is applied atomically and validated in a single pass.
+    async fn sync_services(
+        &self,
+        ctx: &Context<'_>,
+        input: Vec<ServiceInput>,
+        #[graphql(default)] prune: bool,
+        freeze_override: Option<String>,
+    ) -> async_graphql::Result<SyncResult> {
+        let services: Vec<(String, String)> =
+            input.into_iter().map(|service| (service.name, service.config)).collect();
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let report = as_caller(ctx, &mut registry, |registry| match freeze_override {
+            Some(token) => {
+                registry.overriding_freeze(&token, |r| r.sync_services(&services, prune))
+            }
+            None => registry.sync_services(&services, prune),
+        })
+        .map_err(api_error)?;
+        Ok(report.into())
+    }
+
+    /// Reload rules and policies from the repository, keeping the current ones on failure
+    ///
+    /// Only internal callers may swap the rules in effect.
+    async fn reload_rules(&self, ctx: &Context<'_>) -> async_graphql::Result<RuleSetInfo> {
+        if caller_role(ctx) == Role::Public {
+            return Err(forbidden("Only internal callers may reload rules".to_string()));
+        }
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        registry.reload_rules().map_err(api_error)?;
+        Ok(registry.active_rules().expect("rules were applied").into())
+    }
+
+    /// Validate the given services, all services if none are given
+    async fn validate_services(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] names: Vec<String>,
+        #[graphql(default)] include_dependents: bool,
+    ) -> async_graphql::Result<ValidationSummary> {
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let summary = if names.is_empty() {
+            registry.validate_all_services()
+        } else {
+            registry.validate_services(&names, include_dependents)
         }
-        service
+        .map_err(api_error)?;
+        Ok((&summary).into())
     }
+
+    /// Revalidate a service after its definition changed, and by default the services
+    /// depending on it
+    ///
+    /// The definitions of the revalidated services are reloaded from disk; the rest of
+    /// the catalog keeps its cached statuses.
+    async fn revalidate(
+        &self,
+        ctx: &Context<'_>,
+        service: String,
+        #[graphql(default = true)] include_dependents: bool,
+    ) -> async_graphql::Result<Revalidation> {
+        let mut registry = ctx.data_unchecked::<SharedRegistry>().lock().await;
+        let summary = registry
+            .validate_services(std::slice::from_ref(&service), include_dependents)
+            .map_err(api_error)?;
+        let mut revalidated: Vec<String> = summary
+            .successful
+            .iter()
+            .chain(summary.failed.iter().map(|(name, _)| name))
+            .chain(&summary.skipped)
+            .cloned()
+            .collect();
+        revalidated.sort();
+        revalidated.dedup();
+        Ok(Revalidation {
+            service: ServiceInfo::new(
+                registry.get_service(&service).map_err(api_error)?,
+                &registry,
+            ),
+            revalidated,
+            validation: (&summary).into(),
+        })
+    }
+}
+
+/// Kind of a progress update
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ProgressKind {
+    /// An operation started
+    Started,
+    /// The running operation advanced
+    Progress,
+    /// An operation ended
+    Finished,
 }
 
-/// Create the GraphQL schema
-pub fn create_schema() -> Schema<Query, Mutation, EmptySubscription> {
-    Schema::build(Query, Mutation, EmptySubscription).finish()
+/// Progress of a long-running catalog operation
+#[derive(SimpleObject, Debug, PartialEq)]
+pub struct ProgressUpdate {
+    /// Kind of update
+    pub kind: ProgressKind,
+    /// Operation that started or finished
+    pub operation: Option<String>,
+    /// Percentage of the running operation done, from 0 to 100
+    pub percent: Option<i32>,
+    /// What the running operation is doing
+    pub message: Option<String>,
+    /// Whether the finished operation succeeded
+    pub success: Option<bool>,
+}
+
+impl From<ProgressEvent> for ProgressUpdate {
+    fn from(event: ProgressEvent) -> Self {
+        let update = |kind| ProgressUpdate {
+            kind,
+            operation: None,
+            percent: None,
+            message: None,
+            success: None,
+        };
+        match event {
+            ProgressEvent::Started { operation } => {
+                ProgressUpdate { operation: Some(operation), ..update(ProgressKind::Started) }
+            }
+            ProgressEvent::Progress { percent, message } => ProgressUpdate {
+                percent: Some(percent.into()),
+                message: Some(message),
+                ..update(ProgressKind::Progress)
+            },
+            ProgressEvent::Finished { operation, success } => ProgressUpdate {
+                operation: Some(operation),
+                success: Some(success),
+                ..update(ProgressKind::Finished)
+            },
+        }
+    }
+}
+
+/// GraphQL Subscription root
+pub struct Subscription;
+
+#[GraphQLSubscription]
+impl Subscription {
+    /// Reload and validate the catalog from disk, streaming progress until both finish
+    async fn reload_catalog(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl async_graphql::futures_util::Stream<Item = ProgressUpdate> {
+        let registry = ctx.data_unchecked::<SharedRegistry>().clone();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        tokio::task::spawn_blocking(move || {
+            let reporter: Arc<dyn ProgressReporter> = Arc::new(sender);
+            let mut registry = registry.blocking_lock();
+            let result = registry.load_services_with_progress(reporter.clone()).and_then(|_| {
+                registry.validate_all_services_with_progress(&CancellationToken::new(), reporter)
+            });
+            if let Err(err) = result {
+                tracing::warn!("Failed to reload the catalog: {}", err);
+            }
+        });
+        async_graphql::futures_util::stream::unfold(receiver, |mut receiver| async move {
+            let event = receiver.recv().await?;
+            Some((ProgressUpdate::from(event), receiver))
+        })
+    }
+}
+
+/// Create the GraphQL schema backed by the given registry
+pub fn create_schema(registry: SharedRegistry) -> ApiSchema {
+    create_schema_with_limits(registry, &QueryLimits::default())
+}
+
+/// Create the GraphQL schema backed by the given registry, enforcing query limits
+pub fn create_schema_with_limits(registry: SharedRegistry, limits: &QueryLimits) -> ApiSchema {
+    limits.apply(Schema::build(Query, Mutation, Subscription)).data(registry).finish()
+}
+
+/// Create the GraphQL schema of a server that also reports its scheduled jobs
+pub fn create_schema_with_jobs(
+    registry: SharedRegistry,
+    jobs: JobStatuses,
+    limits: &QueryLimits,
+) -> ApiSchema {
+    limits.apply(Schema::build(Query, Mutation, Subscription)).data(registry).data(jobs).finish()
 }
 
 #[cfg(test)]
 mod tests {
-    use async_graphql::Value;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use aureacore::registry::{FreezeCalendar, PagerDutyProvider, ServiceRegistry, Topology};
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
 
     use super::*;
 
+    fn test_registry(temp_dir: &TempDir) -> SharedRegistry {
+        Arc::new(Mutex::new(test_service_registry(temp_dir)))
+    }
+
+    fn test_service_registry(temp_dir: &TempDir) -> ServiceRegistry {
+        let schema_path = temp_dir.path().join("test.yaml");
+        std::fs::write(
+            &schema_path,
+            "name: test\nversion: 0.1.0\nservice_type:\n  type: rest\nendpoints:\n  - name: api\n    path: /api\n    method: GET\n",
+        )
+        .unwrap();
+
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, schema_path.display());
+        registry.register_service("test", &config).unwrap();
+        registry
+    }
+
     #[tokio::test]
     async fn test_service_query() {
-        let schema = create_schema();
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
         let query = r#"
             query {
                 service(name: "test") {
@@ -68,4 +1468,655 @@ mod tests {
         let res = schema.execute(query).await;
         assert_eq!(res.data.to_string(), "{service: {name: \"test\", version: \"0.1.0\"}}");
     }
+
+    #[tokio::test]
+    async fn test_raw_config_and_rendered_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+        // Edits on disk show up in the raw config before the service is reloaded
+        std::fs::write(temp_dir.path().join("test.yaml"), "name: test\nversion: 0.2.0\n").unwrap();
+
+        let res = schema.execute(r#"{ service(name: "test") { rawConfig renderedSchema } }"#).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["service"]["rawConfig"], "name: test\nversion: 0.2.0\n");
+        assert_eq!(data["service"]["renderedSchema"]["version"], "0.1.0");
+        assert_eq!(data["service"]["renderedSchema"]["endpoints"][0]["path"], "/api");
+    }
+
+    #[tokio::test]
+    async fn test_sensitive_metadata_redaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_path = temp_dir.path().join("billing.yaml");
+        std::fs::write(
+            &schema_path,
+            "name: billing\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\nmetadata:\n  tier: 1\n  cost: 1200\n  admin_url: http://billing.internal\nsensitive_metadata: [admin_url]\n",
+        )
+        .unwrap();
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap()
+                .with_sensitive_metadata(["cost".to_string()]);
+        let config = format!(r#"{{"config_path": "{}"}}"#, schema_path.display());
+        registry.register_service("billing", &config).unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        let query = r#"{ service(name: "billing") { metadata renderedSchema } }"#;
+        let res = schema.execute(async_graphql::Request::new(query).data(Role::Public)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["service"]["metadata"], serde_json::json!({"tier": 1}));
+        assert_eq!(data["service"]["renderedSchema"]["metadata"], serde_json::json!({"tier": 1}));
+        let raw = r#"{ service(name: "billing") { rawConfig } }"#;
+        let res = schema.execute(async_graphql::Request::new(raw).data(Role::Public)).await;
+        assert_eq!(res.errors[0].message, "The definition of 'billing' holds sensitive metadata");
+
+        let res = schema.execute(async_graphql::Request::new(query).data(Role::Internal)).await;
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["service"]["metadata"]["cost"], 1200);
+        assert_eq!(data["service"]["metadata"]["admin_url"], "http://billing.internal");
+        assert!(schema.execute(raw).await.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cost_rollup_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = test_registry(&temp_dir);
+        let path = temp_dir.path().join("billing.yaml");
+        std::fs::write(
+            &path,
+            "name: billing\nversion: 1.0.0\nowner: payments\ncost_center: CC-1042\nmonthly_cost_estimate: 250.5\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.lock().await.register_service("billing", &config).unwrap();
+        let schema = create_schema(registry);
+
+        let res = schema
+            .execute("{ costRollup(by: TEAM) { key monthlyCost costCenters unestimated } }")
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(
+            data["costRollup"],
+            serde_json::json!([
+                {"key": "payments", "monthlyCost": 250.5, "costCenters": ["CC-1042"], "unestimated": []},
+                {"key": "unassigned", "monthlyCost": 0.0, "costCenters": [], "unestimated": ["test"]}
+            ])
+        );
+        let res = schema.execute(r#"{ service(name: "billing") { costCenter } }"#).await;
+        assert_eq!(res.data.to_string(), "{service: {costCenter: \"CC-1042\"}}");
+
+        // Cost data is withheld from public callers
+        let query =
+            r#"{ service(name: "billing") { costCenter monthlyCostEstimate renderedSchema } }"#;
+        let res = schema.execute(async_graphql::Request::new(query).data(Role::Public)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["service"]["costCenter"], serde_json::Value::Null);
+        assert_eq!(data["service"]["monthlyCostEstimate"], serde_json::Value::Null);
+        assert!(data["service"]["renderedSchema"].get("cost_center").is_none());
+        let raw = r#"{ service(name: "billing") { rawConfig } }"#;
+        let res = schema.execute(async_graphql::Request::new(raw).data(Role::Public)).await;
+        assert_eq!(res.errors[0].message, "The definition of 'billing' holds cost data");
+        let rollup = "{ costRollup(by: TEAM) { key } }";
+        let res = schema.execute(async_graphql::Request::new(rollup).data(Role::Public)).await;
+        assert_eq!(res.errors[0].message, "Cost data is only available to internal callers");
+    }
+
+    #[tokio::test]
+    async fn test_who_is_oncall_for_query() {
+        let router = axum::Router::new().route(
+            "/oncalls",
+            axum::routing::get(|| async {
+                axum::Json(serde_json::json!({"oncalls": [
+                    {"escalation_level": 1, "user": {"name": "Ada", "email": "ada@example.com"}}
+                ]}))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("billing.yaml");
+        std::fs::write(
+            &path,
+            "name: billing\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\noncall:\n  platform: pagerduty\n  schedule_id: PX1ABC2\n",
+        )
+        .unwrap();
+        let mut registry = test_service_registry(&temp_dir)
+            .with_oncall_provider(Box::new(PagerDutyProvider::new("s3cret").with_base_url(&base)));
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.register_service("billing", &config).unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        let query = r#"{ whoIsOncallFor(name: "billing") { name email escalationLevel } }"#;
+        let res = schema.execute(query).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{whoIsOncallFor: [{name: \"Ada\", email: \"ada@example.com\", escalationLevel: 1}]}"
+        );
+        let res = schema.execute(r#"{ whoIsOncallFor(name: "test") { name } }"#).await;
+        assert_eq!(res.errors.len(), 1);
+
+        // Responders are withheld from public callers
+        let res = schema.execute(async_graphql::Request::new(query).data(Role::Public)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "On-call responders are only available to internal callers"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_service_mutations() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+
+        let res =
+            schema.execute(r#"mutation { archiveService(name: "test") { name state } }"#).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.data.to_string(), "{archiveService: {name: \"test\", state: \"Archived\"}}");
+        let res = schema.execute("{ services { name } archivedServices { name } }").await;
+        assert_eq!(res.data.to_string(), "{services: [], archivedServices: [{name: \"test\"}]}");
+
+        let res = schema.execute(r#"mutation { unarchiveService(name: "test") { state } }"#).await;
+        assert_eq!(res.data.to_string(), "{unarchiveService: {state: \"Active\"}}");
+
+        let res = schema.execute(r#"mutation { unarchiveService(name: "test") { state } }"#).await;
+        assert_eq!(res.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_service_status_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = test_registry(&temp_dir);
+        let path = temp_dir.path().join("broken.yaml");
+        std::fs::write(&path, "name: broken\nversion: 1.0.0\n").unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.lock().await.register_service("broken", &config).unwrap();
+        let schema = create_schema(registry);
+
+        let res = schema
+            .execute("{ services { name status { state errorMessage warnings lastChecked lastUpdated } } }")
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let broken = &data["services"][0]["status"];
+        assert_eq!(broken["state"], "ERROR");
+        assert!(broken["errorMessage"].is_string());
+        let test = &data["services"][1]["status"];
+        assert_eq!(test["state"], "ACTIVE");
+        assert_eq!(test["errorMessage"], serde_json::Value::Null);
+        assert!(test["lastUpdated"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_sync_services_mutation() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+        let path = temp_dir.path().join("orders.yaml");
+        std::fs::write(
+            &path,
+            "name: orders\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+
+        let request = async_graphql::Request::new(
+            r#"mutation($input: [ServiceInput!]!) {
+                syncServices(input: $input, prune: true) {
+                    added updated removed validation { successful }
+                }
+            }"#,
+        )
+        .variables(async_graphql::Variables::from_json(serde_json::json!({
+            "input": [{ "name": "orders", "config": config }]
+        })));
+        let res = schema.execute(request).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{syncServices: {added: [\"orders\"], updated: [], removed: [\"test\"], \
+             validation: {successful: [\"orders\"]}}}"
+        );
+
+        let res = schema
+            .execute(r#"mutation { syncServices(input: [{name: "bad", config: "{"}]) { added } }"#)
+            .await;
+        assert_eq!(
+            res.errors[0].extensions.as_ref().unwrap().get("code"),
+            Some(&async_graphql::Value::from("invalid_config"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validation_queries() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = test_registry(&temp_dir);
+        registry.lock().await.validate_all_services().unwrap();
+        let schema = create_schema(registry);
+
+        let res = schema
+            .execute(
+                r#"{
+                    lastValidation { successful }
+                    validationHistory(name: "test") { state trigger }
+                }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{lastValidation: {successful: [\"test\"]}, \
+             validationHistory: [{state: \"Active\", trigger: \"register\"}]}"
+        );
+
+        let res = schema.execute(r#"{ validationHistory(name: "missing") { state } }"#).await;
+        let error = &res.errors[0];
+        assert!(error.message.contains("Service not found"));
+        assert_eq!(
+            error.extensions.as_ref().unwrap().get("code"),
+            Some(&async_graphql::Value::from("service_not_found"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_mutation() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut registry = test_service_registry(&temp_dir);
+        let client_path = temp_dir.path().join("client.yaml");
+        std::fs::write(
+            &client_path,
+            "name: client\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n\
+             dependencies:\n  - service: test\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, client_path.display());
+        registry.register_service("client", &config).unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        // The edited definition is picked up without reloading the catalog
+        std::fs::write(
+            temp_dir.path().join("test.yaml"),
+            "name: test\nversion: 0.2.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let mutation = |include_dependents: bool| {
+            format!(
+                r#"mutation {{
+                    revalidate(service: "test", includeDependents: {}) {{
+                        service {{ version }}
+                        revalidated
+                        validation {{ successful }}
+                    }}
+                }}"#,
+                include_dependents
+            )
+        };
+        let res = schema.execute(mutation(true)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["revalidate"]["service"]["version"], "0.2.0");
+        assert_eq!(data["revalidate"]["revalidated"], serde_json::json!(["client", "test"]));
+
+        let res = schema.execute(mutation(false)).await;
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["revalidate"]["revalidated"], serde_json::json!(["test"]));
+
+        let res =
+            schema.execute(r#"mutation { revalidate(service: "missing") { revalidated } }"#).await;
+        assert!(res.errors[0].message.contains("Service not found"));
+    }
+
+    #[tokio::test]
+    async fn test_dependency_graph_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+
+        let res = schema
+            .execute(
+                r#"{
+                    dependencyGraph(level: SYSTEM, interactions: [SYNC_CALL]) {
+                        nodes { id services }
+                        edges { from interactions }
+                    }
+                    service(name: "test") { system }
+                }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{dependencyGraph: {nodes: [{id: \"test\", services: [\"test\"]}], edges: []}, \
+             service: {system: null}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graph_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+
+        let res =
+            schema.execute(r#"{ graphQuery(query: "deps(test, depth<=1)") { service } }"#).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.data.to_string(), "{graphQuery: []}");
+
+        let res = schema.execute(r#"{ graphQuery(query: "walk(test)") { service } }"#).await;
+        assert_eq!(
+            res.errors[0].extensions.as_ref().unwrap().get("code"),
+            Some(&async_graphql::Value::from("invalid_query"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_freeze_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let window = RegistryFreezeWindow {
+            name: "always".to_string(),
+            schedule: "* * * * *".parse().unwrap(),
+            namespaces: Vec::new(),
+            tiers: Vec::new(),
+            reason: None,
+        };
+        let registry = test_service_registry(&temp_dir)
+            .with_freeze_calendar(FreezeCalendar::new(vec![window]));
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        let res = schema
+            .execute(r#"{ activeFreezes { name } freezeStatus(name: "test") { frozen } }"#)
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{activeFreezes: [{name: \"always\"}], freezeStatus: {frozen: true}}"
+        );
+
+        let res = schema
+            .execute(r#"mutation { registerService(name: "test", config: "{}") { name } }"#)
+            .await;
+        assert_eq!(
+            res.errors[0].extensions.as_ref().unwrap().get("code"),
+            Some(&async_graphql::Value::from("frozen"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_jobs() {
+        let temp_dir = TempDir::new().unwrap();
+        let res =
+            create_schema(test_registry(&temp_dir)).execute("{ scheduledJobs { name } }").await;
+        assert_eq!(res.data.to_string(), "{scheduledJobs: []}");
+
+        let job =
+            aureacore::scheduler::Job::new("noop", Duration::from_millis(5), || async { Ok(()) });
+        let scheduler = aureacore::scheduler::Scheduler::new().with_job(job).start();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let schema = create_schema_with_jobs(
+            test_registry(&temp_dir),
+            scheduler.statuses(),
+            &QueryLimits::default(),
+        );
+        let res = schema.execute("{ scheduledJobs { name intervalMs lastError } }").await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{scheduledJobs: [{name: \"noop\", intervalMs: 5, lastError: null}]}"
+        );
+        assert!(scheduler.statuses().get("noop").unwrap().runs > 0);
+    }
+
+    #[tokio::test]
+    async fn test_reload_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+        let res = schema.execute("{ activeRules { version } }").await;
+        assert_eq!(res.data.to_string(), "{activeRules: null}");
+
+        let reload = "mutation { reloadRules { version } }";
+        let res = schema.execute(async_graphql::Request::new(reload).data(Role::Public)).await;
+        assert_eq!(res.errors[0].message, "Only internal callers may reload rules");
+        let res = schema.execute(reload).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let res = schema.execute("{ activeRules { version loadedAt } }").await;
+        let initial = res.data.to_string();
+        assert!(initial.contains("loadedAt"));
+
+        std::fs::write(temp_dir.path().join("config/naming-rules.yaml"), "kubernetes: true\n")
+            .unwrap();
+        schema.execute("mutation { reloadRules { version } }").await;
+        let res = schema.execute("{ activeRules { version loadedAt } }").await;
+        assert_ne!(res.data.to_string(), initial);
+
+        std::fs::write(temp_dir.path().join("config/eol.yaml"), "runtimes: 42\n").unwrap();
+        let res = schema.execute("mutation { reloadRules { version } }").await;
+        assert!(res.errors[0].message.contains("Invalid EOL database"));
+    }
+
+    #[tokio::test]
+    async fn test_who_calls_queries() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = test_registry(&temp_dir);
+        let path = temp_dir.path().join("dashboard.yaml");
+        std::fs::write(
+            &path,
+            "name: dashboard\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\ndependencies:\n  - service: test\nconsumes:\n  - service: test\n    endpoint: api\n    version: v1\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.lock().await.register_service("dashboard", &config).unwrap();
+        let schema = create_schema(registry);
+
+        let res = schema
+            .execute(r#"{ whoCalls(endpointPath: "/api") { consumer provider endpoint path required } }"#)
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{whoCalls: [{consumer: \"dashboard\", provider: \"test\", endpoint: \"api\", path: \"/api\", required: true}]}"
+        );
+        let res = schema
+            .execute(r#"{ whoDependsOnEndpoint(service: "test", endpoint: "api") { consumer version } }"#)
+            .await;
+        assert_eq!(
+            res.data.to_string(),
+            "{whoDependsOnEndpoint: [{consumer: \"dashboard\", version: \"v1\"}]}"
+        );
+        let res = schema
+            .execute(
+                r#"{ whoDependsOnEndpoint(service: "missing", endpoint: "api") { consumer } }"#,
+            )
+            .await;
+        assert_eq!(res.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resources_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let resources_dir = temp_dir.path().join("config/resources");
+        std::fs::create_dir_all(&resources_dir).unwrap();
+        std::fs::write(resources_dir.join("sessions.yaml"), "name: sessions\nkind: cache\n")
+            .unwrap();
+        let mut registry = test_service_registry(&temp_dir);
+        registry.load_resources().unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        let res = schema.execute("{ resources { name kind } }").await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.data.to_string(), "{resources: [{name: \"sessions\", kind: CACHE}]}");
+        let res = schema.execute(r#"{ impact(name: "sessions") { service } }"#).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+    }
+
+    #[tokio::test]
+    async fn test_service_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = test_registry(&temp_dir);
+        let id = "01920000-0000-7000-8000-000000000001";
+        let config = format!(
+            r#"{{"config_path": "{}", "id": "{}"}}"#,
+            temp_dir.path().join("test.yaml").display(),
+            id
+        );
+        registry.lock().await.register_service("test", &config).unwrap();
+        let schema = create_schema(registry);
+
+        let res = schema
+            .execute(r#"mutation { renameService(name: "test", newName: "renamed") { id name } }"#)
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let query = format!(
+            r#"{{ serviceById(id: "{}") {{ name }} other: serviceById(id: "x") {{ name }} }}"#,
+            id
+        );
+        let res = schema.execute(query).await;
+        assert_eq!(res.data.to_string(), "{serviceById: {name: \"renamed\"}, other: null}");
+    }
+
+    #[tokio::test]
+    async fn test_impact_in_environment() {
+        let temp_dir = TempDir::new().unwrap();
+        let topology = Topology::new(vec![serde_json::from_str(r#"{"name": "prod"}"#).unwrap()]);
+        let registry = test_service_registry(&temp_dir).with_topology(topology);
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        let res = schema.execute(r#"{ impact(name: "test", env: "prod") { service } }"#).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.data.to_string(), "{impact: []}");
+        let res = schema.execute(r#"{ impact(name: "test", env: "qa") { service } }"#).await;
+        assert_eq!(res.errors[0].message, "Configuration error: Unknown environment 'qa'");
+    }
+
+    #[tokio::test]
+    async fn test_reload_catalog_subscription() {
+        use async_graphql::futures_util::StreamExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let schema = create_schema(test_registry(&temp_dir));
+        let updates: Vec<_> = schema
+            .execute_stream(
+                "subscription { reloadCatalog { kind operation percent message success } }",
+            )
+            .map(|response| response.data.into_json().unwrap()["reloadCatalog"].clone())
+            .collect()
+            .await;
+
+        let kinds: Vec<&str> =
+            updates.iter().map(|update| update["kind"].as_str().unwrap()).collect();
+        assert_eq!(
+            kinds,
+            ["STARTED", "PROGRESS", "PROGRESS", "FINISHED", "STARTED", "PROGRESS", "FINISHED"]
+        );
+        assert_eq!(updates[0]["operation"], "Loading services");
+        assert_eq!(updates[1]["message"], "Loading test");
+        assert_eq!(updates[2]["percent"], 100);
+        assert_eq!(updates[4]["operation"], "Validating services");
+        assert_eq!(updates[6]["success"], true);
+    }
+
+    #[tokio::test]
+    async fn test_services_gated_by_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("checkout.yaml");
+        std::fs::write(
+            &path,
+            "name: checkout\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\nfeature_flags:\n  - provider: unleash\n    key: new-checkout\n    description: New payment flow\n",
+        )
+        .unwrap();
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.register_service("checkout", &config).unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        let query = r#"{
+            all: servicesGatedBy(flag: "new-checkout") { service provider project description }
+            other: servicesGatedBy(flag: "new-checkout", provider: LAUNCH_DARKLY) { service }
+        }"#;
+        let res = schema.execute(query).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(
+            data["all"],
+            serde_json::json!([{
+                "service": "checkout",
+                "provider": "UNLEASH",
+                "project": "default",
+                "description": "New payment flow"
+            }])
+        );
+        assert_eq!(data["other"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_version_scalars() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = test_registry(&temp_dir);
+        let path = temp_dir.path().join("dashboard.yaml");
+        std::fs::write(
+            &path,
+            "name: dashboard\nversion: 2024.05.1\nservice_type:\n  type: rest\nendpoints: []\ndependencies:\n  - service: test\n    version_constraint: ^0.1\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.lock().await.register_service("dashboard", &config).unwrap();
+        let schema = create_schema(registry);
+
+        let res = schema
+            .execute(r#"{ service(name: "dashboard") { version declaredVersion dependencies { service versionConstraint } } }"#)
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            "{service: {version: null, declaredVersion: \"2024.05.1\", dependencies: [{service: \"test\", versionConstraint: \"^0.1\"}]}}"
+        );
+
+        let res = schema.execute(r#"{ services(versionMatching: "<1") { name } }"#).await;
+        assert_eq!(res.data.to_string(), "{services: [{name: \"test\"}]}");
+        let res =
+            schema.execute(r#"{ dependentsRejecting(service: "test", version: "0.2.0") }"#).await;
+        assert_eq!(res.data.to_string(), "{dependentsRejecting: [\"dashboard\"]}");
+        let res =
+            schema.execute(r#"{ dependentsRejecting(service: "test", version: "0.1.7") }"#).await;
+        assert_eq!(res.data.to_string(), "{dependentsRejecting: []}");
+
+        let res =
+            schema.execute(r#"{ dependentsRejecting(service: "test", version: "0.2") }"#).await;
+        assert!(res.errors[0].message.contains("Invalid semantic version '0.2'"));
+        let res = schema.execute(r#"{ services(versionMatching: "=>1") { name } }"#).await;
+        assert!(res.errors[0].message.contains("Invalid version constraint"));
+    }
+
+    #[tokio::test]
+    async fn test_aliased_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let aliases =
+            std::collections::BTreeMap::from([("legacy".to_string(), "test".to_string())]);
+        let mut registry = test_service_registry(&temp_dir).with_aliases(aliases).unwrap();
+        let path = temp_dir.path().join("dashboard.yaml");
+        std::fs::write(
+            &path,
+            "name: dashboard\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\ndependencies:\n  - service: legacy\n    version_constraint: ^0.1\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.register_service("dashboard", &config).unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+
+        // Dependencies name the service now providing the aliased one
+        let res =
+            schema.execute(r#"{ service(name: "dashboard") { dependencies { service } } }"#).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.data.to_string(), "{service: {dependencies: [{service: \"test\"}]}}");
+
+        for name in ["test", "legacy"] {
+            let query =
+                format!(r#"{{ dependentsRejecting(service: "{}", version: "0.2.0") }}"#, name);
+            let res = schema.execute(query.as_str()).await;
+            assert_eq!(res.data.to_string(), "{dependentsRejecting: [\"dashboard\"]}");
+        }
+    }
 }
@@ -0,0 +1,191 @@
+//! Limits protecting the API from abusive queries
+
+use async_graphql::extensions::apollo_persisted_queries::{
+    ApolloPersistedQueries, LruCacheStorage,
+};
+use async_graphql::SchemaBuilder;
+use aureacore::registry::TenantLimits;
+
+use crate::{Mutation, Query, Subscription};
+
+/// Depth of nested selections the server accepts by default
+///
+/// Leaves room for the introspection queries of GraphQL clients.
+pub const DEFAULT_MAX_DEPTH: usize = 16;
+
+/// Complexity the server accepts by default, each selected field counting one
+pub const DEFAULT_MAX_COMPLEXITY: usize = 1000;
+
+/// Limits on the queries a GraphQL schema executes
+///
+/// Queries exceeding a limit are rejected before any resolver runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Deepest nesting of selections a query may have
+    pub max_depth: Option<usize>,
+    /// Highest complexity a query may have, each selected field counting one
+    pub max_complexity: Option<usize>,
+    /// Number of persisted queries to keep, enabling Automatic Persisted Queries
+    ///
+    /// Clients may then send the SHA-256 hash of a query the server has seen
+    /// instead of its text, in the `persistedQuery` request extension.
+    pub persisted_queries: Option<usize>,
+    /// Whether introspection queries are rejected, hiding the schema from clients
+    pub disable_introspection: bool,
+}
+
+impl QueryLimits {
+    /// Creates limits accepting any query
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the deepest nesting of selections a query may have
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets the highest complexity a query may have
+    pub fn with_max_complexity(mut self, complexity: usize) -> Self {
+        self.max_complexity = Some(complexity);
+        self
+    }
+
+    /// Keeps up to `capacity` persisted queries; 0 disables persisted queries
+    pub fn with_persisted_queries(mut self, capacity: usize) -> Self {
+        self.persisted_queries = (capacity > 0).then_some(capacity);
+        self
+    }
+
+    /// Rejects introspection queries
+    pub fn without_introspection(mut self) -> Self {
+        self.disable_introspection = true;
+        self
+    }
+
+    /// Configures a schema builder to enforce the limits
+    pub(crate) fn apply(
+        &self,
+        mut builder: SchemaBuilder<Query, Mutation, Subscription>,
+    ) -> SchemaBuilder<Query, Mutation, Subscription> {
+        if let Some(depth) = self.max_depth {
+            builder = builder.limit_depth(depth);
+        }
+        if let Some(complexity) = self.max_complexity {
+            builder = builder.limit_complexity(complexity);
+        }
+        if let Some(capacity) = self.persisted_queries.filter(|capacity| *capacity > 0) {
+            builder =
+                builder.extension(ApolloPersistedQueries::new(LruCacheStorage::new(capacity)));
+        }
+        if self.disable_introspection {
+            builder = builder.disable_introspection();
+        }
+        builder
+    }
+}
+
+impl From<&TenantLimits> for QueryLimits {
+    fn from(limits: &TenantLimits) -> Self {
+        Self {
+            max_depth: limits.max_query_depth,
+            max_complexity: limits.max_query_complexity,
+            persisted_queries: limits.persisted_queries,
+            disable_introspection: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_graphql::Request;
+    use aureacore::registry::ServiceRegistry;
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::create_schema_with_limits;
+
+    /// Introspection query sent by GraphQL clients such as GraphiQL
+    const INTROSPECTION: &str = r#"
+        query IntrospectionQuery {
+            __schema {
+                queryType { name }
+                mutationType { name }
+                types { ...FullType }
+                directives { name description locations args { ...InputValue } }
+            }
+        }
+        fragment FullType on __Type {
+            kind name description
+            fields(includeDeprecated: true) {
+                name description args { ...InputValue } type { ...TypeRef }
+                isDeprecated deprecationReason
+            }
+            inputFields { ...InputValue }
+            interfaces { ...TypeRef }
+            enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason }
+            possibleTypes { ...TypeRef }
+        }
+        fragment InputValue on __InputValue { name description type { ...TypeRef } defaultValue }
+        fragment TypeRef on __Type {
+            kind name
+            ofType { kind name ofType { kind name ofType { kind name ofType { kind name
+                ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+        }
+    "#;
+
+    const SERVICES: &str = "{ services { name } }";
+    const SERVICES_HASH: &str = "2b5bb8e5d9c3c3baac6f2a909290cc7b2012c6ddca50d87e7dc6f3f5f49389d3";
+
+    fn persisted(query: &str) -> Request {
+        let extension = serde_json::json!({"version": 1, "sha256Hash": SERVICES_HASH});
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            async_graphql::Value::from_json(extension).unwrap(),
+        );
+        request
+    }
+
+    #[tokio::test]
+    async fn test_query_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let registry = Arc::new(Mutex::new(registry));
+
+        let defaults = QueryLimits::new()
+            .with_max_depth(DEFAULT_MAX_DEPTH)
+            .with_max_complexity(DEFAULT_MAX_COMPLEXITY);
+        let schema = create_schema_with_limits(registry.clone(), &defaults);
+        let res = schema.execute(INTROSPECTION).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        let strict = QueryLimits::new().with_max_depth(1).with_max_complexity(2);
+        let schema = create_schema_with_limits(registry.clone(), &strict);
+        assert!(schema.execute("{ catalogFingerprint }").await.errors.is_empty());
+        let res = schema.execute(SERVICES).await;
+        assert_eq!(res.errors[0].message, "Query is nested too deep.");
+        let res = schema
+            .execute("{ catalogFingerprint a: catalogFingerprint b: catalogFingerprint }")
+            .await;
+        assert_eq!(res.errors[0].message, "Query is too complex.");
+
+        // Persisted queries are only accepted once the server has seen their text
+        let schema = create_schema_with_limits(registry.clone(), &QueryLimits::new());
+        assert!(!schema.execute(persisted("")).await.errors.is_empty());
+        let schema =
+            create_schema_with_limits(registry, &QueryLimits::new().with_persisted_queries(10));
+        let res = schema.execute(persisted("")).await;
+        assert_eq!(res.errors[0].message, "PersistedQueryNotFound");
+        assert!(schema.execute(persisted(SERVICES)).await.errors.is_empty());
+        let res = schema.execute(persisted("")).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.data.to_string(), "{services: []}");
+    }
+}
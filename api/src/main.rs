@@ -0,0 +1,356 @@
+//! AureaCore API server
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aureacore::registry::{
+    AccessControl, DirectorySnapshotStore, FileLock, HttpSnapshotStore, OpsgenieProvider,
+    PagerDutyProvider, RuleSet, ServiceRegistry, SnapshotStore, VcsBackend, DEFAULT_LOCK_TTL,
+};
+use aureacore::scheduler::{
+    drift_detection_job, follow_snapshots_job, health_check_job, heartbeat_job, link_check_job,
+    publish_snapshot_job, revalidate_job, sync_job, Scheduler,
+};
+use aureacore::schema::{Layout, RootConfig};
+use aureacore::AureaCoreError;
+use aureacore_api::limits::{DEFAULT_MAX_COMPLEXITY, DEFAULT_MAX_DEPTH};
+use aureacore_api::server::secured_router;
+use aureacore_api::{create_schema_with_jobs, AccessPolicy, QueryLimits, ServerSecurity};
+use axum_server::tls_rustls::RustlsConfig;
+use clap::{Parser, Subcommand};
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// Command-line arguments
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Repository URL (defaults to the AUREACORE_REPO env var)
+    #[arg(short, long, default_value = "")]
+    repository: String,
+
+    /// Git branch
+    #[arg(short, long, default_value = "main")]
+    branch: String,
+
+    /// Working directory for configuration files
+    #[arg(short, long, default_value = "./config")]
+    work_dir: PathBuf,
+
+    /// Backend the catalog repository is accessed with: `git` for a local clone, or
+    /// `github` to go through the GitHub API, authenticating with GITHUB_TOKEN
+    #[arg(long, default_value = "git")]
+    vcs: String,
+
+    /// Subcommand to execute
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Subcommands
+#[derive(Subcommand)]
+enum Commands {
+    /// Serve the GraphQL API, running periodic jobs in the background
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, default_value = "127.0.0.1:8080")]
+        listen: SocketAddr,
+
+        /// Seconds between syncs with the repository (0 disables the job)
+        #[arg(long, default_value_t = 300)]
+        sync_interval: u64,
+
+        /// Seconds between revalidations of all services (0 disables the job)
+        #[arg(long, default_value_t = 900)]
+        revalidate_interval: u64,
+
+        /// Seconds between checks of documentation and runbook links (0 disables the job)
+        #[arg(long, default_value_t = 0)]
+        link_check_interval: u64,
+
+        /// Seconds between runs of the doctor's consistency checks (0 disables the job)
+        #[arg(long, default_value_t = 0)]
+        health_check_interval: u64,
+
+        /// Seconds between checks of the catalog against its lockfile (0 disables the job)
+        #[arg(long, default_value_t = 0)]
+        drift_check_interval: u64,
+
+        /// Upper bound in seconds of the random delay added to each job run
+        #[arg(long, default_value_t = 30)]
+        jitter: u64,
+
+        /// Deepest nesting of selections a query may have
+        #[arg(long, default_value_t = DEFAULT_MAX_DEPTH)]
+        max_depth: usize,
+
+        /// Highest complexity a query may have, each selected field counting one
+        #[arg(long, default_value_t = DEFAULT_MAX_COMPLEXITY)]
+        max_complexity: usize,
+
+        /// Number of persisted queries to keep (0 disables persisted queries)
+        #[arg(long, default_value_t = 0)]
+        persisted_queries: usize,
+
+        /// Metadata key only internal callers may read, besides the keys services mark
+        ///
+        /// Callers are internal if they send a bearer token listed in the comma-separated
+        /// AUREACORE_INTERNAL_TOKENS env var, or if that variable is unset.
+        #[arg(long)]
+        sensitive_metadata: Vec<String>,
+
+        /// Root config of the catalog, whose access grants restrict who may modify services
+        ///
+        /// Callers are identified by bearer tokens listed as `principal=token` pairs in the
+        /// comma-separated AUREACORE_PRINCIPAL_TOKENS env var.
+        #[arg(long)]
+        catalog: Option<PathBuf>,
+
+        /// Directory or HTTP URL to periodically publish catalog snapshots to
+        ///
+        /// An `Authorization` header for HTTP uploads can be set with the
+        /// AUREACORE_SNAPSHOT_AUTH env var.
+        #[arg(long, conflicts_with = "replica_of")]
+        publish_snapshots: Option<String>,
+
+        /// Directory or HTTP URL of the snapshots of a writer to serve read-only
+        ///
+        /// Replicas neither sync nor revalidate, they follow the writer's snapshots.
+        #[arg(long)]
+        replica_of: Option<String>,
+
+        /// Seconds between publishing or fetching snapshots
+        #[arg(long, default_value_t = 60)]
+        snapshot_interval: u64,
+
+        /// YAML file of server security options, which the flags below override
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Reject introspection queries
+        #[arg(long)]
+        no_introspection: bool,
+
+        /// Do not serve the GraphiQL playground on GET /graphql
+        #[arg(long)]
+        no_playground: bool,
+
+        /// Origin allowed to call the API from browsers (`*` allows any)
+        #[arg(long)]
+        cors_origin: Vec<String>,
+
+        /// Do not set security headers, e.g. when a proxy sets them
+        #[arg(long)]
+        no_security_headers: bool,
+
+        /// PEM certificate chain to serve HTTPS with
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+
+        /// PEM private key to serve HTTPS with
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> aureacore::Result<()> {
+    tracing_subscriber::fmt::init();
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Serve {
+            listen,
+            sync_interval,
+            revalidate_interval,
+            link_check_interval,
+            health_check_interval,
+            drift_check_interval,
+            jitter,
+            max_depth,
+            max_complexity,
+            persisted_queries,
+            sensitive_metadata,
+            catalog,
+            publish_snapshots,
+            replica_of,
+            snapshot_interval,
+            config,
+            no_introspection,
+            no_playground,
+            cors_origin,
+            no_security_headers,
+            tls_cert,
+            tls_key,
+        } => {
+            let mut security = match config {
+                Some(path) => ServerSecurity::load(&path)?,
+                None => ServerSecurity::new(),
+            };
+            if no_introspection {
+                security = security.without_introspection();
+            }
+            if no_playground {
+                security = security.without_playground();
+            }
+            for origin in cors_origin {
+                security = security.with_cors_origin(origin);
+            }
+            if no_security_headers {
+                security = security.without_security_headers();
+            }
+            if let (Some(cert), Some(key)) = (tls_cert, tls_key) {
+                security = security.with_tls(cert, key);
+            }
+
+            let repo_url = if cli.repository.is_empty() {
+                std::env::var("AUREACORE_REPO").unwrap_or_default()
+            } else {
+                cli.repository
+            };
+            std::fs::create_dir_all(&cli.work_dir)?;
+            let rules = RuleSet::load(&cli.work_dir, &Layout::default())?;
+            let vcs = cli.vcs.parse::<VcsBackend>()?.open(
+                &repo_url,
+                &cli.branch,
+                cli.work_dir.clone(),
+            )?;
+            let mut registry = ServiceRegistry::new(repo_url, cli.branch, cli.work_dir.clone())?
+                .with_vcs(vcs)?
+                .with_rule_set(rules)?
+                .with_sensitive_metadata(sensitive_metadata);
+            if let Some(catalog) = &catalog {
+                let root = RootConfig::load(catalog)?;
+                registry = registry
+                    .with_access_control(AccessControl::from_root_config(&root))
+                    .with_aliases(root.aliases)?;
+            }
+            // On-call schedules are resolved on the platforms with credentials configured
+            if let Ok(token) = std::env::var("PAGERDUTY_API_TOKEN") {
+                let mut provider = PagerDutyProvider::new(token);
+                if let Ok(url) = std::env::var("PAGERDUTY_API_URL") {
+                    provider = provider.with_base_url(url);
+                }
+                registry = registry.with_oncall_provider(Box::new(provider));
+            }
+            if let Ok(key) = std::env::var("OPSGENIE_API_KEY") {
+                let mut provider = OpsgenieProvider::new(key);
+                if let Ok(url) = std::env::var("OPSGENIE_API_URL") {
+                    provider = provider.with_base_url(url);
+                }
+                registry = registry.with_oncall_provider(Box::new(provider));
+            }
+            let snapshot_every = Duration::from_secs(snapshot_interval.max(1));
+            let interval = |seconds| (seconds > 0).then(|| Duration::from_secs(seconds));
+            let (registry, jobs, heartbeat) = match replica_of {
+                Some(source) => {
+                    let store = snapshot_store(&source, snapshot_every);
+                    if let Some(snapshot) = store.fetch()? {
+                        registry.hydrate(&snapshot)?;
+                    }
+                    info!("Serving a read replica of {}", source);
+                    let registry = Arc::new(Mutex::new(registry));
+                    let jobs =
+                        vec![Some(follow_snapshots_job(registry.clone(), store, snapshot_every))];
+                    (registry, jobs, None)
+                }
+                None => {
+                    // Only one instance sharing the work directory may write at a time; the
+                    // others serve read-only until the writer's lock expires
+                    let mut registry =
+                        registry.with_lock(Box::new(FileLock::for_work_dir(&cli.work_dir)))?;
+                    registry.warm_start()?;
+                    let registry = Arc::new(Mutex::new(registry));
+                    let mut jobs = vec![
+                        interval(sync_interval).map(|every| sync_job(registry.clone(), every)),
+                        interval(revalidate_interval)
+                            .map(|every| revalidate_job(registry.clone(), every)),
+                        interval(link_check_interval)
+                            .map(|every| link_check_job(registry.clone(), every)),
+                        interval(health_check_interval)
+                            .map(|every| health_check_job(registry.clone(), every)),
+                        interval(drift_check_interval)
+                            .map(|every| drift_detection_job(registry.clone(), every)),
+                    ];
+                    if let Some(target) = publish_snapshots {
+                        let store = snapshot_store(&target, snapshot_every);
+                        jobs.push(Some(publish_snapshot_job(
+                            registry.clone(),
+                            store,
+                            snapshot_every,
+                        )));
+                    }
+                    let heartbeat = heartbeat_job(registry.clone(), DEFAULT_LOCK_TTL / 4);
+                    (registry, jobs, Some(heartbeat))
+                }
+            };
+            let mut scheduler = Scheduler::new();
+            for job in jobs.into_iter().flatten() {
+                scheduler = scheduler.with_job(job.with_jitter(Duration::from_secs(jitter)));
+            }
+            // Without jitter, so the lock is refreshed before its TTL runs out
+            if let Some(heartbeat) = heartbeat {
+                scheduler = scheduler.with_job(heartbeat);
+            }
+            let scheduler = scheduler.start();
+
+            let mut limits = QueryLimits::new()
+                .with_max_depth(max_depth)
+                .with_max_complexity(max_complexity)
+                .with_persisted_queries(persisted_queries);
+            if !security.introspection {
+                limits = limits.without_introspection();
+            }
+            let schema = create_schema_with_jobs(registry, scheduler.statuses(), &limits);
+            let mut access = AccessPolicy::new();
+            let tokens = std::env::var("AUREACORE_INTERNAL_TOKENS").unwrap_or_default();
+            for token in tokens.split(',').map(str::trim).filter(|token| !token.is_empty()) {
+                access = access.with_internal_token(token);
+            }
+            let tokens = std::env::var("AUREACORE_PRINCIPAL_TOKENS").unwrap_or_default();
+            for pair in tokens.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+                let Some((principal, token)) = pair.split_once('=') else {
+                    // The entry may be a bare token, so it isn't echoed
+                    return Err(AureaCoreError::Config(
+                        "AUREACORE_PRINCIPAL_TOKENS entries must be principal=token".to_string(),
+                    ));
+                };
+                access = access.with_principal_token(principal.trim(), token.trim());
+            }
+            let router = secured_router(schema, access, &security)?;
+            match &security.tls {
+                Some(tls) => {
+                    // Another crate may have installed a provider already, which is fine
+                    let _ = rustls::crypto::ring::default_provider().install_default();
+                    let config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path).await?;
+                    info!("Serving the catalog API on https://{}/graphql", listen);
+                    axum_server::bind_rustls(listen, config)
+                        .serve(router.into_make_service())
+                        .await?;
+                }
+                None => {
+                    let listener = tokio::net::TcpListener::bind(listen).await?;
+                    info!("Serving the catalog API on http://{}/graphql", listen);
+                    axum::serve(listener, router).await?;
+                }
+            }
+            scheduler.shutdown();
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens the snapshot store at an HTTP URL or directory
+fn snapshot_store(location: &str, timeout: Duration) -> Arc<dyn SnapshotStore> {
+    if !location.starts_with("http://") && !location.starts_with("https://") {
+        return Arc::new(DirectorySnapshotStore::new(location));
+    }
+    let mut store = HttpSnapshotStore::new(location, timeout);
+    if let Ok(authorization) = std::env::var("AUREACORE_SNAPSHOT_AUTH") {
+        store = store.with_authorization(authorization);
+    }
+    Arc::new(store)
+}
@@ -0,0 +1,108 @@
+//! Scalars validating version strings, so clients learn about malformed ones early
+
+use std::fmt;
+use std::str::FromStr;
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use semver::{Version, VersionReq};
+
+/// A semantic version such as `1.4.2` or `2.0.0-rc.1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemVer(pub Version);
+
+impl FromStr for SemVer {
+    type Err = semver::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Version::parse(s.trim()).map(SemVer)
+    }
+}
+
+impl fmt::Display for SemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[Scalar(name = "SemVer")]
+impl ScalarType for SemVer {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map_err(|e| {
+                InputValueError::custom(format!("Invalid semantic version '{}': {}", s, e))
+            }),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A version requirement such as `^1.2`, `~1.4.0` or `>=1.0.0, <2.0.0`
+///
+/// Kept as written, so constraints read back the way they were declared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConstraint {
+    constraint: String,
+    req: VersionReq,
+}
+
+impl VersionConstraint {
+    /// Checks whether a version satisfies the constraint
+    pub fn matches(&self, version: &SemVer) -> bool {
+        self.req.matches(&version.0)
+    }
+}
+
+impl FromStr for VersionConstraint {
+    type Err = semver::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let constraint = s.trim();
+        Ok(Self { constraint: constraint.to_string(), req: VersionReq::parse(constraint)? })
+    }
+}
+
+impl fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.constraint)
+    }
+}
+
+#[Scalar(name = "VersionConstraint")]
+impl ScalarType for VersionConstraint {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map_err(|e| {
+                InputValueError::custom(format!("Invalid version constraint '{}': {}", s, e))
+            }),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.constraint.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scalars() {
+        let version = <SemVer as ScalarType>::parse(Value::from("1.4.2")).unwrap();
+        assert_eq!(version.to_value(), Value::from("1.4.2"));
+        assert!(<SemVer as ScalarType>::parse(Value::from("1.4")).is_err());
+        assert!(<SemVer as ScalarType>::parse(Value::from(1)).is_err());
+
+        let constraint =
+            <VersionConstraint as ScalarType>::parse(Value::from(" >=1.2, <2")).unwrap();
+        assert_eq!(constraint.to_value(), Value::from(">=1.2, <2"));
+        assert!(constraint.matches(&version));
+        assert!(!constraint.matches(&"2.0.0".parse().unwrap()));
+        assert!(<VersionConstraint as ScalarType>::parse(Value::from("1.x.>")).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! Hardening of the HTTP server for production: CORS, security headers and TLS
+
+use std::path::{Path, PathBuf};
+
+use aureacore::{AureaCoreError, Result};
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use axum::Router;
+use serde::Deserialize;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+/// Security options of the HTTP server, read from the `--config` file of `serve`
+///
+/// The defaults suit development: introspection and the playground are enabled,
+/// cross-origin requests are refused and the server speaks plain HTTP.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ServerSecurity {
+    /// Whether the schema answers introspection queries
+    pub introspection: bool,
+    /// Whether `GET /graphql` serves the GraphiQL playground
+    pub playground: bool,
+    /// Origins allowed to call the API from browsers; `*` allows any
+    pub cors_origins: Vec<String>,
+    /// Whether responses carry headers hardening browsers against sniffing and framing
+    pub security_headers: bool,
+    /// Certificate and key to serve HTTPS with
+    pub tls: Option<TlsConfig>,
+}
+
+/// PEM files of the certificate chain and private key of the server
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TlsConfig {
+    /// Path of the certificate chain
+    pub cert_path: PathBuf,
+    /// Path of the private key
+    pub key_path: PathBuf,
+}
+
+impl Default for ServerSecurity {
+    fn default() -> Self {
+        Self {
+            introspection: true,
+            playground: true,
+            cors_origins: Vec::new(),
+            security_headers: true,
+            tls: None,
+        }
+    }
+}
+
+impl ServerSecurity {
+    /// Creates the development defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the options from a YAML file, leaving missing options at their defaults
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid server config {}", path.display()), e)
+        })
+    }
+
+    /// Rejects introspection queries
+    pub fn without_introspection(mut self) -> Self {
+        self.introspection = false;
+        self
+    }
+
+    /// Stops serving the GraphiQL playground
+    pub fn without_playground(mut self) -> Self {
+        self.playground = false;
+        self
+    }
+
+    /// Allows browsers on `origin` to call the API
+    pub fn with_cors_origin(mut self, origin: impl Into<String>) -> Self {
+        self.cors_origins.push(origin.into());
+        self
+    }
+
+    /// Leaves responses without security headers, e.g. behind a proxy setting them
+    pub fn without_security_headers(mut self) -> Self {
+        self.security_headers = false;
+        self
+    }
+
+    /// Serves HTTPS with the certificate chain and private key at the given paths
+    pub fn with_tls(mut self, cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        self.tls = Some(TlsConfig { cert_path: cert_path.into(), key_path: key_path.into() });
+        self
+    }
+
+    /// Adds the CORS and security header layers to a router
+    ///
+    /// Fails if an allowed origin is not a valid header value.
+    pub fn apply(&self, mut router: Router) -> Result<Router> {
+        if let Some(cors) = self.cors_layer()? {
+            router = router.layer(cors);
+        }
+        if self.security_headers {
+            for (name, value) in self.headers() {
+                router = router.layer(SetResponseHeaderLayer::if_not_present(name, value));
+            }
+        }
+        Ok(router)
+    }
+
+    /// Builds the CORS layer, if any origin is allowed
+    fn cors_layer(&self) -> Result<Option<CorsLayer>> {
+        if self.cors_origins.is_empty() {
+            return Ok(None);
+        }
+        let allow_origin = if self.cors_origins.iter().any(|origin| origin == "*") {
+            AllowOrigin::any()
+        } else {
+            let origins = self
+                .cors_origins
+                .iter()
+                .map(|origin| {
+                    HeaderValue::from_str(origin.trim_end_matches('/')).map_err(|_| {
+                        AureaCoreError::Config(format!("Invalid CORS origin '{}'", origin))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        };
+        Ok(Some(
+            CorsLayer::new()
+                .allow_origin(allow_origin)
+                .allow_methods([Method::GET, Method::POST])
+                .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]),
+        ))
+    }
+
+    /// Gets the security headers responses carry
+    ///
+    /// The content security policy would block the scripts of the playground, so it is
+    /// only set without it.
+    fn headers(&self) -> Vec<(HeaderName, HeaderValue)> {
+        let mut headers = vec![
+            (header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+            (header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY")),
+            (header::REFERRER_POLICY, HeaderValue::from_static("no-referrer")),
+        ];
+        if !self.playground {
+            headers.push((
+                header::CONTENT_SECURITY_POLICY,
+                HeaderValue::from_static("default-src 'none'; frame-ancestors 'none'"),
+            ));
+        }
+        if self.tls.is_some() {
+            headers.push((
+                header::STRICT_TRANSPORT_SECURITY,
+                HeaderValue::from_static("max-age=31536000; includeSubDomains"),
+            ));
+        }
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use aureacore::registry::ServiceRegistry;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::server::secured_router;
+    use crate::{create_schema_with_limits, AccessPolicy, QueryLimits};
+
+    #[test]
+    fn test_load_server_security() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("server.yaml");
+        std::fs::write(
+            &path,
+            "introspection: false\ncors_origins: [\"https://portal.example.com\"]\ntls:\n  cert_path: cert.pem\n  key_path: key.pem\n",
+        )
+        .unwrap();
+        let security = ServerSecurity::load(&path).unwrap();
+        assert_eq!(
+            security,
+            ServerSecurity::new()
+                .without_introspection()
+                .with_cors_origin("https://portal.example.com")
+                .with_tls("cert.pem", "key.pem")
+        );
+
+        std::fs::write(&path, "playground: maybe\n").unwrap();
+        assert!(ServerSecurity::load(&path)
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid server config"));
+    }
+
+    #[tokio::test]
+    async fn test_secured_router() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let registry = Arc::new(Mutex::new(registry));
+        let introspect = || {
+            Request::post("/graphql")
+                .header("content-type", "application/json")
+                .header(header::ORIGIN, "https://portal.example.com")
+                .body(Body::from(r#"{"query": "{ __schema { queryType { name } } }"}"#))
+                .unwrap()
+        };
+        let playground = || Request::get("/graphql").body(Body::empty()).unwrap();
+
+        let schema = create_schema_with_limits(registry.clone(), &QueryLimits::new());
+        let router = secured_router(schema, AccessPolicy::new(), &ServerSecurity::new()).unwrap();
+        let response = router.clone().oneshot(introspect()).await.unwrap();
+        assert_eq!(response.headers()[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+        assert!(!response.headers().contains_key(header::CONTENT_SECURITY_POLICY));
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("\"Query\""));
+        assert_eq!(router.oneshot(playground()).await.unwrap().status(), StatusCode::OK);
+
+        let security = ServerSecurity::new()
+            .without_introspection()
+            .without_playground()
+            .with_cors_origin("https://portal.example.com");
+        let limits = QueryLimits::new().without_introspection();
+        let schema = create_schema_with_limits(registry, &limits);
+        let router = secured_router(schema, AccessPolicy::new(), &security).unwrap();
+        let response = router.clone().oneshot(introspect()).await.unwrap();
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://portal.example.com"
+        );
+        assert!(response.headers().contains_key(header::CONTENT_SECURITY_POLICY));
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(!String::from_utf8_lossy(&bytes).contains("\"Query\""));
+        assert_eq!(
+            router.oneshot(playground()).await.unwrap().status(),
+            StatusCode::METHOD_NOT_ALLOWED
+        );
+
+        let invalid = ServerSecurity::new().with_cors_origin("https://bad\norigin");
+        let schema = create_schema_with_limits(
+            Arc::new(Mutex::new(
+                ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("c"))
+                    .unwrap(),
+            )),
+            &QueryLimits::new(),
+        );
+        assert!(secured_router(schema, AccessPolicy::new(), &invalid).is_err());
+    }
+}
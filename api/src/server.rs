@@ -0,0 +1,213 @@
+//! HTTP server for a single catalog
+
+use std::convert::Infallible;
+
+use async_graphql::futures_util::{Stream, StreamExt};
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{Html, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+
+use crate::access::AccessPolicy;
+use crate::idempotency::IdempotencyStore;
+use crate::security::ServerSecurity;
+use crate::ApiSchema;
+
+/// Builds the HTTP router serving a catalog's GraphQL API on `/graphql`
+///
+/// Subscriptions posted to `/graphql/stream` are answered with server-sent events.
+/// Mutations sent with an `Idempotency-Key` header are applied once, see
+/// [`idempotency`](crate::idempotency). `/health` answers `200 OK` while the server is
+/// up, for liveness probes.
+pub fn router(schema: ApiSchema) -> Router {
+    router_with_access(schema, AccessPolicy::default())
+}
+
+/// Builds the HTTP router, deciding the role of each caller with an access policy
+pub fn router_with_access(schema: ApiSchema, access: AccessPolicy) -> Router {
+    Router::new()
+        .route("/graphql", post(graphql))
+        .route("/graphql/stream", post(graphql_stream))
+        .route("/health", get(|| async { "ok" }))
+        .with_state((schema, access, IdempotencyStore::default()))
+}
+
+/// Builds the HTTP router hardened by the security options of the server
+///
+/// Serves the GraphiQL playground on `GET /graphql` if enabled. Introspection is a
+/// property of the schema, see [`QueryLimits::without_introspection`](crate::QueryLimits).
+pub fn secured_router(
+    schema: ApiSchema,
+    access: AccessPolicy,
+    security: &ServerSecurity,
+) -> aureacore::Result<Router> {
+    let mut router = router_with_access(schema, access);
+    if security.playground {
+        router = router.route("/graphql", get(playground));
+    }
+    security.apply(router)
+}
+
+/// Serves the GraphiQL playground
+async fn playground() -> Html<String> {
+    Html(async_graphql::http::GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// Handles a GraphQL request
+async fn graphql(
+    State((schema, access, idempotency)): State<(ApiSchema, AccessPolicy, IdempotencyStore)>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<Response, StatusCode> {
+    let role = access.role_for(&headers);
+    let actor = access.actor_for(&headers);
+    idempotency
+        .respond(&headers, request, |request| async move {
+            Ok(schema.execute(request.data(role).data(actor)).await)
+        })
+        .await
+}
+
+/// Streams the responses of a GraphQL subscription as server-sent events
+async fn graphql_stream(
+    State((schema, access, _)): State<(ApiSchema, AccessPolicy, IdempotencyStore)>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let request = request.data(access.role_for(&headers)).data(access.actor_for(&headers));
+    let responses = schema.execute_stream(request);
+    let events = responses.map(|response| {
+        Ok(Event::default().json_data(&response).unwrap_or_else(|e| {
+            Event::default().event("error").data(format!("Failed to encode response: {}", e))
+        }))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use aureacore::registry::ServiceRegistry;
+    use axum::body::Body;
+    use axum::http::{header, Request};
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::create_schema;
+
+    #[tokio::test]
+    async fn test_stream_subscription() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let schema = create_schema(Arc::new(Mutex::new(registry)));
+        let request = Request::post("/graphql/stream")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"query": "subscription { reloadCatalog { kind operation } }"}"#))
+            .unwrap();
+        let response = router(schema).oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let events: Vec<serde_json::Value> = String::from_utf8_lossy(&bytes)
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0]["data"]["reloadCatalog"]["operation"], "Loading services");
+        assert_eq!(events[4]["data"]["reloadCatalog"]["kind"], "FINISHED");
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_mutations() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("orders.yaml");
+        std::fs::write(
+            &path,
+            "name: orders\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap()
+                .with_journal(aureacore::registry::Journal::new(temp_dir.path().join("journal")));
+        let registry = Arc::new(Mutex::new(registry));
+        let router = router(create_schema(registry.clone()));
+        let mutation = serde_json::json!({
+            "query": "mutation($config: String!) { registerService(name: \"orders\", config: $config) { name } }",
+            "variables": {"config": format!(r#"{{"config_path": "{}"}}"#, path.display())}
+        });
+        let request = |key: &str| {
+            Request::post("/graphql")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("idempotency-key", key)
+                .body(Body::from(mutation.to_string()))
+                .unwrap()
+        };
+
+        let first = router.clone().oneshot(request("deploy-42")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().get("idempotent-replayed").is_none());
+        let retry = router.clone().oneshot(request("deploy-42")).await.unwrap();
+        assert_eq!(retry.headers()["idempotent-replayed"], "true");
+        let body = axum::body::to_bytes(retry.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["registerService"]["name"], "orders");
+
+        let journal = aureacore::registry::Journal::new(temp_dir.path().join("journal"));
+        assert_eq!(journal.read().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mutations_checked_against_access_grants() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("invoices.yaml");
+        std::fs::write(
+            &path,
+            "name: invoices\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let grant = aureacore::schema::AccessGrant {
+            principals: vec!["alice".to_string()],
+            namespaces: vec!["billing".to_string()],
+            teams: Vec::new(),
+        };
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap()
+                .with_access_control(aureacore::registry::AccessControl::new(
+                    vec![grant],
+                    "default",
+                ));
+        let access = AccessPolicy::new().with_principal_token("alice", "alice-token");
+        let router = router_with_access(create_schema(Arc::new(Mutex::new(registry))), access);
+        let mutation = serde_json::json!({
+            "query": "mutation($config: String!) { registerService(name: \"invoices\", config: $config) { name } }",
+            "variables": {"config": format!(r#"{{"config_path": "{}", "namespace": "billing"}}"#, path.display())}
+        });
+        let request = |token: Option<&str>| {
+            let mut request =
+                Request::post("/graphql").header(header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            request.body(Body::from(mutation.to_string())).unwrap()
+        };
+        let body = |response: Response| async move {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+
+        let denied = body(router.clone().oneshot(request(None)).await.unwrap()).await;
+        assert_eq!(denied["errors"][0]["extensions"]["code"], "forbidden");
+        let allowed = body(router.oneshot(request(Some("alice-token"))).await.unwrap()).await;
+        assert_eq!(allowed["data"]["registerService"]["name"], "invoices");
+    }
+}
@@ -0,0 +1,266 @@
+//! Serving several isolated catalogs from one API server
+//!
+//! Each tenant has its own registry and schema. Requests are routed to a tenant by
+//! the `/tenants/{id}/graphql` path or, on `/graphql`, by the `X-Tenant` header.
+//!
+//! Responses carry the tenant's catalog fingerprint as their `ETag`; queries sent with
+//! a matching `If-None-Match` header are answered with `304 Not Modified`. Each tenant
+//! keeps its own idempotency keys.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use aureacore::registry::{SharedRegistry, TenantConfig};
+use axum::extract::{Path as UrlPath, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::idempotency::{is_mutation, IdempotencyStore};
+use crate::{create_schema_with_limits, ApiSchema, QueryLimits};
+
+/// Header naming the tenant of a request sent to `/graphql`
+pub const TENANT_HEADER: &str = "x-tenant";
+
+/// A catalog served by the [`TenantManager`]
+pub struct Tenant {
+    /// Configuration of the tenant
+    pub config: TenantConfig,
+    /// Registry holding the tenant's catalog
+    pub registry: SharedRegistry,
+    schema: ApiSchema,
+    permits: Option<Arc<Semaphore>>,
+    idempotency: IdempotencyStore,
+}
+
+impl Tenant {
+    /// Runs a GraphQL request against the tenant's catalog
+    ///
+    /// Fails with `429 Too Many Requests` if the tenant's concurrency limit is reached.
+    pub async fn execute(
+        &self,
+        request: async_graphql::Request,
+    ) -> Result<async_graphql::Response, StatusCode> {
+        let _permit = match &self.permits {
+            Some(permits) => Some(
+                permits.clone().try_acquire_owned().map_err(|_| StatusCode::TOO_MANY_REQUESTS)?,
+            ),
+            None => None,
+        };
+        Ok(self.schema.execute(request).await)
+    }
+
+    /// Gets the entity tag of the tenant's current catalog
+    pub async fn etag(&self) -> Result<String, StatusCode> {
+        let fingerprint = self.registry.lock().await.fingerprint().map_err(|err| {
+            tracing::error!(
+                "Failed to fingerprint catalog of tenant '{}': {}",
+                self.config.id,
+                err
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        Ok(format!("\"{}\"", fingerprint))
+    }
+
+    /// Runs a GraphQL request, answering unchanged queries with `304 Not Modified`
+    pub async fn respond(
+        &self,
+        headers: &HeaderMap,
+        request: async_graphql::Request,
+    ) -> Result<Response, StatusCode> {
+        let mutates = is_mutation(&request);
+        let mut etag = self.etag().await?;
+        let if_none_match = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok());
+        if !mutates && if_none_match == Some(etag.as_str()) {
+            return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+        }
+
+        let mut response =
+            self.idempotency.respond(headers, request, |request| self.execute(request)).await?;
+        if mutates {
+            etag = self.etag().await?;
+        }
+        if let Ok(value) = HeaderValue::from_str(&etag) {
+            response.headers_mut().insert(header::ETAG, value);
+        }
+        Ok(response)
+    }
+}
+
+/// Routes API requests to the registries of isolated tenants
+#[derive(Default)]
+pub struct TenantManager {
+    tenants: HashMap<String, Tenant>,
+}
+
+impl TenantManager {
+    /// Creates a manager without tenants
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry for each tenant, working in per-tenant directories below `root`
+    pub fn from_configs(configs: Vec<TenantConfig>, root: &Path) -> aureacore::Result<Self> {
+        let mut manager = Self::new();
+        for config in configs {
+            let registry = config.registry(root)?;
+            manager.add_tenant(config, Arc::new(Mutex::new(registry)));
+        }
+        Ok(manager)
+    }
+
+    /// Serves a tenant's catalog from the given registry, replacing a tenant with the same id
+    pub fn add_tenant(&mut self, config: TenantConfig, registry: SharedRegistry) {
+        let permits = config.limits.max_concurrent_requests.map(|n| Arc::new(Semaphore::new(n)));
+        let schema =
+            create_schema_with_limits(registry.clone(), &QueryLimits::from(&config.limits));
+        let idempotency = IdempotencyStore::default();
+        let tenant = Tenant { schema, registry, permits, idempotency, config };
+        self.tenants.insert(tenant.config.id.clone(), tenant);
+    }
+
+    /// Gets a tenant by id
+    pub fn tenant(&self, id: &str) -> Option<&Tenant> {
+        self.tenants.get(id)
+    }
+
+    /// Lists the ids of all tenants
+    pub fn tenant_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.tenants.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Resolves the tenant named in the `X-Tenant` header
+    pub fn resolve(&self, headers: &HeaderMap) -> Result<&Tenant, StatusCode> {
+        let id = headers
+            .get(TENANT_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        self.tenant(id).ok_or(StatusCode::NOT_FOUND)
+    }
+
+    /// Builds the HTTP router serving all tenants
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/graphql", post(graphql_by_header))
+            .route("/tenants/{tenant}/graphql", post(graphql_by_path))
+            .with_state(Arc::new(self))
+    }
+}
+
+/// Handles a GraphQL request for the tenant named in the header
+async fn graphql_by_header(
+    State(manager): State<Arc<TenantManager>>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<Response, StatusCode> {
+    manager.resolve(&headers)?.respond(&headers, request).await
+}
+
+/// Handles a GraphQL request for the tenant named in the path
+async fn graphql_by_path(
+    State(manager): State<Arc<TenantManager>>,
+    UrlPath(tenant): UrlPath<String>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<Response, StatusCode> {
+    manager.tenant(&tenant).ok_or(StatusCode::NOT_FOUND)?.respond(&headers, request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn tenant(id: &str, limits: &str) -> TenantConfig {
+        serde_json::from_str(&format!(
+            r#"{{"id": "{}", "repo_url": "", "limits": {}}}"#,
+            id, limits
+        ))
+        .unwrap()
+    }
+
+    fn request(uri: &str, tenant: Option<&str>) -> Request<Body> {
+        let mut builder = Request::post(uri).header("content-type", "application/json");
+        if let Some(tenant) = tenant {
+            builder = builder.header(TENANT_HEADER, tenant);
+        }
+        builder.body(Body::from(r#"{"query": "{ services { name } }"}"#)).unwrap()
+    }
+
+    async fn body(response: axum::response::Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_routes_requests_by_tenant() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = TenantManager::from_configs(
+            vec![tenant("payments", "{}"), tenant("search", "{}")],
+            temp_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(manager.tenant_ids(), vec!["payments", "search"]);
+
+        let registry = manager.tenant("payments").unwrap().registry.clone();
+        let path = temp_dir.path().join("billing.yaml");
+        std::fs::write(
+            &path,
+            "name: billing\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        registry
+            .lock()
+            .await
+            .register_service("billing", &format!(r#"{{"config_path": "{}"}}"#, path.display()))
+            .unwrap();
+        let router = manager.router();
+
+        let response = router.clone().oneshot(request("/graphql", Some("payments"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG].clone();
+        assert_eq!(etag, format!("\"{}\"", registry.lock().await.fingerprint().unwrap()));
+        assert_eq!(body(response).await, r#"{"data":{"services":[{"name":"billing"}]}}"#);
+
+        let mut cached = request("/graphql", Some("payments"));
+        cached.headers_mut().insert(header::IF_NONE_MATCH, etag);
+        let response = router.clone().oneshot(cached).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response =
+            router.clone().oneshot(request("/tenants/search/graphql", None)).await.unwrap();
+        assert_eq!(body(response).await, r#"{"data":{"services":[]}}"#);
+
+        let response = router.clone().oneshot(request("/graphql", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = router.oneshot(request("/tenants/other/graphql", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = TenantManager::from_configs(
+            vec![tenant("payments", r#"{"max_concurrent_requests": 1}"#)],
+            temp_dir.path(),
+        )
+        .unwrap();
+        let tenant = manager.tenant("payments").unwrap();
+
+        let held = tenant.permits.clone().unwrap().try_acquire_owned().unwrap();
+        let rejected = tenant.execute(async_graphql::Request::new("{ services { name } }")).await;
+        assert_eq!(rejected.unwrap_err(), StatusCode::TOO_MANY_REQUESTS);
+        drop(held);
+        assert!(tenant.execute(async_graphql::Request::new("{ services { name } }")).await.is_ok());
+    }
+}
@@ -0,0 +1,558 @@
+//! Typed Rust client for the AureaCore service catalog API
+//!
+//! ```no_run
+//! # async fn run() -> aureacore_client::Result<()> {
+//! let client = aureacore_client::Client::builder("http://catalog:8080/graphql")
+//!     .with_token("secret")
+//!     .with_retries(3)
+//!     .build()?;
+//! for service in client.list_services().await? {
+//!     println!("{} {}", service.name, service.state);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Errors returned by the client
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The request could not be sent or its response not read
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The server answered with an unsuccessful HTTP status
+    #[error("Server returned {0}")]
+    Status(StatusCode),
+    /// The API rejected the operation
+    #[error("API error: {message}")]
+    Api {
+        /// Machine-readable error code, if the server sent one
+        code: Option<String>,
+        /// Error message
+        message: String,
+    },
+    /// The response did not have the expected shape
+    #[error("Invalid response: {0}")]
+    Decode(String),
+}
+
+impl ClientError {
+    /// Gets the machine-readable code of an API error, such as `service_not_found`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ClientError::Api { code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Checks whether sending the request again may succeed
+    fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Http(err) => err.is_connect() || err.is_timeout(),
+            ClientError::Status(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Result type of client operations
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// A service registered in the catalog
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+    /// Name of the service
+    pub name: String,
+    /// Version declared in the service definition
+    pub version: Option<String>,
+    /// Description from the service definition
+    pub description: Option<String>,
+    /// Namespace of the service
+    pub namespace: Option<String>,
+    /// System the service belongs to
+    pub system: Option<String>,
+    /// Domain the service belongs to
+    pub domain: Option<String>,
+    /// Current state of the service
+    pub state: String,
+    /// Error message of the current status, if any
+    pub error_message: Option<String>,
+    /// Warnings of the current status
+    pub warnings: Vec<String>,
+    /// Last time the status was computed
+    pub last_checked: DateTime<Utc>,
+}
+
+/// A service that failed validation
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ValidationFailure {
+    /// Name of the service
+    pub service: String,
+    /// Validation error message
+    pub message: String,
+}
+
+/// Warnings reported for a service during validation
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ValidationWarnings {
+    /// Name of the service, or `system` for catalog-wide warnings
+    pub service: String,
+    /// Warning messages
+    pub messages: Vec<String>,
+}
+
+/// Result of a validation run
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ValidationSummary {
+    /// Services that validated successfully
+    pub successful: Vec<String>,
+    /// Services that failed validation
+    pub failed: Vec<ValidationFailure>,
+    /// Warnings grouped by service
+    pub warnings: Vec<ValidationWarnings>,
+    /// When the validation ran
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A service affected by a change to another service
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImpactedService {
+    /// Name of the affected service
+    pub service: String,
+    /// Whether the affected service requires the changed one
+    pub required: bool,
+    /// Services from the changed one to the affected one, both ends included
+    pub path: Vec<String>,
+}
+
+/// Outcome of a batch sync
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SyncResult {
+    /// Services that were not registered before
+    pub added: Vec<String>,
+    /// Registered services whose config was replaced
+    pub updated: Vec<String>,
+    /// Services removed because they were absent from the batch
+    pub removed: Vec<String>,
+    /// Validation of the synced services and their dependents
+    pub validation: ValidationSummary,
+}
+
+/// Outcome of revalidating a service and the services depending on it
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Revalidation {
+    /// The revalidated service, with its new status
+    pub service: Service,
+    /// Services revalidated, sorted by name
+    pub revalidated: Vec<String>,
+    /// Validation of the revalidated services
+    pub validation: ValidationSummary,
+}
+
+/// Header carrying the key the server deduplicates retried mutations by
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Fields selected for services
+const SERVICE_FIELDS: &str = "name version description namespace system domain state \
+                              errorMessage warnings lastChecked";
+
+/// Fields selected for validation summaries
+const SUMMARY_FIELDS: &str = "successful failed { service message } \
+                              warnings { service messages } timestamp";
+
+/// Configures a [`Client`]
+pub struct ClientBuilder {
+    url: String,
+    token: Option<String>,
+    headers: Vec<(String, String)>,
+    retries: u32,
+    backoff: Duration,
+    timeout: Duration,
+}
+
+impl ClientBuilder {
+    /// Sends a bearer token with every request
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Sends an additional header with every request, such as `X-Tenant`
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Retries requests failing with connection errors, timeouts, `429` or `5xx` responses
+    ///
+    /// Every attempt of a mutation carries the same `Idempotency-Key`, so the server
+    /// applies it once.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled for every further attempt
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets the timeout of a single request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Builds the client
+    pub fn build(self) -> Result<Client> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                ClientError::Decode(format!("Invalid header name '{}': {}", name, e))
+            })?;
+            let value = reqwest::header::HeaderValue::from_str(value).map_err(|e| {
+                ClientError::Decode(format!("Invalid value of header {}: {}", name, e))
+            })?;
+            headers.insert(name, value);
+        }
+        let http =
+            reqwest::Client::builder().default_headers(headers).timeout(self.timeout).build()?;
+        Ok(Client {
+            http,
+            url: self.url,
+            token: self.token,
+            retries: self.retries,
+            backoff: self.backoff,
+        })
+    }
+}
+
+/// Client for the GraphQL API of a catalog server
+#[derive(Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Client {
+    /// Starts configuring a client for the GraphQL endpoint at `url`
+    pub fn builder(url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            url: url.into(),
+            token: None,
+            headers: Vec::new(),
+            retries: 0,
+            backoff: Duration::from_millis(200),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Lists all services, sorted by name
+    pub async fn list_services(&self) -> Result<Vec<Service>> {
+        let query = format!("{{ services {{ {} }} }}", SERVICE_FIELDS);
+        self.execute(&query, json!({}), "services").await
+    }
+
+    /// Gets a service by name, `None` if it is not registered
+    pub async fn get_service(&self, name: &str) -> Result<Option<Service>> {
+        let query =
+            format!("query($name: String!) {{ service(name: $name) {{ {} }} }}", SERVICE_FIELDS);
+        self.execute(&query, json!({ "name": name }), "service").await
+    }
+
+    /// Registers a service from its catalog config
+    pub async fn register_service(&self, name: &str, config: &str) -> Result<Service> {
+        let query = format!(
+            "mutation($name: String!, $config: String!) {{ \
+             registerService(name: $name, config: $config) {{ {} }} }}",
+            SERVICE_FIELDS
+        );
+        self.execute(&query, json!({ "name": name, "config": config }), "registerService").await
+    }
+
+    /// Upserts (name, config) pairs atomically, with `prune` removing services absent from them
+    pub async fn sync_services(
+        &self,
+        services: &[(&str, &str)],
+        prune: bool,
+    ) -> Result<SyncResult> {
+        let query = format!(
+            "mutation($input: [ServiceInput!]!, $prune: Boolean!) {{ \
+             syncServices(input: $input, prune: $prune) {{ added updated removed validation {{ {} }} }} }}",
+            SUMMARY_FIELDS
+        );
+        let input: Vec<Value> = services
+            .iter()
+            .map(|(name, config)| json!({ "name": name, "config": config }))
+            .collect();
+        self.execute(&query, json!({ "input": input, "prune": prune }), "syncServices").await
+    }
+
+    /// Validates the given services and optionally their dependents, all services if none are given
+    pub async fn validate(
+        &self,
+        names: &[&str],
+        include_dependents: bool,
+    ) -> Result<ValidationSummary> {
+        let query = format!(
+            "mutation($names: [String!]!, $dependents: Boolean!) {{ \
+             validateServices(names: $names, includeDependents: $dependents) {{ {} }} }}",
+            SUMMARY_FIELDS
+        );
+        let variables = json!({ "names": names, "dependents": include_dependents });
+        self.execute(&query, variables, "validateServices").await
+    }
+
+    /// Revalidates a service after its definition changed, and optionally its dependents
+    pub async fn revalidate(&self, name: &str, include_dependents: bool) -> Result<Revalidation> {
+        let query = format!(
+            "mutation($name: String!, $dependents: Boolean!) {{ \
+             revalidate(service: $name, includeDependents: $dependents) {{ \
+             service {{ {} }} revalidated validation {{ {} }} }} }}",
+            SERVICE_FIELDS, SUMMARY_FIELDS
+        );
+        let variables = json!({ "name": name, "dependents": include_dependents });
+        self.execute(&query, variables, "revalidate").await
+    }
+
+    /// Lists the services affected by a change to the given service
+    pub async fn impact(&self, name: &str) -> Result<Vec<ImpactedService>> {
+        let query = "query($name: String!) { impact(name: $name) { service required path } }";
+        self.execute(query, json!({ "name": name }), "impact").await
+    }
+
+    /// Runs a GraphQL operation and decodes one field of its data
+    ///
+    /// Mutations are sent with an idempotency key generated for this call.
+    pub async fn execute<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+        field: &str,
+    ) -> Result<T> {
+        let body = json!({ "query": query, "variables": variables });
+        let idempotency_key = is_mutation(query).then(|| Uuid::now_v7().to_string());
+        let mut attempt = 0;
+        let response = loop {
+            match self.send(&body, idempotency_key.as_deref()).await {
+                Err(err) if err.is_retryable() && attempt < self.retries => {
+                    let delay = self.backoff * 2u32.saturating_pow(attempt);
+                    tracing::debug!("Retrying catalog request in {:?}: {}", delay, err);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => break result?,
+            }
+        };
+
+        if let Some(error) = response["errors"].as_array().and_then(|errors| errors.first()) {
+            return Err(ClientError::Api {
+                code: error["extensions"]["code"].as_str().map(str::to_string),
+                message: error["message"].as_str().unwrap_or("unknown error").to_string(),
+            });
+        }
+        serde_json::from_value(response["data"][field].clone())
+            .map_err(|e| ClientError::Decode(format!("Unexpected '{}' data: {}", field, e)))
+    }
+
+    /// Posts a request body once
+    async fn send(&self, body: &Value, idempotency_key: Option<&str>) -> Result<Value> {
+        let mut request = self.http.post(&self.url).json(body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(key) = idempotency_key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(ClientError::Status(response.status()));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// Checks whether a GraphQL document is a mutation
+fn is_mutation(query: &str) -> bool {
+    query.trim_start().starts_with("mutation")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use aureacore::registry::{ServiceRegistry, TenantConfig};
+    use aureacore_api::tenant::TenantManager;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Serves a router on a local port, returning its base URL
+    async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_catalog_operations() {
+        let temp_dir = TempDir::new().unwrap();
+        let definition = |name: &str, dependency: &str| {
+            let path = temp_dir.path().join(format!("{}.yaml", name));
+            std::fs::write(
+                &path,
+                format!(
+                    "name: {}\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n{}",
+                    name, dependency
+                ),
+            )
+            .unwrap();
+            format!(r#"{{"config_path": "{}"}}"#, path.display())
+        };
+        let tenant: TenantConfig =
+            serde_json::from_str(r#"{"id": "payments", "repo_url": ""}"#).unwrap();
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let mut manager = TenantManager::new();
+        manager.add_tenant(tenant, Arc::new(tokio::sync::Mutex::new(registry)));
+        let url = serve(manager.router()).await;
+
+        let client = Client::builder(format!("{}/graphql", url))
+            .with_header("X-Tenant", "payments")
+            .build()
+            .unwrap();
+        let ledger = client.register_service("ledger", &definition("ledger", "")).await.unwrap();
+        assert_eq!(ledger.version.as_deref(), Some("1.0.0"));
+        client
+            .register_service(
+                "billing",
+                &definition("billing", "dependencies:\n  - service: ledger\n"),
+            )
+            .await
+            .unwrap();
+
+        let names: Vec<_> =
+            client.list_services().await.unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["billing", "ledger"]);
+        assert_eq!(client.get_service("missing").await.unwrap(), None);
+
+        let summary = client.validate(&[], false).await.unwrap();
+        assert_eq!(summary.successful.len(), 2);
+        let summary = client.validate(&["ledger"], true).await.unwrap();
+        assert!(summary.successful.contains(&"billing".to_string()));
+        let revalidation = client.revalidate("ledger", true).await.unwrap();
+        assert_eq!(revalidation.service.name, "ledger");
+        assert_eq!(revalidation.revalidated, vec!["billing", "ledger"]);
+
+        let impact = client.impact("ledger").await.unwrap();
+        assert_eq!(impact[0].service, "billing");
+        assert_eq!(impact[0].path, vec!["ledger", "billing"]);
+
+        let err = client.impact("missing").await.unwrap_err();
+        assert_eq!(err.code(), Some("service_not_found"));
+
+        let orders = definition("orders", "");
+        let result = client.sync_services(&[("orders", &orders)], false).await.unwrap();
+        assert_eq!(result.added, vec!["orders"]);
+        assert_eq!(result.validation.successful, vec!["orders"]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_with_auth() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let router = Router::new().route(
+            "/graphql",
+            post(move |headers: HeaderMap| async move {
+                assert_eq!(headers["authorization"], "Bearer secret");
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return Err(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+                }
+                Ok(Json(json!({ "data": { "services": [] } })))
+            }),
+        );
+        let url = format!("{}/graphql", serve(router).await);
+
+        let client = Client::builder(url.clone())
+            .with_token("secret")
+            .with_retries(2)
+            .with_backoff(Duration::from_millis(1))
+            .build()
+            .unwrap();
+        assert!(client.list_services().await.unwrap().is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        calls.store(0, Ordering::SeqCst);
+        let client = Client::builder(url).with_token("secret").build().unwrap();
+        let err = client.list_services().await.unwrap_err();
+        assert!(matches!(err, ClientError::Status(StatusCode::SERVICE_UNAVAILABLE)));
+    }
+
+    #[tokio::test]
+    async fn test_mutation_retries_keep_idempotency_key() {
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let seen = keys.clone();
+        let router = Router::new().route(
+            "/graphql",
+            post(move |headers: HeaderMap| async move {
+                let key =
+                    headers.get("idempotency-key").map(|key| key.to_str().unwrap().to_string());
+                let mut seen = seen.lock().unwrap();
+                seen.push(key);
+                if seen.len() % 3 != 0 {
+                    return Err(axum::http::StatusCode::BAD_GATEWAY);
+                }
+                Ok(Json(json!({ "data": {
+                    "services": [],
+                    "validateServices": {
+                        "successful": [], "failed": [], "warnings": [],
+                        "timestamp": "2024-01-01T00:00:00Z"
+                    }
+                } })))
+            }),
+        );
+        let url = format!("{}/graphql", serve(router).await);
+        let client = Client::builder(url)
+            .with_retries(2)
+            .with_backoff(Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        // Every attempt of one call carries the same key, and the next call a new one
+        client.validate(&[], false).await.unwrap();
+        client.validate(&[], false).await.unwrap();
+        let sent = keys.lock().unwrap().clone();
+        assert!(sent.iter().all(Option::is_some));
+        assert!(sent[..3].iter().all(|key| *key == sent[0]));
+        assert!(sent[3..].iter().all(|key| *key == sent[3]));
+        assert_ne!(sent[0], sent[3]);
+
+        // Queries are retried without a key
+        keys.lock().unwrap().clear();
+        client.list_services().await.unwrap();
+        assert_eq!(*keys.lock().unwrap(), vec![None, None, None]);
+    }
+}
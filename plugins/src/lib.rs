@@ -4,6 +4,9 @@ use std::error::Error;
 
 use aureacore_core::Service;
 
+pub mod terraform;
+pub mod trace;
+
 /// Trait for implementing service discovery plugins
 #[async_trait::async_trait]
 pub trait ServiceDiscovery: Send + Sync {
@@ -0,0 +1,340 @@
+//! Infrastructure discovery from Terraform state
+//!
+//! Managed resources of known types (load balancers, databases, queues, caches and
+//! buckets) are turned into external catalog entries, so application services can
+//! declare dependencies on the infrastructure they use.
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use aureacore::registry::ServiceRegistry;
+use aureacore::templates::render_definition;
+use aureacore::{ServiceSchema, ServiceType};
+use aureacore_core::Service;
+use serde_json::Value;
+
+use crate::ServiceDiscovery;
+
+/// Where Terraform state is read from
+#[derive(Debug, Clone)]
+pub enum StateSource {
+    /// A local `terraform.tfstate` file
+    File(PathBuf),
+    /// A remote backend serving state over HTTP, such as the `http` backend
+    Http {
+        /// URL the state is downloaded from
+        url: String,
+        /// Bearer token sent with the request
+        token: Option<String>,
+    },
+}
+
+/// Kind of infrastructure discovered in Terraform state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfrastructureKind {
+    /// Load balancer or forwarding rule
+    LoadBalancer,
+    /// Database instance or cluster
+    Database,
+    /// Message queue or topic
+    Queue,
+    /// In-memory cache
+    Cache,
+    /// Object storage bucket
+    Bucket,
+}
+
+impl InfrastructureKind {
+    /// Classifies a Terraform resource type, if it is a supported kind of infrastructure
+    pub fn for_resource_type(resource_type: &str) -> Option<Self> {
+        let kind = match resource_type {
+            "aws_lb" | "aws_alb" | "aws_elb" | "google_compute_forwarding_rule" | "azurerm_lb" => {
+                Self::LoadBalancer
+            }
+            "aws_db_instance"
+            | "aws_rds_cluster"
+            | "aws_dynamodb_table"
+            | "google_sql_database_instance"
+            | "azurerm_postgresql_flexible_server"
+            | "azurerm_mssql_server" => Self::Database,
+            "aws_sqs_queue"
+            | "aws_sns_topic"
+            | "aws_msk_cluster"
+            | "google_pubsub_topic"
+            | "azurerm_servicebus_queue" => Self::Queue,
+            "aws_elasticache_cluster"
+            | "aws_elasticache_replication_group"
+            | "google_redis_instance"
+            | "azurerm_redis_cache" => Self::Cache,
+            "aws_s3_bucket" | "google_storage_bucket" | "azurerm_storage_container" => Self::Bucket,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+impl fmt::Display for InfrastructureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::LoadBalancer => "load-balancer",
+            Self::Database => "database",
+            Self::Queue => "queue",
+            Self::Cache => "cache",
+            Self::Bucket => "bucket",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A managed infrastructure resource found in Terraform state
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfrastructureResource {
+    /// Catalog name of the resource
+    pub name: String,
+    /// Terraform address, e.g. `module.db.aws_db_instance.main`
+    pub address: String,
+    /// Terraform resource type
+    pub resource_type: String,
+    /// Kind of infrastructure
+    pub kind: InfrastructureKind,
+}
+
+impl InfrastructureResource {
+    /// Builds the external catalog entry for the resource
+    pub fn definition(&self) -> ServiceSchema {
+        let metadata = HashMap::from([
+            ("external".to_string(), Value::Bool(true)),
+            ("terraform_address".to_string(), Value::String(self.address.clone())),
+            ("terraform_type".to_string(), Value::String(self.resource_type.clone())),
+        ]);
+        ServiceSchema {
+            name: self.name.clone(),
+            version: "0.0.0".to_string(),
+            description: Some(format!("Terraform-managed {} {}", self.kind, self.address)),
+            owner: None,
+            system: None,
+            domain: None,
+            oncall: None,
+            cost_center: None,
+            monthly_cost_estimate: None,
+            documentation_url: None,
+            service_type: ServiceType::Other(self.kind.to_string()),
+            runtime: None,
+            endpoints: Vec::new(),
+            exposure: None,
+            dependencies: None,
+            consumes: Vec::new(),
+            consumables: Vec::new(),
+            capacity: None,
+            contract_tests: Vec::new(),
+            feature_flags: Vec::new(),
+            sensitive_metadata: Vec::new(),
+            metadata,
+            archetype: None,
+            ignores: Vec::new(),
+        }
+    }
+}
+
+/// Discovers infrastructure from Terraform state
+pub struct TerraformDiscovery {
+    source: StateSource,
+    client: reqwest::Client,
+}
+
+impl TerraformDiscovery {
+    /// Create a discovery plugin reading state from the given source
+    pub fn new(source: StateSource) -> Self {
+        Self { source, client: reqwest::Client::new() }
+    }
+
+    /// Read the state and list the supported infrastructure it manages
+    pub async fn resources(&self) -> Result<Vec<InfrastructureResource>, Box<dyn Error>> {
+        let state: Value = match &self.source {
+            StateSource::File(path) => {
+                serde_json::from_str(&tokio::fs::read_to_string(path).await?)?
+            }
+            StateSource::Http { url, token } => {
+                let mut request = self.client.get(url);
+                if let Some(token) = token {
+                    request = request.bearer_auth(token);
+                }
+                request.send().await?.error_for_status()?.json().await?
+            }
+        };
+        parse_state(&state)
+    }
+}
+
+#[async_trait::async_trait]
+impl ServiceDiscovery for TerraformDiscovery {
+    async fn discover(&self) -> Result<Vec<Service>, Box<dyn Error>> {
+        Ok(self
+            .resources()
+            .await?
+            .into_iter()
+            .map(|r| {
+                let description = format!("Terraform-managed {} {}", r.kind, r.address);
+                Service::new(r.name, "0.0.0").with_description(description)
+            })
+            .collect())
+    }
+}
+
+/// Extracts supported managed resources from Terraform state (format version 4)
+fn parse_state(state: &Value) -> Result<Vec<InfrastructureResource>, Box<dyn Error>> {
+    if state["version"].as_u64() != Some(4) {
+        return Err("Unsupported Terraform state version, expected 4".into());
+    }
+
+    let mut resources = BTreeMap::new();
+    for resource in state["resources"].as_array().into_iter().flatten() {
+        if resource["mode"] != "managed" {
+            continue;
+        }
+        let (Some(resource_type), Some(resource_name)) =
+            (resource["type"].as_str(), resource["name"].as_str())
+        else {
+            continue;
+        };
+        let Some(kind) = InfrastructureKind::for_resource_type(resource_type) else {
+            continue;
+        };
+
+        let mut base = format!("{}.{}", resource_type, resource_name);
+        if let Some(module) = resource["module"].as_str() {
+            base = format!("{}.{}", module, base);
+        }
+        for instance in resource["instances"].as_array().into_iter().flatten() {
+            let address = match &instance["index_key"] {
+                Value::Null => base.clone(),
+                key => format!("{}[{}]", base, key),
+            };
+            let attributes = &instance["attributes"];
+            let label = ["name", "identifier", "bucket", "cluster_id", "replication_group_id"]
+                .iter()
+                .find_map(|key| attributes[*key].as_str())
+                .unwrap_or(resource_name);
+            let name = catalog_name(label);
+            if name.is_empty() {
+                continue;
+            }
+            resources.insert(
+                name.clone(),
+                InfrastructureResource {
+                    name,
+                    address,
+                    resource_type: resource_type.to_string(),
+                    kind,
+                },
+            );
+        }
+    }
+    Ok(resources.into_values().collect())
+}
+
+/// Turns a cloud resource name into a lowercase, dash-separated catalog name
+fn catalog_name(label: &str) -> String {
+    let label = label.rsplit(['/', ':']).next().unwrap_or(label);
+    label
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Registers discovered resources as external catalog entries
+///
+/// A definition file per resource is written to `dir`, replacing earlier discoveries.
+/// Returns the names of the registered entries.
+pub fn register_resources(
+    registry: &mut ServiceRegistry,
+    resources: &[InfrastructureResource],
+    dir: &Path,
+) -> aureacore::Result<Vec<String>> {
+    std::fs::create_dir_all(dir)?;
+    let dir = dir.canonicalize()?;
+    let mut registered = Vec::new();
+    for resource in resources {
+        let path = dir.join(format!("{}.yaml", resource.name));
+        std::fs::write(&path, render_definition(&resource.definition())?)?;
+        let config = serde_json::json!({ "config_path": path }).to_string();
+        registry.register_service(&resource.name, &config)?;
+        registered.push(resource.name.clone());
+    }
+    Ok(registered)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn state() -> Value {
+        json!({
+            "version": 4,
+            "resources": [
+                {
+                    "mode": "managed", "type": "aws_db_instance", "name": "main",
+                    "module": "module.billing",
+                    "instances": [{"attributes": {"identifier": "Billing_DB"}}]
+                },
+                {
+                    "mode": "managed", "type": "aws_sqs_queue", "name": "jobs",
+                    "instances": [
+                        {"index_key": 0, "attributes": {"name": "jobs-0"}},
+                        {"index_key": 1, "attributes": {"name": "jobs-1"}}
+                    ]
+                },
+                {"mode": "managed", "type": "aws_iam_role", "name": "app", "instances": [{}]},
+                {"mode": "data", "type": "aws_s3_bucket", "name": "logs", "instances": [{}]}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_parse_state() {
+        let resources = parse_state(&state()).unwrap();
+        let found: Vec<_> =
+            resources.iter().map(|r| (r.name.as_str(), r.address.as_str(), r.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("billing-db", "module.billing.aws_db_instance.main", InfrastructureKind::Database),
+                ("jobs-0", "aws_sqs_queue.jobs[0]", InfrastructureKind::Queue),
+                ("jobs-1", "aws_sqs_queue.jobs[1]", InfrastructureKind::Queue),
+            ]
+        );
+        assert!(parse_state(&json!({"version": 3})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_register_discovered_resources() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state_path = temp_dir.path().join("terraform.tfstate");
+        std::fs::write(&state_path, state().to_string()).unwrap();
+
+        let discovery = TerraformDiscovery::new(StateSource::File(state_path));
+        let services = discovery.discover().await.unwrap();
+        assert_eq!(services.len(), 3);
+
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let resources = discovery.resources().await.unwrap();
+        let registered =
+            register_resources(&mut registry, &resources, &temp_dir.path().join("infra")).unwrap();
+        assert_eq!(registered, vec!["billing-db", "jobs-0", "jobs-1"]);
+
+        let db = registry.get_service("billing-db").unwrap();
+        assert_eq!(db.status.error_message, None);
+        let definition = db.definition().unwrap();
+        assert_eq!(definition.metadata["external"], Value::Bool(true));
+        assert!(matches!(definition.service_type, ServiceType::Other(kind) if kind == "database"));
+    }
+}
@@ -0,0 +1,415 @@
+//! Dependency suggestions from distributed tracing data
+//!
+//! A [`TraceSource`] reports service-to-service calls observed in traces. Comparing them
+//! with the dependencies declared in the catalog yields a [`SuggestionReport`] listing
+//! calls without a declared dependency and declared dependencies that are never called.
+//! The report is meant for review; it never changes service definitions.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Calls from one service to another observed in traces
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObservedCall {
+    /// Service making the calls
+    pub caller: String,
+    /// Service receiving the calls
+    pub callee: String,
+    /// Number of calls observed
+    pub call_count: u64,
+}
+
+/// Trait for sources of observed service-to-service calls
+#[async_trait::async_trait]
+pub trait TraceSource: Send + Sync {
+    /// Fetch the calls observed by the source
+    async fn observed_calls(&self) -> Result<Vec<ObservedCall>, Box<dyn Error>>;
+}
+
+/// Reads calls from the dependency API of a Jaeger query service
+pub struct JaegerSource {
+    base_url: String,
+    lookback: Duration,
+    client: reqwest::Client,
+}
+
+impl JaegerSource {
+    /// Create a source for the Jaeger query service at the given URL, looking back one day
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            lookback: Duration::from_secs(24 * 60 * 60),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set how far back calls are considered
+    pub fn with_lookback(mut self, lookback: Duration) -> Self {
+        self.lookback = lookback;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl TraceSource for JaegerSource {
+    async fn observed_calls(&self) -> Result<Vec<ObservedCall>, Box<dyn Error>> {
+        let end = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let url = format!("{}/api/dependencies", self.base_url.trim_end_matches('/'));
+        let response: Value = self
+            .client
+            .get(url)
+            .query(&[
+                ("endTs", end.as_millis().to_string()),
+                ("lookback", self.lookback.as_millis().to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_jaeger_dependencies(&response)
+    }
+}
+
+/// Reads calls from the service graph metrics Tempo writes to Prometheus
+pub struct TempoSource {
+    prometheus_url: String,
+    window: String,
+    client: reqwest::Client,
+}
+
+impl TempoSource {
+    /// Create a source querying the Prometheus API at the given URL over the last day
+    pub fn new(prometheus_url: impl Into<String>) -> Self {
+        Self {
+            prometheus_url: prometheus_url.into(),
+            window: "1d".to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set the range over which calls are counted, as a Prometheus duration such as `6h`
+    pub fn with_window(mut self, window: impl Into<String>) -> Self {
+        self.window = window.into();
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl TraceSource for TempoSource {
+    async fn observed_calls(&self) -> Result<Vec<ObservedCall>, Box<dyn Error>> {
+        let query = format!(
+            "sum by (client, server) (increase(traces_service_graph_request_total[{}]))",
+            self.window
+        );
+        let url = format!("{}/api/v1/query", self.prometheus_url.trim_end_matches('/'));
+        let response: Value = self
+            .client
+            .get(url)
+            .query(&[("query", query)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_service_graph_metrics(&response)
+    }
+}
+
+/// Reads calls from spans exported as OTLP JSON, one export request per line
+///
+/// This is the format written by the OpenTelemetry Collector file exporter.
+pub struct OtlpFileSource {
+    path: PathBuf,
+}
+
+impl OtlpFileSource {
+    /// Create a source reading the given export file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl TraceSource for OtlpFileSource {
+    async fn observed_calls(&self) -> Result<Vec<ObservedCall>, Box<dyn Error>> {
+        let content = tokio::fs::read_to_string(&self.path).await?;
+        let mut exports = Vec::new();
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            exports.push(serde_json::from_str(line)?);
+        }
+        Ok(calls_from_otlp(&exports))
+    }
+}
+
+/// Parse the response of Jaeger's `/api/dependencies` endpoint
+fn parse_jaeger_dependencies(response: &Value) -> Result<Vec<ObservedCall>, Box<dyn Error>> {
+    let links = response["data"].as_array().ok_or("Jaeger response has no dependency data")?;
+    let calls = links
+        .iter()
+        .filter_map(|link| {
+            Some(ObservedCall {
+                caller: link["parent"].as_str()?.to_string(),
+                callee: link["child"].as_str()?.to_string(),
+                call_count: link["callCount"].as_u64().unwrap_or(0),
+            })
+        })
+        .collect();
+    Ok(merge_calls(calls))
+}
+
+/// Parse a Prometheus instant query over Tempo's service graph request counter
+fn parse_service_graph_metrics(response: &Value) -> Result<Vec<ObservedCall>, Box<dyn Error>> {
+    let series =
+        response["data"]["result"].as_array().ok_or("Prometheus response has no result")?;
+    let calls = series
+        .iter()
+        .filter_map(|sample| {
+            let count: f64 = sample["value"][1].as_str()?.parse().ok()?;
+            Some(ObservedCall {
+                caller: sample["metric"]["client"].as_str()?.to_string(),
+                callee: sample["metric"]["server"].as_str()?.to_string(),
+                call_count: count.round() as u64,
+            })
+        })
+        .collect();
+    Ok(merge_calls(calls))
+}
+
+/// Derive calls from OTLP JSON exports by linking spans to parents of another service
+fn calls_from_otlp(exports: &[Value]) -> Vec<ObservedCall> {
+    let mut span_services: HashMap<&str, &str> = HashMap::new();
+    let mut parents: Vec<(&str, &str)> = Vec::new();
+
+    let resource_spans = exports.iter().flat_map(|e| array(&e["resourceSpans"]));
+    for resource in resource_spans {
+        let Some(service) = array(&resource["resource"]["attributes"])
+            .find(|a| a["key"] == "service.name")
+            .and_then(|a| a["value"]["stringValue"].as_str())
+        else {
+            continue;
+        };
+        for span in array(&resource["scopeSpans"]).flat_map(|s| array(&s["spans"])) {
+            let Some(span_id) = span["spanId"].as_str() else { continue };
+            span_services.insert(span_id, service);
+            if let Some(parent) = span["parentSpanId"].as_str().filter(|p| !p.is_empty()) {
+                parents.push((parent, service));
+            }
+        }
+    }
+
+    let calls = parents
+        .into_iter()
+        .filter_map(|(parent, callee)| {
+            let caller = span_services.get(parent)?;
+            (*caller != callee).then(|| ObservedCall {
+                caller: caller.to_string(),
+                callee: callee.to_string(),
+                call_count: 1,
+            })
+        })
+        .collect();
+    merge_calls(calls)
+}
+
+/// Iterate over a JSON array, treating anything else as empty
+fn array(value: &Value) -> impl Iterator<Item = &Value> {
+    value.as_array().into_iter().flatten()
+}
+
+/// Sum the counts of calls between the same services, ordered by caller and callee
+fn merge_calls(calls: Vec<ObservedCall>) -> Vec<ObservedCall> {
+    let mut merged: BTreeMap<(String, String), u64> = BTreeMap::new();
+    for call in calls {
+        *merged.entry((call.caller, call.callee)).or_default() += call.call_count;
+    }
+    merged
+        .into_iter()
+        .map(|((caller, callee), call_count)| ObservedCall { caller, callee, call_count })
+        .collect()
+}
+
+/// A dependency observed in traces but not declared in the catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingDependency {
+    /// Service that should declare the dependency
+    pub service: String,
+    /// Service it was observed calling
+    pub dependency: String,
+    /// Number of calls observed
+    pub call_count: u64,
+}
+
+/// A declared dependency without any observed calls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnusedDependency {
+    /// Service declaring the dependency
+    pub service: String,
+    /// Dependency that was never called
+    pub dependency: String,
+}
+
+/// Differences between declared dependencies and observed calls, for review
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SuggestionReport {
+    /// Calls without a declared dependency
+    pub missing: Vec<MissingDependency>,
+    /// Declared dependencies that were never called
+    pub unused: Vec<UnusedDependency>,
+}
+
+impl SuggestionReport {
+    /// Compare declared dependencies, by service, with observed calls
+    ///
+    /// Calls involving services outside the catalog and calls seen fewer than `min_calls`
+    /// times are ignored. Dependencies are only flagged as unused for services that appear
+    /// in the trace data, since uninstrumented services provide no evidence either way.
+    pub fn build(
+        declared: &BTreeMap<String, BTreeSet<String>>,
+        observed: &[ObservedCall],
+        min_calls: u64,
+    ) -> Self {
+        let traced: BTreeSet<&str> =
+            observed.iter().flat_map(|c| [c.caller.as_str(), c.callee.as_str()]).collect();
+        let called: BTreeSet<(&str, &str)> =
+            observed.iter().map(|c| (c.caller.as_str(), c.callee.as_str())).collect();
+
+        let missing = merge_calls(observed.to_vec())
+            .into_iter()
+            .filter(|c| c.call_count >= min_calls && declared.contains_key(&c.callee))
+            .filter(|c| declared.get(&c.caller).is_some_and(|deps| !deps.contains(&c.callee)))
+            .map(|c| MissingDependency {
+                service: c.caller,
+                dependency: c.callee,
+                call_count: c.call_count,
+            })
+            .collect();
+
+        let unused = declared
+            .iter()
+            .filter(|(service, _)| traced.contains(service.as_str()))
+            .flat_map(|(service, deps)| deps.iter().map(move |dep| (service, dep)))
+            .filter(|(service, dep)| !called.contains(&(service.as_str(), dep.as_str())))
+            .map(|(service, dep)| UnusedDependency {
+                service: service.clone(),
+                dependency: dep.clone(),
+            })
+            .collect();
+
+        Self { missing, unused }
+    }
+
+    /// Check whether declared dependencies match the observed calls
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unused.is_empty()
+    }
+
+    /// Render the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Dependency suggestions\n");
+        if self.is_empty() {
+            out.push_str("\nDeclared dependencies match the observed calls.\n");
+            return out;
+        }
+        if !self.missing.is_empty() {
+            out.push_str("\n## Undeclared dependencies\n\n");
+            for m in &self.missing {
+                out.push_str(&format!(
+                    "- `{}` calls `{}` ({} calls) but does not declare it\n",
+                    m.service, m.dependency, m.call_count
+                ));
+            }
+        }
+        if !self.unused.is_empty() {
+            out.push_str("\n## Unused dependencies\n\n");
+            for u in &self.unused {
+                out.push_str(&format!(
+                    "- `{}` declares `{}` but was never seen calling it\n",
+                    u.service, u.dependency
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn call(caller: &str, callee: &str, call_count: u64) -> ObservedCall {
+        ObservedCall { caller: caller.into(), callee: callee.into(), call_count }
+    }
+
+    #[test]
+    fn test_parse_sources() {
+        let jaeger = json!({"data": [
+            {"parent": "checkout", "child": "billing", "callCount": 3},
+            {"parent": "checkout", "child": "billing", "callCount": 2},
+        ]});
+        assert_eq!(
+            parse_jaeger_dependencies(&jaeger).unwrap(),
+            vec![call("checkout", "billing", 5)]
+        );
+
+        let tempo = json!({"data": {"result": [
+            {"metric": {"client": "checkout", "server": "search"}, "value": [1700000000, "41.7"]},
+        ]}});
+        assert_eq!(
+            parse_service_graph_metrics(&tempo).unwrap(),
+            vec![call("checkout", "search", 42)]
+        );
+
+        let resource = |service: &str, spans: Value| {
+            json!({
+                "resource": {"attributes": [{"key": "service.name", "value": {"stringValue": service}}]},
+                "scopeSpans": [{"spans": spans}],
+            })
+        };
+        let export = json!({"resourceSpans": [
+            resource("checkout", json!([{"spanId": "a"}, {"spanId": "b", "parentSpanId": "a"}])),
+            resource("billing", json!([{"spanId": "c", "parentSpanId": "b"}])),
+        ]});
+        assert_eq!(calls_from_otlp(&[export]), vec![call("checkout", "billing", 1)]);
+    }
+
+    #[test]
+    fn test_suggestion_report() {
+        let declared = BTreeMap::from([
+            ("checkout".to_string(), BTreeSet::from(["billing".to_string(), "ledger".to_string()])),
+            ("billing".to_string(), BTreeSet::new()),
+            ("search".to_string(), BTreeSet::new()),
+            ("ledger".to_string(), BTreeSet::from(["audit".to_string()])),
+        ]);
+        let observed = vec![
+            call("checkout", "billing", 10),
+            call("checkout", "search", 7),
+            call("billing", "search", 1),
+            call("checkout", "payments-gateway", 4),
+        ];
+
+        let report = SuggestionReport::build(&declared, &observed, 2);
+        assert_eq!(
+            report.missing,
+            vec![MissingDependency {
+                service: "checkout".into(),
+                dependency: "search".into(),
+                call_count: 7
+            }]
+        );
+        // ledger never shows up in traces, so its declared dependency is not flagged
+        assert_eq!(
+            report.unused,
+            vec![UnusedDependency { service: "checkout".into(), dependency: "ledger".into() }]
+        );
+        assert!(report.to_markdown().contains("- `checkout` calls `search` (7 calls)"));
+    }
+}
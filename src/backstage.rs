@@ -0,0 +1,484 @@
+//! Conversion between service definitions and Backstage catalog entities
+//!
+//! Each service becomes a `Component` entity; services with endpoints also provide an
+//! `API` entity named `<service>-api`. Fields Backstage has no place for are kept in
+//! `aureacore.io/*` annotations so definitions survive a round trip.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::{Service, ServiceRegistry};
+use crate::schema::service::{Dependency, Endpoint, ServiceSchema, ServiceType};
+
+/// API version of the entities written
+pub const BACKSTAGE_API_VERSION: &str = "backstage.io/v1alpha1";
+
+/// Annotation holding the version of the service
+const VERSION_ANNOTATION: &str = "aureacore.io/version";
+/// Annotation holding the service type
+const SERVICE_TYPE_ANNOTATION: &str = "aureacore.io/service-type";
+/// Annotation listing the dependencies that are not required, comma separated
+const OPTIONAL_ANNOTATION: &str = "aureacore.io/optional-dependencies";
+/// Title of the link pointing to the documentation URL
+const DOCUMENTATION_LINK: &str = "Documentation";
+/// Lifecycle used for services without a `lifecycle` metadata value
+const DEFAULT_LIFECYCLE: &str = "production";
+/// Owner used for services without one, which Backstage requires
+const UNKNOWN_OWNER: &str = "unknown";
+
+/// A Backstage catalog entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackstageEntity {
+    /// Version of the entity format
+    pub api_version: String,
+    /// Kind of entity, `Component` or `API`
+    pub kind: String,
+    /// Name, namespace and descriptive fields
+    pub metadata: EntityMetadata,
+    /// Kind-specific fields
+    #[serde(default)]
+    pub spec: EntitySpec,
+}
+
+/// Metadata of a Backstage entity
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityMetadata {
+    /// Name of the entity
+    pub name: String,
+    /// Namespace of the entity, `default` if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Description of the entity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Annotations by key
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+    /// Tags of the entity
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Links shown on the entity page
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<EntityLink>,
+}
+
+/// A link shown on an entity page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityLink {
+    /// Target of the link
+    pub url: String,
+    /// Text of the link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Spec of a `Component` or `API` entity
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntitySpec {
+    /// Type, e.g. `service` for components or `openapi` for APIs
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<String>,
+    /// Lifecycle stage, e.g. `production`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<String>,
+    /// Owning group or user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// System the entity belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    /// Entities depended upon, as entity references
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// APIs provided by a component, as entity references
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provides_apis: Vec<String>,
+    /// APIs consumed by a component, as entity references
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes_apis: Vec<String>,
+    /// Definition of an API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+/// Gets the name of the API entity provided by a service
+pub fn api_entity_name(service: &str) -> String {
+    format!("{}-api", service)
+}
+
+/// Converts a service definition into its Component entity and, if it has endpoints,
+/// its API entity
+pub fn to_entities(
+    definition: &ServiceSchema,
+    namespace: Option<&str>,
+) -> Result<Vec<BackstageEntity>> {
+    let lifecycle = definition
+        .metadata
+        .get("lifecycle")
+        .and_then(|lifecycle| lifecycle.as_str())
+        .unwrap_or(DEFAULT_LIFECYCLE)
+        .to_string();
+    let owner = definition.owner.clone().unwrap_or_else(|| UNKNOWN_OWNER.to_string());
+    let tags = definition
+        .metadata
+        .get("tags")
+        .and_then(|tags| tags.as_array())
+        .map(|tags| tags.iter().filter_map(|tag| tag.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    let dependencies = definition.dependencies.as_deref().unwrap_or_default();
+
+    let mut annotations = BTreeMap::from([
+        (VERSION_ANNOTATION.to_string(), definition.version.clone()),
+        (SERVICE_TYPE_ANNOTATION.to_string(), service_type_name(&definition.service_type)),
+    ]);
+    let optional: Vec<&str> =
+        dependencies.iter().filter(|d| !d.required).map(|d| d.service.as_str()).collect();
+    if !optional.is_empty() {
+        annotations.insert(OPTIONAL_ANNOTATION.to_string(), optional.join(","));
+    }
+    let links = definition
+        .documentation_url
+        .iter()
+        .map(|url| EntityLink { url: url.clone(), title: Some(DOCUMENTATION_LINK.to_string()) })
+        .collect();
+    let consumed: BTreeSet<String> =
+        definition.consumes.iter().map(|consumed| api_ref(&consumed.service)).collect();
+
+    let component = BackstageEntity {
+        api_version: BACKSTAGE_API_VERSION.to_string(),
+        kind: "Component".to_string(),
+        metadata: EntityMetadata {
+            name: definition.name.clone(),
+            namespace: namespace.map(str::to_string),
+            description: definition.description.clone(),
+            annotations,
+            tags,
+            links,
+        },
+        spec: EntitySpec {
+            entity_type: Some("service".to_string()),
+            lifecycle: Some(lifecycle.clone()),
+            owner: Some(owner.clone()),
+            system: definition.system.clone(),
+            depends_on: dependencies.iter().map(|d| format!("component:{}", d.service)).collect(),
+            provides_apis: if definition.endpoints.is_empty() {
+                Vec::new()
+            } else {
+                vec![api_ref(&definition.name)]
+            },
+            consumes_apis: consumed.into_iter().collect(),
+            definition: None,
+        },
+    };
+    if definition.endpoints.is_empty() {
+        return Ok(vec![component]);
+    }
+
+    let endpoints = serde_yaml::to_string(&definition.endpoints).map_err(|e| {
+        AureaCoreError::Internal(format!(
+            "Failed to render endpoints of '{}': {}",
+            definition.name, e
+        ))
+    })?;
+    let api = BackstageEntity {
+        api_version: BACKSTAGE_API_VERSION.to_string(),
+        kind: "API".to_string(),
+        metadata: EntityMetadata {
+            name: api_entity_name(&definition.name),
+            namespace: namespace.map(str::to_string),
+            description: Some(format!("API of {}", definition.name)),
+            ..Default::default()
+        },
+        spec: EntitySpec {
+            entity_type: Some(api_type(&definition.service_type).to_string()),
+            lifecycle: Some(lifecycle),
+            owner: Some(owner),
+            system: definition.system.clone(),
+            definition: Some(endpoints),
+            ..Default::default()
+        },
+    };
+    Ok(vec![component, api])
+}
+
+/// Converts Component entities, and the API entities they provide, into service definitions
+///
+/// Entities of other kinds are ignored. Components without a version annotation get
+/// version `0.1.0`; API definitions that are not endpoint lists aureacore wrote leave
+/// the service without endpoints.
+pub fn from_entities(entities: &[BackstageEntity]) -> Result<Vec<ServiceSchema>> {
+    let apis: HashMap<&str, &BackstageEntity> = entities
+        .iter()
+        .filter(|entity| entity.kind.eq_ignore_ascii_case("api"))
+        .map(|entity| (entity.metadata.name.as_str(), entity))
+        .collect();
+
+    let mut definitions = Vec::new();
+    for entity in entities.iter().filter(|entity| entity.kind.eq_ignore_ascii_case("component")) {
+        let metadata = &entity.metadata;
+        let provided: Vec<&BackstageEntity> = entity
+            .spec
+            .provides_apis
+            .iter()
+            .filter_map(|reference| apis.get(ref_name(reference)).copied())
+            .collect();
+        let service_type = match metadata.annotations.get(SERVICE_TYPE_ANNOTATION) {
+            Some(name) => parse_service_type(name),
+            None => provided
+                .iter()
+                .find_map(|api| api.spec.entity_type.as_deref())
+                .map(service_type_of_api)
+                .unwrap_or(ServiceType::Rest),
+        };
+        let endpoints: Vec<Endpoint> = provided
+            .iter()
+            .filter_map(|api| api.spec.definition.as_deref())
+            .filter_map(|definition| serde_yaml::from_str::<Vec<Endpoint>>(definition).ok())
+            .flatten()
+            .collect();
+        let optional: BTreeSet<&str> = metadata
+            .annotations
+            .get(OPTIONAL_ANNOTATION)
+            .map(|names| names.split(',').map(str::trim).collect())
+            .unwrap_or_default();
+        let dependencies: Vec<Dependency> = entity
+            .spec
+            .depends_on
+            .iter()
+            .filter(|reference| ref_kind(reference).is_none_or(|kind| kind == "component"))
+            .map(|reference| {
+                let service = ref_name(reference).to_string();
+                Dependency {
+                    required: !optional.contains(service.as_str()),
+                    service,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut extra = HashMap::new();
+        if let Some(lifecycle) = &entity.spec.lifecycle {
+            extra.insert("lifecycle".to_string(), lifecycle.clone().into());
+        }
+        if !metadata.tags.is_empty() {
+            extra.insert("tags".to_string(), metadata.tags.clone().into());
+        }
+
+        definitions.push(ServiceSchema {
+            name: metadata.name.clone(),
+            version: metadata
+                .annotations
+                .get(VERSION_ANNOTATION)
+                .cloned()
+                .unwrap_or_else(|| "0.1.0".to_string()),
+            description: metadata.description.clone(),
+            owner: entity.spec.owner.clone().filter(|owner| owner != UNKNOWN_OWNER),
+            system: entity.spec.system.clone(),
+            domain: None,
+            oncall: None,
+            cost_center: None,
+            monthly_cost_estimate: None,
+            documentation_url: metadata
+                .links
+                .iter()
+                .find(|link| link.title.as_deref() == Some(DOCUMENTATION_LINK))
+                .map(|link| link.url.clone()),
+            service_type,
+            runtime: None,
+            endpoints,
+            exposure: None,
+            dependencies: (!dependencies.is_empty()).then_some(dependencies),
+            consumes: Vec::new(),
+            consumables: Vec::new(),
+            capacity: None,
+            contract_tests: Vec::new(),
+            feature_flags: Vec::new(),
+            metadata: extra,
+            sensitive_metadata: Vec::new(),
+            archetype: None,
+            ignores: Vec::new(),
+        });
+    }
+    Ok(definitions)
+}
+
+/// Converts every service of the registry with a loaded definition, sorted by name
+///
+/// Dependencies name the services now providing aliased ones.
+pub fn export_registry(registry: &ServiceRegistry) -> Result<Vec<BackstageEntity>> {
+    let mut names = registry.list_services()?;
+    names.sort();
+
+    let mut entities = Vec::new();
+    for name in &names {
+        let service = registry.get_service(name)?;
+        entities.extend(service_entities(service, registry.canonical_dependencies(service))?);
+    }
+    Ok(entities)
+}
+
+/// Converts the given services, skipping those without a loaded definition
+pub fn export_services(services: &[&Service]) -> Result<Vec<BackstageEntity>> {
+    let mut entities = Vec::new();
+    for service in services {
+        entities.extend(service_entities(service, service.dependencies())?);
+    }
+    Ok(entities)
+}
+
+/// Converts a service with its effective dependencies, if its definition is loaded
+fn service_entities(
+    service: &Service,
+    dependencies: Vec<Dependency>,
+) -> Result<Vec<BackstageEntity>> {
+    let Some(mut definition) = service.definition() else {
+        return Ok(Vec::new());
+    };
+    definition.dependencies = Some(dependencies);
+    to_entities(&definition, service.config.namespace.as_deref())
+}
+
+/// Renders entities as a multi-document `catalog-info.yaml`
+pub fn render_entities(entities: &[BackstageEntity]) -> Result<String> {
+    let mut documents = Vec::with_capacity(entities.len());
+    for entity in entities {
+        documents.push(serde_yaml::to_string(entity).map_err(|e| {
+            AureaCoreError::Internal(format!(
+                "Failed to render entity '{}': {}",
+                entity.metadata.name, e
+            ))
+        })?);
+    }
+    Ok(documents.join("---\n"))
+}
+
+/// Parses the entities of a multi-document `catalog-info.yaml`
+pub fn parse_entities(content: &str) -> Result<Vec<BackstageEntity>> {
+    serde_yaml::Deserializer::from_str(content)
+        .map(|document| {
+            BackstageEntity::deserialize(document)
+                .map_err(|e| AureaCoreError::invalid_config("Invalid Backstage entity", e))
+        })
+        .collect()
+}
+
+/// Gets the reference to the API entity of a service
+fn api_ref(service: &str) -> String {
+    format!("api:{}", api_entity_name(service))
+}
+
+/// Gets the kind of an entity reference such as `component:default/billing`, if given
+fn ref_kind(reference: &str) -> Option<String> {
+    reference.split_once(':').map(|(kind, _)| kind.to_ascii_lowercase())
+}
+
+/// Gets the name of an entity reference, without kind and namespace
+fn ref_name(reference: &str) -> &str {
+    let name = reference.split_once(':').map_or(reference, |(_, name)| name);
+    name.rsplit_once('/').map_or(name, |(_, name)| name)
+}
+
+fn service_type_name(service_type: &ServiceType) -> String {
+    match service_type {
+        ServiceType::Rest => "rest".to_string(),
+        ServiceType::Grpc => "grpc".to_string(),
+        ServiceType::GraphQL => "graphql".to_string(),
+        ServiceType::EventDriven => "event-driven".to_string(),
+        ServiceType::Other(name) => name.clone(),
+    }
+}
+
+fn parse_service_type(name: &str) -> ServiceType {
+    match name {
+        "rest" => ServiceType::Rest,
+        "grpc" => ServiceType::Grpc,
+        "graphql" => ServiceType::GraphQL,
+        "event-driven" => ServiceType::EventDriven,
+        other => ServiceType::Other(other.to_string()),
+    }
+}
+
+/// Gets the Backstage API type matching a service type
+fn api_type(service_type: &ServiceType) -> &str {
+    match service_type {
+        ServiceType::Rest => "openapi",
+        ServiceType::Grpc => "grpc",
+        ServiceType::GraphQL => "graphql",
+        ServiceType::EventDriven => "asyncapi",
+        ServiceType::Other(name) => name,
+    }
+}
+
+fn service_type_of_api(api_type: &str) -> ServiceType {
+    match api_type {
+        "openapi" => ServiceType::Rest,
+        "grpc" => ServiceType::Grpc,
+        "graphql" => ServiceType::GraphQL,
+        "asyncapi" => ServiceType::EventDriven,
+        other => ServiceType::Other(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backstage_round_trip() {
+        let definition: ServiceSchema = serde_yaml::from_str(
+            "name: billing\nversion: 2.1.0\ndescription: Invoices\nowner: payments\nsystem: checkout\ndocumentation_url: https://docs.example.com/billing\nservice_type:\n  type: grpc\nendpoints:\n  - name: invoices\n    path: /invoices\n    method: GET\ndependencies:\n  - service: ledger\n  - service: audit\n    required: false\nconsumes:\n  - service: ledger\n    endpoint: entries\n    version: v1\nmetadata:\n  tags: [money]\n",
+        )
+        .unwrap();
+
+        let entities = to_entities(&definition, Some("finance")).unwrap();
+        assert_eq!(entities.len(), 2);
+        let component = &entities[0];
+        assert_eq!(component.spec.depends_on, vec!["component:ledger", "component:audit"]);
+        assert_eq!(component.spec.provides_apis, vec!["api:billing-api"]);
+        assert_eq!(component.spec.consumes_apis, vec!["api:ledger-api"]);
+        assert_eq!(component.spec.lifecycle.as_deref(), Some("production"));
+        assert_eq!(entities[1].spec.entity_type.as_deref(), Some("grpc"));
+
+        let rendered = render_entities(&entities).unwrap();
+        assert!(rendered.contains("apiVersion: backstage.io/v1alpha1"));
+        assert!(rendered.contains("dependsOn:"));
+        let parsed = parse_entities(&rendered).unwrap();
+        assert_eq!(parsed, entities);
+
+        let imported = from_entities(&parsed).unwrap();
+        assert_eq!(imported.len(), 1);
+        let billing = &imported[0];
+        assert_eq!(billing.version, "2.1.0");
+        assert_eq!(billing.owner.as_deref(), Some("payments"));
+        assert_eq!(billing.documentation_url, definition.documentation_url);
+        assert!(matches!(billing.service_type, ServiceType::Grpc));
+        assert_eq!(billing.endpoints.len(), 1);
+        assert_eq!(billing.endpoints[0].path, "/invoices");
+        let dependencies = billing.dependencies.as_ref().unwrap();
+        assert!(dependencies[0].required && !dependencies[1].required);
+        assert_eq!(billing.metadata["tags"], serde_json::json!(["money"]));
+    }
+
+    #[test]
+    fn test_import_foreign_entities() {
+        let entities = parse_entities(
+            "apiVersion: backstage.io/v1alpha1\nkind: Component\nmetadata:\n  name: search\nspec:\n  type: service\n  owner: group:default/discovery\n  dependsOn: [component:default/index, resource:default/search-db]\n  providesApis: [search-api]\n---\napiVersion: backstage.io/v1alpha1\nkind: API\nmetadata:\n  name: search-api\nspec:\n  type: graphql\n  definition: 'type Query { search: String }'\n",
+        )
+        .unwrap();
+        let imported = from_entities(&entities).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].version, "0.1.0");
+        assert!(matches!(imported[0].service_type, ServiceType::GraphQL));
+        assert!(imported[0].endpoints.is_empty());
+        let dependencies = imported[0].dependencies.as_ref().unwrap();
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].service, "index");
+
+        assert!(parse_entities("kind: [").is_err());
+    }
+}
@@ -0,0 +1,642 @@
+//! Static documentation ("ID card") generation for registered services
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{Result, ResultExt};
+use crate::registry::{Service, ServiceRegistry};
+use crate::schema::service::{Dependency, Endpoint, Interaction, ServiceType};
+
+/// Output format for generated documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    /// Markdown pages with mermaid code blocks
+    Markdown,
+    /// Static HTML site rendering mermaid diagrams client-side
+    Html,
+}
+
+impl DocsFormat {
+    /// File extension used for pages in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for DocsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(DocsFormat::Markdown),
+            "html" => Ok(DocsFormat::Html),
+            other => Err(format!("Unknown docs format '{}', expected 'markdown' or 'html'", other)),
+        }
+    }
+}
+
+/// Renders services into per-service documentation pages
+pub struct DocsGenerator {
+    format: DocsFormat,
+}
+
+impl DocsGenerator {
+    /// Creates a new generator for the given output format
+    pub fn new(format: DocsFormat) -> Self {
+        Self { format }
+    }
+
+    /// Generates pages for every service in the registry into `out_dir`
+    ///
+    /// Returns the paths of all written files, index page first
+    pub fn generate(&self, registry: &ServiceRegistry, out_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut names = registry.list_services()?;
+        names.sort();
+
+        let mut services = Vec::with_capacity(names.len());
+        for name in &names {
+            services.push(registry.get_service(name)?);
+        }
+
+        self.generate_for(&services, out_dir)
+    }
+
+    /// Generates pages for the given services into `out_dir`
+    pub fn generate_for(&self, services: &[&Service], out_dir: &Path) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(out_dir)
+            .with_context(|| format!("Failed to create docs directory {}", out_dir.display()))?;
+
+        let mut written = Vec::with_capacity(services.len() + 1);
+
+        let index_path = out_dir.join(format!("index.{}", self.format.extension()));
+        write_page(&index_path, &self.render_index(services))?;
+        written.push(index_path);
+
+        for service in services {
+            let path = out_dir.join(self.page_file(&service.name));
+            write_page(&path, &self.render_service(service))?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+
+    /// Gets the name of the page file of a service, safe to create in the output directory
+    fn page_file(&self, name: &str) -> String {
+        format!("{}.{}", encode_name(name, |c| c == '-'), self.format.extension())
+    }
+
+    /// Renders the index page listing all services
+    pub fn render_index(&self, services: &[&Service]) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "# Service Catalog\n");
+        let _ = writeln!(md, "| Service | Version | Owner | Status |");
+        let _ = writeln!(md, "|---------|---------|-------|--------|");
+        for service in services {
+            let schema = service.definition();
+            let _ = writeln!(
+                md,
+                "| [{name}]({page}) | {version} | {owner} | {state} |",
+                name = cell(&service.name),
+                page = self.page_file(&service.name),
+                version = cell(schema.as_ref().map(|s| s.version.as_str()).unwrap_or("-")),
+                owner = cell(schema.as_ref().and_then(|s| s.owner.as_deref()).unwrap_or("-")),
+                state = service.status.state,
+            );
+        }
+
+        match self.format {
+            DocsFormat::Markdown => md,
+            DocsFormat::Html => render_html_document("Service Catalog", &markdown_to_html(&md)),
+        }
+    }
+
+    /// Renders the documentation page for a single service
+    pub fn render_service(&self, service: &Service) -> String {
+        let schema = service.definition();
+        let dependencies = service.dependencies();
+
+        let mut md = String::new();
+        let _ = writeln!(md, "# {}\n", service.name);
+
+        if let Some(description) = schema.as_ref().and_then(|s| s.description.as_deref()) {
+            let _ = writeln!(md, "{}\n", description);
+        }
+
+        let _ = writeln!(md, "## Overview\n");
+        let _ = writeln!(md, "| Field | Value |");
+        let _ = writeln!(md, "|-------|-------|");
+        if let Some(schema) = &schema {
+            let _ = writeln!(md, "| Version | {} |", cell(&schema.version));
+            let _ = writeln!(md, "| Type | {} |", cell(&service_type_label(&schema.service_type)));
+            let _ = writeln!(md, "| Owner | {} |", cell(schema.owner.as_deref().unwrap_or("-")));
+            if let Some(url) = &schema.documentation_url {
+                if is_safe_link(url) {
+                    let _ = writeln!(md, "| Documentation | [{url}]({url}) |", url = cell(url));
+                } else {
+                    let _ = writeln!(md, "| Documentation | `{}` |", cell(url));
+                }
+            }
+        }
+        let _ = writeln!(
+            md,
+            "| Namespace | {} |",
+            cell(service.config.namespace.as_deref().unwrap_or("default"))
+        );
+        let _ = writeln!(md, "| Schema version | {} |", cell(&service.config.schema_version));
+        let _ = writeln!(md, "| Config path | `{}` |", cell(&service.config.config_path));
+
+        let _ = writeln!(md, "\n## Status\n");
+        let _ = writeln!(md, "- State: **{}**", service.status.state);
+        let _ = writeln!(
+            md,
+            "- Last checked: {}",
+            service.status.last_checked.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        if let Some(error) = &service.status.error_message {
+            let _ = writeln!(md, "- Error: {}", error);
+        }
+        for warning in &service.status.warnings {
+            let _ = writeln!(md, "- Warning: {}", warning);
+        }
+
+        if let Some(schema) = &schema {
+            render_endpoints(&mut md, &schema.endpoints);
+        }
+
+        let _ = writeln!(md, "\n## Dependencies\n");
+        if dependencies.is_empty() {
+            let _ = writeln!(md, "This service has no declared dependencies.");
+        } else {
+            let _ =
+                writeln!(md, "```mermaid\n{}```", mermaid_diagram(&service.name, &dependencies));
+            let _ = writeln!(md);
+            let _ = writeln!(md, "| Service | Constraint | Required | Interaction |");
+            let _ = writeln!(md, "|---------|------------|----------|-------------|");
+            for dep in &dependencies {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} |",
+                    cell(&dep.service),
+                    cell(dep.version_constraint.as_deref().unwrap_or("-")),
+                    if dep.required { "yes" } else { "no" },
+                    dep.interaction
+                );
+            }
+        }
+
+        match self.format {
+            DocsFormat::Markdown => md,
+            DocsFormat::Html => render_html_document(&service.name, &markdown_to_html(&md)),
+        }
+    }
+}
+
+/// Renders a mermaid flowchart of a service and its direct dependencies
+///
+/// Required dependencies are drawn as solid edges, optional ones as dotted edges.
+/// Edges other than synchronous calls are labelled with their interaction kind.
+pub fn mermaid_diagram(service_name: &str, dependencies: &[Dependency]) -> String {
+    let mut out = String::from("graph LR\n");
+    let _ = writeln!(out, "    {}[\"{}\"]", mermaid_id(service_name), mermaid_label(service_name));
+    for dep in dependencies {
+        let mut arrow = if dep.required { "-->" } else { "-.->" }.to_string();
+        if dep.interaction != Interaction::SyncCall {
+            arrow = format!("{}|{}|", arrow, dep.interaction);
+        }
+        let _ = writeln!(
+            out,
+            "    {} {} {}[\"{}\"]",
+            mermaid_id(service_name),
+            arrow,
+            mermaid_id(&dep.service),
+            mermaid_label(&dep.service)
+        );
+    }
+    out
+}
+
+/// Converts a service name into an identifier mermaid accepts as a node id
+fn mermaid_id(name: &str) -> String {
+    encode_name(name, |_| false)
+}
+
+/// Escapes a service name for use inside a quoted mermaid label
+fn mermaid_label(name: &str) -> String {
+    name.replace('"', "#quot;")
+}
+
+/// Encodes a name keeping ASCII letters, digits and the characters `keep` accepts
+///
+/// Other characters, `_` included, are written as `_` and the hex digits of their UTF-8
+/// bytes, so distinct names never encode to the same string.
+fn encode_name(name: &str, keep: impl Fn(char) -> bool) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || (c != '_' && keep(c)) {
+            encoded.push(c);
+        } else {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                let _ = write!(encoded, "_{:02X}", byte);
+            }
+        }
+    }
+    encoded
+}
+
+/// Escapes text for a markdown table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Splits a markdown table row into its cells, unescaping pipes within them
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').filter(|l| !l.ends_with('\\')).unwrap_or(line);
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                chars.next();
+                cells.last_mut().unwrap().push('|');
+            }
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.into_iter().map(|c| c.trim().to_string()).collect()
+}
+
+/// Checks a link target is an http(s) URL or a relative reference
+///
+/// Other schemes, such as `javascript:`, are not rendered as links.
+fn is_safe_link(target: &str) -> bool {
+    let target = target.trim();
+    let scheme_end = target.find([':', '/', '?', '#']);
+    match scheme_end {
+        Some(end) if target[end..].starts_with(':') => {
+            let scheme = target[..end].to_ascii_lowercase();
+            scheme == "http" || scheme == "https"
+        }
+        _ => !target.starts_with("//"),
+    }
+}
+
+fn render_endpoints(md: &mut String, endpoints: &[Endpoint]) {
+    let _ = writeln!(md, "\n## Endpoints\n");
+    if endpoints.is_empty() {
+        let _ = writeln!(md, "This service declares no endpoints.");
+        return;
+    }
+
+    let _ = writeln!(md, "| Name | Method | Path | Description |");
+    let _ = writeln!(md, "|------|--------|------|-------------|");
+    for endpoint in endpoints {
+        let _ = writeln!(
+            md,
+            "| {} | {} | `{}` | {} |",
+            cell(&endpoint.name),
+            cell(endpoint.method.as_deref().unwrap_or("-")),
+            cell(&endpoint.path),
+            cell(endpoint.description.as_deref().unwrap_or(""))
+        );
+    }
+}
+
+fn service_type_label(service_type: &ServiceType) -> String {
+    match service_type {
+        ServiceType::Rest => "REST".to_string(),
+        ServiceType::Grpc => "gRPC".to_string(),
+        ServiceType::GraphQL => "GraphQL".to_string(),
+        ServiceType::EventDriven => "Event-driven".to_string(),
+        ServiceType::Other(custom) => custom.clone(),
+    }
+}
+
+fn write_page(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write docs page {}", path.display()))
+}
+
+/// Escapes text for inclusion in HTML
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Converts the restricted markdown subset produced by this module into HTML
+fn markdown_to_html(md: &str) -> String {
+    let mut html = String::new();
+    let mut in_mermaid = false;
+    let mut in_list = false;
+    let mut table_rows: Vec<Vec<String>> = Vec::new();
+
+    let flush_table = |html: &mut String, rows: &mut Vec<Vec<String>>| {
+        if rows.is_empty() {
+            return;
+        }
+        html.push_str("<table>\n");
+        for (i, row) in rows.iter().enumerate() {
+            let tag = if i == 0 { "th" } else { "td" };
+            html.push_str("<tr>");
+            for cell in row {
+                let _ = write!(html, "<{tag}>{}</{tag}>", inline_html(cell));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+        rows.clear();
+    };
+
+    for line in md.lines() {
+        if in_mermaid {
+            if line == "```" {
+                in_mermaid = false;
+                html.push_str("</pre>\n");
+            } else {
+                let _ = writeln!(html, "{}", escape_html(line));
+            }
+            continue;
+        }
+
+        if line.starts_with('|') {
+            let cells = split_row(line);
+            // Skip the markdown header separator row
+            if !cells.iter().all(|c| !c.is_empty() && c.chars().all(|ch| ch == '-')) {
+                table_rows.push(cells);
+            }
+            continue;
+        }
+        flush_table(&mut html, &mut table_rows);
+
+        if let Some(item) = line.strip_prefix("- ") {
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            let _ = writeln!(html, "<li>{}</li>", inline_html(item));
+            continue;
+        }
+        if in_list {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+
+        if line == "```mermaid" {
+            in_mermaid = true;
+            html.push_str("<pre class=\"mermaid\">\n");
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            let _ = writeln!(html, "<h2>{}</h2>", escape_html(heading));
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            let _ = writeln!(html, "<h1>{}</h1>", escape_html(heading));
+        } else if !line.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", inline_html(line));
+        }
+    }
+
+    flush_table(&mut html, &mut table_rows);
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+
+    html
+}
+
+/// Renders inline markdown (links, code spans and bold text) as HTML
+fn inline_html(text: &str) -> String {
+    let escaped = escape_html(text);
+    let mut out = String::with_capacity(escaped.len());
+    let mut rest = escaped.as_str();
+
+    while !rest.is_empty() {
+        if let Some(stripped) = rest.strip_prefix('[') {
+            if let Some((label, after)) = stripped.split_once("](") {
+                if let Some((target, remaining)) = after.split_once(')') {
+                    if is_safe_link(target) {
+                        let target = match target.rsplit_once(".md") {
+                            Some((base, "")) => format!("{}.html", base),
+                            _ => target.to_string(),
+                        };
+                        let _ = write!(out, "<a href=\"{}\">{}</a>", target, label);
+                    } else {
+                        out.push_str(label);
+                    }
+                    rest = remaining;
+                    continue;
+                }
+            }
+        }
+        if let Some(stripped) = rest.strip_prefix('`') {
+            if let Some((code, remaining)) = stripped.split_once('`') {
+                let _ = write!(out, "<code>{}</code>", code);
+                rest = remaining;
+                continue;
+            }
+        }
+        if let Some(stripped) = rest.strip_prefix("**") {
+            if let Some((bold, remaining)) = stripped.split_once("**") {
+                let _ = write!(out, "<strong>{}</strong>", bold);
+                rest = remaining;
+                continue;
+            }
+        }
+
+        let c = rest.chars().next().unwrap();
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
+fn render_html_document(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; }}
+table {{ border-collapse: collapse; margin: 1rem 0; }}
+th, td {{ border: 1px solid #ccc; padding: 0.3rem 0.6rem; text-align: left; }}
+</style>
+<script type="module">
+import mermaid from "https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs";
+mermaid.initialize({{ startOnLoad: true }});
+</script>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+        body = body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::registry::{ServiceConfig, ServiceState, ServiceStatus};
+
+    fn create_test_service() -> Service {
+        let config = ServiceConfig {
+            id: None,
+            namespace: Some("payments".to_string()),
+            config_path: "payments/checkout.json".to_string(),
+            schema_version: "1.0.0".to_string(),
+            dependencies: Some(vec![
+                Dependency {
+                    service: "billing".to_string(),
+                    version_constraint: Some("^1.2".to_string()),
+                    required: true,
+                    ..Default::default()
+                },
+                Dependency {
+                    service: "audit-log".to_string(),
+                    required: false,
+                    interaction: Interaction::AsyncEvent,
+                    ..Default::default()
+                },
+            ]),
+        };
+
+        let mut service = Service::new("checkout".to_string(), config);
+        service.status = ServiceStatus::new(ServiceState::Active)
+            .with_warnings(vec!["endpoint #1 has no method".to_string()]);
+        service.schema_data = Some(json!({
+            "name": "checkout",
+            "version": "2.3.0",
+            "description": "Handles <checkout> flows",
+            "owner": "Payments Team",
+            "documentation_url": "https://docs.example.com/checkout",
+            "service_type": { "type": "rest" },
+            "endpoints": [
+                { "name": "create", "path": "/api/checkout", "method": "POST" }
+            ]
+        }));
+        service
+    }
+
+    #[test]
+    fn test_docs_format_from_str() {
+        assert_eq!("markdown".parse::<DocsFormat>().unwrap(), DocsFormat::Markdown);
+        assert_eq!("HTML".parse::<DocsFormat>().unwrap(), DocsFormat::Html);
+        assert!("pdf".parse::<DocsFormat>().is_err());
+    }
+
+    #[test]
+    fn test_mermaid_diagram() {
+        let service = create_test_service();
+        let diagram = mermaid_diagram(&service.name, service.config.dependencies.as_ref().unwrap());
+
+        assert!(diagram.starts_with("graph LR\n"));
+        assert!(diagram.contains("checkout --> billing[\"billing\"]"));
+        assert!(diagram.contains("checkout -.->|async-event| audit_2Dlog[\"audit-log\"]"));
+        assert_ne!(mermaid_id("a-b"), mermaid_id("a_b"));
+    }
+
+    #[test]
+    fn test_render_service_markdown() {
+        let service = create_test_service();
+        let page = DocsGenerator::new(DocsFormat::Markdown).render_service(&service);
+
+        assert!(page.starts_with("# checkout"));
+        assert!(page.contains("| Owner | Payments Team |"));
+        assert!(page.contains("| Version | 2.3.0 |"));
+        assert!(page.contains("| create | POST | `/api/checkout` |  |"));
+        assert!(page.contains("```mermaid"));
+        assert!(page.contains("State: **Active**"));
+        assert!(page.contains("Warning: endpoint #1 has no method"));
+    }
+
+    #[test]
+    fn test_render_service_html_escapes_content() {
+        let service = create_test_service();
+        let page = DocsGenerator::new(DocsFormat::Html).render_service(&service);
+
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<pre class=\"mermaid\">"));
+        assert!(page.contains("Handles &lt;checkout&gt; flows"));
+        assert!(page.contains("<strong>Active</strong>"));
+        assert!(!page.contains("<checkout>"));
+    }
+
+    #[test]
+    fn test_generate_writes_index_and_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = create_test_service();
+
+        let generator = DocsGenerator::new(DocsFormat::Html);
+        let written = generator.generate_for(&[&service], temp_dir.path()).unwrap();
+
+        assert_eq!(written.len(), 2);
+        let index = fs::read_to_string(temp_dir.path().join("index.html")).unwrap();
+        assert!(index.contains("<a href=\"checkout.html\">checkout</a>"));
+        assert!(temp_dir.path().join("checkout.html").exists());
+    }
+
+    #[test]
+    fn test_generate_sanitizes_page_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let out_dir = temp_dir.path().join("docs");
+        let mut service = create_test_service();
+        service.name = "../escape".to_string();
+
+        let generator = DocsGenerator::new(DocsFormat::Markdown);
+        let written = generator.generate_for(&[&service], &out_dir).unwrap();
+
+        assert_eq!(written[1], out_dir.join("_2E_2E_2Fescape.md"));
+        assert!(!temp_dir.path().join("escape.md").exists());
+        let index = fs::read_to_string(out_dir.join("index.md")).unwrap();
+        assert!(index.contains("[../escape](_2E_2E_2Fescape.md)"));
+    }
+
+    #[test]
+    fn test_render_html_neutralizes_unsafe_content() {
+        let mut service = create_test_service();
+        service.schema_data = Some(json!({
+            "name": "checkout",
+            "version": "2.3.0",
+            "owner": "Payments | Billing",
+            "documentation_url": "javascript:alert(1)",
+            "service_type": { "type": "rest" },
+            "endpoints": []
+        }));
+
+        let markdown = DocsGenerator::new(DocsFormat::Markdown).render_service(&service);
+        assert!(markdown.contains("| Owner | Payments \\| Billing |"));
+
+        let page = DocsGenerator::new(DocsFormat::Html).render_service(&service);
+        assert!(page.contains("<td>Payments | Billing</td>"));
+        assert!(!page.contains("href=\"javascript:"));
+        assert!(page.contains("<code>javascript:alert(1)</code>"));
+
+        assert!(is_safe_link("https://docs.example.com"));
+        assert!(is_safe_link("checkout.html"));
+        assert!(is_safe_link("guides/setup.md#install"));
+        assert!(!is_safe_link("JavaScript:alert(1)"));
+        assert!(!is_safe_link("data:text/html,x"));
+        assert!(!is_safe_link("//evil.example.com"));
+    }
+}
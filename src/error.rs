@@ -1,75 +1,260 @@
-use std::error::Error as StdError;
-use std::fmt;
+use thiserror::Error;
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum AureaCoreError {
     /// Error during Git operations
+    #[error("Git error: {0}")]
     Git(String),
+    /// Error reported by the underlying Git library
+    #[error("Git error: {0}")]
+    GitBackend(#[from] git2::Error),
     /// Error during file system operations
-    Io(std::io::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
     /// Error during configuration parsing
+    #[error("Configuration error: {0}")]
     Config(String),
+    /// Catalog config, service definition or settings file that cannot be parsed
+    #[error("Configuration error: {message}: {source}")]
+    InvalidConfig {
+        message: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// Error reading or writing the configuration store
+    #[error(
+        "Config store error: {message}{}",
+        .source.as_ref().map(|e| format!(": {}", e)).unwrap_or_default()
+    )]
+    ConfigStore {
+        message: String,
+        #[source]
+        source: Option<std::io::Error>,
+    },
     /// Error during service operations
+    #[error("Service error: {0}")]
     Service(String),
     /// Error during schema validation
+    #[error("Validation error: {0}")]
     ValidationError(String),
+    /// Validation error
+    #[deprecated(note = "use `AureaCoreError::ValidationError`")]
+    #[error("Validation error: {0}")]
+    Validation(String),
     /// Error during schema compilation
+    #[error("Schema compilation error: {0}")]
     SchemaCompilationError(String),
     /// Incompatible schema version
+    #[error("Incompatible version: {0}")]
     IncompatibleVersion(String),
     /// Feature not implemented
+    #[error("Not implemented: {0}")]
     NotImplemented(String),
     /// Internal error
+    #[error("Internal error: {0}")]
     Internal(String),
     /// Service not found
+    #[error("Service not found: {0}")]
     ServiceNotFound(String),
-    /// Validation error
-    Validation(String),
     /// Circular dependency detected
+    #[error("Circular dependency: {0}")]
     CircularDependency(String),
-    // We'll add more error types as we implement more features
+    /// Graph query that cannot be parsed
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    /// Change attempted during a freeze window
+    #[error("Change freeze in effect: {0}")]
+    Frozen(String),
+    /// Change outside the namespaces or limits of the registry's tenant
+    #[error("Tenant limit exceeded: {0}")]
+    TenantLimit(String),
+    /// Write attempted without holding the registry lock
+    #[error("Registry is read-only: {0}")]
+    ReadOnly(String),
+    /// Catalog bundle that is malformed or fails signature verification
+    #[error("Invalid catalog bundle: {0}")]
+    Bundle(String),
+    /// Operation stopped early because it was cancelled or exceeded its deadline
+    #[error("Timed out: {0}")]
+    TimedOut(String),
+    /// Service name that cannot safely be used as a file name
+    #[error("Invalid service name: {0}")]
+    InvalidName(String),
+    /// Path that would leave the directory it is resolved in
+    #[error("Unsafe path: {0}")]
+    UnsafePath(String),
+    /// Change the caller isn't allowed to make by the catalog's access control lists
+    #[error("Permission denied: {0}")]
+    Forbidden(String),
+    /// An error annotated with additional context
+    #[error("{message}: {source}")]
+    Context {
+        message: String,
+        #[source]
+        source: Box<AureaCoreError>,
+    },
 }
 
-impl fmt::Display for AureaCoreError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl AureaCoreError {
+    /// Gets a stable, machine-readable code for the error
+    ///
+    /// Errors wrapped with [`context`](Self::context) report the code of the underlying error.
+    #[allow(deprecated)]
+    pub fn code(&self) -> &'static str {
         match self {
-            AureaCoreError::Git(msg) => write!(f, "Git error: {}", msg),
-            AureaCoreError::Io(err) => write!(f, "IO error: {}", err),
-            AureaCoreError::Config(msg) => write!(f, "Configuration error: {}", msg),
-            AureaCoreError::Service(msg) => write!(f, "Service error: {}", msg),
-            AureaCoreError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
-            AureaCoreError::SchemaCompilationError(msg) => {
-                write!(f, "Schema compilation error: {}", msg)
-            }
-            AureaCoreError::IncompatibleVersion(msg) => write!(f, "Incompatible version: {}", msg),
-            AureaCoreError::NotImplemented(msg) => write!(f, "Not implemented: {}", msg),
-            AureaCoreError::Internal(msg) => write!(f, "Internal error: {}", msg),
-            AureaCoreError::ServiceNotFound(msg) => write!(f, "Service not found: {}", msg),
-            AureaCoreError::Validation(msg) => write!(f, "Validation error: {}", msg),
-            AureaCoreError::CircularDependency(msg) => write!(f, "Circular dependency: {}", msg),
+            AureaCoreError::Git(_) | AureaCoreError::GitBackend(_) => "git",
+            AureaCoreError::Io(_) => "io",
+            AureaCoreError::Config(_) => "config",
+            AureaCoreError::InvalidConfig { .. } => "invalid_config",
+            AureaCoreError::ConfigStore { .. } => "config_store",
+            AureaCoreError::Service(_) => "service",
+            AureaCoreError::ValidationError(_) | AureaCoreError::Validation(_) => "validation",
+            AureaCoreError::SchemaCompilationError(_) => "schema_compilation",
+            AureaCoreError::IncompatibleVersion(_) => "incompatible_version",
+            AureaCoreError::NotImplemented(_) => "not_implemented",
+            AureaCoreError::Internal(_) => "internal",
+            AureaCoreError::ServiceNotFound(_) => "service_not_found",
+            AureaCoreError::CircularDependency(_) => "circular_dependency",
+            AureaCoreError::InvalidQuery(_) => "invalid_query",
+            AureaCoreError::Frozen(_) => "frozen",
+            AureaCoreError::TenantLimit(_) => "tenant_limit",
+            AureaCoreError::ReadOnly(_) => "read_only",
+            AureaCoreError::Bundle(_) => "invalid_bundle",
+            AureaCoreError::TimedOut(_) => "timed_out",
+            AureaCoreError::InvalidName(_) => "invalid_name",
+            AureaCoreError::UnsafePath(_) => "unsafe_path",
+            AureaCoreError::Forbidden(_) => "forbidden",
+            AureaCoreError::Context { source, .. } => source.code(),
         }
     }
-}
 
-impl StdError for AureaCoreError {
-    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    /// Wraps the error with a message describing what was being attempted
+    pub fn context(self, message: impl Into<String>) -> Self {
+        AureaCoreError::Context { message: message.into(), source: Box::new(self) }
+    }
+
+    /// Creates a config store error with an underlying IO cause
+    pub fn config_store(message: impl Into<String>, source: std::io::Error) -> Self {
+        AureaCoreError::ConfigStore { message: message.into(), source: Some(source) }
+    }
+
+    /// Creates an error for a config or definition that fails to parse
+    pub fn invalid_config(
+        message: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        AureaCoreError::InvalidConfig { message: message.into(), source: source.into() }
+    }
+
+    /// Gets the innermost error, skipping any context wrappers
+    pub fn root_cause(&self) -> &AureaCoreError {
         match self {
-            AureaCoreError::Io(err) => Some(err),
-            _ => None,
+            AureaCoreError::Context { source, .. } => source.root_cause(),
+            other => other,
         }
     }
 }
 
-impl From<std::io::Error> for AureaCoreError {
-    fn from(err: std::io::Error) -> Self {
-        AureaCoreError::Io(err)
-    }
+/// Extension trait for attaching context to fallible results
+pub trait ResultExt<T> {
+    /// Wraps the error, if any, with the given context message
+    fn context(self, message: impl Into<String>) -> Result<T>;
+
+    /// Wraps the error, if any, with a lazily built context message
+    fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T>;
 }
 
-impl From<git2::Error> for AureaCoreError {
-    fn from(err: git2::Error) -> Self {
-        AureaCoreError::Git(err.to_string())
+impl<T, E: Into<AureaCoreError>> ResultExt<T> for std::result::Result<T, E> {
+    fn context(self, message: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.into().context(message))
+    }
+
+    fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T> {
+        self.map_err(|e| e.into().context(f()))
     }
 }
 
 pub type Result<T> = std::result::Result<T, AureaCoreError>;
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as StdError;
+
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(AureaCoreError::ServiceNotFound("a".to_string()).code(), "service_not_found");
+        assert_eq!(
+            AureaCoreError::CircularDependency("a".to_string()).code(),
+            "circular_dependency"
+        );
+        assert_eq!(
+            AureaCoreError::ConfigStore { message: "x".to_string(), source: None }.code(),
+            "config_store"
+        );
+        assert_eq!(AureaCoreError::InvalidName("..".to_string()).code(), "invalid_name");
+        assert_eq!(AureaCoreError::UnsafePath("/etc".to_string()).code(), "unsafe_path");
+        assert_eq!(AureaCoreError::Forbidden("alice".to_string()).code(), "forbidden");
+    }
+
+    #[test]
+    fn test_display_matches_legacy_messages() {
+        assert_eq!(
+            AureaCoreError::Config("bad".to_string()).to_string(),
+            "Configuration error: bad"
+        );
+        assert_eq!(
+            AureaCoreError::ServiceNotFound("billing".to_string()).to_string(),
+            "Service not found: billing"
+        );
+    }
+
+    #[test]
+    fn test_context_preserves_source_chain() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+        let result: std::result::Result<(), std::io::Error> = Err(io);
+
+        let err = result.context("Failed to load service 'billing'").unwrap_err();
+        assert_eq!(err.code(), "io");
+        assert_eq!(err.to_string(), "Failed to load service 'billing': IO error: missing file");
+        assert!(matches!(err.root_cause(), AureaCoreError::Io(_)));
+
+        // The chain is walkable through std::error::Error::source
+        let source = err.source().expect("context should expose its source");
+        assert_eq!(source.to_string(), "IO error: missing file");
+        assert!(source.source().is_some());
+    }
+
+    #[test]
+    fn test_config_store_source() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        let err = AureaCoreError::config_store("Failed to write config", io);
+
+        assert_eq!(err.to_string(), "Config store error: Failed to write config: denied");
+        assert_eq!(err.source().unwrap().to_string(), "denied");
+
+        let err = AureaCoreError::ConfigStore { message: "Not a file".to_string(), source: None };
+        assert_eq!(err.to_string(), "Config store error: Not a file");
+    }
+
+    #[test]
+    fn test_invalid_config_source() {
+        let parse = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let err = AureaCoreError::invalid_config("Invalid service config for 'billing'", parse);
+
+        assert_eq!(err.code(), "invalid_config");
+        assert!(err
+            .to_string()
+            .starts_with("Configuration error: Invalid service config for 'billing': EOF"));
+        assert!(err.source().unwrap().is::<serde_json::Error>());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_validation_variant() {
+        let err = AureaCoreError::Validation("bad".to_string());
+        assert_eq!(err.code(), "validation");
+        assert_eq!(err.to_string(), "Validation error: bad");
+    }
+}
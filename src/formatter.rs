@@ -0,0 +1,248 @@
+//! Canonical formatting of service definitions
+//!
+//! Definitions are rewritten with keys in schema order (unknown keys and metadata
+//! sorted after), YAML anchors and merge keys resolved, and defaults kept as written,
+//! elided or materialized. Comments are not preserved.
+
+use std::str::FromStr;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::service::ServiceSchema;
+
+/// How fields holding their default value are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DefaultsMode {
+    /// Keep fields as the author wrote them
+    #[default]
+    Keep,
+    /// Drop fields that are unset, empty or hold their default value
+    Elide,
+    /// Write out defaulted fields explicitly
+    Materialize,
+}
+
+impl FromStr for DefaultsMode {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "elide" => Ok(Self::Elide),
+            "materialize" => Ok(Self::Materialize),
+            other => Err(AureaCoreError::Config(format!(
+                "Unknown defaults mode '{}', expected keep, elide or materialize",
+                other
+            ))),
+        }
+    }
+}
+
+/// Rewrites service definitions into canonical form
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFormatter {
+    defaults: DefaultsMode,
+}
+
+impl ConfigFormatter {
+    /// Creates a formatter keeping defaults as written
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how defaulted fields are written
+    pub fn with_defaults(mut self, defaults: DefaultsMode) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Formats a YAML (or JSON) service definition
+    ///
+    /// Fails if the content is not a valid service definition.
+    pub fn format(&self, content: &str) -> Result<String> {
+        let invalid =
+            |e: serde_yaml::Error| AureaCoreError::invalid_config("Invalid service definition", e);
+
+        let mut value: Value = serde_yaml::from_str(content).map_err(invalid)?;
+        value.apply_merge().map_err(invalid)?;
+        let definition: ServiceSchema = serde_yaml::from_value(value.clone()).map_err(invalid)?;
+        let template = serde_yaml::to_value(&definition).map_err(invalid)?;
+
+        let mut formatted = canonicalize(value, Some(&template), self.defaults, "");
+        if let Value::Mapping(mapping) = &mut formatted {
+            if self.defaults == DefaultsMode::Elide {
+                mapping.retain(|k, v| k.as_str() == Some("endpoints") || !is_empty(v));
+            }
+        }
+        serde_yaml::to_string(&formatted).map_err(|e| {
+            AureaCoreError::Internal(format!("Failed to render service definition: {}", e))
+        })
+    }
+
+    /// Checks whether a definition is already in canonical form
+    pub fn is_formatted(&self, content: &str) -> Result<bool> {
+        Ok(self.format(content)? == content)
+    }
+}
+
+/// Orders a value after its typed template, recursing into nested mappings and sequences
+///
+/// `field` is the name of the key holding the value, which decides field-specific defaults.
+fn canonicalize(
+    value: Value,
+    template: Option<&Value>,
+    defaults: DefaultsMode,
+    field: &str,
+) -> Value {
+    match value {
+        Value::Mapping(mut mapping) => {
+            // Metadata is a free-form map, so its template carries no meaningful order
+            let template = template.and_then(Value::as_mapping).filter(|_| field != "metadata");
+            let mut ordered = Mapping::new();
+
+            for (key, template_value) in template.into_iter().flatten() {
+                match mapping.remove(key) {
+                    Some(value) => {
+                        let name = key.as_str().unwrap_or_default();
+                        let value = canonicalize(value, Some(template_value), defaults, name);
+                        if defaults == DefaultsMode::Elide && is_default(field, name, &value) {
+                            continue;
+                        }
+                        ordered.insert(key.clone(), value);
+                    }
+                    None if defaults == DefaultsMode::Materialize && !template_value.is_null() => {
+                        ordered.insert(key.clone(), template_value.clone());
+                    }
+                    None => {}
+                }
+            }
+
+            let mut rest: Vec<(Value, Value)> = mapping.into_iter().collect();
+            rest.sort_by_key(|(key, _)| sort_key(key));
+            for (key, value) in rest {
+                let name = key.as_str().unwrap_or_default().to_string();
+                ordered.insert(key, canonicalize(value, None, defaults, &name));
+            }
+            Value::Mapping(ordered)
+        }
+        Value::Sequence(items) => {
+            let templates = template.and_then(Value::as_sequence);
+            Value::Sequence(
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        let template = templates.and_then(|t| t.get(i));
+                        canonicalize(item, template, defaults, field)
+                    })
+                    .collect(),
+            )
+        }
+        other => other,
+    }
+}
+
+/// Gets the key unknown fields are sorted by
+fn sort_key(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default(),
+    }
+}
+
+/// Checks whether a value is unset or an empty collection
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Sequence(items) => items.is_empty(),
+        Value::Mapping(mapping) => mapping.is_empty(),
+        _ => false,
+    }
+}
+
+/// Checks whether a field of a nested object holds its default value
+fn is_default(parent: &str, field: &str, value: &Value) -> bool {
+    match (parent, field) {
+        (_, "endpoints") => false,
+        ("dependencies", "required") => value.as_bool() == Some(true),
+        ("dependencies", "interaction") => value.as_str() == Some("sync-call"),
+        ("", _) => false,
+        _ => is_empty(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITION: &str = "\
+endpoints:
+  - path: /api
+    name: api
+    method: GET
+x-team-channel: '#billing'
+name: billing
+defaults: &deps
+  required: true
+dependencies:
+  - <<: *deps
+    service: ledger
+metadata:
+  zone: eu
+  tier: 1
+service_type:
+  type: rest
+version: 1.0.0
+";
+
+    #[test]
+    fn test_canonical_order() {
+        let formatted = ConfigFormatter::new().format(DEFINITION).unwrap();
+        assert_eq!(
+            formatted,
+            "\
+name: billing
+version: 1.0.0
+service_type:
+  type: rest
+endpoints:
+- name: api
+  path: /api
+  method: GET
+dependencies:
+- service: ledger
+  required: true
+metadata:
+  tier: 1
+  zone: eu
+defaults:
+  required: true
+x-team-channel: '#billing'
+"
+        );
+
+        let formatter = ConfigFormatter::new();
+        assert!(!formatter.is_formatted(DEFINITION).unwrap());
+        assert!(formatter.is_formatted(&formatted).unwrap());
+        assert!(formatter.format("name: billing\n").is_err());
+    }
+
+    #[test]
+    fn test_defaults_modes() {
+        let elided =
+            ConfigFormatter::new().with_defaults(DefaultsMode::Elide).format(DEFINITION).unwrap();
+        assert!(elided.contains("dependencies:\n- service: ledger\nmetadata:"));
+
+        let materialized = ConfigFormatter::new()
+            .with_defaults(DefaultsMode::Materialize)
+            .format("name: a\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\ndependencies:\n- service: b\n")
+            .unwrap();
+        assert!(materialized.contains("- service: b\n  required: true\n"));
+        assert!(materialized.contains("metadata: {}\n"));
+        assert!(!materialized.contains("null"));
+
+        assert_eq!("Elide".parse::<DefaultsMode>().unwrap(), DefaultsMode::Elide);
+        assert!("drop".parse::<DefaultsMode>().is_err());
+    }
+}
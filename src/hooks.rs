@@ -0,0 +1,164 @@
+//! Git hooks catching invalid catalog changes before they are committed or pushed
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{AureaCoreError, Result};
+
+/// First lines of every hook written by [`GitHook::install`], which it may replace
+const HOOK_HEADER: &str = "#!/bin/sh\n# Installed by aureacore install-hooks\n";
+
+/// When git runs a hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    /// Before a commit is created, checking the staged and unstaged changes
+    PreCommit,
+    /// Before commits are pushed, checking everything not yet on the remote branch
+    PrePush,
+}
+
+impl HookKind {
+    /// Gets the file name of the hook in the hooks directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            HookKind::PreCommit => "pre-commit",
+            HookKind::PrePush => "pre-push",
+        }
+    }
+}
+
+impl FromStr for HookKind {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pre-commit" => Ok(Self::PreCommit),
+            "pre-push" => Ok(Self::PrePush),
+            other => Err(AureaCoreError::Config(format!(
+                "Unknown hook '{}', expected pre-commit or pre-push",
+                other
+            ))),
+        }
+    }
+}
+
+/// A hook running format checks and scoped validation of the changed services
+#[derive(Debug, Clone)]
+pub struct GitHook {
+    kind: HookKind,
+    base: String,
+    args: Vec<String>,
+}
+
+impl GitHook {
+    /// Creates a hook checking the services changed since `base`
+    pub fn new(kind: HookKind, base: impl Into<String>) -> Self {
+        Self { kind, base: base.into(), args: Vec::new() }
+    }
+
+    /// Creates a pre-commit hook checking the changes to HEAD
+    pub fn pre_commit() -> Self {
+        Self::new(HookKind::PreCommit, "HEAD")
+    }
+
+    /// Creates a pre-push hook checking the changes to the remote tracking `branch`
+    pub fn pre_push(remote: &str, branch: &str) -> Self {
+        Self::new(HookKind::PrePush, format!("{}/{}", remote, branch))
+    }
+
+    /// Passes a global option such as `--work-dir` to every aureacore invocation
+    pub fn with_arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Renders the hook as a shell script
+    pub fn script(&self) -> String {
+        let command = std::iter::once("aureacore")
+            .chain(self.args.iter().map(String::as_str))
+            .map(shell_quote)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let base = shell_quote(&self.base);
+        format!(
+            "{}set -e\n\n{} fmt --check --changed {}\n{} validate --changed {} --dependents\n",
+            HOOK_HEADER, command, base, command, base
+        )
+    }
+
+    /// Writes the hook into `hooks_dir`, returning its path
+    ///
+    /// A hook written by aureacore before is replaced; any other existing hook only if
+    /// `force` is set.
+    pub fn install(&self, hooks_dir: &Path, force: bool) -> Result<PathBuf> {
+        let path = hooks_dir.join(self.kind.file_name());
+        if let Ok(existing) = fs::read_to_string(&path) {
+            if !force && !existing.starts_with(HOOK_HEADER) {
+                return Err(AureaCoreError::Config(format!(
+                    "Refusing to replace the existing hook {}",
+                    path.display()
+                )));
+            }
+        }
+        fs::create_dir_all(hooks_dir)?;
+        fs::write(&path, self.script())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(path)
+    }
+}
+
+/// Quotes an argument for a POSIX shell, unless it only has safe characters
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=@+,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_install_hook() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path().join(".git/hooks");
+        let hook =
+            GitHook::pre_push("origin", "main").with_arg("--work-dir").with_arg("/srv/my catalog");
+        let script = hook.script();
+        assert!(script.starts_with(HOOK_HEADER));
+        assert!(script.contains(
+            "aureacore --work-dir '/srv/my catalog' validate --changed origin/main --dependents"
+        ));
+        assert!(script.contains("fmt --check --changed origin/main"));
+
+        let path = hook.install(&hooks_dir, false).unwrap();
+        assert_eq!(path, hooks_dir.join("pre-push"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o755);
+        }
+        // Our own hook is replaced, a hand-written one only when forced
+        GitHook::pre_push("origin", "release").install(&hooks_dir, false).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("origin/release"));
+        fs::write(&path, "#!/bin/sh\nmake lint\n").unwrap();
+        let err = hook.install(&hooks_dir, false).unwrap_err();
+        assert!(err.to_string().contains("Refusing to replace"));
+        hook.install(&hooks_dir, true).unwrap();
+
+        assert_eq!("pre-commit".parse::<HookKind>().unwrap(), HookKind::PreCommit);
+        assert!("post-merge".parse::<HookKind>().is_err());
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
@@ -0,0 +1,314 @@
+//! Bulk import of service definitions from spreadsheet (CSV) inventories
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::service::{Dependency, ServiceSchema, ServiceType};
+use crate::schema::validation::ValidationService;
+
+/// Maps spreadsheet columns to service definition fields
+///
+/// Each field names the column header it is read from. Only the name column is
+/// required to exist; missing optional columns leave the field unset.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ColumnMapping {
+    /// Column holding the service name
+    pub name: String,
+    /// Column holding the owning team
+    pub owner: String,
+    /// Column holding the service type
+    #[serde(rename = "type")]
+    pub service_type: String,
+    /// Column holding the documentation URL
+    pub url: String,
+    /// Column holding the names of services depended on
+    pub depends_on: String,
+    /// Column holding the service version
+    pub version: String,
+    /// Separator between dependency names in one cell
+    pub separator: char,
+    /// Version used for rows without one
+    pub default_version: String,
+    /// Service type used for rows without one
+    pub default_type: String,
+    /// Spreadsheet values mapped to service types, e.g. `API: rest`
+    pub types: HashMap<String, String>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            name: "name".to_string(),
+            owner: "owner".to_string(),
+            service_type: "type".to_string(),
+            url: "url".to_string(),
+            depends_on: "depends_on".to_string(),
+            version: "version".to_string(),
+            separator: ';',
+            default_version: "0.1.0".to_string(),
+            default_type: "rest".to_string(),
+            types: HashMap::new(),
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Loads a column mapping from a YAML file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid column mapping {}", path.display()), e)
+        })
+    }
+
+    /// Resolves a spreadsheet value to a service type
+    fn service_type(&self, value: &str) -> Option<ServiceType> {
+        let value = self.types.get(value).map(String::as_str).unwrap_or(value);
+        let normalized: String =
+            value.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+        match normalized.as_str() {
+            "rest" | "http" => Some(ServiceType::Rest),
+            "grpc" => Some(ServiceType::Grpc),
+            "graphql" => Some(ServiceType::GraphQL),
+            "eventdriven" | "event" => Some(ServiceType::EventDriven),
+            _ => None,
+        }
+    }
+}
+
+/// A spreadsheet row that was not imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedRow {
+    /// Line of the row in the file, counting the header
+    pub line: u64,
+    /// Service name in the row, if any
+    pub name: Option<String>,
+    /// Why the row was skipped
+    pub reason: String,
+}
+
+/// Summary of a bulk import
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Names of the imported services
+    pub imported: Vec<String>,
+    /// Rows that were not imported
+    pub skipped: Vec<SkippedRow>,
+}
+
+impl ImportReport {
+    /// Records a row that was not imported
+    pub fn skip(&mut self, line: u64, name: Option<String>, reason: impl Into<String>) {
+        self.skipped.push(SkippedRow { line, name, reason: reason.into() });
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Imported: {}", self.imported.len())?;
+        writeln!(f, "Skipped: {}", self.skipped.len())?;
+        for row in &self.skipped {
+            match &row.name {
+                Some(name) => writeln!(f, "  line {} ({}): {}", row.line, name, row.reason)?,
+                None => writeln!(f, "  line {}: {}", row.line, row.reason)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A service definition read from one spreadsheet row
+#[derive(Debug, Clone)]
+pub struct ImportedService {
+    /// Line of the row in the file, counting the header
+    pub line: u64,
+    /// Service definition built from the row
+    pub definition: ServiceSchema,
+}
+
+/// Imports service definitions from a CSV service inventory
+pub struct CsvImporter {
+    mapping: ColumnMapping,
+    validation_service: ValidationService,
+}
+
+impl CsvImporter {
+    /// Creates an importer using the given column mapping
+    pub fn new(mapping: ColumnMapping) -> Self {
+        Self { mapping, validation_service: ValidationService::new() }
+    }
+
+    /// Reads service definitions from CSV data
+    ///
+    /// Every row is validated on its own; invalid rows are skipped and listed in the
+    /// report rather than failing the import.
+    pub fn import<R: Read>(&mut self, reader: R) -> Result<(Vec<ImportedService>, ImportReport)> {
+        let csv_error = |e: csv::Error| AureaCoreError::Config(format!("Invalid CSV: {}", e));
+
+        let mut reader =
+            csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let column = |header: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(header));
+        let name_column = column(&self.mapping.name).ok_or_else(|| {
+            AureaCoreError::Config(format!("CSV has no '{}' column", self.mapping.name))
+        })?;
+        let columns = [
+            column(&self.mapping.owner),
+            column(&self.mapping.service_type),
+            column(&self.mapping.url),
+            column(&self.mapping.depends_on),
+            column(&self.mapping.version),
+        ];
+
+        let mut services = Vec::new();
+        let mut report = ImportReport::default();
+        let mut seen = HashSet::new();
+        for record in reader.records() {
+            let record = record.map_err(csv_error)?;
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let cell = |index: Option<usize>| {
+                index.and_then(|i| record.get(i)).filter(|v| !v.is_empty()).map(str::to_string)
+            };
+            let [owner, service_type, url, depends_on, version] = columns.map(cell);
+
+            let Some(name) = cell(Some(name_column)) else {
+                report.skip(line, None, "Missing service name");
+                continue;
+            };
+            if name.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\') {
+                report.skip(line, Some(name), "Name must not contain whitespace or slashes");
+                continue;
+            }
+            if !seen.insert(name.clone()) {
+                report.skip(line, Some(name), "Duplicate service name");
+                continue;
+            }
+
+            let type_value = service_type.unwrap_or_else(|| self.mapping.default_type.clone());
+            let Some(service_type) = self.mapping.service_type(&type_value) else {
+                report.skip(line, Some(name), format!("Unknown service type '{}'", type_value));
+                continue;
+            };
+            let version = version.unwrap_or_else(|| self.mapping.default_version.clone());
+            if semver::Version::parse(&version).is_err() {
+                report.skip(line, Some(name), format!("Invalid version '{}'", version));
+                continue;
+            }
+            if url.as_ref().is_some_and(|u| !u.starts_with("http://") && !u.starts_with("https://"))
+            {
+                report.skip(line, Some(name), format!("Invalid URL '{}'", url.unwrap_or_default()));
+                continue;
+            }
+            let dependencies: Vec<Dependency> = depends_on
+                .iter()
+                .flat_map(|d| d.split(self.mapping.separator))
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(|d| Dependency {
+                    service: d.to_string(),
+                    required: true,
+                    ..Default::default()
+                })
+                .collect();
+            if dependencies.iter().any(|d| d.service == name) {
+                report.skip(line, Some(name), "Service depends on itself");
+                continue;
+            }
+
+            let definition = ServiceSchema {
+                name: name.clone(),
+                version,
+                description: None,
+                owner,
+                system: None,
+                domain: None,
+                oncall: None,
+                cost_center: None,
+                monthly_cost_estimate: None,
+                documentation_url: url,
+                service_type,
+                runtime: None,
+                endpoints: Vec::new(),
+                exposure: None,
+                dependencies: (!dependencies.is_empty()).then_some(dependencies),
+                consumes: Vec::new(),
+                consumables: Vec::new(),
+                capacity: None,
+                contract_tests: Vec::new(),
+                feature_flags: Vec::new(),
+                sensitive_metadata: Vec::new(),
+                metadata: HashMap::new(),
+                archetype: None,
+                ignores: Vec::new(),
+            };
+            let value = serde_json::to_value(&definition).map_err(|e| {
+                AureaCoreError::Internal(format!("Failed to serialize service: {}", e))
+            })?;
+            if let Err(err) = self.validation_service.validate_service(&value) {
+                report.skip(line, Some(name), err.to_string());
+                continue;
+            }
+
+            report.imported.push(name);
+            services.push(ImportedService { line, definition });
+        }
+
+        Ok((services, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_with_mapping() {
+        let mapping: ColumnMapping = serde_yaml::from_str(
+            "name: Service\nowner: Team\ntype: Kind\ndepends_on: Calls\ntypes:\n  Worker: event-driven\n",
+        )
+        .unwrap();
+        let csv = "\
+Service,Team,Kind,url,Calls
+billing,payments,REST,https://docs.example.com/billing,ledger; audit
+ledger,payments,Worker,,
+,payments,rest,,
+audit,security,mainframe,,
+search,discovery,grpc,docs.example.com,
+billing,payments,rest,,
+";
+
+        let (services, report) = CsvImporter::new(mapping).import(csv.as_bytes()).unwrap();
+        assert_eq!(report.imported, vec!["billing", "ledger"]);
+        let billing = &services[0].definition;
+        assert_eq!(billing.owner.as_deref(), Some("payments"));
+        let dependencies: Vec<_> =
+            billing.dependencies.iter().flatten().map(|d| d.service.as_str()).collect();
+        assert_eq!(dependencies, vec!["ledger", "audit"]);
+        assert_eq!(services[1].line, 3);
+        assert!(matches!(services[1].definition.service_type, ServiceType::EventDriven));
+
+        let reasons: Vec<_> = report.skipped.iter().map(|r| (r.line, r.reason.as_str())).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (4, "Missing service name"),
+                (5, "Unknown service type 'mainframe'"),
+                (6, "Invalid URL 'docs.example.com'"),
+                (7, "Duplicate service name"),
+            ]
+        );
+        assert!(report.to_string().starts_with("Imported: 2\nSkipped: 4\n"));
+    }
+
+    #[test]
+    fn test_import_requires_name_column() {
+        let err = CsvImporter::new(ColumnMapping::default()).import("id,owner\n1,a\n".as_bytes());
+        assert!(err.is_err());
+    }
+}
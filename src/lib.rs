@@ -1,12 +1,44 @@
+pub mod backstage;
+pub mod docs;
 pub mod error;
+pub mod formatter;
+pub mod hooks;
+pub mod import;
+pub mod lsp;
+pub mod probe;
 pub mod registry;
+pub mod reports;
+pub mod sarif;
+pub mod scheduler;
 pub mod schema;
+pub mod seed;
+pub mod templates;
 
-pub use error::{AureaCoreError, Result};
+pub use backstage::{BackstageEntity, EntityLink, EntityMetadata, EntitySpec};
+pub use docs::{DocsFormat, DocsGenerator};
+pub use error::{AureaCoreError, Result, ResultExt};
+pub use formatter::{ConfigFormatter, DefaultsMode};
+pub use hooks::{GitHook, HookKind};
+pub use import::{ColumnMapping, CsvImporter, ImportReport, ImportedService, SkippedRow};
+pub use lsp::LanguageServer;
+pub use probe::{ProbeReport, Prober};
 // Uncomment the dependency exports now that the module is implemented
 pub use registry::{
     CycleInfo, DependencyGraph, DependencyManager, DependencyResolver, EdgeMetadata, ImpactInfo,
 };
-pub use registry::{Service, ServiceConfig, ServiceState, ServiceStatus};
-pub use schema::service::{Dependency, Endpoint, ServiceSchema, ServiceType};
-pub use schema::validation::{CompiledSchema, SchemaType, ValidationService, VersionCompatibility};
+pub use registry::{Registry, Service, ServiceConfig, ServiceState, ServiceStatus};
+pub use reports::{cost_rollup, CostDimension, CostGroup, CostRollup};
+pub use sarif::sarif_log;
+pub use scheduler::{Job, JobStatus, JobStatuses, Scheduler, SchedulerHandle};
+pub use schema::contract::{Consumable, ConsumedApi, ContractViolation};
+pub use schema::oncall::{Oncall, OncallPlatform};
+pub use schema::remote::{ConfluentSchemaRegistry, HttpSchemaRegistry, SchemaSource};
+pub use schema::resource::{ResourceKind, ResourceSchema};
+pub use schema::service::{
+    Dependency, Endpoint, Exposure, Interaction, ServiceSchema, ServiceType, StartupPolicy,
+};
+pub use schema::validation::{
+    CompiledSchema, SchemaType, SchemaVersionPolicy, ValidationService, VersionCompatibility,
+};
+pub use seed::{CatalogSeeder, SeedOptions, SeededCatalog};
+pub use templates::{ServiceTemplate, TemplateRegistry};
@@ -0,0 +1,573 @@
+//! Language server for editing service definitions
+//!
+//! Speaks the Language Server Protocol over stdio: completion of definition fields from
+//! the service schema and of dependency names from the registry, hover docs, and
+//! validation diagnostics as definitions are edited. Only the messages these features
+//! need are implemented; documents are synced in full.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, Write};
+
+use schemars::schema_for;
+use serde_json::{json, Value};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::ServiceRegistry;
+use crate::schema::position::{child_pointer, Position, PositionIndex};
+use crate::schema::service::{normalize_definition, ServiceSchema};
+use crate::schema::validation::ValidationService;
+
+/// JSON-RPC error code for requests the server doesn't implement
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Deepest nesting of schema references followed
+const MAX_SCHEMA_DEPTH: usize = 16;
+
+// Kinds of completion items and severities of diagnostics, as numbered by the protocol
+const KIND_PROPERTY: u32 = 10;
+const KIND_VALUE: u32 = 12;
+const KIND_REFERENCE: u32 = 18;
+const SEVERITY_ERROR: u32 = 1;
+const SEVERITY_WARNING: u32 = 2;
+
+/// Language server for YAML and JSON service definitions
+pub struct LanguageServer {
+    validation_service: ValidationService,
+    registry: Option<ServiceRegistry>,
+    /// JSON Schema of service definitions, for completion and hover docs
+    schema: Value,
+    /// Text of the open documents, by URI
+    documents: HashMap<String, String>,
+    shutdown: bool,
+}
+
+impl Default for LanguageServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageServer {
+    /// Creates a server validating definitions on their own
+    pub fn new() -> Self {
+        Self {
+            validation_service: ValidationService::new(),
+            registry: None,
+            schema: serde_json::to_value(schema_for!(ServiceSchema)).unwrap_or_default(),
+            documents: HashMap::new(),
+            shutdown: false,
+        }
+    }
+
+    /// Validates definitions against the services of a registry, with its settings, and
+    /// completes dependency names from it
+    pub fn with_registry(mut self, registry: ServiceRegistry) -> Self {
+        self.validation_service = registry.validation_service().clone();
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Serves a client until it sends `exit` or closes the input
+    pub fn serve(&mut self, mut input: impl BufRead, mut output: impl Write) -> Result<()> {
+        while let Some(message) = read_message(&mut input)? {
+            let Some(method) = message.get("method").and_then(Value::as_str) else {
+                // Responses to requests the server never sends
+                continue;
+            };
+            if method == "exit" {
+                break;
+            }
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            let outcome = self.handle(method, &params);
+            if let Some(id) = message.get("id") {
+                let response = match outcome {
+                    Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    None => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("Unsupported method {}", method),
+                        },
+                    }),
+                };
+                write_message(&mut output, &response)?;
+            }
+            if let Some(uri) = params.pointer("/textDocument/uri").and_then(Value::as_str) {
+                if method.starts_with("textDocument/did") && !self.shutdown {
+                    let diagnostics = self.diagnostics(uri);
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "textDocument/publishDiagnostics",
+                        "params": { "uri": uri, "diagnostics": diagnostics },
+                    });
+                    write_message(&mut output, &notification)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a request or notification, returning the result of requests it knows
+    fn handle(&mut self, method: &str, params: &Value) -> Option<Value> {
+        let uri = params.pointer("/textDocument/uri").and_then(Value::as_str).unwrap_or_default();
+        let position = params.get("position").map(|position| {
+            let field = |name| position.get(name).and_then(Value::as_u64).unwrap_or(0) as usize;
+            Position { line: field("line") + 1, column: field("character") + 1 }
+        });
+        match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": [":", " ", "-"] },
+                    "hoverProvider": true,
+                },
+                "serverInfo": { "name": "aureacore", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+                Some(Value::Null)
+            }
+            "textDocument/didOpen" => {
+                let text = params.pointer("/textDocument/text").and_then(Value::as_str);
+                self.documents.insert(uri.to_string(), text.unwrap_or_default().to_string());
+                None
+            }
+            "textDocument/didChange" => {
+                let changes = params.get("contentChanges").and_then(Value::as_array);
+                let text = changes.and_then(|c| c.last()).and_then(|c| c.get("text"));
+                if let Some(text) = text.and_then(Value::as_str) {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                None
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                None
+            }
+            "textDocument/completion" => Some(json!(self.completion(uri, position?))),
+            "textDocument/hover" => Some(self.hover(uri, position?).unwrap_or(Value::Null)),
+            _ => None,
+        }
+    }
+
+    /// Validates an open document, locating problems in it
+    fn diagnostics(&mut self, uri: &str) -> Vec<Value> {
+        let Some(text) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        let mut definition = match parse(uri, text) {
+            Ok(definition) => definition,
+            Err((position, message)) => {
+                return vec![diagnostic(text, position, SEVERITY_ERROR, &message)];
+            }
+        };
+        normalize_definition(&mut definition);
+        let index = PositionIndex::parse(text);
+        let name = definition.get("name").and_then(Value::as_str).unwrap_or_default();
+
+        let mut available: HashSet<String> = HashSet::from([name.to_string()]);
+        if let Some(registry) = &self.registry {
+            available.extend(registry.list_services().unwrap_or_default());
+            available.extend(registry.list_resources().into_iter().map(|r| r.name.clone()));
+        }
+        let (result, warnings) =
+            self.validation_service.validate_service_with_context(name, &definition, &available);
+
+        let mut diagnostics = Vec::new();
+        let violations = self.validation_service.schema_violations(&definition);
+        for violation in &violations {
+            let position = index.nearest(&violation.pointer);
+            diagnostics.push(diagnostic(text, position, SEVERITY_ERROR, &violation.message));
+        }
+        if let (Err(err), true) = (result, violations.is_empty()) {
+            let message = err.to_string();
+            let position = index.locate_message(name, &message);
+            diagnostics.push(diagnostic(text, position, SEVERITY_ERROR, &message));
+        }
+        for warning in &warnings {
+            let position = index.locate_message(name, warning);
+            diagnostics.push(diagnostic(text, position, SEVERITY_WARNING, warning));
+        }
+        diagnostics
+    }
+
+    /// Completes the field name or value being typed in a YAML document
+    fn completion(&self, uri: &str, position: Position) -> Vec<Value> {
+        let Some(text) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        let line = text.lines().nth(position.line - 1).unwrap_or_default();
+        let typed = line.get(..position.column - 1).unwrap_or(line);
+        let indent = typed.len() - typed.trim_start().len();
+        let index = PositionIndex::parse(text);
+
+        // A new sequence item belongs to the sequence the dash lines up with
+        let mut field = typed.trim_start();
+        let mut column = indent + 1;
+        let mut parent = None;
+        if let Some(rest) = field.strip_prefix("- ") {
+            let sequence = index.parent_at(Position { line: position.line, column });
+            parent = Some(child_pointer(&sequence, "0"));
+            column += field.len() - rest.trim_start().len();
+            field = rest.trim_start();
+        }
+        let parent =
+            parent.unwrap_or_else(|| index.parent_at(Position { line: position.line, column }));
+
+        let Some((key, _)) = field.split_once(':') else {
+            return self.property_items(&parent);
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        let pointer = child_pointer(&parent, key);
+        let depends = ["/dependencies/", "/consumes/"].iter().any(|p| pointer.starts_with(p));
+        if key == "service" && depends {
+            return self.service_items();
+        }
+        values_at(&self.schema, &pointer)
+            .into_iter()
+            .map(|value| json!({ "label": value, "kind": KIND_VALUE }))
+            .collect()
+    }
+
+    /// Lists the fields the schema allows at a pointer
+    fn property_items(&self, pointer: &str) -> Vec<Value> {
+        let mut properties = BTreeMap::new();
+        for schema in schemas_at(&self.schema, pointer) {
+            for (name, property) in
+                schema.get("properties").and_then(Value::as_object).into_iter().flatten()
+            {
+                properties
+                    .entry(name.clone())
+                    .or_insert_with(|| description(&self.schema, property));
+            }
+        }
+        properties
+            .into_iter()
+            .map(|(name, description)| {
+                let mut item = json!({ "label": name, "kind": KIND_PROPERTY });
+                if let Some(description) = description {
+                    item["documentation"] = json!(description);
+                }
+                item
+            })
+            .collect()
+    }
+
+    /// Lists the services and resources of the registry, to depend on
+    fn service_items(&self) -> Vec<Value> {
+        let Some(registry) = &self.registry else {
+            return Vec::new();
+        };
+        let mut names = registry.list_services().unwrap_or_default();
+        names.extend(registry.list_resources().into_iter().map(|r| r.name.clone()));
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| {
+                let mut item = json!({ "label": name, "kind": KIND_REFERENCE });
+                if let Some(definition) =
+                    registry.get_service(&name).ok().and_then(|s| s.definition())
+                {
+                    if let Some(owner) = definition.owner {
+                        item["detail"] = json!(owner);
+                    }
+                    if let Some(description) = definition.description {
+                        item["documentation"] = json!(description);
+                    }
+                }
+                item
+            })
+            .collect()
+    }
+
+    /// Describes the field under the cursor, and the service a dependency names
+    fn hover(&self, uri: &str, position: Position) -> Option<Value> {
+        let text = self.documents.get(uri)?;
+        let index = PositionIndex::parse(text);
+        let pointer = index.field_at(position)?;
+        let mut docs: Vec<String> = schemas_at(&self.schema, pointer)
+            .into_iter()
+            .find_map(|schema| description(&self.schema, schema))
+            .into_iter()
+            .collect();
+        if pointer.ends_with("/service") {
+            let service = index
+                .value(pointer)
+                .and_then(|name| self.registry.as_ref()?.get_service(name).ok()?.definition());
+            if let Some(service) = service {
+                docs.push(format!(
+                    "**{}** {} — {}",
+                    service.name,
+                    service.version,
+                    service.description.as_deref().unwrap_or("no description")
+                ));
+            }
+        }
+        if docs.is_empty() {
+            return None;
+        }
+        Some(json!({ "contents": { "kind": "markdown", "value": docs.join("\n\n") } }))
+    }
+}
+
+/// Reads a message framed with a `Content-Length` header, or `None` at the end of input
+fn read_message(input: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        AureaCoreError::Service("LSP message without a Content-Length header".to_string())
+    })?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| AureaCoreError::Service(format!("Invalid LSP message: {}", e)))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+/// Parses a document, JSON if its URI says so, locating syntax errors
+fn parse(uri: &str, text: &str) -> std::result::Result<Value, (Position, String)> {
+    if uri.ends_with(".json") {
+        return serde_json::from_str(text).map_err(|e| {
+            let position = Position { line: e.line().max(1), column: e.column().max(1) };
+            (position, format!("Invalid JSON: {}", e))
+        });
+    }
+    let yaml: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| {
+        let position = e.location().map_or(Position::START, |location| Position {
+            line: location.line(),
+            column: location.column(),
+        });
+        (position, format!("Invalid YAML: {}", e))
+    })?;
+    serde_json::to_value(yaml).map_err(|e| (Position::START, format!("Invalid YAML: {}", e)))
+}
+
+/// Builds a diagnostic spanning the rest of the line from a position
+fn diagnostic(text: &str, position: Position, severity: u32, message: &str) -> Value {
+    let line = position.line - 1;
+    let start = position.column - 1;
+    let end = text.lines().nth(line).map_or(start, |text| text.len().max(start));
+    json!({
+        "range": {
+            "start": { "line": line, "character": start },
+            "end": { "line": line, "character": end },
+        },
+        "severity": severity,
+        "source": "aureacore",
+        "message": message,
+    })
+}
+
+/// Finds the subschemas describing the field at a pointer
+///
+/// Fields of tagged enums are described by several subschemas, one per variant.
+fn schemas_at<'a>(root: &'a Value, pointer: &str) -> Vec<&'a Value> {
+    let mut schemas = expand(root, root, 0);
+    for token in pointer.split('/').skip(1) {
+        let mut next = Vec::new();
+        for schema in schemas {
+            if let Some(property) = schema.get("properties").and_then(|p| p.get(token)) {
+                next.extend(expand(root, property, 0));
+            } else if let Some(items) =
+                schema.get("items").filter(|_| token.parse::<usize>().is_ok())
+            {
+                next.extend(expand(root, items, 0));
+            } else if let Some(values) =
+                schema.get("additionalProperties").filter(|v| v.is_object())
+            {
+                next.extend(expand(root, values, 0));
+            }
+        }
+        schemas = next;
+    }
+    schemas
+}
+
+/// Resolves references and flattens combinations of a schema
+fn expand<'a>(root: &'a Value, schema: &'a Value, depth: usize) -> Vec<&'a Value> {
+    let mut schemas = vec![schema];
+    if depth > MAX_SCHEMA_DEPTH {
+        return schemas;
+    }
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = reference.strip_prefix('#').and_then(|pointer| root.pointer(pointer));
+        schemas.extend(target.into_iter().flat_map(|target| expand(root, target, depth + 1)));
+    }
+    for combination in ["allOf", "anyOf", "oneOf"] {
+        for subschema in schema.get(combination).and_then(Value::as_array).into_iter().flatten() {
+            schemas.extend(expand(root, subschema, depth + 1));
+        }
+    }
+    schemas
+}
+
+/// Gets the first description of a schema or the schemas it refers to
+fn description(root: &Value, schema: &Value) -> Option<String> {
+    expand(root, schema, 0)
+        .into_iter()
+        .find_map(|schema| schema.get("description").and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+/// Lists the values the schema allows at a pointer, for enums and booleans
+fn values_at(root: &Value, pointer: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    for schema in schemas_at(root, pointer) {
+        let allowed = schema.get("enum").and_then(Value::as_array).into_iter().flatten();
+        let allowed = allowed.chain(schema.get("const"));
+        for value in allowed.filter_map(Value::as_str) {
+            if !values.iter().any(|known| known == value) {
+                values.push(value.to_string());
+            }
+        }
+        let is_boolean = schema.get("type").is_some_and(|t| {
+            t == "boolean" || t.as_array().is_some_and(|types| types.contains(&json!("boolean")))
+        });
+        if is_boolean && values.is_empty() {
+            values.extend(["true".to_string(), "false".to_string()]);
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn frame(message: Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    fn run(server: &mut LanguageServer, messages: Vec<Value>) -> Vec<Value> {
+        let input: String = messages.into_iter().map(frame).collect();
+        let mut output = Vec::new();
+        server.serve(Cursor::new(input), &mut output).unwrap();
+        let mut output = Cursor::new(output);
+        let mut responses = Vec::new();
+        while let Some(response) = read_message(&mut output).unwrap() {
+            responses.push(response);
+        }
+        responses
+    }
+
+    #[test]
+    fn test_language_server() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let billing = temp_dir.path().join("billing.yaml");
+        std::fs::write(
+            &billing,
+            "name: billing\nversion: 2.1.0\ndescription: Bills customers\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, billing.display());
+        registry.register_service("billing", &config).unwrap();
+        let mut server = LanguageServer::new().with_registry(registry);
+
+        let uri = "file:///catalog/orders.yaml";
+        let text = "\
+name: orders
+version: 1.0.0
+service_type:
+  type: rest
+endpoints: []
+dependencies:
+  - service: billing
+    required: maybe
+  - service: ghost
+    required: false
+  - service: b
+";
+        let position = |line: u32, character: u32| json!({ "line": line, "character": character });
+        let document = json!({ "uri": uri });
+        let responses = run(
+            &mut server,
+            vec![
+                json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+                json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/didOpen",
+                    "params": { "textDocument": { "uri": uri, "languageId": "yaml", "version": 1, "text": text } },
+                }),
+                json!({
+                    "jsonrpc": "2.0", "id": 2, "method": "textDocument/completion",
+                    "params": { "textDocument": document, "position": position(10, 13) },
+                }),
+                json!({
+                    "jsonrpc": "2.0", "id": 3, "method": "textDocument/completion",
+                    "params": { "textDocument": document, "position": position(9, 4) },
+                }),
+                json!({
+                    "jsonrpc": "2.0", "id": 4, "method": "textDocument/hover",
+                    "params": { "textDocument": document, "position": position(6, 8) },
+                }),
+                json!({ "jsonrpc": "2.0", "id": 5, "method": "workspace/symbol", "params": {} }),
+                json!({ "jsonrpc": "2.0", "id": 6, "method": "shutdown" }),
+                json!({ "jsonrpc": "2.0", "method": "exit" }),
+            ],
+        );
+        let response = |id: u64| responses.iter().find(|r| r["id"] == id).unwrap();
+        assert_eq!(response(1)["result"]["capabilities"]["hoverProvider"], true);
+
+        let published = responses.iter().find(|r| r["method"] == "textDocument/publishDiagnostics");
+        let diagnostics = published.unwrap()["params"]["diagnostics"].as_array().unwrap();
+        let reported = |severity: u32, line: u32, character: u32| {
+            diagnostics.iter().any(|d| {
+                d["severity"] == severity
+                    && d["range"]["start"] == json!({ "line": line, "character": character })
+            })
+        };
+        // The invalid `required` flag, and the optional dependency that doesn't exist
+        assert!(reported(SEVERITY_ERROR, 7, 4));
+        assert!(reported(SEVERITY_WARNING, 8, 4));
+
+        let labels = |id: u64| -> Vec<String> {
+            let items = response(id)["result"].as_array().unwrap().clone();
+            items.iter().map(|item| item["label"].as_str().unwrap().to_string()).collect()
+        };
+        assert_eq!(labels(2), vec!["billing"]);
+        assert!(labels(3).contains(&"version_constraint".to_string()));
+        let hover = response(4)["result"]["contents"]["value"].as_str().unwrap();
+        assert!(hover.contains("Bills customers"));
+        assert_eq!(response(5)["error"]["code"], METHOD_NOT_FOUND);
+        assert!(response(6)["result"].is_null());
+    }
+
+    #[test]
+    fn test_schema_values() {
+        let schema = serde_json::to_value(schema_for!(ServiceSchema)).unwrap();
+        let types = values_at(&schema, "/service_type/type");
+        assert!(types.contains(&"rest".to_string()) && types.contains(&"grpc".to_string()));
+        assert_eq!(values_at(&schema, "/dependencies/0/required"), vec!["true", "false"]);
+    }
+}
@@ -1,11 +1,34 @@
 //! AureaCore service catalog
 
-use std::path::PathBuf;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use aureacore::registry::{ServiceRegistry, ValidationSummary};
+use aureacore::docs::{DocsFormat, DocsGenerator};
+use aureacore::formatter::{ConfigFormatter, DefaultsMode};
+use aureacore::hooks::{GitHook, HookKind};
+use aureacore::import::{ColumnMapping, CsvImporter, ImportReport, ImportedService};
+use aureacore::lsp::LanguageServer;
+use aureacore::probe::Prober;
+use aureacore::registry::{
+    from_hex, to_hex, AccessControl, Actor, BundleSigner, CancellationToken, ContractVerifier,
+    FileLock, GitHubIssues, GitLabIssues, GraphLevel, GraphQuery, Journal, LaunchDarklyProvider,
+    LinkChecker, OpsgenieProvider, PagerDutyProvider, ProgressReporter, RuleSet, ServiceRegistry,
+    SyncStatus, Topology, UnleashProvider, ValidationSummary, VcsBackend, WebhookNotifier,
+    WriteBatching, DEFAULT_STALE_AFTER_DAYS, LOCKFILE_NAME,
+};
+use aureacore::reports::{cost_rollup, CostDimension};
+use aureacore::sarif::sarif_log;
+use aureacore::schema::{ConfluentSchemaRegistry, HttpSchemaRegistry, Layout, RootConfig};
+use aureacore::seed::{CatalogSeeder, SeedOptions, DEFAULT_SEED};
+use aureacore::templates::{render_definition, TemplateRegistry};
+use aureacore::{backstage, Interaction, ResultExt};
 use clap::{Parser, Subcommand};
-use tracing::{error, info};
+use indicatif::{ProgressBar, ProgressStyle};
+use tracing::{error, info, warn};
 
 /// Command-line arguments
 #[derive(Parser)]
@@ -23,6 +46,27 @@ struct Cli {
     #[arg(short, long, default_value = "./config")]
     work_dir: PathBuf,
 
+    /// Stop syncing, validation and link checks after this many seconds, keeping
+    /// partial results
+    #[arg(long, global = true)]
+    timeout: Option<u64>,
+
+    /// Root config whose layout places the catalog inside the work directory, so
+    /// several catalogs can share one work directory, and whose access grants restrict
+    /// who may modify services
+    #[arg(long, global = true)]
+    catalog: Option<PathBuf>,
+
+    /// How services are identified besides their names: `names`, or `uuid-v7` to
+    /// assign stable IDs to services registered without one
+    #[arg(long, global = true, default_value = "names")]
+    id_strategy: String,
+
+    /// Backend the catalog repository is accessed with: `git` for a local clone, or
+    /// `github` to go through the GitHub API, authenticating with GITHUB_TOKEN
+    #[arg(long, global = true, default_value = "git")]
+    vcs: String,
+
     /// Subcommand to execute
     #[command(subcommand)]
     command: Option<Commands>,
@@ -37,8 +81,73 @@ enum Commands {
     /// Update the service catalog
     Update,
 
-    /// Validate all services
-    Validate,
+    /// Validate all services, or only the given ones
+    Validate {
+        /// Services to validate (defaults to all services)
+        #[arg(short, long)]
+        service: Vec<String>,
+
+        /// Only validate services whose config or definition changed since this
+        /// revision (defaults to HEAD)
+        #[arg(
+            long,
+            value_name = "REVISION",
+            num_args = 0..=1,
+            default_missing_value = "HEAD",
+            conflicts_with = "service"
+        )]
+        changed: Option<String>,
+
+        /// Also validate services that transitively depend on the given ones
+        #[arg(long)]
+        dependents: bool,
+
+        /// Check that documentation, dashboard and runbook links answer with 2xx
+        #[arg(long)]
+        check_links: bool,
+
+        /// Check that declared feature flags exist in LaunchDarkly or Unleash
+        #[arg(long)]
+        check_flags: bool,
+
+        /// File, update and close GitHub or GitLab issues for problems reported in
+        /// several validations in a row
+        #[arg(long)]
+        file_issues: bool,
+
+        /// Compare the methods reachable gRPC services serve, per server reflection,
+        /// with their declared endpoints
+        #[arg(long)]
+        grpc_reflection: bool,
+
+        /// Apply safe lint fixes to the definition files before validating
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, also fill in placeholders such as missing HTTP methods
+        #[arg(long, requires = "fix")]
+        unsafe_fixes: bool,
+
+        /// Fail if the catalog drifted from aureacore.lock instead of updating it
+        #[arg(long)]
+        locked: bool,
+
+        /// Output format (text, or sarif for code-review UIs to show problems inline)
+        #[arg(short, long, default_value = "text")]
+        output: ValidationOutput,
+
+        /// Directory with organization templates whose archetype rules services are
+        /// checked against (defaults to <work-dir>/templates)
+        #[arg(long)]
+        templates_dir: Option<PathBuf>,
+
+        /// Fail services that do not declare which archetype they follow
+        #[arg(long)]
+        require_archetype: bool,
+    },
+
+    /// Make the registry match the repository exactly, then validate it
+    Reconcile,
 
     /// Register a new service
     Register {
@@ -49,10 +158,390 @@ enum Commands {
         /// Path to the service configuration file
         #[arg(short, long)]
         config: PathBuf,
+
+        /// Token allowing the change during a freeze window
+        #[arg(long)]
+        freeze_override: Option<String>,
+    },
+
+    /// Create a new service definition from a template
+    New {
+        /// Service name
+        name: String,
+
+        /// Template (archetype) to instantiate (defaults to the probed service type, or rest)
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Initial service version
+        #[arg(long, default_value = "0.1.0")]
+        version: String,
+
+        /// Directory with organization templates (defaults to <work-dir>/templates)
+        #[arg(long)]
+        templates_dir: Option<PathBuf>,
+
+        /// Path of the definition file to write (defaults to <name>.yaml)
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+
+        /// Base URL of the running service to probe for its type and endpoints
+        #[arg(long)]
+        probe: Option<String>,
+    },
+
+    /// Move a service out of the active catalog into the archive
+    Archive {
+        /// Service name
+        service: String,
+    },
+
+    /// Rename a service, keeping its ID
+    Rename {
+        /// Current service name
+        service: String,
+
+        /// New service name
+        new_name: String,
+    },
+
+    /// Restore an archived service into the active catalog
+    Unarchive {
+        /// Service name
+        service: String,
+    },
+
+    /// Run the contract test suites of a service and record the outcome in its status
+    Verify {
+        /// Service name
+        service: String,
+    },
+
+    /// Probe a running service for its type and endpoints
+    Probe {
+        /// Base URL of the service
+        url: String,
+    },
+
+    /// Import service definitions from a CSV inventory or Backstage catalog
+    Import {
+        /// CSV file to import, or Backstage entities YAML with `--backstage`
+        file: PathBuf,
+
+        /// YAML file mapping CSV columns to service fields
+        #[arg(short, long, conflicts_with = "backstage")]
+        mapping: Option<PathBuf>,
+
+        /// Read Component and API entities of a Backstage catalog instead of CSV
+        #[arg(long)]
+        backstage: bool,
+
+        /// Directory to write the service definitions to
+        #[arg(short, long, default_value = ".")]
+        out: PathBuf,
+
+        /// Also register the written definitions in the catalog, in batches
+        #[arg(long)]
+        register: bool,
+    },
+
+    /// Run a language server over stdio for editing service definitions
+    ///
+    /// Validates open definitions against the services of the work directory, and
+    /// completes dependency names from them.
+    Lsp,
+
+    /// Generate a synthetic catalog into the work directory, for demos and benchmarks
+    Seed {
+        /// Number of services to generate
+        #[arg(long, default_value_t = 50)]
+        services: usize,
+
+        /// Length of the longest dependency chain
+        #[arg(long, default_value_t = 4)]
+        max_depth: usize,
+
+        /// Number of dependency cycles to close
+        #[arg(long, default_value_t = 0)]
+        cycles: usize,
+
+        /// Seed of the generator, the same seed yields the same catalog
+        #[arg(long, default_value_t = DEFAULT_SEED)]
+        seed: u64,
+
+        /// Commit the generated definitions and configs on the checked out branch
+        #[arg(long)]
+        commit: bool,
+    },
+
+    /// Rewrite service definitions into canonical form
+    Fmt {
+        /// Definition files to format
+        #[arg(required_unless_present = "changed")]
+        files: Vec<PathBuf>,
+
+        /// Also format the definitions of services whose config or definition changed
+        /// since this revision (defaults to HEAD)
+        #[arg(long, value_name = "REVISION", num_args = 0..=1, default_missing_value = "HEAD")]
+        changed: Option<String>,
+
+        /// Only report files that are not formatted, without rewriting them
+        #[arg(long)]
+        check: bool,
+
+        /// How defaulted fields are written (keep, elide or materialize)
+        #[arg(short, long, default_value = "keep")]
+        defaults: DefaultsMode,
+    },
+
+    /// Generate documentation pages for all services
+    Docs {
+        /// Output directory for the generated pages
+        #[arg(short, long, default_value = "./site")]
+        out: PathBuf,
+
+        /// Output format (markdown or html)
+        #[arg(short, long, default_value = "markdown")]
+        format: DocsFormat,
+
+        /// Also write the catalog as Backstage entities to catalog-info.yaml
+        #[arg(long)]
+        emit_backstage: bool,
+    },
+
+    /// Export the dependency graph as JSON
+    Graph {
+        /// Graph level (service or system)
+        #[arg(short, long, default_value = "service")]
+        level: GraphLevel,
+
+        /// Only keep dependencies of this interaction kind, e.g. sync-call (repeatable)
+        #[arg(short, long)]
+        interaction: Vec<Interaction>,
+
+        /// Print the catalog as Backstage entities (YAML) instead
+        #[arg(long, conflicts_with_all = ["level", "interaction"])]
+        emit_backstage: bool,
+    },
+
+    /// Query the dependency graph, e.g. `deps(checkout, depth<=2, required_only)`
+    Query {
+        /// Query expression
+        expression: GraphQuery,
+    },
+
+    /// List the databases, queues and other resources services depend on
+    Resources,
+
+    /// Show the services affected by a change to, or outage of, a service or resource
+    Impact {
+        /// Name of the service or resource
+        name: String,
+
+        /// Only follow dependents deployed to this environment of the `--catalog`
+        #[arg(long)]
+        env: Option<String>,
+    },
+
+    /// List the services gated by a feature flag
+    GatedBy {
+        /// Key of the flag
+        flag: String,
+
+        /// Only match flags on this platform: launchdarkly or unleash
+        #[arg(long)]
+        provider: Option<String>,
+    },
+
+    /// List who is currently on call for a service
+    Oncall {
+        /// Name of the service
+        service: String,
+    },
+
+    /// Show the status of a service and who last changed its definition
+    Show {
+        /// Name of the service
+        name: String,
+    },
+
+    /// Generate a changelog of catalog changes
+    Changelog {
+        /// Revision (tag, branch or commit) to start from
+        #[arg(short, long)]
+        since: String,
+    },
+
+    /// Preview a branch, e.g. of a pull request, listing its catalog changes and the
+    /// services failing validation on it
+    Diff {
+        /// Branch to preview
+        branch: String,
+    },
+
+    /// Print the content hash identifying the catalog
+    Fingerprint,
+
+    /// Summarize the catalog: last sync, services by state, top warnings, cycles and
+    /// stale services
+    Status {
+        /// Days after which a service whose status wasn't checked counts as stale
+        #[arg(long, default_value_t = DEFAULT_STALE_AFTER_DAYS)]
+        stale_after_days: i64,
+
+        /// Print the overview as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run consistency checks across the catalog, work directory and caches
+    Doctor {
+        /// Root config listing the services of the catalog (defaults to --catalog)
+        #[arg(long)]
+        root: Option<PathBuf>,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Roll up the estimated monthly cost of services by team, system or namespace
+    CostReport {
+        /// Dimension to group by: team, system or namespace
+        #[arg(long, default_value = "team")]
+        by: CostDimension,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List services running on end-of-life runtimes, grouped by owning team
+    EolReport {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove orphaned config files, abandoned temp files and stale snapshots
+    Gc {
+        /// Root config listing the services to keep
+        #[arg(long)]
+        root: PathBuf,
+
+        /// List what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also delete loaded services missing from the root config, e.g. ones
+        /// registered through the API
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Rebuild the catalog from the journal of registry operations
+    Replay {
+        /// Empty directory to rebuild the catalog in
+        #[arg(long, required_unless_present = "list")]
+        into: Option<PathBuf>,
+
+        /// Journal to replay, by default the one of the work directory
+        #[arg(long)]
+        journal: Option<PathBuf>,
+
+        /// Skip operations applied after this time (RFC 3339)
+        #[arg(long)]
+        until: Option<chrono::DateTime<chrono::Utc>>,
+
+        /// List the journaled operations instead of replaying them
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Generate a key pair for signing catalog bundles
+    BundleKeygen {
+        /// File to write the private key to; the public key is written next to it with `.pub`
+        #[arg(short, long, default_value = "bundle.key")]
+        out: PathBuf,
+    },
+
+    /// Export the catalog as a signed bundle for distribution without git access
+    ExportBundle {
+        /// File to write the bundle to
+        #[arg(short, long, default_value = "catalog.tar.gz")]
+        output: PathBuf,
+
+        /// Private key to sign the bundle with
+        #[arg(short, long)]
+        key: PathBuf,
+    },
+
+    /// Replace the catalog with the contents of a signed bundle
+    ImportBundle {
+        /// Bundle to import
+        bundle: PathBuf,
+
+        /// Public key the bundle must be signed with
+        #[arg(short, long)]
+        public_key: PathBuf,
+    },
+
+    /// Suggest raising dependency constraints that lag the versions of their services
+    Outdated {
+        /// Write the suggested constraints into the definitions
+        #[arg(long, conflicts_with = "propose")]
+        apply: bool,
+
+        /// Commit the suggested constraints to this new branch for review
+        #[arg(long, value_name = "BRANCH")]
+        propose: Option<String>,
+    },
+
+    /// List the validation warnings suppressed per service and when they expire
+    Suppressions {
+        /// Only list suppressions expiring within this many days, or already expired
+        #[arg(long, value_name = "DAYS")]
+        expiring_within: Option<i64>,
+    },
+
+    /// Install git hooks into the catalog repository that check the changed services
+    InstallHooks {
+        /// Hooks to install (pre-commit or pre-push)
+        #[arg(long = "hook", default_value = "pre-commit")]
+        hooks: Vec<HookKind>,
+
+        /// Remote whose copy of the branch a pre-push hook compares with
+        #[arg(long, default_value = "origin")]
+        remote: String,
+
+        /// Replace existing hooks that were not installed by aureacore
+        #[arg(long)]
+        force: bool,
     },
 }
 
 /// Initialize the service registry
+/// How `validate` reports its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValidationOutput {
+    /// Human-readable summary
+    Text,
+    /// SARIF log, e.g. for GitHub code scanning
+    Sarif,
+}
+
+impl FromStr for ValidationOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(ValidationOutput::Text),
+            "sarif" => Ok(ValidationOutput::Sarif),
+            other => Err(format!("Unknown output format '{}', expected 'text' or 'sarif'", other)),
+        }
+    }
+}
+
 fn init_registry(cli: &Cli) -> aureacore::Result<ServiceRegistry> {
     // Use environment variables if repository URL is not provided
     let repo_url = if cli.repository.is_empty() {
@@ -72,7 +561,173 @@ fn init_registry(cli: &Cli) -> aureacore::Result<ServiceRegistry> {
         })?;
     }
 
-    ServiceRegistry::new(repo_url, cli.branch.clone(), work_dir)
+    // Rules and policies are configured next to the catalog, the freeze override token
+    // out of band
+    let root = cli.catalog.as_deref().map(RootConfig::load).transpose()?;
+    let layout = root.as_ref().map(|root| root.layout.clone()).unwrap_or_default();
+    let mut rules = RuleSet::load(&work_dir, &layout)?;
+    if let Ok(token) = std::env::var("AUREACORE_FREEZE_TOKEN") {
+        rules.freeze = rules.freeze.with_override_token(token);
+    }
+
+    // Only one instance sharing the work directory may write at a time
+    let lock = FileLock::for_work_dir(&work_dir);
+    let vcs = cli.vcs.parse::<VcsBackend>()?.open(&repo_url, &cli.branch, work_dir.clone())?;
+    let mut registry = ServiceRegistry::new(repo_url, cli.branch.clone(), work_dir)?
+        .with_vcs(vcs)?
+        .with_layout(layout)?
+        .with_rule_set(rules)?
+        .with_journal(Journal::for_work_dir(&cli.work_dir))
+        .with_id_strategy(cli.id_strategy.parse()?);
+    if let Some(root) = &root {
+        // Changes are made on behalf of the user, identified by AUREACORE_PRINCIPAL or
+        // their git email, and checked against the catalog's access grants
+        let principal = std::env::var("AUREACORE_PRINCIPAL")
+            .ok()
+            .or_else(|| git2::Config::open_default().ok()?.get_string("user.email").ok());
+        registry = registry
+            .with_topology(Topology::from_root_config(root)?)
+            .with_access_control(AccessControl::from_root_config(root))
+            .with_actor(Actor::from_principal(principal))
+            .with_aliases(root.aliases.clone())?;
+    }
+
+    // Owners of dependent services are told about changes through this endpoint
+    if let Ok(url) = std::env::var("AUREACORE_NOTIFY_WEBHOOK") {
+        registry =
+            registry.with_notifier(Box::new(WebhookNotifier::new(url, Duration::from_secs(10))));
+    }
+
+    // Feature flags are checked against the platforms with credentials configured
+    if let Ok(token) = std::env::var("LAUNCHDARKLY_API_TOKEN") {
+        registry = registry.with_flag_provider(Box::new(LaunchDarklyProvider::new(token)));
+    }
+    if let (Ok(url), Ok(token)) = (std::env::var("UNLEASH_URL"), std::env::var("UNLEASH_API_TOKEN"))
+    {
+        registry = registry.with_flag_provider(Box::new(UnleashProvider::new(url, token)));
+    }
+
+    // On-call schedules are resolved on the platforms with credentials configured
+    if let Ok(token) = std::env::var("PAGERDUTY_API_TOKEN") {
+        let mut provider = PagerDutyProvider::new(token);
+        if let Ok(url) = std::env::var("PAGERDUTY_API_URL") {
+            provider = provider.with_base_url(url);
+        }
+        registry = registry.with_oncall_provider(Box::new(provider));
+    }
+    if let Ok(key) = std::env::var("OPSGENIE_API_KEY") {
+        let mut provider = OpsgenieProvider::new(key);
+        if let Ok(url) = std::env::var("OPSGENIE_API_URL") {
+            provider = provider.with_base_url(url);
+        }
+        registry = registry.with_oncall_provider(Box::new(provider));
+    }
+
+    // Definition schemas are managed centrally when a schema registry is configured
+    let authorization = std::env::var("AUREACORE_SCHEMA_REGISTRY_AUTH").ok();
+    if let Ok(url) = std::env::var("AUREACORE_SCHEMA_REGISTRY") {
+        let mut source = HttpSchemaRegistry::new(url, Duration::from_secs(10));
+        if let Some(authorization) = authorization {
+            source = source.with_authorization(authorization);
+        }
+        registry = registry.with_schema_source(source);
+    } else if let Ok(url) = std::env::var("AUREACORE_CONFLUENT_SCHEMA_REGISTRY") {
+        let mut source = ConfluentSchemaRegistry::new(url, Duration::from_secs(10));
+        if let Some(authorization) = authorization {
+            source = source.with_authorization(authorization);
+        }
+        if let Ok(prefix) = std::env::var("AUREACORE_SCHEMA_SUBJECT_PREFIX") {
+            source = source.with_subject_prefix(prefix);
+        }
+        registry = registry.with_schema_source(source);
+    }
+
+    // Persistent validation problems are filed in GitHub or GitLab issues
+    if let (Ok(repo), Ok(token)) =
+        (std::env::var("AUREACORE_GITHUB_ISSUES"), std::env::var("GITHUB_TOKEN"))
+    {
+        let mut tracker = GitHubIssues::new(&repo, token)?;
+        if let Ok(url) = std::env::var("GITHUB_API_URL") {
+            tracker = tracker.with_api_url(url);
+        }
+        registry = registry.with_issue_tracker(Box::new(tracker));
+    } else if let (Ok(project), Ok(token)) =
+        (std::env::var("AUREACORE_GITLAB_ISSUES"), std::env::var("GITLAB_TOKEN"))
+    {
+        let mut tracker = GitLabIssues::new(&project, token);
+        if let Ok(url) = std::env::var("GITLAB_URL") {
+            tracker = tracker.with_base_url(url);
+        }
+        registry = registry.with_issue_tracker(Box::new(tracker));
+    }
+    registry.with_lock(Box::new(lock))
+}
+
+/// Gets the layout of the catalog from the `--catalog` root config, if given
+fn layout(cli: &Cli) -> aureacore::Result<Layout> {
+    Ok(cli
+        .catalog
+        .as_deref()
+        .map(RootConfig::load)
+        .transpose()?
+        .map(|root| root.layout)
+        .unwrap_or_default())
+}
+
+/// Gets the built-in templates and those in `dir`, or `<work-dir>/templates` if not given
+fn templates(cli: &Cli, dir: Option<&Path>) -> aureacore::Result<TemplateRegistry> {
+    let mut templates = TemplateRegistry::builtin();
+    let dir = dir.map_or_else(|| cli.work_dir.join("templates"), Path::to_path_buf);
+    if dir.is_dir() {
+        templates.load_dir(&dir)?;
+    }
+    Ok(templates)
+}
+
+/// Creates the token stopping long-running operations once the `--timeout` passes
+fn cancellation(cli: &Cli) -> CancellationToken {
+    match cli.timeout {
+        Some(seconds) => CancellationToken::with_timeout(Duration::from_secs(seconds)),
+        None => CancellationToken::new(),
+    }
+}
+
+/// Draws the progress of registry operations as a bar on stderr
+///
+/// Nothing is drawn unless stderr is a terminal.
+#[derive(Default)]
+struct ProgressBarReporter {
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+impl ProgressBarReporter {
+    /// Creates a reporter to hand to registry operations
+    fn shared() -> Arc<dyn ProgressReporter> {
+        Arc::new(Self::default())
+    }
+}
+
+impl ProgressReporter for ProgressBarReporter {
+    fn started(&self, operation: &str) {
+        let bar = ProgressBar::new(100).with_prefix(operation.to_string());
+        if let Ok(style) = ProgressStyle::with_template("{prefix} [{bar:30}] {pos:>3}% {msg}") {
+            bar.set_style(style.progress_chars("=> "));
+        }
+        *self.bar.lock().unwrap_or_else(|e| e.into_inner()) = Some(bar);
+    }
+
+    fn progress(&self, percent: u8, message: &str) {
+        if let Some(bar) = &*self.bar.lock().unwrap_or_else(|e| e.into_inner()) {
+            bar.set_position(percent.into());
+            bar.set_message(message.to_string());
+        }
+    }
+
+    fn finished(&self, _operation: &str, _success: bool) {
+        if let Some(bar) = self.bar.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            bar.finish_and_clear();
+        }
+    }
 }
 
 /// Display validation summary
@@ -83,7 +738,14 @@ fn display_validation_summary(summary: &ValidationSummary) {
     println!("Successful: {}", summary.successful_count());
     println!("Failed: {}", summary.failed_count());
     println!("Warnings: {}", summary.warning_count());
+    if summary.suppressed_count() > 0 {
+        println!("Suppressed warnings: {}", summary.suppressed_count());
+    }
+    println!("Dead links: {}", summary.dead_link_count());
     println!("Timestamp: {}", summary.timestamp.format("%Y-%m-%d %H:%M:%S UTC"));
+    if summary.timed_out {
+        println!("Timed out: {} service(s) skipped", summary.skipped.len());
+    }
 
     if !summary.successful.is_empty() {
         println!("\nSuccessful services:");
@@ -101,6 +763,22 @@ fn display_validation_summary(summary: &ValidationSummary) {
         }
     }
 
+    if !summary.dead_links.is_empty() {
+        println!("\nDead links:");
+        for (service, links) in &summary.dead_links {
+            for link in links {
+                println!("  🔗 {}: {} {} ({})", service, link.field, link.url, link.reason);
+            }
+        }
+    }
+
+    if !summary.skipped.is_empty() {
+        println!("\nSkipped services:");
+        for service in &summary.skipped {
+            println!("  ⏱️  {}", service);
+        }
+    }
+
     if !summary.failed.is_empty() {
         println!("\nFailed services:");
         for (service, error) in &summary.failed {
@@ -111,8 +789,8 @@ fn display_validation_summary(summary: &ValidationSummary) {
 
 #[tokio::main]
 async fn main() -> aureacore::Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+    // Log to stderr, so machine-readable output can be piped
+    tracing_subscriber::fmt().with_writer(std::io::stderr).init();
 
     info!("Starting AureaCore service catalog...");
 
@@ -123,29 +801,155 @@ async fn main() -> aureacore::Result<()> {
         Some(Commands::Init) => {
             info!("Initializing service catalog...");
             let mut registry = init_registry(&cli)?;
-            registry.init()?;
+            registry.init_with_progress(ProgressBarReporter::shared())?;
             info!("Service catalog initialized successfully");
         }
         Some(Commands::Update) => {
             info!("Updating service catalog...");
             let mut registry = init_registry(&cli)?;
-            registry.update()?;
-            registry.load_services()?;
-            info!("Service catalog updated successfully");
+            let status = registry.sync_with_cancellation(&cancellation(&cli))?.clone();
+            registry.load_services_with_progress(ProgressBarReporter::shared())?;
+            match status {
+                SyncStatus::Stale { last_sync, error } => {
+                    let last_sync = last_sync
+                        .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    warn!(
+                        "Repository unavailable ({}), using local state from {}",
+                        error, last_sync
+                    );
+                }
+                _ => info!("Service catalog updated successfully"),
+            }
+        }
+        Some(Commands::Validate {
+            service,
+            changed,
+            dependents,
+            check_links,
+            check_flags,
+            file_issues,
+            grpc_reflection,
+            fix,
+            unsafe_fixes,
+            locked,
+            output,
+            templates_dir,
+            require_archetype,
+        }) => {
+            let cancellation = cancellation(&cli);
+            let progress = ProgressBarReporter::shared();
+            let templates =
+                templates(&cli, templates_dir.as_deref())?.require_archetype(*require_archetype);
+            let mut registry = init_registry(&cli)?.with_templates(templates);
+            registry.load_services_with_progress(progress.clone())?;
+
+            let service = match changed {
+                Some(revision) => {
+                    let changed = registry.changed_services(revision)?;
+                    if changed.is_empty() {
+                        info!("No services changed since {}", revision);
+                        return Ok(());
+                    }
+                    changed
+                }
+                None => service.clone(),
+            };
+            if *fix {
+                for (name, fixes) in registry.apply_lint_fixes(*unsafe_fixes)? {
+                    for fix in fixes {
+                        match output {
+                            ValidationOutput::Text => println!("🔧 {}: {}", name, fix.description),
+                            ValidationOutput::Sarif => info!("Fixed {}: {}", name, fix.description),
+                        }
+                    }
+                }
+            }
+
+            let mut summary = if service.is_empty() {
+                info!("Validating all services...");
+                registry.validate_all_services_with_progress(&cancellation, progress)?
+            } else {
+                info!("Validating {}...", service.join(", "));
+                registry.validate_services(&service, *dependents)?
+            };
+            if *check_links {
+                info!("Checking links...");
+                LinkChecker::new()
+                    .with_cancellation(cancellation)
+                    .check_summary(&registry, &mut summary)
+                    .await;
+            }
+            if *check_flags {
+                info!("Checking feature flags...");
+                registry.check_feature_flags(&mut summary).await;
+            }
+            if *grpc_reflection {
+                info!("Checking gRPC services for drift...");
+                Prober::default().check_grpc_drift(&registry, &mut summary);
+            }
+            match output {
+                ValidationOutput::Text => display_validation_summary(&summary),
+                ValidationOutput::Sarif => {
+                    let log = sarif_log(&registry, &summary);
+                    let json = serde_json::to_string_pretty(&log).map_err(|e| {
+                        aureacore::AureaCoreError::Internal(format!(
+                            "Failed to serialize SARIF log: {}",
+                            e
+                        ))
+                    })?;
+                    println!("{}", json);
+                }
+            }
+            if *file_issues {
+                info!("Syncing issues...");
+                let report = registry.sync_issues(&summary).await?;
+                for (action, services) in [
+                    ("Opened", &report.opened),
+                    ("Updated", &report.updated),
+                    ("Closed", &report.closed),
+                ] {
+                    for service in services {
+                        info!("{} issue for {}", action, service);
+                    }
+                }
+            }
+
+            if !summary.is_successful() {
+                process::exit(1);
+            }
+            if *locked {
+                let drift = registry.check_lockfile()?;
+                if !drift.is_empty() {
+                    for change in &drift {
+                        println!("❌ {}", change);
+                    }
+                    error!("The catalog drifted from {}", LOCKFILE_NAME);
+                    process::exit(1);
+                }
+            } else {
+                registry.write_lockfile()?;
+                info!("Wrote {}", registry.lockfile_path().display());
+            }
         }
-        Some(Commands::Validate) => {
-            info!("Validating all services...");
+        Some(Commands::Reconcile) => {
+            info!("Reconciling registry with the repository...");
             let mut registry = init_registry(&cli)?;
-            registry.load_services()?;
+            registry.sync()?;
+
+            let report = registry.reconcile()?;
+            print!("{}", report);
 
             let summary = registry.validate_all_services()?;
             display_validation_summary(&summary);
-
+            if let Err(err) = registry.save_snapshot() {
+                warn!("Failed to write registry snapshot: {}", err);
+            }
             if summary.failed_count() > 0 {
                 process::exit(1);
             }
         }
-        Some(Commands::Register { name, config }) => {
+        Some(Commands::Register { name, config, freeze_override }) => {
             info!("Registering service {}...", name);
             let mut registry = init_registry(&cli)?;
 
@@ -156,9 +960,630 @@ async fn main() -> aureacore::Result<()> {
             })?;
 
             // Register service
-            registry.register_service(name, &config_content)?;
+            match freeze_override {
+                Some(token) => registry
+                    .overriding_freeze(token, |r| r.register_service(name, &config_content))?,
+                None => registry.register_service(name, &config_content)?,
+            }
             info!("Service {} registered successfully", name);
         }
+        Some(Commands::New { name, template, version, templates_dir, out, probe }) => {
+            let templates = templates(&cli, templates_dir.as_deref())?;
+
+            let report = match probe {
+                Some(url) => {
+                    let report = Prober::default().probe(url)?;
+                    print!("{}", report);
+                    Some(report)
+                }
+                None => None,
+            };
+            // An explicit template wins; otherwise follow the probed service type
+            let template = template.clone().unwrap_or_else(|| {
+                report
+                    .as_ref()
+                    .and_then(|r| r.service_type.as_ref())
+                    .and_then(|t| templates.for_service_type(t))
+                    .map(|t| t.name.clone())
+                    .unwrap_or_else(|| "rest".to_string())
+            });
+            let template = &template;
+
+            let Some(archetype) = templates.get(template) else {
+                error!(
+                    "Unknown template '{}'. Available: {}",
+                    template,
+                    templates.names().join(", ")
+                );
+                process::exit(1);
+            };
+
+            let out = out.clone().unwrap_or_else(|| PathBuf::from(format!("{}.yaml", name)));
+            if out.exists() {
+                error!("Refusing to overwrite existing file {}", out.display());
+                process::exit(1);
+            }
+            let mut definition = archetype.instantiate(name, version);
+            if let Some(report) = &report {
+                report.apply(&mut definition);
+                // Archetypes fix the service type, so a mismatching probe result is only reported
+                if serde_json::to_value(&definition.service_type).ok()
+                    != serde_json::to_value(&archetype.service_type).ok()
+                {
+                    warn!(
+                        "Probed service type differs from template '{}', keeping the template's",
+                        template
+                    );
+                    definition.service_type = archetype.service_type.clone();
+                }
+            }
+            std::fs::write(&out, render_definition(&definition)?)?;
+            info!("Created {} from template '{}'", out.display(), template);
+        }
+        Some(Commands::Archive { service }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let impacted = registry.archive_service(service)?;
+            info!("Service {} archived", service);
+            if !impacted.is_empty() {
+                warn!("Services depending on {}: {}", service, impacted.join(", "));
+            }
+        }
+        Some(Commands::Rename { service, new_name }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let dependents = registry.rename_service(service, new_name)?;
+            let renamed = registry.get_service(new_name)?;
+            match renamed.id() {
+                Some(id) => info!("Service {} renamed to {} ({})", service, new_name, id),
+                None => info!("Service {} renamed to {}", service, new_name),
+            }
+            if !dependents.is_empty() {
+                warn!("Services still depending on {}: {}", service, dependents.join(", "));
+            }
+        }
+        Some(Commands::Unarchive { service }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            registry.unarchive_service(service)?;
+            let restored = registry.get_service(service)?;
+            info!("Service {} restored in state {}", service, restored.status.state);
+        }
+        Some(Commands::Verify { service }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let contracts = registry.verify_contracts(service, &ContractVerifier::default())?;
+            for result in &contracts.results {
+                let mark = if result.passed { "✅" } else { "❌" };
+                println!("{} {}: {}", mark, result.name, result.detail);
+            }
+            if !contracts.passed {
+                process::exit(1);
+            }
+        }
+        Some(Commands::Probe { url }) => {
+            print!("{}", Prober::default().probe(url)?);
+        }
+        Some(Commands::Import { file, mapping, backstage, out, register }) => {
+            let (services, mut report) = if *backstage {
+                let entities = backstage::parse_entities(&std::fs::read_to_string(file)?)?;
+                let services: Vec<ImportedService> = backstage::from_entities(&entities)?
+                    .into_iter()
+                    .map(|definition| ImportedService { line: 0, definition })
+                    .collect();
+                let imported = services.iter().map(|s| s.definition.name.clone()).collect();
+                (services, ImportReport { imported, skipped: Vec::new() })
+            } else {
+                let mapping = match mapping {
+                    Some(path) => ColumnMapping::load(path)?,
+                    None => ColumnMapping::default(),
+                };
+                CsvImporter::new(mapping).import(std::fs::File::open(file)?)?
+            };
+
+            std::fs::create_dir_all(out)?;
+            let mut written = Vec::with_capacity(services.len());
+            for service in &services {
+                let name = &service.definition.name;
+                let path = out.join(format!("{}.yaml", name));
+                if path.exists() {
+                    report.imported.retain(|imported| imported != name);
+                    let reason = format!("{} already exists", path.display());
+                    report.skip(service.line, Some(name.clone()), reason);
+                    continue;
+                }
+                std::fs::write(&path, render_definition(&service.definition)?)?;
+                written.push((name.clone(), path));
+            }
+            print!("{}", report);
+
+            if *register {
+                // Registrations are coalesced into a few writes and validation passes
+                let mut registry = init_registry(&cli)?;
+                registry.load_services()?;
+                registry.begin_batch(WriteBatching::default())?;
+                for (name, path) in &written {
+                    let path = path.canonicalize()?;
+                    let config = serde_json::json!({ "config_path": path.display().to_string() });
+                    registry.register_service(name, &config.to_string())?;
+                }
+                let reports = match registry.commit() {
+                    Ok(reports) => reports,
+                    Err(err) => {
+                        let discarded = registry.abort_batch();
+                        error!("Discarded {} pending registration(s)", discarded.len());
+                        return Err(err);
+                    }
+                };
+                let failed: usize = reports.iter().map(|r| r.validation.failed.len()).sum();
+                info!(
+                    "Registered {} services in {} batch(es), {} failed validation",
+                    written.len(),
+                    reports.len(),
+                    failed
+                );
+            }
+        }
+        Some(Commands::Lsp) => {
+            let mut server = LanguageServer::new();
+            let registry = init_registry(&cli).and_then(|mut registry| {
+                registry.load_services()?;
+                Ok(registry)
+            });
+            match registry {
+                Ok(registry) => server = server.with_registry(registry),
+                Err(err) => warn!("Validating definitions without the catalog: {}", err),
+            }
+            server.serve(std::io::stdin().lock(), std::io::stdout().lock())?;
+        }
+        Some(Commands::Seed { services, max_depth, cycles, seed, commit }) => {
+            let options = SeedOptions {
+                services: *services,
+                max_depth: *max_depth,
+                cycles: *cycles,
+                seed: *seed,
+            };
+            let definitions = CatalogSeeder::new(options).generate()?;
+
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+            let message = format!("Seed a synthetic catalog of {} services", definitions.len());
+            let seeded = registry.seed_catalog(&definitions, commit.then_some(message.as_str()))?;
+            info!(
+                "Seeded {} services ({} valid, {} failed, {} with warnings)",
+                seeded.sync.added.len(),
+                seeded.sync.validation.successful.len(),
+                seeded.sync.validation.failed.len(),
+                seeded.sync.validation.warnings.len()
+            );
+            if let Some(commit) = seeded.commit {
+                info!("Committed the seeded catalog ({})", commit);
+            }
+        }
+        Some(Commands::Fmt { files, changed, check, defaults }) => {
+            let mut files = files.clone();
+            if let Some(revision) = changed {
+                let mut registry = init_registry(&cli)?;
+                registry.load_services()?;
+                for name in registry.changed_services(revision)? {
+                    files.push(PathBuf::from(&registry.get_service(&name)?.config.config_path));
+                }
+            }
+
+            let formatter = ConfigFormatter::new().with_defaults(*defaults);
+            let mut unformatted = Vec::new();
+            for file in &files {
+                let content = std::fs::read_to_string(file)?;
+                let formatted = formatter
+                    .format(&content)
+                    .with_context(|| format!("Failed to format {}", file.display()))?;
+                if formatted == content {
+                    continue;
+                }
+                if *check {
+                    println!("{}", file.display());
+                } else {
+                    std::fs::write(file, formatted)?;
+                    info!("Formatted {}", file.display());
+                }
+                unformatted.push(file);
+            }
+            if *check && !unformatted.is_empty() {
+                error!("{} file(s) are not formatted", unformatted.len());
+                process::exit(1);
+            }
+        }
+        Some(Commands::Docs { out, format, emit_backstage }) => {
+            info!("Generating service documentation...");
+            let mut registry = init_registry(&cli)?;
+            registry.warm_start()?;
+
+            let written = DocsGenerator::new(*format).generate(&registry, out)?;
+            info!("Wrote {} documentation pages to {}", written.len(), out.display());
+            if *emit_backstage {
+                let entities = backstage::export_registry(&registry)?;
+                let path = out.join("catalog-info.yaml");
+                std::fs::write(&path, backstage::render_entities(&entities)?)?;
+                info!("Wrote {} Backstage entities to {}", entities.len(), path.display());
+            }
+        }
+        Some(Commands::Graph { level, interaction, emit_backstage }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.warm_start()?;
+
+            if *emit_backstage {
+                let entities = backstage::export_registry(&registry)?;
+                print!("{}", backstage::render_entities(&entities)?);
+                return Ok(());
+            }
+
+            let export = registry.export_graph_of(*level, interaction);
+            let json = serde_json::to_string_pretty(&export).map_err(|e| {
+                aureacore::AureaCoreError::Internal(format!("Failed to serialize graph: {}", e))
+            })?;
+            println!("{}", json);
+        }
+        Some(Commands::Query { expression }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.warm_start()?;
+
+            for found in registry.query_graph(expression)? {
+                println!("{}\t{}\t{}", found.depth, found.service, found.path.join(" -> "));
+            }
+        }
+        Some(Commands::Resources) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            for resource in registry.list_resources() {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    resource.name,
+                    resource.kind,
+                    resource.engine.as_deref().unwrap_or("-"),
+                    resource.owner.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        Some(Commands::Impact { name, env }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let impacts = match env {
+                Some(env) => registry.get_detailed_impact_in_environment(name, env)?,
+                None => registry.get_detailed_impact(name)?,
+            };
+            for impact in impacts {
+                let kind = if impact.is_required { "required" } else { "optional" };
+                println!("{}\t{}\t{}", impact.service_name, kind, impact.impact_path.join(" -> "));
+            }
+        }
+        Some(Commands::GatedBy { flag, provider }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let platform = provider.as_deref().map(str::parse).transpose()?;
+            for usage in registry.services_gated_by(flag, platform) {
+                let description = usage.flag.description.unwrap_or_default();
+                println!("{}\t{}\t{}", usage.service, usage.flag.provider, description);
+            }
+        }
+        Some(Commands::Oncall { service }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            for contact in registry.who_is_oncall_for(service).await? {
+                let email = contact.email.unwrap_or_default();
+                println!("{}\t{}\t{}", contact.escalation_level, contact.name, email);
+            }
+        }
+        Some(Commands::Show { name }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.warm_start()?;
+
+            let service =
+                registry.get_service(name).or_else(|_| registry.get_archived_service(name))?;
+            println!("Name: {}", service.name);
+            if let Some(namespace) = &service.config.namespace {
+                println!("Namespace: {}", namespace);
+            }
+            println!("Definition: {}", service.config.config_path);
+            println!("State: {}", service.status.state);
+            if let Some(error) = &service.status.error_message {
+                println!("Error: {}", error);
+            }
+            for warning in &service.status.warnings {
+                println!("Warning: {}", warning);
+            }
+            match registry.last_modified_by(name) {
+                Ok(Some(commit)) => println!(
+                    "Last modified: {} by {} <{}> on {}: {}",
+                    &commit.id[..commit.id.len().min(7)],
+                    commit.author,
+                    commit.email,
+                    commit.time.format("%Y-%m-%d %H:%M:%S UTC"),
+                    commit.summary
+                ),
+                Ok(None) => println!("Last modified: not tracked in the catalog repository"),
+                Err(err) => println!("Last modified: unknown ({})", err),
+            }
+        }
+        Some(Commands::Changelog { since }) => {
+            info!("Generating changelog since {}...", since);
+            let registry = init_registry(&cli)?;
+            let changelog = registry.changelog_since(since)?;
+            print!("{}", changelog.to_markdown());
+        }
+        Some(Commands::Diff { branch }) => {
+            info!("Previewing branch {}...", branch);
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let temp_dir = tempfile::tempdir()?;
+            let mut preview = registry.preview_branch(branch, temp_dir.path().join("preview"))?;
+            let changes = preview.diff_from(&registry);
+            let summary = preview.validate_all_services();
+            preview.close_preview()?;
+            let summary = summary?;
+
+            if changes.is_empty() {
+                println!("No catalog changes");
+            }
+            for change in &changes {
+                println!("{}", change);
+            }
+            for (name, error) in &summary.failed {
+                println!("Failed: {}: {}", name, error);
+            }
+            if !summary.failed.is_empty() {
+                process::exit(1);
+            }
+        }
+        Some(Commands::Fingerprint) => {
+            let mut registry = init_registry(&cli)?;
+            registry.warm_start()?;
+            println!("{}", registry.fingerprint()?);
+        }
+        Some(Commands::Status { stale_after_days, json }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.warm_start()?;
+
+            let overview = registry.overview(chrono::Duration::days(*stale_after_days));
+            if *json {
+                let json = serde_json::to_string_pretty(&overview).map_err(|e| {
+                    aureacore::AureaCoreError::Internal(format!(
+                        "Failed to serialize overview: {}",
+                        e
+                    ))
+                })?;
+                println!("{}", json);
+            } else {
+                print!("{}", overview);
+            }
+        }
+        Some(Commands::Doctor { root, json }) => {
+            let root =
+                root.as_ref().or(cli.catalog.as_ref()).map(|p| RootConfig::load(p)).transpose()?;
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let report = registry.doctor(root.as_ref())?;
+            if *json {
+                let json = serde_json::to_string_pretty(&report).map_err(|e| {
+                    aureacore::AureaCoreError::Internal(format!(
+                        "Failed to serialize report: {}",
+                        e
+                    ))
+                })?;
+                println!("{}", json);
+            } else {
+                print!("{}", report);
+            }
+            if !report.is_healthy() {
+                process::exit(1);
+            }
+        }
+        Some(Commands::CostReport { by, json }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let rollup = cost_rollup(&registry, *by)?;
+            if *json {
+                let json = serde_json::to_string_pretty(&rollup).map_err(|e| {
+                    aureacore::AureaCoreError::Internal(format!(
+                        "Failed to serialize report: {}",
+                        e
+                    ))
+                })?;
+                println!("{}", json);
+            } else {
+                print!("{}", rollup);
+            }
+        }
+        Some(Commands::EolReport { json }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let report = registry.eol_report();
+            if *json {
+                let json = serde_json::to_string_pretty(&report).map_err(|e| {
+                    aureacore::AureaCoreError::Internal(format!(
+                        "Failed to serialize report: {}",
+                        e
+                    ))
+                })?;
+                println!("{}", json);
+            } else {
+                print!("{}", report);
+            }
+        }
+        Some(Commands::Gc { root, dry_run, force }) => {
+            let root = RootConfig::load(root)?;
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let report = registry.cleanup(&root, *dry_run, *force)?;
+            print!("{}", report);
+        }
+        Some(Commands::Replay { into, journal, until, list }) => {
+            let journal = match journal {
+                Some(path) => Journal::new(path),
+                None => Journal::for_work_dir(&cli.work_dir),
+            };
+            let entries: Vec<_> = journal
+                .read()?
+                .into_iter()
+                .filter(|entry| until.is_none_or(|until| entry.timestamp <= until))
+                .collect();
+            if *list {
+                for entry in &entries {
+                    println!("{} {}", entry.timestamp.to_rfc3339(), entry.operation);
+                }
+                return Ok(());
+            }
+
+            let into = into.as_ref().expect("clap requires --into without --list");
+            if into.read_dir().is_ok_and(|mut dir| dir.next().is_some()) {
+                error!("Refusing to replay into non-empty directory {}", into.display());
+                process::exit(1);
+            }
+            let mut registry =
+                ServiceRegistry::new(String::new(), cli.branch.clone(), into.clone())?
+                    .with_layout(layout(&cli)?)?;
+            let applied = registry.replay(&entries, None)?;
+            info!(
+                "Replayed {} operations into {}: {} services, {} archived",
+                applied,
+                into.display(),
+                registry.list_services()?.len(),
+                registry.list_archived_services().len()
+            );
+        }
+        Some(Commands::Outdated { apply, propose }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let bumps = registry.constraint_bumps();
+            if bumps.is_empty() {
+                info!("All dependency constraints are up to date");
+                return Ok(());
+            }
+            for bump in &bumps {
+                println!("{}", bump);
+            }
+            if *apply {
+                let updated = registry.apply_constraint_bumps(&bumps)?;
+                info!("Updated the definitions of {} service(s)", updated.len());
+            } else if let Some(branch) = propose {
+                let commit = registry.propose_constraint_bumps(&bumps, branch)?;
+                info!("Proposed {} bump(s) on branch {} ({})", bumps.len(), branch, commit);
+            }
+        }
+        Some(Commands::Suppressions { expiring_within }) => {
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let today = chrono::Utc::now().date_naive();
+            for (service, suppression) in registry.suppressions() {
+                let days = (suppression.expires - today).num_days();
+                if expiring_within.is_some_and(|within| days > within) {
+                    continue;
+                }
+                let expiry = if suppression.is_active(today) {
+                    format!("expires {} (in {} days)", suppression.expires, days)
+                } else {
+                    format!("EXPIRED {}", suppression.expires)
+                };
+                println!("{}\t{}\t{}\t{}", service, suppression.code, expiry, suppression.reason);
+            }
+        }
+        Some(Commands::InstallHooks { hooks, remote, force }) => {
+            let git_dir = cli.work_dir.join(".git");
+            if !git_dir.is_dir() {
+                error!("{} is not a git repository", cli.work_dir.display());
+                process::exit(1);
+            }
+
+            // Hooks run from the repository root, so they get absolute paths
+            let mut args = Vec::new();
+            if !cli.repository.is_empty() {
+                args.extend(["--repository".to_string(), cli.repository.clone()]);
+            }
+            args.extend(["--branch".to_string(), cli.branch.clone()]);
+            let work_dir = std::path::absolute(&cli.work_dir)?;
+            args.extend(["--work-dir".to_string(), work_dir.display().to_string()]);
+            if let Some(catalog) = &cli.catalog {
+                let catalog = std::path::absolute(catalog)?;
+                args.extend(["--catalog".to_string(), catalog.display().to_string()]);
+            }
+
+            for kind in hooks {
+                let hook = match kind {
+                    HookKind::PreCommit => GitHook::pre_commit(),
+                    HookKind::PrePush => GitHook::pre_push(remote, &cli.branch),
+                };
+                let hook = args.iter().fold(hook, |hook, arg| hook.with_arg(arg));
+                let path = hook.install(&git_dir.join("hooks"), *force)?;
+                info!("Installed {}", path.display());
+            }
+        }
+        Some(Commands::BundleKeygen { out }) => {
+            // Only the owner may read the private key; never replace an existing one
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            let mut file = match options.open(out) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    error!("Refusing to overwrite existing key {}", out.display());
+                    process::exit(1);
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let (signer, pkcs8) = BundleSigner::generate()?;
+            file.write_all(to_hex(&pkcs8).as_bytes())?;
+            let public = out.with_extension("pub");
+            std::fs::write(&public, to_hex(&signer.public_key()))?;
+            info!("Wrote signing key to {} and public key to {}", out.display(), public.display());
+        }
+        Some(Commands::ExportBundle { output, key }) => {
+            let signer = BundleSigner::load(key)?;
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let manifest = registry.export_bundle(output, &signer)?;
+            info!(
+                "Exported {} services to bundle {} ({})",
+                manifest.services.len(),
+                manifest.id(),
+                output.display()
+            );
+        }
+        Some(Commands::ImportBundle { bundle, public_key }) => {
+            let public_key = from_hex(std::fs::read_to_string(public_key)?.trim())?;
+            let mut registry = init_registry(&cli)?;
+            registry.load_services()?;
+
+            let report = registry.import_bundle(bundle, &public_key)?;
+            info!(
+                "Imported bundle: {} added, {} updated, {} removed",
+                report.added.len(),
+                report.updated.len(),
+                report.removed.len()
+            );
+            display_validation_summary(&report.validation);
+            if report.validation.failed_count() > 0 {
+                process::exit(1);
+            }
+        }
         None => {
             info!("No command specified, use --help for available commands");
         }
@@ -0,0 +1,770 @@
+//! Service type inference by probing a running service
+//!
+//! A [`Prober`] looks for well-known API descriptions on a base URL (a GraphQL
+//! endpoint, gRPC, OpenAPI and AsyncAPI documents) and suggests the service type and
+//! endpoints to scaffold a definition with.
+//!
+//! Probes use HTTP/1.1, so gRPC is only recognized behind gRPC-Web or a gateway that
+//! answers with gRPC status headers; plain HTTP/2-only servers are not detected. Where
+//! server reflection is reachable that way, the methods it lists become endpoints, and
+//! [`Prober::check_grpc_drift`] compares them with the endpoints services declare.
+
+use std::fmt;
+use std::io::Read;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::{ServiceRegistry, ValidationSummary};
+use crate::schema::service::{Endpoint, ServiceSchema, ServiceType};
+
+/// Paths an OpenAPI (or Swagger) document is commonly served at
+const OPENAPI_PATHS: &[&str] =
+    &["/openapi.json", "/openapi.yaml", "/swagger.json", "/v3/api-docs", "/api-docs"];
+
+/// Paths an AsyncAPI document is commonly served at
+const ASYNCAPI_PATHS: &[&str] = &["/asyncapi.json", "/asyncapi.yaml"];
+
+/// Method of the gRPC server reflection service
+const GRPC_REFLECTION_PATH: &str = "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo";
+
+/// Prefix of the services implementing reflection itself, left out of method lists
+const GRPC_REFLECTION_PACKAGE: &str = "grpc.reflection.";
+
+/// Largest gRPC-Web response read from a reflection service
+const MAX_GRPC_RESPONSE: u64 = 16 * 1024 * 1024;
+
+/// HTTP methods described by OpenAPI path items
+const HTTP_METHODS: &[&str] =
+    &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// What probing a service found
+#[derive(Debug, Clone, Default)]
+pub struct ProbeReport {
+    /// Suggested service type, if any API was recognized
+    pub service_type: Option<ServiceType>,
+    /// Endpoints found in API descriptions
+    pub endpoints: Vec<Endpoint>,
+    /// What was found where, in probing order
+    pub evidence: Vec<String>,
+}
+
+impl ProbeReport {
+    /// Applies the suggestions to a service definition
+    ///
+    /// The service type is replaced; probed endpoints are added unless an endpoint
+    /// with the same name or path and method is already declared.
+    pub fn apply(&self, definition: &mut ServiceSchema) {
+        if let Some(service_type) = &self.service_type {
+            definition.service_type = service_type.clone();
+        }
+        for endpoint in &self.endpoints {
+            let declared = definition.endpoints.iter().any(|e| {
+                e.name == endpoint.name || (e.path == endpoint.path && e.method == endpoint.method)
+            });
+            if !declared {
+                definition.endpoints.push(endpoint.clone());
+            }
+        }
+    }
+}
+
+impl fmt::Display for ProbeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.service_type {
+            Some(service_type) => {
+                writeln!(f, "Suggested service type: {}", type_name(service_type))?
+            }
+            None => writeln!(f, "No known API found")?,
+        }
+        for line in &self.evidence {
+            writeln!(f, "  - {}", line)?;
+        }
+        writeln!(f, "Endpoints: {}", self.endpoints.len())?;
+        for endpoint in &self.endpoints {
+            let method = endpoint.method.as_deref().unwrap_or("-");
+            writeln!(f, "  {} {} ({})", method, endpoint.path, endpoint.name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Gets the name a service type is written as in definitions
+fn type_name(service_type: &ServiceType) -> String {
+    match service_type {
+        ServiceType::Rest => "rest".to_string(),
+        ServiceType::Grpc => "grpc".to_string(),
+        ServiceType::GraphQL => "graphql".to_string(),
+        ServiceType::EventDriven => "eventdriven".to_string(),
+        ServiceType::Other(other) => other.clone(),
+    }
+}
+
+/// Probes a running service for well-known API descriptions
+pub struct Prober {
+    agent: ureq::Agent,
+}
+
+impl Default for Prober {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+impl Prober {
+    /// Creates a prober waiting at most `timeout` for each probe
+    pub fn new(timeout: Duration) -> Self {
+        Self { agent: ureq::AgentBuilder::new().timeout(timeout).redirects(2).build() }
+    }
+
+    /// Probes the service at a base URL
+    ///
+    /// GraphQL and gRPC take precedence over OpenAPI, which takes precedence over
+    /// AsyncAPI, as services commonly serve documents of the weaker kinds alongside.
+    /// Fails only if the URL is invalid; unreachable services yield an empty report.
+    pub fn probe(&self, base_url: &str) -> Result<ProbeReport> {
+        let base_url = base_url.trim_end_matches('/');
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(AureaCoreError::Config(format!(
+                "Invalid base URL '{}', expected http:// or https://",
+                base_url
+            )));
+        }
+
+        let mut report = ProbeReport::default();
+
+        if self.is_graphql(&format!("{}/graphql", base_url)) {
+            report.service_type.get_or_insert(ServiceType::GraphQL);
+            report.evidence.push("GraphQL endpoint at /graphql".to_string());
+            report.endpoints.push(Endpoint {
+                name: "graphql".to_string(),
+                path: "/graphql".to_string(),
+                method: Some("POST".to_string()),
+                description: Some("GraphQL endpoint".to_string()),
+                provides: Vec::new(),
+            });
+        }
+
+        if self.is_grpc(&format!("{}{}", base_url, GRPC_REFLECTION_PATH)) {
+            report.service_type.get_or_insert(ServiceType::Grpc);
+            match self.grpc_methods(base_url) {
+                Ok(methods) => {
+                    report
+                        .evidence
+                        .push(format!("gRPC server reflection ({} methods)", methods.len()));
+                    report.endpoints.extend(methods.into_iter().map(grpc_endpoint));
+                }
+                Err(_) => report.evidence.push("gRPC server reflection".to_string()),
+            }
+        }
+
+        if let Some((path, document)) = self.find_document(base_url, OPENAPI_PATHS) {
+            if document.get("openapi").is_some() || document.get("swagger").is_some() {
+                report.service_type.get_or_insert(ServiceType::Rest);
+                let endpoints = openapi_endpoints(&document);
+                report.evidence.push(format!(
+                    "OpenAPI document at {} ({} operations)",
+                    path,
+                    endpoints.len()
+                ));
+                report.endpoints.extend(endpoints);
+            }
+        }
+
+        if let Some((path, document)) = self.find_document(base_url, ASYNCAPI_PATHS) {
+            if document.get("asyncapi").is_some() {
+                report.service_type.get_or_insert(ServiceType::EventDriven);
+                let endpoints = asyncapi_endpoints(&document);
+                report.evidence.push(format!(
+                    "AsyncAPI document at {} ({} channels)",
+                    path,
+                    endpoints.len()
+                ));
+                report.endpoints.extend(endpoints);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Checks whether a URL answers a GraphQL introspection query
+    fn is_graphql(&self, url: &str) -> bool {
+        let response = self.agent.post(url).send_json(serde_json::json!({
+            "query": "{ __typename }"
+        }));
+        // GraphQL servers answer invalid operations with errors, but still as GraphQL responses
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(_) => return false,
+        };
+        response
+            .into_json::<Value>()
+            .is_ok_and(|body| body.get("data").is_some() || body.get("errors").is_some())
+    }
+
+    /// Checks whether a URL answers like a gRPC server
+    fn is_grpc(&self, url: &str) -> bool {
+        let response = self
+            .agent
+            .post(url)
+            .set("Content-Type", "application/grpc-web+proto")
+            .set("X-Grpc-Web", "1")
+            .send_bytes(&[0, 0, 0, 0, 0]);
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(_) => return false,
+        };
+        response.header("grpc-status").is_some()
+            || response.content_type().starts_with("application/grpc")
+    }
+
+    /// Lists the methods a gRPC server serves through server reflection, as `package.Service/Method`
+    ///
+    /// The reflection service itself is left out. Methods are sorted.
+    pub fn grpc_methods(&self, base_url: &str) -> Result<Vec<String>> {
+        let base_url = base_url.trim_end_matches('/');
+        let mut services = Vec::new();
+        for response in self.reflect(base_url, &proto_field(7, b"*"))? {
+            let list = reflection_result(&response, 6)?;
+            for service in proto_fields(list).into_iter().filter(|(field, _)| *field == 1) {
+                if let Some((_, name)) = proto_fields(service.1).into_iter().find(|(f, _)| *f == 1)
+                {
+                    services.push(String::from_utf8_lossy(name).into_owned());
+                }
+            }
+        }
+
+        let mut methods = Vec::new();
+        for service in services.iter().filter(|s| !s.starts_with(GRPC_REFLECTION_PACKAGE)) {
+            for response in self.reflect(base_url, &proto_field(4, service.as_bytes()))? {
+                let files = reflection_result(&response, 4)?;
+                for (_, file) in proto_fields(files).into_iter().filter(|(field, _)| *field == 1) {
+                    methods.extend(
+                        descriptor_methods(file)
+                            .into_iter()
+                            .filter(|m| m.split_once('/').is_some_and(|(s, _)| s == service)),
+                    );
+                }
+            }
+        }
+        methods.sort();
+        methods.dedup();
+        Ok(methods)
+    }
+
+    /// Compares the methods gRPC services serve with the endpoints they declare
+    ///
+    /// Covers the gRPC services of a validation summary whose exposure names a
+    /// reachable address; services that can't be reached are skipped. Mismatches are
+    /// recorded as warnings of the service.
+    pub fn check_grpc_drift(&self, registry: &ServiceRegistry, summary: &mut ValidationSummary) {
+        let names: Vec<String> = summary
+            .successful
+            .iter()
+            .chain(summary.failed.iter().map(|(name, _)| name))
+            .cloned()
+            .collect();
+        for name in names {
+            let Some(definition) = registry.get_service(&name).ok().and_then(|s| s.definition())
+            else {
+                continue;
+            };
+            if !matches!(definition.service_type, ServiceType::Grpc) {
+                continue;
+            }
+            let Some(base_url) = grpc_base_url(&definition) else {
+                continue;
+            };
+            match self.grpc_methods(&base_url) {
+                Ok(served) => {
+                    let drift = grpc_drift(&definition.endpoints, &served);
+                    if !drift.is_empty() {
+                        summary.warnings.entry(name).or_default().extend(drift);
+                    }
+                }
+                Err(err) => {
+                    tracing::debug!("Skipping gRPC drift check of '{}': {}", name, err)
+                }
+            }
+        }
+    }
+
+    /// Sends one request to the gRPC-Web reflection service, returning the response messages
+    fn reflect(&self, base_url: &str, request: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let url = format!("{}{}", base_url, GRPC_REFLECTION_PATH);
+        let mut body = vec![0];
+        body.extend_from_slice(&(request.len() as u32).to_be_bytes());
+        body.extend_from_slice(request);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Content-Type", "application/grpc-web+proto")
+            .set("X-Grpc-Web", "1")
+            .send_bytes(&body)
+            .map_err(|e| AureaCoreError::Service(format!("gRPC reflection at {}: {}", url, e)))?;
+        if let Some(status) = response.header("grpc-status").filter(|status| *status != "0") {
+            return Err(AureaCoreError::Service(format!(
+                "gRPC reflection at {} failed with status {}",
+                url, status
+            )));
+        }
+        let mut bytes = Vec::new();
+        response.into_reader().take(MAX_GRPC_RESPONSE).read_to_end(&mut bytes)?;
+
+        let mut messages = Vec::new();
+        let mut rest = bytes.as_slice();
+        while rest.len() >= 5 {
+            let length = u32::from_be_bytes([rest[1], rest[2], rest[3], rest[4]]) as usize;
+            let Some(frame) = rest.get(5..5 + length) else {
+                break;
+            };
+            if rest[0] & 0x80 == 0 {
+                messages.push(frame.to_vec());
+            } else {
+                // Trailers are HTTP header lines
+                let trailers = String::from_utf8_lossy(frame).to_ascii_lowercase();
+                let status = trailers
+                    .lines()
+                    .find_map(|line| line.strip_prefix("grpc-status:"))
+                    .map(str::trim)
+                    .unwrap_or("0");
+                if status != "0" {
+                    return Err(AureaCoreError::Service(format!(
+                        "gRPC reflection at {} failed with status {}",
+                        url, status
+                    )));
+                }
+            }
+            rest = &rest[5 + length..];
+        }
+        if messages.is_empty() {
+            return Err(AureaCoreError::Service(format!(
+                "gRPC reflection at {} returned no response",
+                url
+            )));
+        }
+        Ok(messages)
+    }
+
+    /// Fetches the first API document found at one of the given paths
+    fn find_document(
+        &self,
+        base_url: &str,
+        paths: &[&'static str],
+    ) -> Option<(&'static str, Value)> {
+        paths.iter().find_map(|path| {
+            let body =
+                self.agent.get(&format!("{}{}", base_url, path)).call().ok()?.into_string().ok()?;
+            // YAML is a superset of JSON, so one parser covers both document formats
+            let document: Value = serde_yaml::from_str(&body).ok()?;
+            document.is_object().then_some((*path, document))
+        })
+    }
+}
+
+/// Lists the operations of an OpenAPI or Swagger document as endpoints
+fn openapi_endpoints(document: &Value) -> Vec<Endpoint> {
+    let mut endpoints = Vec::new();
+    for (path, item) in document["paths"].as_object().into_iter().flatten() {
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method) else {
+                continue;
+            };
+            let name = operation["operationId"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| endpoint_name(&format!("{} {}", method, path)));
+            endpoints.push(Endpoint {
+                name,
+                path: path.clone(),
+                method: Some(method.to_uppercase()),
+                description: operation["summary"].as_str().map(str::to_string),
+                provides: Vec::new(),
+            });
+        }
+    }
+    endpoints
+}
+
+/// Lists the channels of an AsyncAPI document as endpoints
+///
+/// Version 2 documents name the operation per channel; version 3 documents declare
+/// operations separately, so their channels are listed without one.
+fn asyncapi_endpoints(document: &Value) -> Vec<Endpoint> {
+    let mut endpoints = Vec::new();
+    for (name, channel) in document["channels"].as_object().into_iter().flatten() {
+        let path = channel["address"].as_str().unwrap_or(name).to_string();
+        let operations: Vec<&str> =
+            ["publish", "subscribe"].into_iter().filter(|op| channel.get(*op).is_some()).collect();
+        endpoints.push(Endpoint {
+            name: endpoint_name(name),
+            path,
+            method: (!operations.is_empty()).then(|| operations.join(",")),
+            description: channel["description"].as_str().map(str::to_string),
+            provides: Vec::new(),
+        });
+    }
+    endpoints
+}
+
+/// Describes a gRPC method as an endpoint
+fn grpc_endpoint(method: String) -> Endpoint {
+    let name = method.rsplit('/').next().unwrap_or_default().to_string();
+    Endpoint { name, path: method, method: None, description: None, provides: Vec::new() }
+}
+
+/// Derives the address a gRPC service is reachable at from its exposure
+///
+/// Uses the first concrete hostname and the first port, with TLS on port 443 or
+/// without a port.
+pub fn grpc_base_url(definition: &ServiceSchema) -> Option<String> {
+    let exposure = definition.exposure.as_ref()?;
+    let host = exposure.hostnames.iter().find(|host| !host.contains('*'))?;
+    Some(match exposure.ports.first() {
+        Some(443) | None => format!("https://{}", host),
+        Some(port) => format!("http://{}:{}", host, port),
+    })
+}
+
+/// Compares the gRPC methods a service serves with the endpoints it declares
+///
+/// Endpoint paths are matched as `package.Service/Method`, ignoring a leading slash.
+pub fn grpc_drift(declared: &[Endpoint], served: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for endpoint in declared {
+        if !served.iter().any(|method| method == endpoint.path.trim_start_matches('/')) {
+            warnings.push(format!(
+                "gRPC drift: endpoint '{}' declares {}, which the server does not serve",
+                endpoint.name, endpoint.path
+            ));
+        }
+    }
+    for method in served {
+        if !declared.iter().any(|endpoint| endpoint.path.trim_start_matches('/') == method) {
+            warnings.push(format!("gRPC drift: {} is served but not declared", method));
+        }
+    }
+    warnings
+}
+
+/// Gets the given field of a reflection response, failing on an error response
+fn reflection_result(response: &[u8], field: u32) -> Result<&[u8]> {
+    let fields = proto_fields(response);
+    if let Some((_, error)) = fields.iter().find(|(f, _)| *f == 7) {
+        let message = proto_fields(error)
+            .into_iter()
+            .find(|(f, _)| *f == 2)
+            .map(|(_, message)| String::from_utf8_lossy(message).into_owned())
+            .unwrap_or_default();
+        return Err(AureaCoreError::Service(format!("gRPC reflection error: {}", message)));
+    }
+    fields
+        .into_iter()
+        .find(|(f, _)| *f == field)
+        .map(|(_, value)| value)
+        .ok_or_else(|| AureaCoreError::Service("Unexpected gRPC reflection response".to_string()))
+}
+
+/// Lists the methods of the services in a serialized `FileDescriptorProto`
+fn descriptor_methods(file: &[u8]) -> Vec<String> {
+    let fields = proto_fields(file);
+    let package = fields
+        .iter()
+        .find(|(field, _)| *field == 2)
+        .map(|(_, package)| String::from_utf8_lossy(package).into_owned());
+    let mut methods = Vec::new();
+    for (_, service) in fields.iter().filter(|(field, _)| *field == 6) {
+        let service = proto_fields(service);
+        let Some((_, name)) = service.iter().find(|(field, _)| *field == 1) else {
+            continue;
+        };
+        let name = String::from_utf8_lossy(name);
+        let qualified = match &package {
+            Some(package) if !package.is_empty() => format!("{}.{}", package, name),
+            _ => name.into_owned(),
+        };
+        for (_, method) in service.iter().filter(|(field, _)| *field == 2) {
+            if let Some((_, method)) = proto_fields(method).into_iter().find(|(f, _)| *f == 1) {
+                methods.push(format!("{}/{}", qualified, String::from_utf8_lossy(method)));
+            }
+        }
+    }
+    methods
+}
+
+/// Encodes a length-delimited protobuf field
+fn proto_field(number: u32, value: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(value.len() + 6);
+    write_varint(&mut encoded, u64::from(number << 3 | 2));
+    write_varint(&mut encoded, value.len() as u64);
+    encoded.extend_from_slice(value);
+    encoded
+}
+
+/// Appends a protobuf varint
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Reads a protobuf varint, advancing `pos`
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Lists the length-delimited fields of a protobuf message as (field number, bytes)
+///
+/// Other fields are skipped; decoding stops at the first malformed field.
+fn proto_fields(message: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < message.len() {
+        let Some(key) = read_varint(message, &mut pos) else {
+            break;
+        };
+        let skipped = match key & 7 {
+            0 => read_varint(message, &mut pos).map(|_| 0),
+            1 => Some(8),
+            2 => {
+                let Some(length) = read_varint(message, &mut pos) else {
+                    break;
+                };
+                let end = pos.checked_add(length as usize);
+                let Some(value) = end.and_then(|end| message.get(pos..end)) else {
+                    break;
+                };
+                fields.push(((key >> 3) as u32, value));
+                Some(length as usize)
+            }
+            5 => Some(4),
+            _ => None,
+        };
+        match skipped {
+            Some(skipped) => pos += skipped,
+            None => break,
+        }
+    }
+    fields
+}
+
+/// Derives a lowercase, dash-separated endpoint name
+fn endpoint_name(label: &str) -> String {
+    label
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Serves fixed responses by request path until the test ends, returning the base URL
+    ///
+    /// Routes map to a status line, optionally followed by extra header lines, and a body.
+    fn serve(routes: HashMap<&'static str, (&'static str, &'static str)>) -> String {
+        serve_with(move |path, _| {
+            let (status, body) = routes.get(path).copied().unwrap_or(("404 Not Found", ""));
+            (status.to_string(), body.as_bytes().to_vec())
+        })
+    }
+
+    /// Serves responses computed from the request path and body until the test ends
+    fn serve_with(handler: impl Fn(&str, &[u8]) -> (String, Vec<u8>) + Send + 'static) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                // Read the headers, then as much body as announced
+                let split = |request: &[u8]| {
+                    let end = request.windows(4).position(|w| w == b"\r\n\r\n")?;
+                    let headers = String::from_utf8_lossy(&request[..end]).into_owned();
+                    let length = headers
+                        .to_lowercase()
+                        .lines()
+                        .find_map(|l| l.strip_prefix("content-length:").map(str::to_string))
+                        .and_then(|v| v.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    (request.len() >= end + 4 + length).then_some((headers, end + 4))
+                };
+                let (headers, body_start) = loop {
+                    if let Some(split) = split(&request) {
+                        break split;
+                    }
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break (String::from_utf8_lossy(&request).into_owned(), request.len());
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                };
+                let path = headers.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let (status, body) = handler(&path, &request[body_start..]);
+                let mut response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                )
+                .into_bytes();
+                response.extend_from_slice(&body);
+                let _ = stream.write_all(&response);
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_probe_rest_service() {
+        let url = serve(HashMap::from([
+            (
+                "/v3/api-docs",
+                (
+                    "200 OK",
+                    r#"{"openapi": "3.0.0", "paths": {"/users/{id}": {"get": {"summary": "Get a user"}, "delete": {"operationId": "deleteUser"}}}}"#,
+                ),
+            ),
+            (
+                "/asyncapi.yaml",
+                ("200 OK", "asyncapi: 2.6.0\nchannels:\n  user/deleted:\n    publish: {}\n"),
+            ),
+        ]));
+
+        let report = Prober::default().probe(&url).unwrap();
+        assert!(matches!(report.service_type, Some(ServiceType::Rest)));
+        let endpoints: Vec<_> = report
+            .endpoints
+            .iter()
+            .map(|e| (e.name.as_str(), e.path.as_str(), e.method.as_deref()))
+            .collect();
+        assert_eq!(
+            endpoints,
+            vec![
+                ("get-users-id", "/users/{id}", Some("GET")),
+                ("deleteUser", "/users/{id}", Some("DELETE")),
+                ("user-deleted", "user/deleted", Some("publish")),
+            ]
+        );
+        assert_eq!(report.evidence[0], "OpenAPI document at /v3/api-docs (2 operations)");
+
+        let mut definition: ServiceSchema = serde_yaml::from_str(
+            "name: users\nversion: 0.1.0\nservice_type:\n  type: grpc\nendpoints:\n  - name: deleteUser\n    path: /users\n",
+        )
+        .unwrap();
+        report.apply(&mut definition);
+        assert!(matches!(definition.service_type, ServiceType::Rest));
+        assert_eq!(definition.endpoints.len(), 3);
+    }
+
+    #[test]
+    fn test_probe_graphql_and_grpc() {
+        let graphql = serve(HashMap::from([(
+            "/graphql",
+            ("400 Bad Request", r#"{"errors": [{"message": "unknown"}]}"#),
+        )]));
+        let report = Prober::default().probe(&graphql).unwrap();
+        assert!(matches!(report.service_type, Some(ServiceType::GraphQL)));
+        assert_eq!(report.endpoints[0].path, "/graphql");
+
+        let grpc =
+            serve(HashMap::from([(GRPC_REFLECTION_PATH, ("200 OK\r\ngrpc-status: 12", ""))]));
+        let report = Prober::default().probe(&grpc).unwrap();
+        assert!(matches!(report.service_type, Some(ServiceType::Grpc)));
+
+        let report = Prober::new(Duration::from_millis(200)).probe("http://127.0.0.1:1").unwrap();
+        assert!(report.service_type.is_none());
+        assert!(report.to_string().starts_with("No known API found\n"));
+        assert!(Prober::default().probe("catalog.example.com").is_err());
+    }
+
+    /// Frames a gRPC-Web response of one message and an OK status
+    fn grpc_web_response(message: &[u8]) -> (String, Vec<u8>) {
+        let trailers = b"grpc-status:0\r\n";
+        let mut body = vec![0];
+        body.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        body.extend_from_slice(message);
+        body.push(0x80);
+        body.extend_from_slice(&(trailers.len() as u32).to_be_bytes());
+        body.extend_from_slice(trailers);
+        ("200 OK\r\nContent-Type: application/grpc-web+proto".to_string(), body)
+    }
+
+    #[test]
+    fn test_grpc_reflection() {
+        let url = serve_with(|path, body| {
+            if path != GRPC_REFLECTION_PATH {
+                return ("404 Not Found".to_string(), Vec::new());
+            }
+            let request = proto_fields(&body[5..]);
+            let message = match request.first().copied().unwrap_or_default() {
+                (7, _) => {
+                    let services = [
+                        proto_field(1, &proto_field(1, b"shop.v1.Orders")),
+                        proto_field(
+                            1,
+                            &proto_field(1, b"grpc.reflection.v1alpha.ServerReflection"),
+                        ),
+                    ]
+                    .concat();
+                    proto_field(6, &services)
+                }
+                (4, b"shop.v1.Orders") => {
+                    let methods = [proto_field(1, b"Get"), proto_field(1, b"List")]
+                        .map(|method| proto_field(2, &method))
+                        .concat();
+                    let service = [proto_field(1, b"Orders"), methods].concat();
+                    let file = [proto_field(2, b"shop.v1"), proto_field(6, &service)].concat();
+                    proto_field(4, &proto_field(1, &file))
+                }
+                _ => proto_field(7, &proto_field(2, b"not found")),
+            };
+            grpc_web_response(&message)
+        });
+
+        let prober = Prober::default();
+        assert_eq!(
+            prober.grpc_methods(&url).unwrap(),
+            vec!["shop.v1.Orders/Get", "shop.v1.Orders/List"]
+        );
+        let report = prober.probe(&url).unwrap();
+        assert!(matches!(report.service_type, Some(ServiceType::Grpc)));
+        assert_eq!(report.evidence, vec!["gRPC server reflection (2 methods)"]);
+        assert_eq!(report.endpoints[1].name, "List");
+
+        let definition: ServiceSchema = serde_yaml::from_str(
+            "name: orders\nversion: 1.0.0\nservice_type:\n  type: grpc\nexposure:\n  hostnames: [orders.internal]\n  ports: [8080]\nendpoints:\n  - name: get\n    path: /shop.v1.Orders/Get\n  - name: cancel\n    path: shop.v1.Orders/Cancel\n",
+        )
+        .unwrap();
+        assert_eq!(grpc_base_url(&definition).unwrap(), "http://orders.internal:8080");
+        let served = prober.grpc_methods(&url).unwrap();
+        assert_eq!(
+            grpc_drift(&definition.endpoints, &served),
+            vec![
+                "gRPC drift: endpoint 'cancel' declares shop.v1.Orders/Cancel, which the server does not serve",
+                "gRPC drift: shop.v1.Orders/List is served but not declared",
+            ]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Write permissions on services, by namespace and owning team
+//!
+//! Every change to a service is checked here on behalf of the [`Actor`] making it.
+//! Without access grants in the root config, everyone may modify every service.
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::Service;
+use crate::schema::root::{AccessGrant, RootConfig};
+
+/// Namespace of services that don't declare one, unless the root config sets another
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// Who changes to the catalog are made on behalf of
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Actor {
+    /// The registry itself, e.g. syncing from the repository, never restricted
+    #[default]
+    System,
+    /// A caller that didn't identify itself
+    Anonymous,
+    /// An identified user or API token
+    Principal(String),
+}
+
+impl Actor {
+    /// Creates the actor for an optional principal, anonymous without one
+    pub fn from_principal(principal: Option<String>) -> Self {
+        principal.map(Actor::Principal).unwrap_or(Actor::Anonymous)
+    }
+}
+
+/// Access control lists deciding who may modify which services
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessControl {
+    grants: Vec<AccessGrant>,
+    default_namespace: String,
+}
+
+impl Default for AccessControl {
+    fn default() -> Self {
+        Self::new(Vec::new(), DEFAULT_NAMESPACE)
+    }
+}
+
+impl AccessControl {
+    /// Creates access control lists from grants, placing services without a namespace in
+    /// `default_namespace`
+    pub fn new(grants: Vec<AccessGrant>, default_namespace: impl Into<String>) -> Self {
+        Self { grants, default_namespace: default_namespace.into() }
+    }
+
+    /// Builds the access control lists declared in a root configuration
+    pub fn from_root_config(config: &RootConfig) -> Self {
+        Self::new(config.access.clone(), config.global.default_namespace.clone())
+    }
+
+    /// Checks whether changes are restricted at all
+    pub fn is_enforced(&self) -> bool {
+        !self.grants.is_empty()
+    }
+
+    /// Checks whether a principal may modify services in a namespace owned by a team
+    pub fn may_modify(
+        &self,
+        principal: &str,
+        namespace: Option<&str>,
+        owner: Option<&str>,
+    ) -> bool {
+        if !self.is_enforced() {
+            return true;
+        }
+        let namespace = namespace.unwrap_or(&self.default_namespace);
+        self.grants.iter().filter(|grant| grant.principals.iter().any(|p| p == principal)).any(
+            |grant| {
+                grant.namespaces.iter().any(|ns| ns == "*" || ns == namespace)
+                    || owner.is_some_and(|owner| grant.teams.iter().any(|team| team == owner))
+            },
+        )
+    }
+
+    /// Fails unless the actor may modify the service
+    pub fn authorize(&self, actor: &Actor, service: &Service, operation: &str) -> Result<()> {
+        if !self.is_enforced() {
+            return Ok(());
+        }
+        let namespace = service.config.namespace.as_deref();
+        match actor {
+            Actor::System => Ok(()),
+            Actor::Anonymous => {
+                Err(AureaCoreError::Forbidden(format!("anonymous callers cannot {}", operation)))
+            }
+            Actor::Principal(principal)
+                if self.may_modify(principal, namespace, service.owner()) =>
+            {
+                Ok(())
+            }
+            Actor::Principal(principal) => Err(AureaCoreError::Forbidden(format!(
+                "'{}' cannot {} in namespace '{}'",
+                principal,
+                operation,
+                namespace.unwrap_or(&self.default_namespace)
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::ServiceConfig;
+
+    #[test]
+    fn test_authorize() {
+        let grant = |principal: &str, namespaces: &[&str], teams: &[&str]| AccessGrant {
+            principals: vec![principal.to_string()],
+            namespaces: namespaces.iter().map(|ns| ns.to_string()).collect(),
+            teams: teams.iter().map(|team| team.to_string()).collect(),
+        };
+        let acl = AccessControl::new(
+            vec![
+                grant("alice", &["billing"], &[]),
+                grant("bob", &[], &["search-team"]),
+                grant("admin", &["*"], &[]),
+            ],
+            "shared",
+        );
+        assert!(acl.may_modify("alice", Some("billing"), None));
+        assert!(!acl.may_modify("alice", Some("search"), Some("search-team")));
+        assert!(acl.may_modify("bob", Some("search"), Some("search-team")));
+        assert!(!acl.may_modify("bob", Some("search"), None));
+        assert!(acl.may_modify("admin", None, None));
+        assert!(!acl.may_modify("mallory", Some("billing"), None));
+
+        let mut config: ServiceConfig =
+            serde_json::from_str(r#"{"config_path": "billing.yaml"}"#).unwrap();
+        config.namespace = Some("billing".to_string());
+        let mut service = Service::new("invoices".to_string(), config);
+        service.schema_data = Some(serde_json::json!({ "owner": "search-team" }));
+        let alice = Actor::Principal("alice".to_string());
+        assert!(acl.authorize(&alice, &service, "edit").is_ok());
+        assert!(acl.authorize(&Actor::Principal("bob".to_string()), &service, "edit").is_ok());
+        assert!(acl.authorize(&Actor::System, &service, "edit").is_ok());
+        let err = acl.authorize(&Actor::Anonymous, &service, "edit").unwrap_err();
+        assert_eq!(err.code(), "forbidden");
+
+        service.config.namespace = None;
+        let err = acl.authorize(&alice, &service, "update service 'invoices'").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Permission denied: 'alice' cannot update service 'invoices' in namespace 'shared'"
+        );
+        assert!(AccessControl::default().authorize(&Actor::Anonymous, &service, "edit").is_ok());
+    }
+}
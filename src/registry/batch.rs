@@ -0,0 +1,126 @@
+//! Batched registration, coalescing config writes and validation during bulk imports
+
+use std::time::{Duration, Instant};
+
+use crate::registry::{Actor, BatchSyncReport};
+
+/// Thresholds at which batched registrations are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteBatching {
+    /// Number of pending services that triggers a flush
+    pub max_pending: usize,
+    /// Time after the oldest pending registration that triggers a flush
+    pub max_delay: Duration,
+}
+
+impl Default for WriteBatching {
+    fn default() -> Self {
+        Self { max_pending: 500, max_delay: Duration::from_secs(5) }
+    }
+}
+
+impl WriteBatching {
+    /// Creates thresholds flushing at `max_pending` services or after `max_delay`
+    pub fn new(max_pending: usize, max_delay: Duration) -> Self {
+        Self { max_pending, max_delay }
+    }
+}
+
+/// A registration to apply, with who it is applied on behalf of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingWrite {
+    /// Name of the service
+    pub(crate) name: String,
+    /// Catalog config of the service
+    pub(crate) config: String,
+    /// Actor the registration is checked against access grants for
+    pub(crate) actor: Actor,
+    /// Whether the registration may bypass freeze windows
+    pub(crate) freeze_overridden: bool,
+}
+
+/// Registrations buffered while batching
+#[derive(Debug)]
+pub(crate) struct PendingWrites {
+    batching: WriteBatching,
+    /// One registration per service
+    services: Vec<PendingWrite>,
+    /// When the oldest pending registration was buffered
+    since: Option<Instant>,
+    /// Outcome of each flush so far
+    pub(crate) reports: Vec<BatchSyncReport>,
+}
+
+impl PendingWrites {
+    pub(crate) fn new(batching: WriteBatching) -> Self {
+        Self { batching, services: Vec::new(), since: None, reports: Vec::new() }
+    }
+
+    /// Buffers a registration, replacing a pending one of the same service
+    pub(crate) fn push(&mut self, write: PendingWrite) {
+        match self.services.iter_mut().find(|pending| pending.name == write.name) {
+            Some(pending) => *pending = write,
+            None => self.services.push(write),
+        }
+        self.since.get_or_insert_with(Instant::now);
+    }
+
+    /// Checks whether a threshold is reached
+    pub(crate) fn is_due(&self) -> bool {
+        self.services.len() >= self.batching.max_pending.max(1)
+            || self.since.is_some_and(|since| since.elapsed() >= self.batching.max_delay)
+    }
+
+    /// Takes the pending registrations, in the order they were first buffered
+    pub(crate) fn take(&mut self) -> Vec<PendingWrite> {
+        self.since = None;
+        std::mem::take(&mut self.services)
+    }
+
+    /// Puts back registrations taken for a flush that failed
+    pub(crate) fn restore(&mut self, writes: Vec<PendingWrite>) {
+        if writes.is_empty() {
+            return;
+        }
+        self.services = writes;
+        self.since.get_or_insert_with(Instant::now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(name: &str, config: &str) -> PendingWrite {
+        PendingWrite {
+            name: name.to_string(),
+            config: config.to_string(),
+            actor: Actor::System,
+            freeze_overridden: false,
+        }
+    }
+
+    #[test]
+    fn test_pending_writes() {
+        let mut pending = PendingWrites::new(WriteBatching::new(2, Duration::from_secs(60)));
+        assert!(!pending.is_due());
+        pending.push(write("billing", "v1"));
+        pending.push(write("billing", "v2"));
+        assert!(!pending.is_due());
+        pending.push(write("orders", "v1"));
+        assert!(pending.is_due());
+        let taken = pending.take();
+        assert_eq!(taken, vec![write("billing", "v2"), write("orders", "v1")]);
+        assert!(!pending.is_due());
+
+        // Registrations of a failed flush stay pending
+        pending.restore(taken.clone());
+        assert!(pending.is_due());
+        assert_eq!(pending.take(), taken);
+
+        let mut pending = PendingWrites::new(WriteBatching::new(10, Duration::ZERO));
+        assert!(!pending.is_due());
+        pending.push(write("billing", "v1"));
+        assert!(pending.is_due());
+    }
+}
@@ -0,0 +1,114 @@
+//! Suggestions to raise dependency constraints lagging the versions of the services they name
+
+use std::fmt;
+
+use semver::{Op, Version, VersionReq};
+use serde::Serialize;
+
+use crate::schema::lint::PatchOperation;
+
+/// A dependency constraint that can be raised to the current version of its service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConstraintBump {
+    /// Dependent service declaring the constraint
+    pub service: String,
+    /// Service depended upon
+    pub dependency: String,
+    /// Position of the dependency in the dependent's definition
+    pub index: usize,
+    /// Constraint as declared
+    pub constraint: String,
+    /// Current version of the dependency
+    pub version: String,
+    /// Suggested constraint, starting at the current version
+    pub suggested: String,
+    /// Whether the declared constraint rejects the current version, so the dependent
+    /// may need changes besides the bump
+    pub breaking: bool,
+}
+
+impl ConstraintBump {
+    /// Gets the patch replacing the constraint in the dependent's definition
+    pub fn patch(&self) -> PatchOperation {
+        PatchOperation::Replace {
+            path: format!("/dependencies/{}/version_constraint", self.index),
+            value: self.suggested.clone().into(),
+        }
+    }
+}
+
+impl fmt::Display for ConstraintBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} {} -> {} ({} is at {})",
+            self.service,
+            self.dependency,
+            self.constraint,
+            self.suggested,
+            self.dependency,
+            self.version
+        )?;
+        if self.breaking {
+            write!(f, ", breaking")?;
+        }
+        Ok(())
+    }
+}
+
+/// Suggests a constraint of the same kind and precision starting at `version`
+///
+/// Only single caret, tilde, `>=`, `=` and bare requirements are raised; ranges and
+/// upper bounds are deliberate and left alone, as are pre-releases. Returns `None` if
+/// the constraint already starts at the version.
+pub fn suggest_constraint(constraint: &str, version: &Version) -> Option<String> {
+    let constraint = constraint.trim();
+    let req = VersionReq::parse(constraint).ok()?;
+    let [comparator] = req.comparators.as_slice() else {
+        return None;
+    };
+    if !comparator.pre.is_empty() || !version.pre.is_empty() {
+        return None;
+    }
+    let prefix = match comparator.op {
+        Op::Caret if constraint.starts_with('^') => "^",
+        Op::Caret => "",
+        Op::Tilde => "~",
+        Op::GreaterEq => ">=",
+        Op::Exact => "=",
+        _ => return None,
+    };
+
+    let lower = (comparator.major, comparator.minor.unwrap_or(0), comparator.patch.unwrap_or(0));
+    let render = |major: u64, minor: u64, patch: u64| match (comparator.minor, comparator.patch) {
+        (None, _) => format!("{}{}", prefix, major),
+        (Some(_), None) => format!("{}{}.{}", prefix, major, minor),
+        (Some(_), Some(_)) => format!("{}{}.{}.{}", prefix, major, minor, patch),
+    };
+    let suggested = render(version.major, version.minor, version.patch);
+    ((version.major, version.minor, version.patch) > lower
+        && suggested != render(lower.0, lower.1, lower.2))
+    .then_some(suggested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_constraint() {
+        let version = Version::parse("1.9.3").unwrap();
+        assert_eq!(suggest_constraint("^1.2", &version).as_deref(), Some("^1.9"));
+        assert_eq!(suggest_constraint("1.2.0", &version).as_deref(), Some("1.9.3"));
+        assert_eq!(suggest_constraint("~1.2.0", &version).as_deref(), Some("~1.9.3"));
+        assert_eq!(suggest_constraint(">= 1.0", &version).as_deref(), Some(">=1.9"));
+        assert_eq!(suggest_constraint("=0.9", &version).as_deref(), Some("=1.9"));
+        assert_eq!(suggest_constraint("^1.9", &version), None);
+        assert_eq!(suggest_constraint("^1", &version), None);
+        assert_eq!(suggest_constraint(">=1.0, <2.0", &version), None);
+        assert_eq!(suggest_constraint("<2.0", &version), None);
+        assert_eq!(suggest_constraint("^2.0", &version), None);
+        assert_eq!(suggest_constraint("not a constraint", &version), None);
+        assert_eq!(suggest_constraint("^1.2", &Version::parse("1.9.0-rc.1").unwrap()), None);
+    }
+}
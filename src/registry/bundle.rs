@@ -0,0 +1,378 @@
+//! Signed, self-contained catalog bundles for distribution without git access
+//!
+//! A bundle is a gzipped tarball holding the catalog config and definition of every
+//! service, a manifest listing the SHA-256 of each file, and an Ed25519 signature of
+//! the manifest. Verifying the signature therefore covers every file in the bundle.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{AureaCoreError, Result};
+
+/// Layout version of bundles; bundles of another layout are rejected
+const BUNDLE_FORMAT: u32 = 1;
+
+/// Path of the manifest inside a bundle
+const MANIFEST_PATH: &str = "manifest.json";
+
+/// Path of the hex-encoded manifest signature inside a bundle
+const SIGNATURE_PATH: &str = "manifest.sig";
+
+/// Signs catalog bundles with an Ed25519 key
+pub struct BundleSigner {
+    key_pair: Ed25519KeyPair,
+}
+
+impl BundleSigner {
+    /// Generates a new key, returning the signer and its PKCS#8 encoding to store
+    pub fn generate() -> Result<(Self, Vec<u8>)> {
+        let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| AureaCoreError::Internal("Failed to generate signing key".to_string()))?;
+        let signer = Self::from_pkcs8(document.as_ref())?;
+        Ok((signer, document.as_ref().to_vec()))
+    }
+
+    /// Creates a signer from a PKCS#8-encoded Ed25519 key
+    pub fn from_pkcs8(pkcs8: &[u8]) -> Result<Self> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| AureaCoreError::Config(format!("Invalid signing key: {}", e)))?;
+        Ok(Self { key_pair })
+    }
+
+    /// Loads a signer from a file holding the hex-encoded PKCS#8 key
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_pkcs8(&from_hex(fs::read_to_string(path)?.trim())?)
+    }
+
+    /// Gets the public key verifying this signer's bundles
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key_pair.public_key().as_ref().to_vec()
+    }
+
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.key_pair.sign(message).as_ref().to_vec()
+    }
+}
+
+/// Contents of a catalog bundle, covered by its signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    format: u32,
+    /// When the bundle was exported
+    pub created_at: DateTime<Utc>,
+    /// Commit the catalog was exported at, if it was a git checkout
+    pub commit: Option<String>,
+    /// Services in the bundle, with the paths of their config and definition
+    pub services: BTreeMap<String, BundleService>,
+    /// SHA-256 of every file in the bundle, by path
+    pub files: BTreeMap<String, String>,
+}
+
+impl BundleManifest {
+    /// Gets a short identifier of the bundle contents
+    pub fn id(&self) -> String {
+        let mut hasher = Sha256::new();
+        for (path, hash) in &self.files {
+            hasher.update(path.as_bytes());
+            hasher.update(hash.as_bytes());
+        }
+        to_hex(&hasher.finalize())[..16].to_string()
+    }
+}
+
+/// Files of a service inside a bundle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleService {
+    /// Path of the catalog config
+    pub config: String,
+    /// Path of the service definition
+    pub definition: String,
+}
+
+/// A service as exported into a bundle
+pub(crate) struct ExportedService {
+    pub name: String,
+    pub config: serde_json::Value,
+    pub definition: String,
+    /// Extension of the definition file, e.g. `yaml`
+    pub extension: String,
+}
+
+/// A service read from a verified bundle
+#[derive(Debug)]
+pub(crate) struct ImportedService {
+    pub name: String,
+    pub config: serde_json::Value,
+    pub definition: Vec<u8>,
+    /// File name to store the definition under
+    pub definition_file: String,
+}
+
+/// Writes a signed bundle of services to a file
+///
+/// The `config_path` of each config is rewritten to the definition's path inside the
+/// bundle.
+pub(crate) fn write_bundle(
+    path: &Path,
+    services: Vec<ExportedService>,
+    commit: Option<String>,
+    signer: &BundleSigner,
+) -> Result<BundleManifest> {
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut entries = BTreeMap::new();
+    for mut service in services {
+        let definition = format!("definitions/{}.{}", service.name, service.extension);
+        let config = format!("configs/{}.json", service.name);
+        service.config["config_path"] = serde_json::Value::String(definition.clone());
+        let config_content = serde_json::to_vec_pretty(&service.config)
+            .map_err(|e| AureaCoreError::Internal(format!("Failed to encode config: {}", e)))?;
+        files.insert(config.clone(), config_content);
+        files.insert(definition.clone(), service.definition.into_bytes());
+        entries.insert(service.name, BundleService { config, definition });
+    }
+
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        created_at: Utc::now(),
+        commit,
+        services: entries,
+        files: files.iter().map(|(path, content)| (path.clone(), sha256_hex(content))).collect(),
+    };
+    let manifest_content = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AureaCoreError::Internal(format!("Failed to encode manifest: {}", e)))?;
+    let signature = to_hex(&signer.sign(&manifest_content));
+
+    let mtime = manifest.created_at.timestamp().max(0) as u64;
+    let mut archive = Vec::new();
+    append_entry(&mut archive, MANIFEST_PATH, &manifest_content, mtime)?;
+    append_entry(&mut archive, SIGNATURE_PATH, signature.as_bytes(), mtime)?;
+    for (name, content) in &files {
+        append_entry(&mut archive, name, content, mtime)?;
+    }
+    archive.extend_from_slice(&[0; 2 * BLOCK]);
+
+    let mut encoder = GzEncoder::new(File::create(path)?, Compression::default());
+    encoder.write_all(&archive)?;
+    encoder.finish()?;
+    Ok(manifest)
+}
+
+/// Reads a bundle, verifying its signature and the hash of every file
+pub(crate) fn read_bundle(
+    path: &Path,
+    public_key: &[u8],
+) -> Result<(BundleManifest, Vec<ImportedService>)> {
+    let mut archive = Vec::new();
+    GzDecoder::new(File::open(path)?)
+        .read_to_end(&mut archive)
+        .map_err(|e| AureaCoreError::Bundle(format!("not a gzip archive: {}", e)))?;
+    let files = read_entries(&archive)?;
+
+    let invalid = |message: &str| AureaCoreError::Bundle(message.to_string());
+    let manifest_content = files.get(MANIFEST_PATH).ok_or_else(|| invalid("missing manifest"))?;
+    let signature = files.get(SIGNATURE_PATH).ok_or_else(|| invalid("missing signature"))?;
+    let signature = from_hex(String::from_utf8_lossy(signature).trim())?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(manifest_content, &signature)
+        .map_err(|_| invalid("signature does not match the public key"))?;
+
+    let manifest: BundleManifest = serde_json::from_slice(manifest_content)
+        .map_err(|e| AureaCoreError::Bundle(format!("unreadable manifest: {}", e)))?;
+    if manifest.format != BUNDLE_FORMAT {
+        return Err(AureaCoreError::Bundle(format!(
+            "unsupported bundle format {}",
+            manifest.format
+        )));
+    }
+    for (name, hash) in &manifest.files {
+        let content = files
+            .get(name)
+            .ok_or_else(|| AureaCoreError::Bundle(format!("missing file {}", name)))?;
+        if &sha256_hex(content) != hash {
+            return Err(AureaCoreError::Bundle(format!("file {} was modified", name)));
+        }
+    }
+
+    let mut services = Vec::with_capacity(manifest.services.len());
+    for (name, entry) in &manifest.services {
+        let is_safe = |s: &str| {
+            !s.is_empty()
+                && !s.starts_with('.')
+                && s.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        };
+        let extension = Path::new(&entry.definition)
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "yaml".to_string());
+        if !is_safe(name) || !is_safe(&extension) {
+            return Err(AureaCoreError::Bundle(format!("invalid service name '{}'", name)));
+        }
+        let read = |path: &str| {
+            manifest
+                .files
+                .contains_key(path)
+                .then(|| files.get(path))
+                .flatten()
+                .ok_or_else(|| AureaCoreError::Bundle(format!("unlisted file {}", path)))
+        };
+        let config = serde_json::from_slice(read(&entry.config)?).map_err(|e| {
+            AureaCoreError::Bundle(format!("invalid config of service '{}': {}", name, e))
+        })?;
+        services.push(ImportedService {
+            name: name.clone(),
+            config,
+            definition: read(&entry.definition)?.clone(),
+            definition_file: format!("{}.{}", name, extension),
+        });
+    }
+    Ok((manifest, services))
+}
+
+/// Size of a tar block; headers take one block and contents are padded to whole blocks
+const BLOCK: usize = 512;
+
+/// Appends a regular file to a ustar archive
+fn append_entry(archive: &mut Vec<u8>, name: &str, content: &[u8], mtime: u64) -> Result<()> {
+    if name.len() >= 100 {
+        return Err(AureaCoreError::Bundle(format!("path too long for archive: {}", name)));
+    }
+    let mut header = [0u8; BLOCK];
+    let mut field = |offset: usize, value: &[u8]| {
+        header[offset..offset + value.len()].copy_from_slice(value);
+    };
+    field(0, name.as_bytes());
+    field(100, b"0000644\0");
+    field(108, b"0000000\0");
+    field(116, b"0000000\0");
+    field(124, format!("{:011o}\0", content.len()).as_bytes());
+    field(136, format!("{:011o}\0", mtime).as_bytes());
+    field(148, b"        ");
+    field(156, b"0");
+    field(257, b"ustar\0");
+    field(263, b"00");
+    let checksum: u32 = header.iter().map(|&byte| u32::from(byte)).sum();
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+
+    archive.extend_from_slice(&header);
+    archive.extend_from_slice(content);
+    archive.resize(archive.len().div_ceil(BLOCK) * BLOCK, 0);
+    Ok(())
+}
+
+/// Reads the regular files of a ustar archive by path
+fn read_entries(archive: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
+    let truncated = || AureaCoreError::Bundle("truncated archive".to_string());
+    let mut files = BTreeMap::new();
+    let mut offset = 0;
+    loop {
+        let header = archive.get(offset..offset + BLOCK).ok_or_else(truncated)?;
+        if header.iter().all(|&byte| byte == 0) {
+            return Ok(files);
+        }
+        let text = |range: std::ops::Range<usize>| {
+            let field = &header[range];
+            let end = field.iter().position(|&byte| byte == 0).unwrap_or(field.len());
+            String::from_utf8_lossy(&field[..end]).trim().to_string()
+        };
+        let name = text(0..100);
+        let size = usize::from_str_radix(&text(124..136), 8)
+            .map_err(|_| AureaCoreError::Bundle(format!("invalid size of entry {}", name)))?;
+        let start = offset + BLOCK;
+        let content = archive.get(start..start + size).ok_or_else(truncated)?;
+        if matches!(header[156], b'0' | 0) {
+            files.insert(name, content.to_vec());
+        }
+        offset = start + size.div_ceil(BLOCK) * BLOCK;
+    }
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    to_hex(&Sha256::digest(content))
+}
+
+/// Encodes bytes as lowercase hex, e.g. to store keys
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes hex-encoded bytes
+pub fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(AureaCoreError::Config("Invalid hex: odd length".to_string()));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| AureaCoreError::Config(format!("Invalid hex at offset {}", i)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_bundle_round_trip_and_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("catalog.tar.gz");
+        let (signer, pkcs8) = BundleSigner::generate().unwrap();
+        let services = vec![ExportedService {
+            name: "billing".to_string(),
+            config: json!({"config_path": "/srv/billing.yaml", "namespace": "payments"}),
+            definition: "name: billing\nversion: 1.0.0\n".to_string(),
+            extension: "yaml".to_string(),
+        }];
+        write_bundle(&path, services, Some("abc123".to_string()), &signer).unwrap();
+
+        let (manifest, services) = read_bundle(&path, &signer.public_key()).unwrap();
+        assert_eq!(manifest.commit.as_deref(), Some("abc123"));
+        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(services[0].config["config_path"], "definitions/billing.yaml");
+        assert_eq!(services[0].config["namespace"], "payments");
+        assert_eq!(services[0].definition, b"name: billing\nversion: 1.0.0\n");
+
+        // A key restored from its encoding signs the same way
+        let restored = BundleSigner::from_pkcs8(&from_hex(&to_hex(&pkcs8)).unwrap()).unwrap();
+        assert_eq!(restored.public_key(), signer.public_key());
+
+        let (other, _) = BundleSigner::generate().unwrap();
+        let err = read_bundle(&path, &other.public_key()).unwrap_err();
+        assert_eq!(err.code(), "invalid_bundle");
+
+        // Rewrite the bundle with a modified definition but the original manifest
+        let mut archive = Vec::new();
+        GzDecoder::new(File::open(&path).unwrap()).read_to_end(&mut archive).unwrap();
+        let mut tampered = Vec::new();
+        for (name, content) in read_entries(&archive).unwrap() {
+            let content = if name.starts_with("definitions/") {
+                b"name: billing\nversion: 6.6.6\n".to_vec()
+            } else {
+                content
+            };
+            append_entry(&mut tampered, &name, &content, 0).unwrap();
+        }
+        tampered.extend_from_slice(&[0; 2 * BLOCK]);
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        encoder.write_all(&tampered).unwrap();
+        encoder.finish().unwrap();
+        let err = read_bundle(&path, &signer.public_key()).unwrap_err();
+        assert!(err.to_string().contains("was modified"));
+    }
+}
@@ -0,0 +1,86 @@
+//! Cooperative cancellation and deadlines for long-running operations
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::{AureaCoreError, Result};
+
+/// Token stopping long-running operations, on request or once a deadline passes
+///
+/// Operations check the token between units of work, such as services to validate or
+/// objects received from a remote, and stop early with the results they have so far.
+/// Clones share the cancellation state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled and has no deadline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a token that expires once `timeout` has elapsed
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::new().with_deadline(Instant::now() + timeout)
+    }
+
+    /// Sets the instant the token expires at
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Cancels operations observing the token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks whether the token was cancelled or its deadline has passed
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst) || self.is_timed_out()
+    }
+
+    /// Checks whether the deadline of the token has passed
+    pub fn is_timed_out(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Fails with [`AureaCoreError::TimedOut`] if the token was cancelled
+    pub fn check(&self, operation: &str) -> Result<()> {
+        if self.is_timed_out() {
+            Err(AureaCoreError::TimedOut(format!("{} exceeded its deadline", operation)))
+        } else if self.is_cancelled() {
+            Err(AureaCoreError::TimedOut(format!("{} was cancelled", operation)))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+        assert!(token.check("sync").is_ok());
+
+        let clone = token.clone();
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert!(!token.is_timed_out());
+        assert!(token.check("sync").unwrap_err().to_string().contains("sync was cancelled"));
+
+        let expired = CancellationToken::with_timeout(Duration::ZERO);
+        assert!(expired.is_cancelled());
+        assert!(expired.is_timed_out());
+        assert_eq!(expired.check("validation").unwrap_err().code(), "timed_out");
+        assert!(!CancellationToken::with_timeout(Duration::from_secs(60)).is_cancelled());
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::registry::git::CommitInfo;
+use crate::registry::service::{parse_schema_content, ServiceConfig};
+
+/// Catalog-relevant facts about a service at a single revision
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceSnapshot {
+    /// Service version declared in its schema file, if readable
+    pub version: Option<String>,
+    /// Dependencies keyed by service name, with their version constraint
+    pub dependencies: BTreeMap<String, Option<String>>,
+}
+
+/// Catalog state reconstructed from the files of a single revision
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogSnapshot {
+    /// Services keyed by name
+    pub services: BTreeMap<String, ServiceSnapshot>,
+}
+
+impl CatalogSnapshot {
+    /// Builds a snapshot from the root config files of a revision
+    ///
+    /// `read_file` resolves the `config_path` of each service to its schema content
+    /// at the same revision. Files that do not parse as a service config are ignored.
+    pub fn from_files<F>(files: &[(PathBuf, String)], read_file: F) -> Self
+    where
+        F: Fn(&Path) -> Option<String>,
+    {
+        let mut services = BTreeMap::new();
+
+        for (path, content) in files {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            let Ok(config) = serde_json::from_str::<ServiceConfig>(content) else {
+                continue;
+            };
+
+            let schema_path = Path::new(&config.config_path);
+            let schema = read_file(schema_path)
+                .and_then(|content| parse_schema_content(schema_path, &content).ok());
+
+            let mut snapshot = ServiceSnapshot {
+                version: schema
+                    .as_ref()
+                    .and_then(|s| s.get("version"))
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string()),
+                dependencies: BTreeMap::new(),
+            };
+
+            for dep in config.resolve_dependencies(schema.as_ref()) {
+                snapshot.dependencies.insert(dep.service, dep.version_constraint);
+            }
+
+            services.insert(name, snapshot);
+        }
+
+        Self { services }
+    }
+}
+
+/// A single semantic change between two catalog snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub enum CatalogChange {
+    /// A service was added to the catalog
+    ServiceAdded { name: String, version: Option<String> },
+    /// A service was removed from the catalog
+    ServiceRemoved { name: String },
+    /// The declared version of a service changed
+    VersionChanged { name: String, from: Option<String>, to: Option<String> },
+    /// A service declared a new dependency
+    DependencyAdded { service: String, dependency: String, constraint: Option<String> },
+    /// A service dropped a dependency
+    DependencyRemoved { service: String, dependency: String },
+}
+
+impl CatalogChange {
+    /// Changelog section titles, in rendering order
+    pub const SECTIONS: [&'static str; 4] =
+        ["Added services", "Removed services", "Version changes", "Dependency changes"];
+
+    /// Gets the changelog section this change is listed under
+    pub fn section(&self) -> &'static str {
+        match self {
+            CatalogChange::ServiceAdded { .. } => Self::SECTIONS[0],
+            CatalogChange::ServiceRemoved { .. } => Self::SECTIONS[1],
+            CatalogChange::VersionChanged { .. } => Self::SECTIONS[2],
+            CatalogChange::DependencyAdded { .. } | CatalogChange::DependencyRemoved { .. } => {
+                Self::SECTIONS[3]
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CatalogChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let version = |v: &Option<String>| v.clone().unwrap_or_else(|| "unknown".to_string());
+        match self {
+            CatalogChange::ServiceAdded { name, version: v } => {
+                write!(f, "Added service `{}` ({})", name, version(v))
+            }
+            CatalogChange::ServiceRemoved { name } => write!(f, "Removed service `{}`", name),
+            CatalogChange::VersionChanged { name, from, to } => {
+                write!(f, "`{}` version {} → {}", name, version(from), version(to))
+            }
+            CatalogChange::DependencyAdded { service, dependency, constraint } => {
+                match constraint {
+                    Some(c) => write!(f, "`{}` now depends on `{}` ({})", service, dependency, c),
+                    None => write!(f, "`{}` now depends on `{}`", service, dependency),
+                }
+            }
+            CatalogChange::DependencyRemoved { service, dependency } => {
+                write!(f, "`{}` no longer depends on `{}`", service, dependency)
+            }
+        }
+    }
+}
+
+/// Computes the semantic changes from `old` to `new`, in a stable order
+pub fn diff_snapshots(old: &CatalogSnapshot, new: &CatalogSnapshot) -> Vec<CatalogChange> {
+    let mut changes = Vec::new();
+
+    for (name, service) in &new.services {
+        match old.services.get(name) {
+            None => changes.push(CatalogChange::ServiceAdded {
+                name: name.clone(),
+                version: service.version.clone(),
+            }),
+            Some(previous) => {
+                if previous.version != service.version {
+                    changes.push(CatalogChange::VersionChanged {
+                        name: name.clone(),
+                        from: previous.version.clone(),
+                        to: service.version.clone(),
+                    });
+                }
+                for (dependency, constraint) in &service.dependencies {
+                    if !previous.dependencies.contains_key(dependency) {
+                        changes.push(CatalogChange::DependencyAdded {
+                            service: name.clone(),
+                            dependency: dependency.clone(),
+                            constraint: constraint.clone(),
+                        });
+                    }
+                }
+                for dependency in previous.dependencies.keys() {
+                    if !service.dependencies.contains_key(dependency) {
+                        changes.push(CatalogChange::DependencyRemoved {
+                            service: name.clone(),
+                            dependency: dependency.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    for name in old.services.keys() {
+        if !new.services.contains_key(name) {
+            changes.push(CatalogChange::ServiceRemoved { name: name.clone() });
+        }
+    }
+
+    changes
+}
+
+/// Human-readable changelog of catalog changes since a revision
+#[derive(Debug, Clone)]
+pub struct Changelog {
+    /// Revision the changelog starts from (exclusive)
+    pub since: String,
+    /// Commits included in the changelog, newest first
+    pub commits: Vec<CommitInfo>,
+    /// Semantic catalog changes between `since` and HEAD
+    pub changes: Vec<CatalogChange>,
+}
+
+impl Changelog {
+    /// Checks whether the changelog contains any catalog changes
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Renders the changelog as markdown suitable for release notes
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Catalog changes since {}\n", self.since);
+
+        if self.changes.is_empty() {
+            let _ = writeln!(out, "No catalog changes.\n");
+        }
+        for title in CatalogChange::SECTIONS {
+            let entries: Vec<_> = self.changes.iter().filter(|c| c.section() == title).collect();
+            if entries.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "## {}\n", title);
+            for change in entries {
+                let _ = writeln!(out, "- {}", change);
+            }
+            let _ = writeln!(out);
+        }
+
+        if !self.commits.is_empty() {
+            let _ = writeln!(out, "## Commits\n");
+            for commit in &self.commits {
+                let short_id = &commit.id[..commit.id.len().min(7)];
+                let _ = writeln!(out, "- {} {} ({})", short_id, commit.summary, commit.author);
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> Vec<(PathBuf, String)> {
+        entries.iter().map(|(p, c)| (PathBuf::from(p), c.to_string())).collect()
+    }
+
+    fn schema_reader(
+        schemas: &'static [(&'static str, &'static str)],
+    ) -> impl Fn(&Path) -> Option<String> {
+        move |path: &Path| {
+            schemas.iter().find(|(p, _)| Path::new(p) == path).map(|(_, c)| c.to_string())
+        }
+    }
+
+    #[test]
+    fn test_snapshot_from_files() {
+        let files = files(&[
+            (
+                "checkout.json",
+                r#"{"config_path": "schemas/checkout.yaml", "dependencies": [
+                    {"service": "billing", "version_constraint": "^1.0"}
+                ]}"#,
+            ),
+            ("README.md", "# not a service"),
+            ("broken.json", "{ not json"),
+        ]);
+        let snapshot = CatalogSnapshot::from_files(
+            &files,
+            schema_reader(&[("schemas/checkout.yaml", "name: checkout\nversion: 1.2.0\n")]),
+        );
+
+        assert_eq!(snapshot.services.len(), 1);
+        let checkout = &snapshot.services["checkout"];
+        assert_eq!(checkout.version.as_deref(), Some("1.2.0"));
+        assert_eq!(checkout.dependencies.get("billing"), Some(&Some("^1.0".to_string())));
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let mut old = CatalogSnapshot::default();
+        old.services.insert(
+            "checkout".to_string(),
+            ServiceSnapshot {
+                version: Some("1.0.0".to_string()),
+                dependencies: BTreeMap::from([("legacy".to_string(), None)]),
+            },
+        );
+        old.services.insert("legacy".to_string(), ServiceSnapshot::default());
+
+        let mut new = CatalogSnapshot::default();
+        new.services.insert(
+            "checkout".to_string(),
+            ServiceSnapshot {
+                version: Some("1.1.0".to_string()),
+                dependencies: BTreeMap::from([("billing".to_string(), Some("^2".to_string()))]),
+            },
+        );
+        new.services.insert(
+            "billing".to_string(),
+            ServiceSnapshot { version: Some("2.0.0".to_string()), ..Default::default() },
+        );
+
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                CatalogChange::ServiceAdded {
+                    name: "billing".to_string(),
+                    version: Some("2.0.0".to_string())
+                },
+                CatalogChange::VersionChanged {
+                    name: "checkout".to_string(),
+                    from: Some("1.0.0".to_string()),
+                    to: Some("1.1.0".to_string())
+                },
+                CatalogChange::DependencyAdded {
+                    service: "checkout".to_string(),
+                    dependency: "billing".to_string(),
+                    constraint: Some("^2".to_string())
+                },
+                CatalogChange::DependencyRemoved {
+                    service: "checkout".to_string(),
+                    dependency: "legacy".to_string()
+                },
+                CatalogChange::ServiceRemoved { name: "legacy".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changelog_markdown() {
+        let changelog = Changelog {
+            since: "v1.0.0".to_string(),
+            commits: vec![CommitInfo {
+                id: "0123456789abcdef".to_string(),
+                summary: "Add billing".to_string(),
+                author: "Jane".to_string(),
+                email: "jane@example.com".to_string(),
+                time: chrono::Utc::now(),
+            }],
+            changes: vec![CatalogChange::ServiceAdded {
+                name: "billing".to_string(),
+                version: Some("2.0.0".to_string()),
+            }],
+        };
+
+        let markdown = changelog.to_markdown();
+        assert!(markdown.starts_with("# Catalog changes since v1.0.0"));
+        assert!(markdown.contains("## Added services\n\n- Added service `billing` (2.0.0)"));
+        assert!(!markdown.contains("## Removed services"));
+        assert!(markdown.contains("- 0123456 Add billing (Jane)"));
+    }
+}
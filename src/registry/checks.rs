@@ -0,0 +1,134 @@
+//! Dependency checks shared by the registry implementations
+
+use std::collections::HashSet;
+
+use crate::registry::{CyclePolicy, DependencyGraph, Service};
+use crate::schema::service::Dependency;
+use crate::schema::validation::{ValidationService, VersionCompatibility};
+
+/// Problem found with a dependency of a service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DependencyProblem {
+    /// Fails the service depending on it
+    Failing(String),
+    /// Reported as a warning of the service depending on it
+    Warning(String),
+}
+
+/// Warns about dependencies declared through an alias of the service now providing them
+///
+/// `canonical` holds the `declared` dependencies with aliases resolved, in the same order.
+pub(crate) fn alias_warnings(declared: &[Dependency], canonical: &[Dependency]) -> Vec<String> {
+    declared
+        .iter()
+        .zip(canonical)
+        .filter(|(declared, dependency)| declared.service != dependency.service)
+        .map(|(declared, dependency)| {
+            format!(
+                "Dependency '{}' is an alias of '{}'; depend on '{}' instead",
+                declared.service, dependency.service, dependency.service
+            )
+        })
+        .collect()
+}
+
+/// Checks a dependency exists and its version satisfies the constraint on it
+///
+/// `provider` is the registered service named by the dependency, if any; dependencies on
+/// resources are never reported missing. Providers without a version are not checked
+/// against the constraint.
+pub(crate) fn check_dependency(
+    dependency: &Dependency,
+    provider: Option<&Service>,
+    is_resource: bool,
+    validation_service: &ValidationService,
+) -> Option<DependencyProblem> {
+    let dep_name = &dependency.service;
+    let Some(provider) = provider else {
+        if is_resource {
+            return None;
+        }
+        return Some(if dependency.required {
+            DependencyProblem::Failing(format!("Required dependency '{}' not found", dep_name))
+        } else {
+            DependencyProblem::Warning(format!("Optional dependency '{}' not found", dep_name))
+        });
+    };
+
+    let constraint = dependency.version_constraint.as_ref()?;
+    let version = provider.schema_data.as_ref()?.get("version")?.as_str()?;
+    match validation_service.check_dependency_version(dependency, version, constraint) {
+        VersionCompatibility::Compatible => None,
+        VersionCompatibility::MinorIncompatible => Some(DependencyProblem::Warning(format!(
+            "Minor version incompatibility for dependency '{}': expected {} but found {}",
+            dep_name, constraint, version
+        ))),
+        VersionCompatibility::MajorIncompatible => {
+            let msg = format!(
+                "Major version incompatibility for dependency '{}': expected {} but found {}",
+                dep_name, constraint, version
+            );
+            Some(if dependency.required {
+                DependencyProblem::Failing(msg)
+            } else {
+                DependencyProblem::Warning(format!(
+                    "Optional dependency '{}' has incompatible version: {}",
+                    dep_name, msg
+                ))
+            })
+        }
+    }
+}
+
+/// Outcome of checking the dependency graph for cycles
+#[derive(Debug, Default)]
+pub(crate) struct CycleCheck {
+    /// Services failed for being on a cycle, with the error failing them
+    pub failed: Vec<(String, String)>,
+    /// Catalog-wide warning about a cycle that failed no service
+    pub warning: Option<String>,
+}
+
+/// Checks the dependency graph for cycles under a cycle policy
+///
+/// Services in `scope` on a cycle the policy escalates are failed; any other cycle
+/// between `services` is reported as a single catalog-wide warning.
+pub(crate) fn check_cycles<'a>(
+    graph: &DependencyGraph,
+    policy: CyclePolicy,
+    scope: impl IntoIterator<Item = &'a String>,
+    services: impl IntoIterator<Item = &'a String>,
+) -> CycleCheck {
+    let required_only = match policy {
+        CyclePolicy::Warn => {
+            let warning = graph
+                .detect_cycles()
+                .map(|cycle| format!("Circular dependency detected: {}", cycle.description));
+            return CycleCheck { failed: Vec::new(), warning };
+        }
+        CyclePolicy::Fail => false,
+        CyclePolicy::FailRequired => true,
+    };
+
+    let mut check = CycleCheck::default();
+    let mut names: Vec<&String> = scope.into_iter().collect();
+    names.sort();
+    names.dedup();
+    for name in names {
+        if let Some(cycle) = graph.cycle_through(name, required_only) {
+            let msg = format!("Circular dependency: {}", cycle.join(" -> "));
+            check.failed.push((name.clone(), msg));
+        }
+    }
+
+    // Cycles that were not escalated remain a catalog-wide warning
+    let on_failed_cycle: HashSet<&String> = check.failed.iter().map(|(name, _)| name).collect();
+    let mut names: Vec<&String> =
+        services.into_iter().filter(|name| !on_failed_cycle.contains(name)).collect();
+    names.sort();
+    check.warning = names
+        .into_iter()
+        .find_map(|name| graph.cycle_through(name, false))
+        .map(|cycle| format!("Circular dependency detected: {}", cycle.join(" -> ")));
+    check
+}
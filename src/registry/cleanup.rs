@@ -0,0 +1,64 @@
+//! Removal of work-directory files the catalog no longer needs, reported by `aureacore gc`
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Age after which temporary files in the work directory are considered abandoned
+pub const TEMP_FILE_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Files removed from the work directory, or found removable in a dry run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CleanupReport {
+    /// Whether the files were only listed
+    pub dry_run: bool,
+    /// Services whose config files are no longer referenced by the root config
+    pub orphaned_configs: Vec<String>,
+    /// Loaded services missing from the root config, kept since removal wasn't forced
+    pub kept_services: Vec<String>,
+    /// Abandoned temporary files
+    pub temp_files: Vec<PathBuf>,
+    /// Snapshot caches not matching the checked-out commit
+    pub snapshots: Vec<PathBuf>,
+}
+
+impl CleanupReport {
+    /// Checks whether there was nothing to clean up
+    pub fn is_empty(&self) -> bool {
+        self.orphaned_configs.is_empty()
+            && self.kept_services.is_empty()
+            && self.temp_files.is_empty()
+            && self.snapshots.is_empty()
+    }
+}
+
+impl fmt::Display for CleanupReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.dry_run { "Would remove" } else { "Removed" };
+        writeln!(f, "{} {} orphaned config(s)", verb, self.orphaned_configs.len())?;
+        for name in &self.orphaned_configs {
+            writeln!(f, "  - {}", name)?;
+        }
+        if !self.kept_services.is_empty() {
+            writeln!(
+                f,
+                "Kept {} service(s) missing from the root config, use --force to remove them",
+                self.kept_services.len()
+            )?;
+            for name in &self.kept_services {
+                writeln!(f, "  - {}", name)?;
+            }
+        }
+        writeln!(f, "{} {} temporary file(s)", verb, self.temp_files.len())?;
+        for path in &self.temp_files {
+            writeln!(f, "  - {}", path.display())?;
+        }
+        writeln!(f, "{} {} stale snapshot(s)", verb, self.snapshots.len())?;
+        for path in &self.snapshots {
+            writeln!(f, "  - {}", path.display())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::registry::topology::Topology;
+use crate::schema::service::Exposure;
+
+/// Kind of network address services can claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClaimKind {
+    /// Public route prefix
+    Route,
+    /// Hostname
+    Hostname,
+    /// Listening port
+    Port,
+}
+
+impl fmt::Display for ClaimKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimKind::Route => write!(f, "Route"),
+            ClaimKind::Hostname => write!(f, "Hostname"),
+            ClaimKind::Port => write!(f, "Port"),
+        }
+    }
+}
+
+/// Two services claiming the same network address
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// Kind of the contested address
+    pub kind: ClaimKind,
+    /// The contested address, normalized
+    pub value: String,
+    /// First offender, in name order
+    pub first: String,
+    /// Second offender, in name order
+    pub second: String,
+    /// Environment both services are deployed to, if environments are configured
+    pub environment: Option<String>,
+}
+
+impl Conflict {
+    /// Builds the warning reported for one of the offenders, naming the other one
+    pub fn warning_for(&self, service: &str) -> String {
+        let other = if service == self.first { &self.second } else { &self.first };
+        let mut warning =
+            format!("{} '{}' is also claimed by service '{}'", self.kind, self.value, other);
+        if let Some(environment) = &self.environment {
+            warning.push_str(&format!(" in environment '{}'", environment));
+        }
+        warning
+    }
+}
+
+/// Normalizes a route prefix so equivalent spellings compare equal
+fn normalize_route(route: &str) -> String {
+    let trimmed = route.trim().trim_end_matches('/');
+    if trimmed.starts_with('/') {
+        trimmed.to_string()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+/// Finds addresses claimed by more than one service deployed to the same environment
+///
+/// Without configured environments all services are treated as sharing one environment.
+pub fn find_conflicts(
+    exposures: &BTreeMap<String, Exposure>,
+    topology: &Topology,
+) -> Vec<Conflict> {
+    let environments: Vec<Option<&str>> = if topology.environments().is_empty() {
+        vec![None]
+    } else {
+        topology.environments().iter().map(|e| Some(e.name.as_str())).collect()
+    };
+
+    let mut conflicts = Vec::new();
+    for environment in environments {
+        let mut claims: BTreeMap<(ClaimKind, String), Vec<&str>> = BTreeMap::new();
+        for (service, exposure) in exposures {
+            if environment.is_some_and(|e| !topology.is_placed(service, e)) {
+                continue;
+            }
+
+            let addresses = exposure
+                .routes
+                .iter()
+                .map(|r| (ClaimKind::Route, normalize_route(r)))
+                .chain(exposure.hostnames.iter().map(|h| {
+                    (ClaimKind::Hostname, h.trim().trim_end_matches('.').to_ascii_lowercase())
+                }))
+                .chain(exposure.ports.iter().map(|p| (ClaimKind::Port, p.to_string())));
+            for address in addresses {
+                let claimants = claims.entry(address).or_default();
+                if !claimants.contains(&service.as_str()) {
+                    claimants.push(service);
+                }
+            }
+        }
+
+        for ((kind, value), claimants) in claims {
+            for (i, first) in claimants.iter().enumerate() {
+                for second in &claimants[i + 1..] {
+                    conflicts.push(Conflict {
+                        kind,
+                        value: value.clone(),
+                        first: first.to_string(),
+                        second: second.to_string(),
+                        environment: environment.map(str::to_string),
+                    });
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exposure(routes: &[&str], hostnames: &[&str], ports: &[u16]) -> Exposure {
+        Exposure {
+            hostnames: hostnames.iter().map(|h| h.to_string()).collect(),
+            ports: ports.to_vec(),
+            routes: routes.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let exposures = BTreeMap::from([
+            ("billing".to_string(), exposure(&["/billing/"], &["api.example.com"], &[8080])),
+            ("invoices".to_string(), exposure(&["billing"], &["API.example.com."], &[8081])),
+            ("search".to_string(), exposure(&["/search"], &[], &[8080])),
+        ]);
+
+        let conflicts = find_conflicts(&exposures, &Topology::default());
+        let found: Vec<_> =
+            conflicts.iter().map(|c| (c.kind, c.value.as_str(), c.first.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (ClaimKind::Route, "/billing", "billing"),
+                (ClaimKind::Hostname, "api.example.com", "billing"),
+                (ClaimKind::Port, "8080", "billing"),
+            ]
+        );
+        assert_eq!(
+            conflicts[2].warning_for("search"),
+            "Port '8080' is also claimed by service 'billing'"
+        );
+    }
+
+    #[test]
+    fn test_conflicts_are_scoped_to_environments() {
+        let mut topology = Topology::new(vec![
+            serde_json::from_str(r#"{"name": "dev"}"#).unwrap(),
+            serde_json::from_str(r#"{"name": "prod"}"#).unwrap(),
+        ]);
+        topology.place("billing-canary", vec!["dev".to_string()]).unwrap();
+
+        let exposures = BTreeMap::from([
+            ("billing".to_string(), exposure(&[], &[], &[8080])),
+            ("billing-canary".to_string(), exposure(&[], &[], &[8080])),
+        ]);
+
+        let conflicts = find_conflicts(&exposures, &topology);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].warning_for("billing"),
+            "Port '8080' is also claimed by service 'billing-canary' in environment 'dev'"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Running the contract test suites services declare
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::contract::ContractTest;
+
+/// How long a Pact broker may take to answer by default
+const DEFAULT_BROKER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Outcome of one contract test suite
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractTestResult {
+    /// Name of the suite
+    pub name: String,
+    /// Whether the contracts hold
+    pub passed: bool,
+    /// What the broker or command reported
+    pub detail: String,
+}
+
+/// Outcome of verifying all contract test suites of a service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractStatus {
+    /// Whether every suite passed
+    pub passed: bool,
+    /// When the suites ran
+    pub checked: DateTime<Utc>,
+    /// Outcome of each suite, in declaration order
+    pub results: Vec<ContractTestResult>,
+}
+
+/// Runs contract test suites: queries Pact brokers and runs test commands
+pub struct ContractVerifier {
+    agent: ureq::Agent,
+}
+
+impl Default for ContractVerifier {
+    fn default() -> Self {
+        Self::new(DEFAULT_BROKER_TIMEOUT)
+    }
+}
+
+impl ContractVerifier {
+    /// Creates a verifier waiting at most `timeout` for each broker request
+    pub fn new(timeout: Duration) -> Self {
+        Self { agent: ureq::AgentBuilder::new().timeout(timeout).build() }
+    }
+
+    /// Runs the contract test suites a service definition declares
+    ///
+    /// Commands run relative to `definition_dir`. A suite that can't be run counts
+    /// as failed.
+    pub fn verify(
+        &self,
+        service: &str,
+        definition: &Value,
+        definition_dir: &Path,
+    ) -> Result<ContractStatus> {
+        let tests: Vec<ContractTest> = match definition.get("contract_tests") {
+            Some(tests) => serde_json::from_value(tests.clone()).map_err(|e| {
+                AureaCoreError::invalid_config(
+                    format!("Invalid contract tests of service '{}'", service),
+                    e,
+                )
+            })?,
+            None => Vec::new(),
+        };
+        if tests.is_empty() {
+            return Err(AureaCoreError::Config(format!(
+                "Service '{}' declares no contract tests",
+                service
+            )));
+        }
+
+        let version = definition.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+        let results: Vec<ContractTestResult> = tests
+            .iter()
+            .map(|test| {
+                let outcome = match test {
+                    ContractTest::Pact { broker_url, pacticipant, environment, .. } => self
+                        .query_broker(
+                            broker_url,
+                            pacticipant.as_deref().unwrap_or(service),
+                            version,
+                            environment.as_deref(),
+                        ),
+                    ContractTest::Command { command, working_dir, .. } => {
+                        let dir = match working_dir {
+                            Some(dir) => definition_dir.join(dir),
+                            None => definition_dir.to_path_buf(),
+                        };
+                        run_command(command, &dir, service, version)
+                    }
+                };
+                let (passed, detail) = outcome.unwrap_or_else(|err| (false, err.to_string()));
+                ContractTestResult { name: test.name().to_string(), passed, detail }
+            })
+            .collect();
+        Ok(ContractStatus {
+            passed: results.iter().all(|result| result.passed),
+            checked: Utc::now(),
+            results,
+        })
+    }
+
+    /// Asks a Pact broker whether a version is compatible with its counterparts
+    fn query_broker(
+        &self,
+        broker_url: &str,
+        pacticipant: &str,
+        version: &str,
+        environment: Option<&str>,
+    ) -> Result<(bool, String)> {
+        let url = format!("{}/matrix", broker_url.trim_end_matches('/'));
+        let mut request = self
+            .agent
+            .get(&url)
+            .set("Accept", "application/hal+json")
+            .query("q[][pacticipant]", pacticipant)
+            .query("q[][version]", version)
+            .query("latestby", "cvp");
+        request = match environment {
+            Some(environment) => request.query("environment", environment),
+            None => request.query("latest", "true"),
+        };
+        let response: Value = request
+            .call()
+            .map_err(|e| AureaCoreError::Service(format!("Pact broker {}: {}", url, e)))?
+            .into_json()
+            .map_err(|e| AureaCoreError::Service(format!("Invalid Pact broker response: {}", e)))?;
+        let summary = response.get("summary");
+        let deployable = summary.and_then(|s| s.get("deployable")).and_then(|d| d.as_bool());
+        let reason = summary
+            .and_then(|s| s.get("reason"))
+            .and_then(|r| r.as_str())
+            .unwrap_or("No verification results")
+            .to_string();
+        Ok((deployable == Some(true), reason))
+    }
+}
+
+/// Runs a test command, passing when it exits with 0
+///
+/// The service name and version are available to the command as `AUREACORE_SERVICE`
+/// and `AUREACORE_VERSION`.
+fn run_command(command: &str, dir: &Path, service: &str, version: &str) -> Result<(bool, String)> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .env("AUREACORE_SERVICE", service)
+        .env("AUREACORE_VERSION", version)
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => format!("`{}` {}: {}", command, output.status, line.trim()),
+        None => format!("`{}` {}", command, output.status),
+    };
+    Ok((output.status.success(), detail))
+}
@@ -4,13 +4,17 @@ use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
 use crate::error::{AureaCoreError, Result};
-use crate::registry::ServiceRegistry;
-use crate::schema::validation::ValidationService;
+use crate::registry::matrix::{CompatibilityMatrix, MatrixCell, VersionSkew};
+use crate::registry::{Registry, ServiceRegistry};
+use crate::schema::service::Interaction;
+use crate::schema::validation::{ValidationService, VersionCompatibility};
 
 #[derive(Debug, Clone)]
 pub struct EdgeMetadata {
     pub required: bool,
     pub version_constraint: Option<String>,
+    /// Whether the edge is a synchronous call, an event or a data access
+    pub interaction: Interaction,
 }
 
 #[derive(Debug, Clone)]
@@ -27,6 +31,82 @@ pub struct ImpactInfo {
     pub description: String,
 }
 
+/// How a dependent's version constraint fares against a planned upgrade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintStatus {
+    /// The constraint already accepts the new version
+    Satisfied,
+    /// The change is compatible, so the constraint can be widened automatically
+    Relaxable { proposed: String },
+    /// The dependent has to be adapted before it can use the new version
+    Breaking { reason: String },
+}
+
+/// A dependent's constraint on the upgraded service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintCheck {
+    pub dependent: String,
+    pub constraint: Option<String>,
+    pub required: bool,
+    pub status: ConstraintStatus,
+}
+
+/// What to do with a service when rolling out an upgrade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeAction {
+    /// Release the upgraded service at the new version
+    Upgrade { from: String, to: String },
+    /// Replace the constraint on the upgraded service with a wider one
+    RelaxConstraint { from: String, to: String },
+    /// Adapt the service to the breaking change and update its constraint
+    Adapt { constraint: String },
+    /// Revalidate the service, which depends on the upgraded one transitively or already accepts it
+    Revalidate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeStep {
+    pub service: String,
+    pub action: UpgradeAction,
+}
+
+/// Plan for upgrading a service to a new version across the dependency graph
+#[derive(Debug, Clone)]
+pub struct UpgradePlan {
+    pub service: String,
+    pub from: String,
+    pub to: String,
+    /// Whether the new version is semver-incompatible with the current one
+    pub breaking_change: bool,
+    /// Constraints of the direct dependents on the service
+    pub constraints: Vec<ConstraintCheck>,
+    /// Steps in rollout order, dependencies before their dependents
+    pub steps: Vec<UpgradeStep>,
+}
+
+impl UpgradePlan {
+    /// Dependents whose constraints break and need manual changes
+    pub fn breaking(&self) -> Vec<&ConstraintCheck> {
+        self.constraints
+            .iter()
+            .filter(|check| matches!(check.status, ConstraintStatus::Breaking { .. }))
+            .collect()
+    }
+
+    /// Dependents whose constraints can be widened automatically
+    pub fn relaxable(&self) -> Vec<&ConstraintCheck> {
+        self.constraints
+            .iter()
+            .filter(|check| matches!(check.status, ConstraintStatus::Relaxable { .. }))
+            .collect()
+    }
+
+    /// Whether the upgrade can be rolled out without adapting any dependent
+    pub fn is_safe(&self) -> bool {
+        self.breaking().is_empty()
+    }
+}
+
 #[derive(Debug)]
 pub struct DependencyGraph {
     pub adjacency_list: HashMap<String, Vec<(String, EdgeMetadata)>>,
@@ -56,6 +136,74 @@ impl DependencyGraph {
         }
     }
 
+    /// Keeps only edges of the given interaction kinds, or all edges if none are given
+    ///
+    /// Every service stays in the graph, e.g. to analyze only synchronous call chains.
+    pub fn with_interactions(&self, interactions: &[Interaction]) -> DependencyGraph {
+        let adjacency_list = self
+            .adjacency_list
+            .iter()
+            .map(|(service, edges)| {
+                let edges = edges
+                    .iter()
+                    .filter(|(_, metadata)| {
+                        interactions.is_empty() || interactions.contains(&metadata.interaction)
+                    })
+                    .cloned()
+                    .collect();
+                (service.clone(), edges)
+            })
+            .collect();
+        DependencyGraph { adjacency_list }
+    }
+
+    /// Keeps only the services `keep` accepts, and the edges between them
+    ///
+    /// E.g. to analyze only the services deployed to one environment.
+    pub fn restricted_to(&self, keep: impl Fn(&str) -> bool) -> DependencyGraph {
+        let adjacency_list = self
+            .adjacency_list
+            .iter()
+            .filter(|(service, _)| keep(service))
+            .map(|(service, edges)| {
+                let edges = edges.iter().filter(|(to, _)| keep(to)).cloned().collect();
+                (service.clone(), edges)
+            })
+            .collect();
+        DependencyGraph { adjacency_list }
+    }
+
+    /// Finds the shortest cycle through a service, from the service back to itself
+    ///
+    /// With `required_only`, only required dependencies are followed.
+    pub fn cycle_through(&self, service: &str, required_only: bool) -> Option<Vec<String>> {
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut queue = std::collections::VecDeque::from([service]);
+        while let Some(node) = queue.pop_front() {
+            for (neighbor, metadata) in self.adjacency_list.get(node).into_iter().flatten() {
+                if required_only && !metadata.required {
+                    continue;
+                }
+                if neighbor == service {
+                    let mut cycle = vec![service.to_string()];
+                    let mut current = node;
+                    while current != service {
+                        cycle.push(current.to_string());
+                        current = previous[current];
+                    }
+                    cycle[1..].reverse();
+                    cycle.push(service.to_string());
+                    return Some(cycle);
+                }
+                if !previous.contains_key(neighbor.as_str()) {
+                    previous.insert(neighbor, node);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+
     pub fn detect_cycles(&self) -> Option<CycleInfo> {
         // Track three states for nodes in DFS:
         // - Not visited: not in visited_set
@@ -323,18 +471,26 @@ impl DependencyResolver {
     }
 }
 
+/// Shared handle to a [`Registry`] used by the [`DependencyManager`]
 pub trait RegistryRef {
-    fn registry_ref(&self) -> &RwLock<ServiceRegistry>;
+    /// Registry behind the handle
+    type Registry: Registry;
+
+    fn registry_ref(&self) -> &RwLock<Self::Registry>;
 }
 
-impl RegistryRef for Arc<RwLock<ServiceRegistry>> {
-    fn registry_ref(&self) -> &RwLock<ServiceRegistry> {
+impl<R: Registry> RegistryRef for Arc<RwLock<R>> {
+    type Registry = R;
+
+    fn registry_ref(&self) -> &RwLock<R> {
         self
     }
 }
 
-impl RegistryRef for Rc<RwLock<ServiceRegistry>> {
-    fn registry_ref(&self) -> &RwLock<ServiceRegistry> {
+impl<R: Registry> RegistryRef for Rc<RwLock<R>> {
+    type Registry = R;
+
+    fn registry_ref(&self) -> &RwLock<R> {
         self
     }
 }
@@ -371,19 +527,18 @@ impl<T: RegistryRef> DependencyManager<T> {
             for service_name in &services {
                 let service = registry.get_service(service_name)?;
 
-                if let Some(deps) = &service.config.dependencies {
-                    for dep in deps {
-                        // Only add edge if the dependency exists in the registry
-                        if services.contains(&dep.service) {
-                            graph.add_edge(
-                                service_name.clone(),
-                                dep.service.clone(),
-                                EdgeMetadata {
-                                    required: dep.required,
-                                    version_constraint: dep.version_constraint.clone(),
-                                },
-                            );
-                        }
+                for dep in service.dependencies() {
+                    // Only add edge if the dependency exists in the registry
+                    if services.contains(&dep.service) {
+                        graph.add_edge(
+                            service_name.clone(),
+                            dep.service.clone(),
+                            EdgeMetadata {
+                                required: dep.required,
+                                version_constraint: dep.version_constraint.clone(),
+                                interaction: dep.interaction,
+                            },
+                        );
                     }
                 }
             }
@@ -652,14 +807,14 @@ impl<T: RegistryRef> DependencyManager<T> {
         let service_deps = {
             let registry = self.registry.registry_ref().read().unwrap();
             let service = registry.get_service(service_name)?;
-            service.config.dependencies.clone()
+            service.dependencies()
         };
 
         // Now check each dependency
-        if let Some(dependencies) = service_deps {
+        {
             let registry = self.registry.registry_ref().read().unwrap();
 
-            for dep in dependencies {
+            for dep in service_deps {
                 match registry.get_service(&dep.service) {
                     Ok(_) => {
                         // Service exists, check version compatibility if constraint provided
@@ -667,11 +822,11 @@ impl<T: RegistryRef> DependencyManager<T> {
                             let dep_service = registry.get_service(&dep.service)?;
 
                             // Use validation service to check version compatibility
-                            let compatibility =
-                                self.validation_service.check_version_compatibility(
-                                    &dep_service.config.schema_version,
-                                    constraint,
-                                );
+                            let compatibility = self.validation_service.check_dependency_version(
+                                &dep,
+                                &dep_service.config.schema_version,
+                                constraint,
+                            );
 
                             // Check compatibility result
                             match compatibility {
@@ -730,4 +885,164 @@ impl<T: RegistryRef> DependencyManager<T> {
 
         Ok(all_warnings)
     }
+
+    /// Builds the provider × consumer matrix of version constraint satisfaction
+    ///
+    /// Checks the effective dependencies of every service against the `version` of the
+    /// provider, with the same compatibility policies as validation. Dependencies
+    /// without a constraint are compatible; those on unregistered providers or
+    /// providers without a version are unknown.
+    pub fn compatibility_matrix(&self) -> Result<CompatibilityMatrix> {
+        let registry = self.registry.registry_ref().read().unwrap();
+        let mut cells = Vec::new();
+        for consumer in registry.list_services()? {
+            let service = registry.get_service(&consumer)?;
+            for dependency in service.config.resolve_dependencies(service.schema_data.as_ref()) {
+                let version = registry
+                    .get_service(&dependency.service)
+                    .ok()
+                    .and_then(|provider| provider.schema_data.as_ref())
+                    .and_then(|definition| definition.get("version"))
+                    .and_then(|version| version.as_str())
+                    .map(str::to_string);
+                let skew = match (&version, &dependency.version_constraint) {
+                    (None, _) => VersionSkew::Unknown,
+                    (Some(_), None) => VersionSkew::Compatible,
+                    (Some(version), Some(constraint)) => {
+                        match self.validation_service.check_dependency_version(
+                            &dependency,
+                            version,
+                            constraint,
+                        ) {
+                            VersionCompatibility::Compatible => VersionSkew::Compatible,
+                            VersionCompatibility::MinorIncompatible => VersionSkew::Minor,
+                            VersionCompatibility::MajorIncompatible => VersionSkew::Major,
+                        }
+                    }
+                };
+                cells.push(MatrixCell {
+                    provider: dependency.service.clone(),
+                    consumer: consumer.clone(),
+                    constraint: dependency.version_constraint.clone(),
+                    version,
+                    skew,
+                });
+            }
+        }
+        Ok(CompatibilityMatrix::new(cells))
+    }
+
+    /// Plans upgrading a service to a new version
+    ///
+    /// Checks the constraints of the direct dependents against the new version. When
+    /// the change is a compatible (patch or minor) one, failing constraints are proposed
+    /// a relaxed caret requirement; otherwise the dependent has to be adapted. The plan
+    /// lists the service and everything depending on it, dependencies first.
+    pub fn plan_upgrade(&self, service_name: &str, new_version: &str) -> Result<UpgradePlan> {
+        let to = semver::Version::parse(new_version).map_err(|e| {
+            AureaCoreError::Config(format!("Invalid version '{}': {}", new_version, e))
+        })?;
+        let from = {
+            let registry = self.registry.registry_ref().read().unwrap();
+            let service = registry.get_service(service_name)?;
+            service
+                .definition()
+                .map(|definition| definition.version)
+                .unwrap_or_else(|| service.config.schema_version.clone())
+        };
+        let current = semver::Version::parse(&from).map_err(|e| {
+            AureaCoreError::Config(format!(
+                "Invalid current version '{}' of {}: {}",
+                from, service_name, e
+            ))
+        })?;
+        // Caret semantics: same major, or same minor (patch) below 1.0.0
+        let compatible = semver::VersionReq::parse(&format!("^{}", current))
+            .map(|req| to >= current && req.matches(&to))
+            .unwrap_or(false);
+
+        let graph = self.build_dependency_graph()?;
+        let mut dependents: Vec<(&String, &EdgeMetadata)> = graph
+            .adjacency_list
+            .iter()
+            .flat_map(|(from, edges)| {
+                edges.iter().filter(|(to, _)| to == service_name).map(move |(_, edge)| (from, edge))
+            })
+            .filter(|(from, _)| from.as_str() != service_name)
+            .collect();
+        dependents.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut constraints = Vec::new();
+        for (dependent, edge) in dependents {
+            let status = match &edge.version_constraint {
+                None => ConstraintStatus::Satisfied,
+                Some(constraint) => match semver::VersionReq::parse(constraint) {
+                    Err(e) => ConstraintStatus::Breaking {
+                        reason: format!("invalid constraint '{}': {}", constraint, e),
+                    },
+                    Ok(req) if req.matches(&to) => ConstraintStatus::Satisfied,
+                    Ok(req) if compatible => {
+                        let base = if req.matches(&current) { &current } else { &to };
+                        ConstraintStatus::Relaxable { proposed: format!("^{}", base) }
+                    }
+                    Ok(_) => ConstraintStatus::Breaking {
+                        reason: format!(
+                            "{} is not compatible with {} required by '{}'",
+                            to, constraint, dependent
+                        ),
+                    },
+                },
+            };
+            constraints.push(ConstraintCheck {
+                dependent: dependent.clone(),
+                constraint: edge.version_constraint.clone(),
+                required: edge.required,
+                status,
+            });
+        }
+
+        // Order the service and everything depending on it, dependencies first
+        let resolver = DependencyResolver::new();
+        let mut affected = resolver.find_impact_path(&graph, service_name);
+        affected.sort();
+        affected.insert(0, service_name.to_string());
+        let order = resolver.resolve_order(&graph, &affected)?;
+
+        let steps = order
+            .into_iter()
+            .filter(|name| affected.contains(name))
+            .map(|name| {
+                let action = if name == service_name {
+                    UpgradeAction::Upgrade { from: from.clone(), to: to.to_string() }
+                } else {
+                    match constraints.iter().find(|check| check.dependent == name) {
+                        Some(ConstraintCheck {
+                            constraint: Some(constraint),
+                            status: ConstraintStatus::Relaxable { proposed },
+                            ..
+                        }) => UpgradeAction::RelaxConstraint {
+                            from: constraint.clone(),
+                            to: proposed.clone(),
+                        },
+                        Some(ConstraintCheck {
+                            constraint: Some(constraint),
+                            status: ConstraintStatus::Breaking { .. },
+                            ..
+                        }) => UpgradeAction::Adapt { constraint: constraint.clone() },
+                        _ => UpgradeAction::Revalidate,
+                    }
+                };
+                UpgradeStep { service: name, action }
+            })
+            .collect();
+
+        Ok(UpgradePlan {
+            service: service_name.to_string(),
+            from,
+            to: to.to_string(),
+            breaking_change: !compatible,
+            constraints,
+            steps,
+        })
+    }
 }
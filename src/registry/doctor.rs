@@ -0,0 +1,155 @@
+//! Catalog-wide consistency checks reported by `aureacore doctor`
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+/// How urgently a doctor finding needs attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, nothing is wrong
+    Info,
+    /// Likely to cause surprises, e.g. on the next sync
+    Warning,
+    /// The catalog is inconsistent
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found by one of the doctor's checks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorFinding {
+    /// Check that found the problem
+    pub check: &'static str,
+    /// How urgently the problem needs attention
+    pub severity: Severity,
+    /// Service the problem concerns, if any
+    pub service: Option<String>,
+    /// What is wrong
+    pub message: String,
+    /// How to resolve the problem
+    pub remediation: String,
+}
+
+impl DoctorFinding {
+    pub(crate) fn new(
+        check: &'static str,
+        severity: Severity,
+        message: String,
+        remediation: impl Into<String>,
+    ) -> Self {
+        Self { check, severity, service: None, message, remediation: remediation.into() }
+    }
+
+    pub(crate) fn for_service(mut self, service: &str) -> Self {
+        self.service = Some(service.to_string());
+        self
+    }
+}
+
+/// Outcome of [`ServiceRegistry::doctor`](crate::registry::ServiceRegistry::doctor)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    /// Checks that ran
+    pub checks: Vec<&'static str>,
+    /// Problems found, most severe first
+    pub findings: Vec<DoctorFinding>,
+}
+
+impl DoctorReport {
+    /// Counts the findings of a severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|finding| finding.severity == severity).count()
+    }
+
+    /// Checks whether no errors were found
+    pub fn is_healthy(&self) -> bool {
+        self.count(Severity::Error) == 0
+    }
+
+    /// Records that a check ran, along with its findings
+    pub(crate) fn add(&mut self, check: &'static str, findings: Vec<DoctorFinding>) {
+        self.checks.push(check);
+        self.findings.extend(findings);
+    }
+
+    /// Orders findings by descending severity, then by check and service
+    pub(crate) fn sort(&mut self) {
+        self.findings.sort_by(|a, b| {
+            b.severity.cmp(&a.severity).then(a.check.cmp(b.check)).then(a.service.cmp(&b.service))
+        });
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Ran {} check(s): {}", self.checks.len(), self.checks.join(", "))?;
+        for finding in &self.findings {
+            write!(f, "[{}] {}: ", finding.severity, finding.check)?;
+            if let Some(service) = &finding.service {
+                write!(f, "{}: ", service)?;
+            }
+            writeln!(f, "{}", finding.message)?;
+            writeln!(f, "    hint: {}", finding.remediation)?;
+        }
+        writeln!(
+            f,
+            "{} error(s), {} warning(s), {} info",
+            self.count(Severity::Error),
+            self.count(Severity::Warning),
+            self.count(Severity::Info)
+        )
+    }
+}
+
+/// Finds endpoints a definition declares more than once, by name or by method and path
+pub(crate) fn duplicate_endpoints(
+    service: &str,
+    definition: &serde_json::Value,
+) -> Vec<DoctorFinding> {
+    let mut findings = Vec::new();
+    let mut names = HashMap::new();
+    let mut routes = HashMap::new();
+    let endpoints = definition.get("endpoints").and_then(|e| e.as_array());
+    for (i, endpoint) in endpoints.into_iter().flatten().enumerate() {
+        let field = |key: &str| endpoint.get(key).and_then(|v| v.as_str());
+        if let Some(name) = field("name") {
+            if let Some(first) = names.insert(name.to_string(), i) {
+                findings.push(DoctorFinding::new(
+                    "duplicate-endpoints",
+                    Severity::Warning,
+                    format!("Endpoints #{} and #{} are both named '{}'", first + 1, i + 1, name),
+                    "Rename one of the endpoints",
+                ));
+            }
+        }
+        if let Some(path) = field("path") {
+            let method = field("method").unwrap_or_default().to_ascii_uppercase();
+            if let Some(first) = routes.insert((method.clone(), path.to_string()), i) {
+                let route = if method.is_empty() {
+                    path.to_string()
+                } else {
+                    format!("{} {}", method, path)
+                };
+                findings.push(DoctorFinding::new(
+                    "duplicate-endpoints",
+                    Severity::Warning,
+                    format!("Endpoints #{} and #{} both declare {}", first + 1, i + 1, route),
+                    "Remove the duplicate endpoint or merge their descriptions",
+                ));
+            }
+        }
+    }
+    findings.into_iter().map(|finding| finding.for_service(service)).collect()
+}
@@ -0,0 +1,75 @@
+//! Reverse lookup of the services consuming an endpoint, for planning API deprecations
+
+use serde::Serialize;
+
+use crate::registry::ServiceRegistry;
+
+/// A service consuming an endpoint of another service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EndpointConsumer {
+    /// Consuming service
+    pub consumer: String,
+    /// Service providing the endpoint
+    pub provider: String,
+    /// Name of the endpoint
+    pub endpoint: String,
+    /// Path or address of the endpoint, if the provider still declares it
+    pub path: Option<String>,
+    /// API version, or semver requirement, the consumer is pinned to
+    pub version: String,
+    /// Whether the consumer requires the provider, so removing the endpoint breaks it
+    pub required: bool,
+}
+
+/// Finds the consumers of the endpoints of `provider` matching `endpoint` by name or path
+///
+/// Consumptions of endpoints the provider no longer declares match by name, and
+/// consumptions and dependencies naming an alias of the provider count as its own. Sorted
+/// by consumer, then endpoint.
+pub(crate) fn endpoint_consumers(
+    registry: &ServiceRegistry,
+    provider: &str,
+    endpoint: &str,
+) -> Vec<EndpointConsumer> {
+    let declared = registry
+        .services
+        .get(provider)
+        .and_then(|service| service.definition())
+        .map(|definition| definition.endpoints)
+        .unwrap_or_default();
+    let matching: Vec<(String, Option<String>)> = declared
+        .iter()
+        .filter(|e| e.name == endpoint || e.path == endpoint)
+        .map(|e| (e.name.clone(), Some(e.path.clone())))
+        .collect();
+    let matching = if matching.is_empty() { vec![(endpoint.to_string(), None)] } else { matching };
+
+    let mut consumers = Vec::new();
+    for (name, service) in &registry.services {
+        let Some(definition) = service.definition() else {
+            continue;
+        };
+        let required = registry
+            .canonical_dependencies(service)
+            .iter()
+            .find(|dependency| dependency.service == provider)
+            .is_some_and(|dependency| dependency.required);
+        let consumes = definition.consumes.iter();
+        for consumed in consumes.filter(|c| registry.resolve_alias(&c.service) == provider) {
+            let Some((_, path)) = matching.iter().find(|(name, _)| *name == consumed.endpoint)
+            else {
+                continue;
+            };
+            consumers.push(EndpointConsumer {
+                consumer: name.clone(),
+                provider: provider.to_string(),
+                endpoint: consumed.endpoint.clone(),
+                path: path.clone(),
+                version: consumed.version.clone(),
+                required,
+            });
+        }
+    }
+    consumers.sort_by(|a, b| a.consumer.cmp(&b.consumer).then(a.endpoint.cmp(&b.endpoint)));
+    consumers
+}
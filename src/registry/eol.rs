@@ -0,0 +1,249 @@
+//! End-of-life dates of language runtimes and the per-team report of services using them
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::{fmt, fs};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::runtime::Runtime;
+
+/// Release cycles whose end of life is known without configuration
+///
+/// Each entry is (language, release cycle, end of life as `YYYY-MM-DD`).
+const BUILTIN_EOL: &[(&str, &str, &str)] = &[
+    ("node", "10", "2021-04-30"),
+    ("node", "12", "2022-04-30"),
+    ("node", "14", "2023-04-30"),
+    ("node", "16", "2023-09-11"),
+    ("node", "18", "2025-04-30"),
+    ("python", "2.7", "2020-01-01"),
+    ("python", "3.6", "2021-12-23"),
+    ("python", "3.7", "2023-06-27"),
+    ("python", "3.8", "2024-10-07"),
+    ("ruby", "2.7", "2023-03-31"),
+    ("ruby", "3.0", "2024-04-23"),
+    ("go", "1.20", "2024-02-06"),
+    ("go", "1.21", "2024-08-13"),
+];
+
+/// End of life of a release cycle of a runtime
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EolEntry {
+    /// Language or runtime, compared case-insensitively
+    pub language: String,
+    /// Release cycle, matching versions equal to it or starting with it and a dot
+    pub cycle: String,
+    /// Last day the cycle is supported
+    pub eol: NaiveDate,
+}
+
+impl EolEntry {
+    /// Checks whether a runtime belongs to this release cycle
+    fn matches(&self, runtime: &Runtime) -> bool {
+        let Some(version) = runtime.version.as_deref() else {
+            return false;
+        };
+        let version = version.trim().trim_start_matches('v');
+        language_key(&runtime.language) == language_key(&self.language)
+            && (version == self.cycle
+                || version.strip_prefix(&self.cycle).is_some_and(|rest| rest.starts_with('.')))
+    }
+}
+
+/// Normalizes a language name, treating common aliases alike
+fn language_key(language: &str) -> String {
+    match language.trim().to_ascii_lowercase().as_str() {
+        "nodejs" | "node.js" => "node".to_string(),
+        "golang" => "go".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// End-of-life dates of runtime release cycles
+///
+/// Starts with well-known dates; configured entries are added, replacing built-in
+/// entries of the same language and cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EolDatabase {
+    entries: Vec<EolEntry>,
+    warn_before_days: Option<u32>,
+}
+
+/// EOL configuration as written in `eol.yaml`
+#[derive(Deserialize)]
+#[serde(default)]
+struct EolConfig {
+    /// Start from the built-in dates
+    builtin: bool,
+    /// Days before the end of life to start warning about it
+    warn_before_days: Option<u32>,
+    /// Additional or overriding entries
+    runtimes: Vec<EolEntry>,
+}
+
+impl Default for EolConfig {
+    fn default() -> Self {
+        Self { builtin: true, warn_before_days: None, runtimes: Vec::new() }
+    }
+}
+
+impl Default for EolDatabase {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl EolDatabase {
+    /// Creates a database with the built-in end-of-life dates
+    pub fn builtin() -> Self {
+        let entries = BUILTIN_EOL
+            .iter()
+            .map(|(language, cycle, eol)| EolEntry {
+                language: language.to_string(),
+                cycle: cycle.to_string(),
+                eol: NaiveDate::parse_from_str(eol, "%Y-%m-%d").expect("valid built-in date"),
+            })
+            .collect();
+        Self { entries, warn_before_days: None }
+    }
+
+    /// Creates a database without any entries
+    pub fn empty() -> Self {
+        Self { entries: Vec::new(), warn_before_days: None }
+    }
+
+    /// Loads a database from a YAML file
+    pub fn load(path: &Path) -> Result<Self> {
+        let config: EolConfig = serde_yaml::from_str(&fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid EOL database {}", path.display()), e)
+        })?;
+        let mut database = if config.builtin { Self::builtin() } else { Self::empty() };
+        for entry in config.runtimes {
+            database = database.with_entry(entry);
+        }
+        database.warn_before_days = config.warn_before_days;
+        Ok(database)
+    }
+
+    /// Adds an entry, replacing one of the same language and cycle
+    pub fn with_entry(mut self, entry: EolEntry) -> Self {
+        self.entries.retain(|e| {
+            language_key(&e.language) != language_key(&entry.language) || e.cycle != entry.cycle
+        });
+        self.entries.push(entry);
+        self
+    }
+
+    /// Also warns about runtimes reaching their end of life within `days`
+    pub fn with_warn_before_days(mut self, days: u32) -> Self {
+        self.warn_before_days = Some(days);
+        self
+    }
+
+    /// Finds the end of life of a runtime, preferring the most specific cycle
+    pub fn eol_of(&self, runtime: &Runtime) -> Option<NaiveDate> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.matches(runtime))
+            .max_by_key(|entry| entry.cycle.len())
+            .map(|entry| entry.eol)
+    }
+
+    /// Describes why a runtime needs attention on `today`, if it does
+    pub fn check(&self, runtime: &Runtime, today: NaiveDate) -> Option<String> {
+        let eol = self.eol_of(runtime)?;
+        if eol < today {
+            return Some(format!("Runtime {} reached its end of life on {}", runtime, eol));
+        }
+        let days = self.warn_before_days?;
+        ((eol - today).num_days() <= i64::from(days))
+            .then(|| format!("Runtime {} reaches its end of life on {}", runtime, eol))
+    }
+}
+
+/// A service running on a runtime at or near its end of life
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EolFinding {
+    /// Name of the service
+    pub service: String,
+    /// Runtime of the service
+    pub runtime: String,
+    /// Last day the runtime is supported
+    pub eol: NaiveDate,
+    /// Whether the end of life has passed
+    pub expired: bool,
+}
+
+/// Services on end-of-life runtimes, grouped by owning team
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EolReport {
+    /// Findings per owner, services without an owner under `unowned`
+    pub teams: BTreeMap<String, Vec<EolFinding>>,
+}
+
+impl EolReport {
+    /// Counts the services found
+    pub fn count(&self) -> usize {
+        self.teams.values().map(Vec::len).sum()
+    }
+}
+
+impl fmt::Display for EolReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.teams.is_empty() {
+            return writeln!(f, "No services run end-of-life runtimes");
+        }
+        for (team, findings) in &self.teams {
+            writeln!(f, "{} ({} service(s)):", team, findings.len())?;
+            for finding in findings {
+                let verb = if finding.expired { "reached" } else { "reaches" };
+                writeln!(
+                    f,
+                    "  {}: {} {} end of life on {}",
+                    finding.service, finding.runtime, verb, finding.eol
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime(language: &str, version: &str) -> Runtime {
+        Runtime { language: language.to_string(), framework: None, version: Some(version.into()) }
+    }
+
+    #[test]
+    fn test_eol_database() {
+        let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let database = EolDatabase::builtin();
+        assert_eq!(
+            database.check(&runtime("NodeJS", "14.17.0"), today).unwrap(),
+            "Runtime NodeJS 14.17.0 reached its end of life on 2023-04-30"
+        );
+        assert!(database.check(&runtime("python", "3.7"), today).is_some());
+        assert!(database.check(&runtime("python", "3.70"), today).is_none());
+        assert!(database.check(&runtime("python", "3.8.10"), today).is_none());
+        assert!(database.check(&runtime("rust", "1.70"), today).is_none());
+
+        let warning = database.with_warn_before_days(365);
+        assert!(warning.check(&runtime("python", "3.8.10"), today).unwrap().contains("reaches"));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("eol.yaml");
+        std::fs::write(
+            &path,
+            "builtin: false\nruntimes:\n  - language: java\n    cycle: '11'\n    eol: 2023-09-30\n",
+        )
+        .unwrap();
+        let database = EolDatabase::load(&path).unwrap();
+        assert!(database.check(&runtime("java", "11.0.2"), today).is_some());
+        assert!(database.check(&runtime("node", "14"), today).is_none());
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::graph::GraphExport;
+use crate::registry::service::Service;
+use crate::schema::service::Dependency;
+
+/// Content of a service that contributes to the catalog fingerprint
+///
+/// The config path is left out, as it depends on where the catalog is checked out.
+#[derive(Serialize)]
+struct FingerprintedService<'a> {
+    namespace: Option<&'a str>,
+    schema_version: &'a str,
+    dependencies: Option<&'a [Dependency]>,
+    definition: Option<&'a serde_json::Value>,
+}
+
+/// Hashes service configs and the dependency graph into a hex-encoded SHA-256 digest
+///
+/// JSON objects serialize with sorted keys, so the digest does not depend on key order
+/// in the definitions or on the order services were registered in.
+pub(crate) fn catalog_fingerprint(
+    services: &HashMap<String, Service>,
+    graph: &GraphExport,
+) -> Result<String> {
+    let services: BTreeMap<&str, FingerprintedService> = services
+        .iter()
+        .map(|(name, service)| {
+            let entry = FingerprintedService {
+                namespace: service.config.namespace.as_deref(),
+                schema_version: &service.config.schema_version,
+                dependencies: service.config.dependencies.as_deref(),
+                definition: service.schema_data.as_ref(),
+            };
+            (name.as_str(), entry)
+        })
+        .collect();
+
+    let content = serde_json::to_vec(&(services, graph)).map_err(|e| {
+        AureaCoreError::Internal(format!("Failed to serialize catalog for fingerprint: {}", e))
+    })?;
+    Ok(Sha256::digest(content).iter().map(|byte| format!("{:02x}", byte)).collect())
+}
@@ -0,0 +1,180 @@
+//! Feature flags services are gated by, checked against the platforms hosting them
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::flags::{FeatureFlag, FlagPlatform};
+
+/// Base URL of the LaunchDarkly API
+const LAUNCHDARKLY_API_URL: &str = "https://app.launchdarkly.com";
+
+/// A service gated by a feature flag
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlagUsage {
+    /// Service declaring the flag
+    pub service: String,
+    /// The flag, as the service declares it
+    pub flag: FeatureFlag,
+}
+
+/// Looks up feature flags on a flag platform's API
+#[async_trait]
+pub trait FlagProvider: Send + Sync {
+    /// Platform whose flags this provider looks up
+    fn platform(&self) -> FlagPlatform;
+
+    /// Checks whether a flag exists in its project
+    async fn flag_exists(&self, flag: &FeatureFlag) -> Result<bool>;
+}
+
+/// Looks up flags with the LaunchDarkly REST API
+pub struct LaunchDarklyProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_token: String,
+}
+
+impl LaunchDarklyProvider {
+    /// Creates a provider authenticating with an API access token
+    pub fn new(api_token: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: LAUNCHDARKLY_API_URL.to_string(),
+            api_token: api_token.into(),
+        }
+    }
+
+    /// Talks to a federal or self-hosted instance, or a test double, instead
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl FlagProvider for LaunchDarklyProvider {
+    fn platform(&self) -> FlagPlatform {
+        FlagPlatform::LaunchDarkly
+    }
+
+    async fn flag_exists(&self, flag: &FeatureFlag) -> Result<bool> {
+        let url = format!("{}/api/v2/flags/{}/{}", self.base_url, flag.project(), flag.key);
+        resource_exists(&self.client, &url, &self.api_token, self.platform()).await
+    }
+}
+
+/// Looks up flags with the admin API of an Unleash server
+pub struct UnleashProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_token: String,
+}
+
+impl UnleashProvider {
+    /// Creates a provider for the server at `base_url`, authenticating with an admin token
+    pub fn new(base_url: impl Into<String>, api_token: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_token: api_token.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl FlagProvider for UnleashProvider {
+    fn platform(&self) -> FlagPlatform {
+        FlagPlatform::Unleash
+    }
+
+    async fn flag_exists(&self, flag: &FeatureFlag) -> Result<bool> {
+        let url = format!(
+            "{}/api/admin/projects/{}/features/{}",
+            self.base_url,
+            flag.project(),
+            flag.key
+        );
+        resource_exists(&self.client, &url, &self.api_token, self.platform()).await
+    }
+}
+
+/// Requests a resource, telling found from not found and failing on other answers
+async fn resource_exists(
+    client: &reqwest::Client,
+    url: &str,
+    api_token: &str,
+    platform: FlagPlatform,
+) -> Result<bool> {
+    let response = client
+        .get(url)
+        .header(reqwest::header::AUTHORIZATION, api_token)
+        .send()
+        .await
+        .map_err(|e| AureaCoreError::Service(format!("{} {}: {}", platform, url, e)))?;
+    match response.status() {
+        status if status.is_success() => Ok(true),
+        reqwest::StatusCode::NOT_FOUND => Ok(false),
+        status => Err(AureaCoreError::Service(format!(
+            "{} {} returned HTTP {}",
+            platform,
+            url,
+            status.as_u16()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::Path;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+
+    use super::*;
+
+    fn flag(provider: FlagPlatform, key: &str) -> FeatureFlag {
+        FeatureFlag { provider, key: key.to_string(), project: None, description: None }
+    }
+
+    #[tokio::test]
+    async fn test_flag_exists() {
+        let lookup = |headers: HeaderMap, key: String| async move {
+            match (headers.get("authorization").and_then(|v| v.to_str().ok()), key.as_str()) {
+                (Some("s3cret"), "new-checkout") => StatusCode::OK,
+                (Some("s3cret"), "broken") => StatusCode::INTERNAL_SERVER_ERROR,
+                (Some("s3cret"), _) => StatusCode::NOT_FOUND,
+                _ => StatusCode::UNAUTHORIZED,
+            }
+        };
+        let router = Router::new()
+            .route(
+                "/api/v2/flags/{project}/{key}",
+                get(move |headers: HeaderMap, Path((_, key)): Path<(String, String)>| {
+                    lookup(headers, key)
+                }),
+            )
+            .route(
+                "/api/admin/projects/{project}/features/{key}",
+                get(move |headers: HeaderMap, Path((_, key)): Path<(String, String)>| {
+                    lookup(headers, key)
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let launchdarkly = LaunchDarklyProvider::new("s3cret").with_base_url(&base);
+        let unleash = UnleashProvider::new(&base, "s3cret");
+        for provider in [&launchdarkly as &dyn FlagProvider, &unleash] {
+            let platform = provider.platform();
+            assert!(provider.flag_exists(&flag(platform, "new-checkout")).await.unwrap());
+            assert!(!provider.flag_exists(&flag(platform, "retired")).await.unwrap());
+            let err = provider.flag_exists(&flag(platform, "broken")).await.unwrap_err();
+            assert!(err.to_string().contains("HTTP 500"));
+        }
+
+        let unauthorized = UnleashProvider::new(&base, "wrong");
+        assert!(unauthorized.flag_exists(&flag(FlagPlatform::Unleash, "retired")).await.is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Change freeze windows blocking mutations of affected services
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::{Deserialize, Deserializer};
+
+use crate::error::{AureaCoreError, Result};
+
+/// A cron-like schedule of frozen minutes
+///
+/// Uses the five cron fields (minute, hour, day of month, month, day of week, with
+/// Sunday as 0 or 7), each accepting `*`, values, ranges `a-b`, lists and steps `*/n`.
+/// A minute is frozen if all fields match it, evaluated in UTC; `* 18-23 * * 5`
+/// freezes Friday evenings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreezeSchedule {
+    expression: String,
+    fields: [Vec<u32>; 5],
+}
+
+/// Value ranges of the cron fields, in order
+const FIELD_RANGES: [(u32, u32); 5] = [(0, 59), (0, 23), (1, 31), (1, 12), (0, 7)];
+
+impl FreezeSchedule {
+    /// Checks whether a point in time falls into the schedule
+    pub fn matches(&self, time: DateTime<Utc>) -> bool {
+        let values = [
+            time.minute(),
+            time.hour(),
+            time.day(),
+            time.month(),
+            time.weekday().num_days_from_sunday(),
+        ];
+        values.iter().zip(&self.fields).all(|(value, allowed)| allowed.contains(value))
+    }
+}
+
+impl FromStr for FreezeSchedule {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            AureaCoreError::Config(format!("Invalid freeze schedule '{}': {}", s, reason))
+        };
+
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        if parts.len() != 5 {
+            return Err(invalid("expected 5 fields"));
+        }
+
+        let mut fields: [Vec<u32>; 5] = Default::default();
+        for (i, (part, (min, max))) in parts.iter().zip(FIELD_RANGES).enumerate() {
+            for item in part.split(',') {
+                let (range, step) = match item.split_once('/') {
+                    Some((range, step)) => {
+                        (range, step.parse::<u32>().map_err(|_| invalid("invalid step"))?)
+                    }
+                    None => (item, 1),
+                };
+                let (start, end) = match range {
+                    "*" => (min, max),
+                    _ => match range.split_once('-') {
+                        Some((a, b)) => (
+                            a.parse().map_err(|_| invalid("invalid range"))?,
+                            b.parse().map_err(|_| invalid("invalid range"))?,
+                        ),
+                        None => {
+                            let value = range.parse().map_err(|_| invalid("invalid value"))?;
+                            (value, value)
+                        }
+                    },
+                };
+                if step == 0 || start < min || end > max || start > end {
+                    return Err(invalid("value out of range"));
+                }
+                fields[i].extend((start..=end).step_by(step as usize));
+            }
+        }
+
+        // Sunday may be written as 7
+        if fields[4].contains(&7) {
+            fields[4].push(0);
+        }
+
+        Ok(Self { expression: s.to_string(), fields })
+    }
+}
+
+impl<'de> Deserialize<'de> for FreezeSchedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let expression = String::deserialize(deserializer)?;
+        expression.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::fmt::Display for FreezeSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+/// A recurring change freeze for a set of namespaces and tiers
+#[derive(Debug, Clone, Deserialize)]
+pub struct FreezeWindow {
+    /// Name of the window
+    pub name: String,
+    /// Minutes during which the freeze is in effect
+    pub schedule: FreezeSchedule,
+    /// Namespaces affected by the freeze, all namespaces if empty
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Service tiers (the `tier` metadata value) affected by the freeze, all tiers if empty
+    #[serde(default)]
+    pub tiers: Vec<String>,
+    /// Why changes are frozen
+    pub reason: Option<String>,
+}
+
+impl FreezeWindow {
+    /// Checks whether the window applies to a service in the given namespace and tier
+    pub fn applies_to(&self, namespace: Option<&str>, tier: Option<&str>) -> bool {
+        let matches = |allowed: &[String], value: Option<&str>| {
+            allowed.is_empty() || value.is_some_and(|v| allowed.iter().any(|a| a == v))
+        };
+        matches(&self.namespaces, namespace) && matches(&self.tiers, tier)
+    }
+}
+
+/// Freeze windows enforced by a registry, with the token that overrides them
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FreezeCalendar {
+    /// Configured freeze windows
+    #[serde(default)]
+    pub windows: Vec<FreezeWindow>,
+    /// Token allowing changes during a freeze; never read from configuration files
+    #[serde(skip)]
+    override_token: Option<String>,
+}
+
+impl FreezeCalendar {
+    /// Creates a calendar from freeze windows
+    pub fn new(windows: Vec<FreezeWindow>) -> Self {
+        Self { windows, override_token: None }
+    }
+
+    /// Loads freeze windows from a YAML file
+    pub fn load(path: &Path) -> Result<Self> {
+        serde_yaml::from_str(&fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid freeze config {}", path.display()), e)
+        })
+    }
+
+    /// Sets the token that allows changes during a freeze
+    pub fn with_override_token(mut self, token: impl Into<String>) -> Self {
+        self.override_token = Some(token.into());
+        self
+    }
+
+    /// Gets the token that allows changes during a freeze, if set
+    pub(crate) fn override_token(&self) -> Option<&str> {
+        self.override_token.as_deref()
+    }
+
+    /// Checks whether a token overrides the freeze
+    pub fn accepts_override(&self, token: &str) -> bool {
+        self.override_token.as_deref().is_some_and(|expected| constant_time_eq(expected, token))
+    }
+
+    /// Gets the windows in effect at the given time
+    pub fn active_at(&self, time: DateTime<Utc>) -> Vec<&FreezeWindow> {
+        self.windows.iter().filter(|w| w.schedule.matches(time)).collect()
+    }
+
+    /// Gets the windows in effect at the given time for a service's namespace and tier
+    pub fn active_for(
+        &self,
+        time: DateTime<Utc>,
+        namespace: Option<&str>,
+        tier: Option<&str>,
+    ) -> Vec<&FreezeWindow> {
+        self.active_at(time).into_iter().filter(|w| w.applies_to(namespace, tier)).collect()
+    }
+}
+
+/// Compares tokens in time independent of where they differ
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_schedule_matching() {
+        let schedule: FreezeSchedule = "*/15 18-23 * 12 5,7".parse().unwrap();
+        // Friday 2025-12-19 and Sunday 2025-12-21
+        assert!(schedule.matches(Utc.with_ymd_and_hms(2025, 12, 19, 18, 30, 0).unwrap()));
+        assert!(schedule.matches(Utc.with_ymd_and_hms(2025, 12, 21, 23, 45, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2025, 12, 19, 18, 31, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2025, 12, 20, 18, 30, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2025, 11, 21, 18, 30, 0).unwrap()));
+
+        for invalid in ["* * * *", "60 * * * *", "* * * * mon", "*/0 * * * *", "5-1 * * * *"] {
+            assert!(invalid.parse::<FreezeSchedule>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_calendar_scoping() {
+        let calendar: FreezeCalendar = serde_yaml::from_str(
+            "windows:\n  - name: always\n    schedule: '* * * * *'\n    namespaces: [payments]\n    tiers: ['1']\n",
+        )
+        .unwrap();
+        let now = Utc::now();
+
+        assert_eq!(calendar.active_for(now, Some("payments"), Some("1")).len(), 1);
+        assert!(calendar.active_for(now, Some("payments"), Some("2")).is_empty());
+        assert!(calendar.active_for(now, None, Some("1")).is_empty());
+
+        assert!(!calendar.accepts_override("secret"));
+        let calendar = calendar.with_override_token("secret");
+        assert!(calendar.accepts_override("secret"));
+        assert!(!calendar.accepts_override("guess"));
+        assert!(!calendar.accepts_override("secrets"));
+        assert!(!calendar.accepts_override(""));
+    }
+}
@@ -1,10 +1,95 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+use chrono::{DateTime, TimeZone, Utc};
 use git2::build::CheckoutBuilder;
-use git2::{FetchOptions, RemoteCallbacks, Repository};
+use git2::{FetchOptions, ObjectType, Progress, RemoteCallbacks, Repository, Sort, StatusOptions};
+use tokio::sync::Semaphore;
 use tracing;
 
 use crate::error::{AureaCoreError, Result};
+use crate::registry::cancel::CancellationToken;
+use crate::registry::progress::{percent, ProgressReporter};
+
+/// File inside the `.git` directory recording the last successful sync
+const LAST_SYNC_FILE: &str = "aureacore-last-sync";
+
+/// Prefix of the worktrees and local branches created to preview branches
+const PREVIEW_PREFIX: &str = "aureacore-preview";
+
+/// Summary of a single commit in the repository history.
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    /// Full commit id
+    pub id: String,
+    /// First line of the commit message
+    pub summary: String,
+    /// Name of the commit author
+    pub author: String,
+    /// Email address of the commit author
+    pub email: String,
+    /// Commit timestamp
+    pub time: DateTime<Utc>,
+}
+
+/// Summarizes a commit
+fn commit_info(commit: &git2::Commit) -> CommitInfo {
+    let author = commit.author();
+    CommitInfo {
+        id: commit.id().to_string(),
+        summary: commit.summary().unwrap_or_default().to_string(),
+        author: author.name().unwrap_or_default().to_string(),
+        email: author.email().unwrap_or_default().to_string(),
+        time: Utc.timestamp_opt(commit.time().seconds(), 0).single().unwrap_or_default(),
+    }
+}
+
+/// Differences between the working directory and the branch it tracks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkDirDrift {
+    /// Files modified, added or deleted without being committed
+    pub changed_files: Vec<String>,
+    /// Local commits missing from the remote branch
+    pub ahead: usize,
+    /// Remote commits not checked out yet
+    pub behind: usize,
+}
+
+impl WorkDirDrift {
+    /// Checks whether the working directory matches the remote branch
+    pub fn is_clean(&self) -> bool {
+        self.changed_files.is_empty() && self.ahead == 0 && self.behind == 0
+    }
+}
+
+/// Observes transfer progress of fetches as (received objects, total objects, received
+/// bytes); returning `false` aborts the transfer.
+type TransferHook = Arc<dyn Fn(usize, usize, usize) -> bool + Send + Sync>;
+
+/// Aggregated progress of a [`GitProvider::fetch_all`] run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchProgress {
+    /// Number of repositories being fetched
+    pub total_repos: usize,
+    /// Repositories whose fetch has finished, successfully or not
+    pub finished_repos: usize,
+    /// Repositories whose fetch failed or was cancelled
+    pub failed_repos: usize,
+    /// Objects received so far across all repositories
+    pub received_objects: usize,
+    /// Objects to receive across all repositories that started transferring
+    pub total_objects: usize,
+    /// Bytes received so far across all repositories
+    pub received_bytes: usize,
+}
+
+/// Outcome of fetching one repository in a [`GitProvider::fetch_all`] run.
+pub struct FetchOutcome {
+    /// The provider, holding the opened repository if the fetch succeeded
+    pub provider: GitProvider,
+    /// Whether the repository was cloned or pulled
+    pub result: Result<()>,
+}
 
 /// A Git provider that manages a local clone of a Git repository.
 pub struct GitProvider {
@@ -16,43 +101,184 @@ pub struct GitProvider {
     work_dir: PathBuf,
     /// The Git repository instance.
     repo: Option<Repository>,
+    /// Observer of transfer progress, set while fetching as part of a batch.
+    transfer_hook: Option<TransferHook>,
+    /// Token aborting clones and fetches once cancelled.
+    cancellation: CancellationToken,
 }
 
 impl GitProvider {
     /// Creates a new Git provider.
     pub fn new(repo_url: String, branch: String, work_dir: PathBuf) -> Self {
-        Self { repo_url, branch, work_dir, repo: None }
+        Self {
+            repo_url,
+            branch,
+            work_dir,
+            repo: None,
+            transfer_hook: None,
+            cancellation: CancellationToken::new(),
+        }
     }
 
-    /// Clones the repository to the working directory.
-    pub fn clone_repo(&mut self) -> Result<()> {
-        if self.repo.is_some() {
-            return Ok(());
+    /// Sets the token aborting clones and fetches.
+    ///
+    /// Transfers in progress stop as soon as the next objects arrive.
+    pub fn set_cancellation(&mut self, cancellation: CancellationToken) {
+        self.cancellation = cancellation;
+    }
+
+    /// Reports the transfer progress of clones and fetches, or stops reporting it.
+    pub(crate) fn set_progress_reporter(&mut self, reporter: Option<Arc<dyn ProgressReporter>>) {
+        self.transfer_hook = reporter.map(|reporter| -> TransferHook {
+            Arc::new(move |received, total, _bytes| {
+                let message = format!("Received {} of {} objects", received, total);
+                reporter.progress(percent(received, total), &message);
+                true
+            })
+        });
+    }
+
+    /// Gets the URL of the repository.
+    pub fn repo_url(&self) -> &str {
+        &self.repo_url
+    }
+
+    /// Clones or pulls many repositories concurrently.
+    ///
+    /// At most `concurrency` repositories are synced at a time. `on_progress` receives
+    /// the progress aggregated over all repositories whenever objects arrive or a
+    /// repository finishes. Outcomes are returned in the order of `repos`. Once
+    /// `cancellation` is cancelled, fetches not yet started are skipped and running
+    /// transfers are aborted.
+    pub async fn fetch_all(
+        repos: Vec<GitProvider>,
+        concurrency: usize,
+        cancellation: CancellationToken,
+        on_progress: impl Fn(&FetchProgress) + Send + Sync + 'static,
+    ) -> Vec<FetchOutcome> {
+        let total_repos = repos.len();
+        // Overall progress, plus per-repository transfer counters
+        let state = Arc::new(Mutex::new((
+            FetchProgress { total_repos, ..Default::default() },
+            vec![(0, 0, 0); total_repos],
+        )));
+        let on_progress = Arc::new(on_progress);
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+        let mut handles = Vec::with_capacity(total_repos);
+        for (index, mut provider) in repos.into_iter().enumerate() {
+            let state = state.clone();
+            let on_progress = on_progress.clone();
+            let semaphore = semaphore.clone();
+            let cancellation = cancellation.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let operation = format!("Fetch of {}", provider.repo_url);
+                let outcome = if let Err(err) = cancellation.check(&operation) {
+                    FetchOutcome { provider, result: Err(err) }
+                } else {
+                    let hook_state = state.clone();
+                    let hook_progress = on_progress.clone();
+                    provider.cancellation = cancellation.clone();
+                    provider.transfer_hook = Some(Arc::new(move |received, total, bytes| {
+                        let mut state = hook_state.lock().unwrap_or_else(|e| e.into_inner());
+                        state.1[index] = (received, total, bytes);
+                        let (mut progress, repos) = (state.0.clone(), &state.1);
+                        progress.received_objects = repos.iter().map(|r| r.0).sum();
+                        progress.total_objects = repos.iter().map(|r| r.1).sum();
+                        progress.received_bytes = repos.iter().map(|r| r.2).sum();
+                        state.0 = progress.clone();
+                        drop(state);
+                        hook_progress(&progress);
+                        true
+                    }));
+
+                    let url = provider.repo_url.clone();
+                    match tokio::task::spawn_blocking(move || {
+                        let result = provider.sync();
+                        provider.transfer_hook = None;
+                        (provider, result)
+                    })
+                    .await
+                    {
+                        Ok((provider, result)) => {
+                            let result = result
+                                .map_err(|err| cancellation.check(&operation).err().unwrap_or(err));
+                            FetchOutcome { provider, result }
+                        }
+                        Err(err) => FetchOutcome {
+                            provider: GitProvider::new(url, String::new(), PathBuf::new()),
+                            result: Err(AureaCoreError::Internal(format!(
+                                "Fetch task failed: {}",
+                                err
+                            ))),
+                        },
+                    }
+                };
+
+                let progress = {
+                    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                    state.0.finished_repos += 1;
+                    if outcome.result.is_err() {
+                        state.0.failed_repos += 1;
+                    }
+                    state.0.clone()
+                };
+                on_progress(&progress);
+                outcome
+            }));
         }
 
+        let mut outcomes = Vec::with_capacity(total_repos);
+        for handle in handles {
+            // Tasks only fail to join if they panicked, which the blocking part guards against
+            if let Ok(outcome) = handle.await {
+                outcomes.push(outcome);
+            }
+        }
+        outcomes
+    }
+
+    /// Builds fetch options logging transfer progress and reporting it to the hook.
+    fn fetch_options(&self) -> FetchOptions<'static> {
+        let hook = self.transfer_hook.clone();
+        let cancellation = self.cancellation.clone();
         let mut callbacks = RemoteCallbacks::new();
-        callbacks.transfer_progress(|stats| {
+        callbacks.transfer_progress(move |stats: Progress<'_>| {
             tracing::debug!(
-                "Transferred {} of {} objects ({} bytes)",
+                "Received {} of {} objects ({} bytes)",
                 stats.received_objects(),
                 stats.total_objects(),
                 stats.received_bytes()
             );
-            true
+            !cancellation.is_cancelled()
+                && hook.as_ref().is_none_or(|hook| {
+                    hook(stats.received_objects(), stats.total_objects(), stats.received_bytes())
+                })
         });
 
         let mut fetch_options = FetchOptions::new();
         fetch_options.remote_callbacks(callbacks);
+        fetch_options
+    }
+
+    /// Clones the repository to the working directory.
+    pub fn clone_repo(&mut self) -> Result<()> {
+        if self.repo.is_some() {
+            return Ok(());
+        }
+        self.cancellation.check("Clone of the repository")?;
 
         // Clone with specific branch
         let mut builder = git2::build::RepoBuilder::new();
-        builder.fetch_options(fetch_options);
+        builder.fetch_options(self.fetch_options());
         builder.branch(&self.branch);
 
         let repo = match builder.clone(&self.repo_url, &self.work_dir) {
             Ok(repo) => repo,
             Err(e) => {
-                return Err(AureaCoreError::Git(format!("Failed to clone repository: {}", e)))
+                self.cancellation.check("Clone of the repository")?;
+                return Err(AureaCoreError::Git(format!("Failed to clone repository: {}", e)));
             }
         };
 
@@ -80,22 +306,13 @@ impl GitProvider {
             .as_ref()
             .ok_or_else(|| AureaCoreError::Git("Repository not initialized".to_string()))?;
 
+        self.cancellation.check("Fetch of the repository")?;
         let mut remote = repo.find_remote("origin")?;
-        let mut callbacks = RemoteCallbacks::new();
-        callbacks.transfer_progress(|stats| {
-            tracing::debug!(
-                "Received {} of {} objects ({} bytes)",
-                stats.received_objects(),
-                stats.total_objects(),
-                stats.received_bytes()
-            );
-            true
-        });
-
-        let mut fetch_options = FetchOptions::new();
-        fetch_options.remote_callbacks(callbacks);
-
-        remote.fetch(&[&self.branch], Some(&mut fetch_options), None)?;
+        let mut fetch_options = self.fetch_options();
+        if let Err(err) = remote.fetch(&[&self.branch], Some(&mut fetch_options), None) {
+            self.cancellation.check("Fetch of the repository")?;
+            return Err(err.into());
+        }
 
         let fetch_head = repo.find_reference("FETCH_HEAD")?;
         let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
@@ -110,6 +327,431 @@ impl GitProvider {
         Ok(())
     }
 
+    /// Opens a previously cloned repository in the working directory.
+    ///
+    /// Returns `false` if the working directory does not contain a repository yet.
+    pub fn open_existing(&mut self) -> Result<bool> {
+        if self.repo.is_some() {
+            return Ok(true);
+        }
+        if !self.work_dir.join(".git").exists() {
+            return Ok(false);
+        }
+
+        self.repo = Some(Repository::open(&self.work_dir)?);
+        Ok(true)
+    }
+
+    /// Clones the repository, or pulls the latest changes if it was cloned before.
+    pub fn sync(&mut self) -> Result<()> {
+        if self.open_existing()? {
+            self.pull()?;
+        } else {
+            self.clone_repo()?;
+        }
+        self.record_sync()
+    }
+
+    /// Gets the time of the last successful sync, if any.
+    pub fn last_sync(&self) -> Option<DateTime<Utc>> {
+        let content = std::fs::read_to_string(self.last_sync_path()).ok()?;
+        DateTime::parse_from_rfc3339(content.trim()).ok().map(|time| time.with_timezone(&Utc))
+    }
+
+    /// Records the current time as the last successful sync.
+    fn record_sync(&self) -> Result<()> {
+        std::fs::write(self.last_sync_path(), Utc::now().to_rfc3339())?;
+        Ok(())
+    }
+
+    fn last_sync_path(&self) -> PathBuf {
+        self.metadata_path(LAST_SYNC_FILE)
+    }
+
+    /// Gets the path of an AureaCore bookkeeping file inside the `.git` directory.
+    ///
+    /// Worktrees keep their files in their own directory of the main repository.
+    pub fn metadata_path(&self, name: &str) -> PathBuf {
+        match &self.repo {
+            Some(repo) if repo.is_worktree() => repo.path().join(name),
+            _ => self.work_dir.join(".git").join(name),
+        }
+    }
+
+    /// Gets the id of the commit currently checked out.
+    pub fn head_commit(&self) -> Result<String> {
+        self.with_repo(|repo| Ok(repo.head()?.peel_to_commit()?.id().to_string()))
+    }
+
+    /// Compares the working directory with HEAD and HEAD with the fetched remote branch.
+    ///
+    /// Ignored files don't count as changes. Without a fetched remote branch, HEAD is
+    /// neither ahead nor behind.
+    pub fn drift(&self) -> Result<WorkDirDrift> {
+        self.with_repo(|repo| {
+            let mut options = StatusOptions::new();
+            options.include_untracked(true).include_ignored(false);
+            let mut changed_files: Vec<String> = repo
+                .statuses(Some(&mut options))?
+                .iter()
+                .filter_map(|entry| entry.path().map(str::to_string))
+                .collect();
+            changed_files.sort();
+
+            let remote = format!("refs/remotes/origin/{}", self.branch);
+            let (ahead, behind) = match repo.refname_to_id(&remote) {
+                Ok(remote) => {
+                    let head = repo.head()?.peel_to_commit()?.id();
+                    repo.graph_ahead_behind(head, remote)?
+                }
+                Err(_) => (0, 0),
+            };
+            Ok(WorkDirDrift { changed_files, ahead, behind })
+        })
+    }
+
+    /// Lists commits reachable from HEAD but not from `since`, newest first.
+    pub fn history_since(&self, since: &str) -> Result<Vec<CommitInfo>> {
+        self.with_repo(|repo| {
+            let since_commit = repo
+                .revparse_single(since)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|e| AureaCoreError::Git(format!("Unknown revision '{}': {}", since, e)))?;
+
+            let mut revwalk = repo.revwalk()?;
+            revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+            revwalk.push_head()?;
+            revwalk.hide(since_commit.id())?;
+
+            let mut commits = Vec::new();
+            for oid in revwalk {
+                commits.push(commit_info(&repo.find_commit(oid?)?));
+            }
+
+            Ok(commits)
+        })
+    }
+
+    /// Finds the most recent commit reachable from `revision` that changed `path`.
+    ///
+    /// `path` is relative to the repository root. Like `git log -1 -- <path>`, merges
+    /// that took the file unchanged from one of their parents are skipped.
+    pub fn last_commit_touching(&self, revision: &str, path: &Path) -> Result<Option<CommitInfo>> {
+        self.with_repo(|repo| {
+            let start =
+                repo.revparse_single(revision).and_then(|obj| obj.peel_to_commit()).map_err(
+                    |e| AureaCoreError::Git(format!("Unknown revision '{}': {}", revision, e)),
+                )?;
+            let blob_at = |commit: &git2::Commit| {
+                commit.tree().ok()?.get_path(path).ok().map(|entry| entry.id())
+            };
+
+            let mut revwalk = repo.revwalk()?;
+            revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+            revwalk.push(start.id())?;
+            for oid in revwalk {
+                let commit = repo.find_commit(oid?)?;
+                let blob = blob_at(&commit);
+                if blob.is_some() && commit.parents().all(|parent| blob_at(&parent) != blob) {
+                    return Ok(Some(commit_info(&commit)));
+                }
+            }
+            Ok(None)
+        })
+    }
+
+    /// Lists the files whose working tree or staged content differs from `revision`.
+    ///
+    /// Paths are relative to the repository root; renamed files are listed under both
+    /// names, untracked files are included.
+    pub fn changed_paths(&self, revision: &str) -> Result<Vec<PathBuf>> {
+        self.with_repo(|repo| {
+            let tree =
+                repo.revparse_single(revision).and_then(|obj| obj.peel_to_tree()).map_err(|e| {
+                    AureaCoreError::Git(format!("Unknown revision '{}': {}", revision, e))
+                })?;
+            let mut options = git2::DiffOptions::new();
+            options.include_untracked(true).recurse_untracked_dirs(true);
+            let diff = repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))?;
+
+            let mut paths = Vec::new();
+            for delta in diff.deltas() {
+                for file in [delta.old_file(), delta.new_file()] {
+                    if let Some(path) = file.path() {
+                        if !paths.iter().any(|p: &PathBuf| p == path) {
+                            paths.push(path.to_path_buf());
+                        }
+                    }
+                }
+            }
+            Ok(paths)
+        })
+    }
+
+    /// Commits files on top of HEAD to a new branch, without touching the checkout.
+    ///
+    /// Paths are relative to the repository root. Returns the id of the new commit;
+    /// fails if the branch already exists.
+    pub fn commit_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        files: &[(PathBuf, String)],
+    ) -> Result<String> {
+        self.with_repo(|repo| {
+            if repo.find_branch(branch, git2::BranchType::Local).is_ok() {
+                return Err(AureaCoreError::Git(format!("Branch '{}' already exists", branch)));
+            }
+            let head = repo.head().and_then(|head| head.peel_to_commit())?;
+            let mut update = git2::build::TreeUpdateBuilder::new();
+            for (path, content) in files {
+                let blob = repo.blob(content.as_bytes())?;
+                update.upsert(path, blob, git2::FileMode::Blob);
+            }
+            let tree = repo.find_tree(update.create_updated(repo, &head.tree()?)?)?;
+
+            let signature = repo
+                .signature()
+                .or_else(|_| git2::Signature::now("AureaCore", "aureacore@example.com"))?;
+            let oid = repo.commit(None, &signature, &signature, message, &tree, &[&head])?;
+            repo.branch(branch, &repo.find_commit(oid)?, false)?;
+            Ok(oid.to_string())
+        })
+    }
+
+    /// Writes files to the working directory and commits them on the checked out branch.
+    ///
+    /// Paths are relative to the repository root and must stay inside it. Returns the id
+    /// of the new commit.
+    pub fn commit_files(&self, message: &str, files: &[(PathBuf, String)]) -> Result<String> {
+        self.with_repo(|repo| {
+            let mut index = repo.index()?;
+            for (path, content) in files {
+                if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+                    return Err(AureaCoreError::Git(format!(
+                        "Path '{}' is outside the repository",
+                        path.display()
+                    )));
+                }
+                let target = self.work_dir.join(path);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&target, content)?;
+                index.add_path(path)?;
+            }
+            index.write()?;
+            let tree = repo.find_tree(index.write_tree()?)?;
+            let head = repo.head().and_then(|head| head.peel_to_commit())?;
+
+            let signature = repo
+                .signature()
+                .or_else(|_| git2::Signature::now("AureaCore", "aureacore@example.com"))?;
+            let oid =
+                repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&head])?;
+            Ok(oid.to_string())
+        })
+    }
+
+    /// Pushes the checked out branch to `origin`.
+    ///
+    /// Fails if the remote rejects the update, e.g. because it is not a fast-forward.
+    pub fn push(&self) -> Result<()> {
+        self.cancellation.check("Push of the repository")?;
+        self.with_repo(|repo| {
+            let mut remote = repo.find_remote("origin")?;
+            let mut callbacks = RemoteCallbacks::new();
+            callbacks.push_update_reference(|reference, status| match status {
+                Some(reason) => Err(git2::Error::from_str(&format!(
+                    "Remote rejected {}: {}",
+                    reference, reason
+                ))),
+                None => Ok(()),
+            });
+            let mut options = git2::PushOptions::new();
+            options.remote_callbacks(callbacks);
+            let refspec = format!("refs/heads/{0}:refs/heads/{0}", self.branch);
+            remote.push(&[refspec.as_str()], Some(&mut options))?;
+            Ok(())
+        })
+    }
+
+    /// Checks out the remote `branch` into a new worktree at `path`
+    ///
+    /// The branch is fetched from `origin`, falling back to the local branch of that
+    /// name, and checked out on a dedicated local branch so even the branch of the
+    /// primary working directory can be previewed. The primary working directory is left
+    /// untouched. Returns a provider for the worktree; remove it with
+    /// [`remove_worktree`](Self::remove_worktree).
+    pub fn add_worktree(&self, branch: &str, path: PathBuf) -> Result<GitProvider> {
+        self.cancellation.check("Fetch of the preview branch")?;
+        let name = format!("{}-{}", PREVIEW_PREFIX, branch.replace(['/', '\\'], "-"));
+        self.with_repo(|repo| {
+            let remote_ref = format!("refs/remotes/origin/{}", branch);
+            let fetched = repo.find_remote("origin").and_then(|mut remote| {
+                let refspec = format!("+refs/heads/{}:{}", branch, remote_ref);
+                let mut fetch_options = self.fetch_options();
+                remote.fetch(&[refspec.as_str()], Some(&mut fetch_options), None)
+            });
+            let reference = match fetched {
+                Ok(()) => repo.find_reference(&remote_ref),
+                Err(err) => {
+                    self.cancellation.check("Fetch of the preview branch")?;
+                    tracing::warn!(
+                        "Failed to fetch branch {}, using the local one: {}",
+                        branch,
+                        err
+                    );
+                    repo.find_reference(&format!("refs/heads/{}", branch))
+                }
+            };
+            let commit = reference
+                .and_then(|reference| reference.peel_to_commit())
+                .map_err(|e| AureaCoreError::Git(format!("Branch {} not found: {}", branch, e)))?;
+
+            if repo.find_worktree(&name).is_ok() {
+                return Err(AureaCoreError::Git(format!(
+                    "Branch {} is already being previewed",
+                    branch
+                )));
+            }
+            let local = repo.branch(&name, &commit, true)?;
+            let mut options = git2::WorktreeAddOptions::new();
+            options.reference(Some(local.get()));
+            let worktree = repo.worktree(&name, &path, Some(&options)).map_err(|e| {
+                AureaCoreError::Git(format!("Failed to add worktree at {}: {}", path.display(), e))
+            })?;
+            let mut provider = GitProvider::new(self.repo_url.clone(), branch.to_string(), path);
+            provider.repo = Some(Repository::open_from_worktree(&worktree)?);
+            Ok(provider)
+        })
+    }
+
+    /// Removes a worktree created by [`add_worktree`](Self::add_worktree), with its
+    /// working directory and local branch
+    pub fn remove_worktree(mut self) -> Result<()> {
+        let repo = match self.repo.take() {
+            Some(repo) => repo,
+            None => Repository::open(&self.work_dir)?,
+        };
+        if !repo.is_worktree() {
+            return Err(AureaCoreError::Git(format!(
+                "{} is not a worktree",
+                self.work_dir.display()
+            )));
+        }
+        let worktree = git2::Worktree::open_from_repository(&repo)?;
+        let name = worktree.name().unwrap_or_default().to_string();
+        let common = Repository::open(repo.commondir())?;
+        drop(repo);
+        let mut options = git2::WorktreePruneOptions::new();
+        options.valid(true).working_tree(true);
+        worktree.prune(Some(&mut options))?;
+        if name.starts_with(PREVIEW_PREFIX) {
+            common.find_branch(&name, git2::BranchType::Local)?.delete()?;
+        }
+        Ok(())
+    }
+
+    /// Creates a provider for the same repository that opens it on demand.
+    pub fn reopen(&self) -> Self {
+        Self::new(self.repo_url.clone(), self.branch.clone(), self.work_dir.clone())
+    }
+
+    /// Gets the path to the working directory.
+    pub fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
+
+    /// Resolves a revision such as a short sha, tag or `HEAD~2` to a full commit id.
+    pub fn resolve_commit(&self, revision: &str) -> Result<String> {
+        self.with_repo(|repo| {
+            let commit =
+                repo.revparse_single(revision).and_then(|obj| obj.peel_to_commit()).map_err(
+                    |e| AureaCoreError::Git(format!("Unknown revision '{}': {}", revision, e)),
+                )?;
+            Ok(commit.id().to_string())
+        })
+    }
+
+    /// Reads the contents of all files at the root of the tree for `revision`.
+    ///
+    /// Returns pairs of (path, content); files that are not valid UTF-8 are skipped.
+    pub fn read_root_files(&self, revision: &str) -> Result<Vec<(PathBuf, String)>> {
+        self.read_dir_files(revision, Path::new(""))
+    }
+
+    /// Reads the contents of all files directly in `dir` of the tree for `revision`.
+    ///
+    /// Returns pairs of (path relative to `dir`, content); a missing directory has no files.
+    pub fn read_dir_files(&self, revision: &str, dir: &Path) -> Result<Vec<(PathBuf, String)>> {
+        self.with_repo(|repo| {
+            let root =
+                repo.revparse_single(revision).and_then(|obj| obj.peel_to_tree()).map_err(|e| {
+                    AureaCoreError::Git(format!("Unknown revision '{}': {}", revision, e))
+                })?;
+            let dir: PathBuf =
+                dir.components().filter(|c| !matches!(c, Component::CurDir)).collect();
+            let tree = if dir.as_os_str().is_empty() {
+                root
+            } else {
+                match root.get_path(&dir) {
+                    Ok(entry) if entry.kind() == Some(ObjectType::Tree) => {
+                        repo.find_tree(entry.id())?
+                    }
+                    _ => return Ok(Vec::new()),
+                }
+            };
+
+            let mut files = Vec::new();
+            for entry in tree.iter() {
+                if entry.kind() != Some(ObjectType::Blob) {
+                    continue;
+                }
+                let Some(name) = entry.name() else { continue };
+                let blob = repo.find_blob(entry.id())?;
+                if let Ok(content) = std::str::from_utf8(blob.content()) {
+                    files.push((Path::new(name).to_path_buf(), content.to_string()));
+                }
+            }
+
+            Ok(files)
+        })
+    }
+
+    /// Reads a single file from the tree for `revision`, if it exists.
+    pub fn read_file_at(&self, revision: &str, path: &Path) -> Result<Option<String>> {
+        self.with_repo(|repo| {
+            let tree =
+                repo.revparse_single(revision).and_then(|obj| obj.peel_to_tree()).map_err(|e| {
+                    AureaCoreError::Git(format!("Unknown revision '{}': {}", revision, e))
+                })?;
+
+            let entry = match tree.get_path(path) {
+                Ok(entry) => entry,
+                Err(_) => return Ok(None),
+            };
+            let blob = repo.find_blob(entry.id())?;
+            Ok(std::str::from_utf8(blob.content()).ok().map(|s| s.to_string()))
+        })
+    }
+
+    /// Runs `f` against the cloned repository, or opens the one already in the work dir.
+    fn with_repo<T>(&self, f: impl FnOnce(&Repository) -> Result<T>) -> Result<T> {
+        if let Some(repo) = &self.repo {
+            return f(repo);
+        }
+
+        let repo = Repository::open(&self.work_dir).map_err(|e| {
+            AureaCoreError::Git(format!(
+                "Failed to open repository at {}: {}",
+                self.work_dir.display(),
+                e
+            ))
+        })?;
+        f(&repo)
+    }
+
     /// Commits changes to the repository.
     /// This method is currently only used in tests but will be used for automated
     /// configuration updates in future implementations.
@@ -210,6 +852,46 @@ mod tests {
         assert!(work_dir.join(".git").exists());
     }
 
+    #[tokio::test]
+    async fn test_fetch_all_concurrently() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let url = repo_path.to_str().unwrap().to_string();
+        let target = TempDir::new().unwrap();
+        let repos = vec![
+            GitProvider::new(url.clone(), "main".to_string(), target.path().join("a")),
+            GitProvider::new(url.clone(), "main".to_string(), target.path().join("b")),
+            GitProvider::new(
+                target.path().join("missing").display().to_string(),
+                "main".to_string(),
+                target.path().join("c"),
+            ),
+        ];
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let seen = reports.clone();
+        let outcomes =
+            GitProvider::fetch_all(repos, 2, CancellationToken::new(), move |progress| {
+                seen.lock().unwrap().push(progress.clone())
+            })
+            .await;
+
+        assert_eq!(outcomes.len(), 3);
+        assert!(outcomes[0].result.is_ok() && outcomes[1].result.is_ok());
+        assert!(outcomes[2].result.is_err());
+        assert!(target.path().join("b/README.md").exists());
+        assert!(outcomes[0].provider.head_commit().is_ok());
+        let last = reports.lock().unwrap().last().cloned().unwrap();
+        assert_eq!((last.total_repos, last.finished_repos, last.failed_repos), (3, 3, 1));
+
+        // A cancelled run skips fetches that have not started
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let repos = vec![GitProvider::new(url, "main".to_string(), target.path().join("d"))];
+        let outcomes = GitProvider::fetch_all(repos, 1, cancellation, |_| {}).await;
+        assert!(outcomes[0].result.as_ref().unwrap_err().to_string().contains("cancelled"));
+        assert!(!target.path().join("d").exists());
+    }
+
     #[test]
     fn test_git_provider_commit_changes() {
         let (_temp_dir, repo_path) = setup_test_repo();
@@ -280,4 +962,96 @@ mod tests {
         let config_content = fs::read_to_string(&service_config).unwrap();
         assert!(config_content.contains("name: test-service"));
     }
+
+    #[test]
+    fn test_git_provider_history_and_file_reads() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v0.1.0", head.as_object(), false).unwrap();
+
+        // Add a second commit with a service config
+        fs::write(repo_path.join("service.json"), r#"{"config_path": "service.yaml"}"#).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("service.json")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Add service", &tree, &[&head]).unwrap();
+
+        // The provider opens the existing repository in its work dir
+        let provider = GitProvider::new(String::new(), "main".to_string(), repo_path.to_path_buf());
+
+        let history = provider.history_since("v0.1.0").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].summary, "Add service");
+        assert_eq!(history[0].author, "test");
+
+        let files = provider.read_root_files("HEAD").unwrap();
+        assert!(files.iter().any(|(path, _)| path == std::path::Path::new("service.json")));
+        assert!(provider.read_dir_files("HEAD", Path::new("services")).unwrap().is_empty());
+        assert!(provider
+            .read_file_at("v0.1.0", std::path::Path::new("service.json"))
+            .unwrap()
+            .is_none());
+
+        assert!(provider.history_since("no-such-tag").is_err());
+
+        assert!(provider.changed_paths("HEAD").unwrap().is_empty());
+        fs::write(repo_path.join("service.json"), "{}").unwrap();
+        fs::write(repo_path.join("other.json"), "{}").unwrap();
+        let changed = provider.changed_paths("HEAD").unwrap();
+        assert_eq!(changed, vec![PathBuf::from("other.json"), PathBuf::from("service.json")]);
+        assert_eq!(provider.changed_paths("v0.1.0").unwrap().len(), 2);
+
+        let files = vec![(PathBuf::from("services/other.json"), "{}".to_string())];
+        let commit = provider.commit_to_branch("proposal", "Propose", &files).unwrap();
+        let branch = repo.find_branch("proposal", git2::BranchType::Local).unwrap();
+        assert_eq!(branch.get().target().unwrap().to_string(), commit);
+        assert!(provider
+            .read_file_at("proposal", Path::new("services/other.json"))
+            .unwrap()
+            .is_some());
+        assert_eq!(repo.head().unwrap().peel_to_commit().unwrap().summary(), Some("Add service"));
+        assert!(provider.commit_to_branch("proposal", "Again", &files).is_err());
+
+        let touched = provider.last_commit_touching("HEAD", Path::new("service.json")).unwrap();
+        assert_eq!(touched.unwrap().summary, "Add service");
+        let touched = provider.last_commit_touching("HEAD", Path::new("README.md")).unwrap();
+        assert_eq!(touched.as_ref().map(|c| c.summary.as_str()), Some("Initial commit"));
+        assert_eq!(touched.unwrap().email, "test@example.com");
+        assert!(provider
+            .last_commit_touching("v0.1.0", Path::new("service.json"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_git_provider_sync_reopens_existing_clone() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let work_dir = repo_path.parent().unwrap().join("work-dir");
+        let url = repo_path.to_str().unwrap().to_string();
+
+        let mut provider = GitProvider::new(url.clone(), "main".to_string(), work_dir.clone());
+        assert!(provider.last_sync().is_none());
+        provider.sync().unwrap();
+        let first_sync = provider.last_sync().expect("sync time should be recorded");
+
+        // A fresh provider (e.g. after a restart) pulls into the existing clone
+        let mut restarted = GitProvider::new(url, "main".to_string(), work_dir.clone());
+        assert!(restarted.open_existing().unwrap());
+        restarted.sync().unwrap();
+        assert!(restarted.last_sync().unwrap() >= first_sync);
+
+        // An unreachable remote fails the sync but keeps the recorded time
+        let mut offline = GitProvider::new(
+            repo_path.parent().unwrap().join("missing").to_str().unwrap().to_string(),
+            "main".to_string(),
+            work_dir,
+        );
+        offline.repo = Some(Repository::open(&offline.work_dir).unwrap());
+        offline.repo.as_ref().unwrap().remote_set_url("origin", "/nonexistent/remote").unwrap();
+        assert!(offline.sync().is_err());
+        assert!(offline.last_sync().is_some());
+    }
 }
@@ -0,0 +1,498 @@
+//! Access to a catalog repository through the GitHub REST API, for hosts where libgit2
+//! cannot be built or reach the remote
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde_json::{json, Value};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::git::CommitInfo;
+use crate::registry::vcs::{VcsBackend, VcsProvider};
+
+/// Base URL of the public GitHub API
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Commits listed per page of the commits API
+const COMMITS_PER_PAGE: usize = 100;
+
+/// A repository checked out through the GitHub contents and Git data APIs
+///
+/// Files of the branch are downloaded to the working directory. Commits are created as
+/// Git objects on GitHub and only move the branch once pushed.
+#[derive(Clone)]
+pub struct GitHubProvider {
+    client: reqwest::Client,
+    api_url: String,
+    owner: String,
+    repo: String,
+    branch: String,
+    token: Option<String>,
+    work_dir: PathBuf,
+    /// Blob ids of the checked out files by path; empty for files written locally
+    files: BTreeMap<String, String>,
+    /// Commit of the branch the working directory was last synced to
+    head: Option<String>,
+    /// Commit created on top of `head` that is not pushed yet
+    pending: Option<String>,
+}
+
+impl GitHubProvider {
+    /// Creates a provider for a `https://github.com/<owner>/<repo>` URL or `<owner>/<repo>` slug
+    pub fn new(repository: &str, branch: String, work_dir: PathBuf) -> Result<Self> {
+        let slug = repository
+            .trim_start_matches("https://")
+            .trim_start_matches("github.com/")
+            .trim_end_matches('/')
+            .trim_end_matches(".git");
+        let (owner, repo) = slug
+            .split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty() && !repo.contains('/'))
+            .ok_or_else(|| {
+                AureaCoreError::Config(format!("Invalid GitHub repository '{}'", repository))
+            })?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_url: GITHUB_API_URL.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            branch,
+            token: None,
+            work_dir,
+            files: BTreeMap::new(),
+            head: None,
+            pending: None,
+        })
+    }
+
+    /// Talks to a GitHub Enterprise server, or a test double, instead of github.com
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Authenticates requests with a personal access or installation token
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Gets the commit the working directory is at, including unpushed commits
+    pub fn head_commit(&self) -> Option<&str> {
+        self.pending.as_deref().or(self.head.as_deref())
+    }
+
+    /// Sends a request to an endpoint of the repository, e.g. `git/trees/<sha>`
+    async fn request(&self, method: Method, endpoint: &str, body: Option<Value>) -> Result<Value> {
+        let url = format!("{}/repos/{}/{}/{}", self.api_url, self.owner, self.repo, endpoint);
+        let mut request = self
+            .client
+            .request(method, &url)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header(reqwest::header::USER_AGENT, "aureacore");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(|e| {
+            AureaCoreError::Git(format!("GitHub request to {} failed: {}", endpoint, e))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(AureaCoreError::Git(format!(
+                "GitHub request to {} returned HTTP {}: {}",
+                endpoint,
+                status.as_u16(),
+                message.trim()
+            )));
+        }
+        response.json().await.map_err(|e| {
+            AureaCoreError::Git(format!("Invalid GitHub response from {}: {}", endpoint, e))
+        })
+    }
+
+    /// Downloads the files of the branch that changed since the last sync
+    async fn fetch(&mut self) -> Result<()> {
+        let commit = self.request(Method::GET, &format!("commits/{}", self.branch), None).await?;
+        let head = string_field(&commit, "sha")?;
+        let tree =
+            self.request(Method::GET, &format!("git/trees/{}?recursive=1", head), None).await?;
+        if tree["truncated"].as_bool().unwrap_or(false) {
+            return Err(AureaCoreError::Git(format!(
+                "Tree of {} is too large for the GitHub API",
+                head
+            )));
+        }
+
+        let mut files = BTreeMap::new();
+        for entry in tree["tree"].as_array().into_iter().flatten() {
+            if entry["type"] == "blob" {
+                files.insert(string_field(entry, "path")?, string_field(entry, "sha")?);
+            }
+        }
+        for (path, sha) in &files {
+            if self.files.get(path) == Some(sha) {
+                continue;
+            }
+            let blob = self.request(Method::GET, &format!("git/blobs/{}", sha), None).await?;
+            let encoded: String = string_field(&blob, "content")?.split_whitespace().collect();
+            let content = base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| AureaCoreError::Git(format!("Invalid content of {}: {}", path, e)))?;
+            let target = self.checkout_path(Path::new(path))?;
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(target, content)?;
+        }
+        for path in self.files.keys().filter(|path| !files.contains_key(*path)) {
+            let target = self.checkout_path(Path::new(path))?;
+            if target.exists() {
+                std::fs::remove_file(target)?;
+            }
+        }
+
+        self.files = files;
+        self.head = Some(head);
+        Ok(())
+    }
+
+    /// Resolves a repository path inside the working directory
+    fn checkout_path(&self, path: &Path) -> Result<PathBuf> {
+        if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(AureaCoreError::Git(format!(
+                "Path '{}' is outside the repository",
+                path.display()
+            )));
+        }
+        Ok(self.work_dir.join(path))
+    }
+}
+
+/// Reads a string field of a GitHub response
+fn string_field(value: &Value, field: &str) -> Result<String> {
+    value[field].as_str().map(|s| s.to_string()).ok_or_else(|| {
+        AureaCoreError::Git(format!("GitHub response is missing the '{}' field", field))
+    })
+}
+
+/// Summarizes a commit listed by the commits API
+fn commit_info(commit: &Value) -> Result<CommitInfo> {
+    let author = &commit["commit"]["author"];
+    Ok(CommitInfo {
+        id: string_field(commit, "sha")?,
+        summary: commit["commit"]["message"]
+            .as_str()
+            .and_then(|message| message.lines().next())
+            .unwrap_or_default()
+            .to_string(),
+        author: author["name"].as_str().unwrap_or_default().to_string(),
+        email: author["email"].as_str().unwrap_or_default().to_string(),
+        time: author["date"]
+            .as_str()
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or_default(),
+    })
+}
+
+#[async_trait]
+impl VcsProvider for GitHubProvider {
+    fn backend(&self) -> VcsBackend {
+        VcsBackend::GitHub
+    }
+
+    fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
+
+    async fn clone_repo(&mut self) -> Result<()> {
+        if self.head.is_some() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.work_dir)?;
+        self.fetch().await
+    }
+
+    async fn pull(&mut self) -> Result<()> {
+        if self.head.is_none() {
+            return Err(AureaCoreError::Git("Repository not initialized".to_string()));
+        }
+        if let Some(pending) = &self.pending {
+            return Err(AureaCoreError::Git(format!("Commit {} is not pushed yet", pending)));
+        }
+        self.fetch().await
+    }
+
+    async fn sync(&mut self) -> Result<()> {
+        if self.head.is_some() {
+            self.pull().await
+        } else {
+            self.clone_repo().await
+        }
+    }
+
+    async fn history(&mut self, since: &str) -> Result<Vec<CommitInfo>> {
+        let start =
+            self.head_commit().map(|head| head.to_string()).unwrap_or_else(|| self.branch.clone());
+        let mut commits = Vec::new();
+        for page in 1.. {
+            let endpoint =
+                format!("commits?sha={}&per_page={}&page={}", start, COMMITS_PER_PAGE, page);
+            let listed = self.request(Method::GET, &endpoint, None).await?;
+            let listed = listed.as_array().cloned().unwrap_or_default();
+            if listed.is_empty() {
+                break;
+            }
+            for commit in &listed {
+                if commit["sha"].as_str().is_some_and(|sha| sha.starts_with(since)) {
+                    return Ok(commits);
+                }
+                commits.push(commit_info(commit)?);
+            }
+        }
+        Err(AureaCoreError::Git(format!("Unknown revision '{}'", since)))
+    }
+
+    async fn commit(&mut self, message: &str, files: &[(PathBuf, String)]) -> Result<String> {
+        let parent = self
+            .head_commit()
+            .ok_or_else(|| AureaCoreError::Git("Repository not initialized".to_string()))?
+            .to_string();
+        let mut entries = Vec::with_capacity(files.len());
+        for (path, content) in files {
+            self.checkout_path(path)?;
+            entries.push(json!({
+                "path": path.to_string_lossy().replace('\\', "/"),
+                "mode": "100644",
+                "type": "blob",
+                "content": content,
+            }));
+        }
+
+        let parent_commit =
+            self.request(Method::GET, &format!("git/commits/{}", parent), None).await?;
+        let base_tree = string_field(&parent_commit["tree"], "sha")?;
+        let tree = self
+            .request(
+                Method::POST,
+                "git/trees",
+                Some(json!({ "base_tree": base_tree, "tree": entries })),
+            )
+            .await?;
+        let commit = self
+            .request(
+                Method::POST,
+                "git/commits",
+                Some(json!({
+                    "message": message,
+                    "tree": string_field(&tree, "sha")?,
+                    "parents": [parent],
+                })),
+            )
+            .await?;
+        let id = string_field(&commit, "sha")?;
+
+        for (path, content) in files {
+            let target = self.checkout_path(path)?;
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(target, content)?;
+            // The blob id is unknown locally, so the next pull downloads the file again
+            self.files.insert(path.to_string_lossy().replace('\\', "/"), String::new());
+        }
+        self.pending = Some(id.clone());
+        Ok(id)
+    }
+
+    async fn push(&mut self) -> Result<()> {
+        let Some(pending) = self.pending.clone() else {
+            return Ok(());
+        };
+        self.request(
+            Method::PATCH,
+            &format!("git/refs/heads/{}", self.branch),
+            Some(json!({ "sha": pending, "force": false })),
+        )
+        .await?;
+        self.head = self.pending.take();
+        Ok(())
+    }
+
+    fn current_commit(&self) -> Result<String> {
+        self.head_commit()
+            .map(str::to_string)
+            .ok_or_else(|| AureaCoreError::Git("Repository not initialized".to_string()))
+    }
+
+    fn reopen(&self) -> Box<dyn VcsProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use axum::extract::{Query, State};
+    use axum::routing::{get, patch, post};
+    use axum::{Json, Router};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::registry::ServiceRegistry;
+
+    #[test]
+    fn test_parse_repository() {
+        let work_dir = PathBuf::from("work");
+        for repository in ["acme/catalog", "https://github.com/acme/catalog.git"] {
+            let provider =
+                GitHubProvider::new(repository, "main".to_string(), work_dir.clone()).unwrap();
+            assert_eq!((provider.owner.as_str(), provider.repo.as_str()), ("acme", "catalog"));
+        }
+        assert!(GitHubProvider::new("catalog", "main".to_string(), work_dir).is_err());
+    }
+
+    /// Serves the endpoints of a one-commit repository, recording the pushed ref
+    async fn mock_github(pushed: Arc<Mutex<Option<String>>>) -> String {
+        let content = base64::engine::general_purpose::STANDARD.encode("name: orders\n");
+        let commit = |sha: &str, message: &str| {
+            json!({
+                "sha": sha,
+                "commit": {
+                    "message": message,
+                    "author": {
+                        "name": "Dev",
+                        "email": "dev@example.com",
+                        "date": "2026-01-02T03:04:05Z"
+                    }
+                }
+            })
+        };
+        let listed = json!([commit("c2", "Add payments\n\nDetails"), commit("c1", "Initial")]);
+        let repo = "/repos/acme/catalog";
+        let router =
+            Router::new()
+                .route(
+                    &format!("{repo}/commits/main"),
+                    get(|| async { Json(json!({"sha": "c1"})) }),
+                )
+                .route(
+                    &format!("{repo}/commits"),
+                    get(move |Query(query): Query<HashMap<String, String>>| async move {
+                        Json(if query["page"] == "1" { listed } else { json!([]) })
+                    }),
+                )
+                .route(
+                    &format!("{repo}/git/trees/c1"),
+                    get(|| async {
+                        Json(json!({"tree": [
+                            {"path": "services", "type": "tree", "sha": "t2"},
+                            {"path": "services/orders.yaml", "type": "blob", "sha": "b1"}
+                        ]}))
+                    }),
+                )
+                .route(
+                    &format!("{repo}/git/blobs/b1"),
+                    get(move || async move { Json(json!({"content": content})) }),
+                )
+                .route(
+                    &format!("{repo}/git/commits/c1"),
+                    get(|| async { Json(json!({"tree": {"sha": "t1"}})) }),
+                )
+                .route(&format!("{repo}/git/trees"), post(|| async { Json(json!({"sha": "t3"})) }))
+                .route(
+                    &format!("{repo}/git/commits"),
+                    post(|| async { Json(json!({"sha": "c2"})) }),
+                )
+                .route(
+                    &format!("{repo}/git/refs/heads/main"),
+                    patch(
+                        |State(pushed): State<Arc<Mutex<Option<String>>>>,
+                         Json(body): Json<Value>| async move {
+                            *pushed.lock().unwrap() = body["sha"].as_str().map(|s| s.to_string());
+                            Json(body)
+                        },
+                    ),
+                )
+                .with_state(pushed);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_github_round_trip() {
+        let pushed = Arc::new(Mutex::new(None));
+        let api_url = mock_github(pushed.clone()).await;
+        let temp_dir = TempDir::new().unwrap();
+        let mut provider =
+            GitHubProvider::new("acme/catalog", "main".to_string(), temp_dir.path().join("work"))
+                .unwrap()
+                .with_api_url(api_url)
+                .with_token("secret");
+        assert!(provider.pull().await.is_err());
+
+        provider.clone_repo().await.unwrap();
+        assert_eq!(provider.head_commit(), Some("c1"));
+        let orders = temp_dir.path().join("work/services/orders.yaml");
+        assert_eq!(std::fs::read_to_string(&orders).unwrap(), "name: orders\n");
+
+        let files = vec![(PathBuf::from("services/payments.yaml"), "name: payments\n".to_string())];
+        assert_eq!(provider.commit("Add payments", &files).await.unwrap(), "c2");
+        assert!(temp_dir.path().join("work/services/payments.yaml").exists());
+        assert!(provider.pull().await.unwrap_err().to_string().contains("not pushed"));
+
+        let history = provider.history("c1").await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].summary, "Add payments");
+        assert_eq!(history[0].author, "Dev");
+        assert!(provider.history("c9").await.is_err());
+
+        provider.push().await.unwrap();
+        assert_eq!(pushed.lock().unwrap().as_deref(), Some("c2"));
+        assert_eq!(provider.head_commit(), Some("c2"));
+
+        let outside = vec![(PathBuf::from("../escape.yaml"), String::new())];
+        assert!(provider.commit("Escape", &outside).await.is_err());
+    }
+
+    // Multi-threaded, so the mock keeps serving while the registry blocks on a sync
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_registry_syncs_through_github() {
+        let api_url = mock_github(Arc::new(Mutex::new(None))).await;
+        let temp_dir = TempDir::new().unwrap();
+        let work_dir = temp_dir.path().join("work");
+        let provider = GitHubProvider::new("acme/catalog", "main".to_string(), work_dir.clone())
+            .unwrap()
+            .with_api_url(api_url);
+
+        let registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), work_dir.clone()).unwrap();
+        let elsewhere =
+            GitHubProvider::new("acme/catalog", "main".to_string(), temp_dir.path().join("other"))
+                .unwrap();
+        assert!(registry.with_vcs(Box::new(elsewhere)).is_err());
+
+        let mut registry = ServiceRegistry::new(String::new(), "main".to_string(), work_dir)
+            .unwrap()
+            .with_vcs(Box::new(provider))
+            .unwrap();
+        assert!(!registry.sync().unwrap().is_stale());
+        assert!(temp_dir.path().join("work/services/orders.yaml").exists());
+        registry.save_snapshot().unwrap();
+
+        // Operations reading git objects need a local clone
+        assert_eq!(registry.changelog_since("c1").unwrap_err().code(), "not_implemented");
+    }
+}
@@ -0,0 +1,304 @@
+//! Golden tests comparing validation of a fixture catalog against a committed report
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::{RuleSet, ServiceRegistry, ValidationSummary};
+use crate::schema::root::Layout;
+use crate::schema::suppress::WarningCode;
+
+/// Environment variable that makes golden tests rewrite their golden files
+pub const UPDATE_GOLDEN_ENV: &str = "AUREACORE_UPDATE_GOLDEN";
+
+/// Golden file of a fixture directory, unless set otherwise
+pub const GOLDEN_FILE: &str = "expected.json";
+
+/// Validation results of a fixture catalog, free of timestamps and temporary paths
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GoldenReport {
+    /// Results per service
+    pub services: BTreeMap<String, GoldenService>,
+    /// Definitions that could not be read or parsed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub load_failures: Vec<String>,
+}
+
+/// Validation result of one fixture service
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GoldenService {
+    /// Whether the service validated successfully
+    pub valid: bool,
+    /// Error failing the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Warnings reported for the service
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<GoldenWarning>,
+    /// Warnings left out by suppressions of the service
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<GoldenWarning>,
+}
+
+/// A warning with the code it is classified as
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoldenWarning {
+    /// Code of the warning, e.g. `W002`
+    pub code: String,
+    /// Warning message
+    pub message: String,
+}
+
+impl GoldenWarning {
+    fn new(message: &str) -> Self {
+        Self { code: WarningCode::of(message).to_string(), message: message.to_string() }
+    }
+}
+
+/// Validates a fixture catalog and compares the results against its golden file
+///
+/// A fixture directory holds service definitions in `services/`, one `<service>.yaml`,
+/// `.yml` or `.json` each, resource definitions in `resources/`, and the policy files and
+/// `schemas/` directory of a catalog with the default layout. Results are compared against
+/// `expected.json` in the fixture directory. Set [`UPDATE_GOLDEN_ENV`] to write the results
+/// instead, then review and commit the golden file.
+#[derive(Debug, Clone)]
+pub struct GoldenTest {
+    fixture: PathBuf,
+    golden: PathBuf,
+    rules: Option<RuleSet>,
+    update: bool,
+}
+
+impl GoldenTest {
+    /// Creates a golden test of a fixture directory
+    pub fn new(fixture: impl Into<PathBuf>) -> Self {
+        let fixture = fixture.into();
+        Self {
+            golden: fixture.join(GOLDEN_FILE),
+            fixture,
+            rules: None,
+            update: std::env::var_os(UPDATE_GOLDEN_ENV).is_some(),
+        }
+    }
+
+    /// Compares against another golden file
+    pub fn with_golden_file(mut self, golden: impl Into<PathBuf>) -> Self {
+        self.golden = golden.into();
+        self
+    }
+
+    /// Validates with these rules instead of the policy files of the fixture
+    pub fn with_rule_set(mut self, rules: RuleSet) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Writes the golden file instead of comparing against it
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Validates the fixture catalog
+    pub fn run(&self) -> Result<GoldenReport> {
+        let work_dir = tempfile::TempDir::new()?;
+        let rules = match &self.rules {
+            Some(rules) => rules.clone(),
+            None => RuleSet::load(&self.fixture, &Layout::default())?,
+        };
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), work_dir.path().into())?
+                .with_rule_set(rules)?;
+
+        let resources = self.fixture.join(&Layout::default().resources_dir);
+        if resources.is_dir() {
+            let target = Layout::default().resources_path(work_dir.path());
+            fs::create_dir_all(&target)?;
+            for entry in fs::read_dir(&resources)? {
+                let path = entry?.path();
+                if path.is_file() {
+                    fs::copy(&path, target.join(path.file_name().unwrap()))?;
+                }
+            }
+        }
+        let definitions = self.fixture.canonicalize()?.join("services");
+        for path in fixture_definitions(&definitions)? {
+            let name = path.file_stem().unwrap().to_string_lossy();
+            let config = serde_json::json!({ "config_path": path });
+            fs::write(work_dir.path().join(super::config_file(&name)), config.to_string())?;
+        }
+
+        let loaded = registry.load_services()?;
+        let summary = registry.validate_all_services()?;
+        let scrub = |message: &str| {
+            message
+                .replace(&definitions.to_string_lossy().into_owned(), "$SERVICES")
+                .replace(&work_dir.path().to_string_lossy().into_owned(), "$WORK_DIR")
+        };
+        let mut report = golden_report(&summary, scrub);
+        report.load_failures = loaded.failed.iter().map(|failure| scrub(&failure.error)).collect();
+        Ok(report)
+    }
+
+    /// Validates the fixture catalog and compares the results against the golden file
+    ///
+    /// Fails with a line diff if they differ or the golden file is missing.
+    pub fn check(&self) -> Result<()> {
+        let actual = serde_json::to_string_pretty(&self.run()?)
+            .map_err(|e| AureaCoreError::Internal(format!("Failed to serialize report: {}", e)))?
+            + "\n";
+        if self.update {
+            fs::write(&self.golden, actual)?;
+            return Ok(());
+        }
+        let expected = match fs::read_to_string(&self.golden) {
+            Ok(expected) => expected,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        if expected == actual {
+            return Ok(());
+        }
+        Err(AureaCoreError::ValidationError(format!(
+            "Validation of {} differs from {} (set {} to update):\n{}",
+            self.fixture.display(),
+            self.golden.display(),
+            UPDATE_GOLDEN_ENV,
+            line_diff(&expected, &actual)
+        )))
+    }
+
+    /// Panics unless the results match the golden file, for use in `#[test]` functions
+    pub fn assert(&self) {
+        if let Err(e) = self.check() {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// Lists the definition files of a fixture, sorted by path
+fn fixture_definitions(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Err(AureaCoreError::Config(format!(
+            "Fixture has no services directory at {}",
+            dir.display()
+        )));
+    }
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml" || ext == "json")
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Builds the report of a validation run, scrubbing messages with `scrub`
+fn golden_report(summary: &ValidationSummary, scrub: impl Fn(&str) -> String) -> GoldenReport {
+    let mut services = BTreeMap::new();
+    for name in &summary.successful {
+        services.insert(name.clone(), GoldenService { valid: true, ..Default::default() });
+    }
+    for (name, error) in &summary.failed {
+        services.insert(
+            name.clone(),
+            GoldenService { error: Some(scrub(error)), ..Default::default() },
+        );
+    }
+    let warnings = |messages: &[String]| {
+        messages.iter().map(|message| GoldenWarning::new(&scrub(message))).collect::<Vec<_>>()
+    };
+    for (name, messages) in &summary.warnings {
+        services.entry(name.clone()).or_default().warnings = warnings(messages);
+    }
+    for (name, messages) in &summary.suppressed {
+        services.entry(name.clone()).or_default().suppressed = warnings(messages);
+    }
+    GoldenReport { services, load_failures: Vec::new() }
+}
+
+/// Diffs two texts line by line, prefixing removed lines with `-` and added ones with `+`
+fn line_diff(expected: &str, actual: &str) -> String {
+    let old: Vec<_> = expected.lines().collect();
+    let new: Vec<_> = actual.lines().collect();
+    // Longest common subsequence lengths of the suffixes
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut diff = String::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff += &format!("  {}\n", old[i]);
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            diff += &format!("- {}\n", old[i]);
+            i += 1;
+        } else {
+            diff += &format!("+ {}\n", new[j]);
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden_test() {
+        let fixture = tempfile::TempDir::new().unwrap();
+        let services = fixture.path().join("services");
+        fs::create_dir_all(&services).unwrap();
+        let definition = |name: &str, extra: &str| {
+            format!(
+                "name: {}\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n{}",
+                name, extra
+            )
+        };
+        fs::write(services.join("billing.yaml"), definition("billing", "")).unwrap();
+        fs::write(
+            services.join("orders.yaml"),
+            definition("orders", "dependencies:\n  - service: ledger\n    required: false\n"),
+        )
+        .unwrap();
+        fs::write(services.join("search.yaml"), "name: search\n").unwrap();
+
+        let report = GoldenTest::new(fixture.path()).run().unwrap();
+        assert!(report.services["billing"].valid);
+        assert_eq!(report.services["orders"].warnings[0].code, "W002");
+        assert!(!report.services["search"].valid);
+
+        let test = GoldenTest::new(fixture.path()).with_update(false);
+        assert!(test.check().unwrap_err().to_string().contains("+ {"));
+        test.clone().with_update(true).check().unwrap();
+        let golden = fs::read_to_string(fixture.path().join(GOLDEN_FILE)).unwrap();
+        assert_eq!(serde_json::from_str::<GoldenReport>(&golden).unwrap(), report);
+        test.check().unwrap();
+
+        fs::write(services.join("billing.yaml"), "name: billing\n").unwrap();
+        let diff = test.check().unwrap_err().to_string();
+        assert!(diff.contains("-       \"valid\": true"), "{}", diff);
+        assert!(diff.contains("+       \"valid\": false"), "{}", diff);
+    }
+
+    #[test]
+    fn test_line_diff() {
+        assert_eq!(line_diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n- b\n+ x\n  c\n");
+        assert_eq!(line_diff("", "a\n"), "+ a\n");
+    }
+}
@@ -0,0 +1,218 @@
+//! Exportable views of the dependency graph at service or system level
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::registry::dependency::DependencyGraph;
+use crate::schema::resource::ResourceKind;
+use crate::schema::service::Interaction;
+
+/// Granularity of an exported dependency graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphLevel {
+    /// One node per service
+    Service,
+    /// One node per system, with edges derived from service edges
+    System,
+}
+
+impl FromStr for GraphLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "service" => Ok(GraphLevel::Service),
+            "system" => Ok(GraphLevel::System),
+            other => {
+                Err(format!("Unknown graph level '{}', expected 'service' or 'system'", other))
+            }
+        }
+    }
+}
+
+/// System and domain a service is grouped into
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceGroup {
+    /// System the service belongs to
+    pub system: Option<String>,
+    /// Domain the service belongs to
+    pub domain: Option<String>,
+    /// Kind of resource, if the node is a resource rather than a service
+    pub resource: Option<ResourceKind>,
+}
+
+/// A node of an exported graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphNode {
+    /// Service name, or system name for system-level graphs
+    pub id: String,
+    /// System the node belongs to
+    pub system: Option<String>,
+    /// Domain the node belongs to
+    pub domain: Option<String>,
+    /// Services represented by the node
+    pub services: Vec<String>,
+    /// Kind of resource, if the node is a single resource rather than services
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<ResourceKind>,
+}
+
+/// A dependency between two nodes of an exported graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphEdge {
+    /// Dependent node
+    pub from: String,
+    /// Node depended upon
+    pub to: String,
+    /// Whether any underlying service dependency is required
+    pub required: bool,
+    /// Kinds of interaction of the underlying service dependencies, sorted
+    pub interactions: Vec<Interaction>,
+}
+
+/// A dependency graph prepared for export
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphExport {
+    /// Granularity of the graph
+    pub level: GraphLevel,
+    /// Nodes sorted by id
+    pub nodes: Vec<GraphNode>,
+    /// Edges sorted by source and target
+    pub edges: Vec<GraphEdge>,
+}
+
+impl GraphExport {
+    /// Builds an export of a service dependency graph at the given level
+    ///
+    /// At system level, services without a system are kept as their own node and
+    /// dependencies between services of the same system are omitted.
+    pub fn build(
+        graph: &DependencyGraph,
+        groups: &HashMap<String, ServiceGroup>,
+        level: GraphLevel,
+    ) -> Self {
+        let no_group = ServiceGroup::default();
+        let group_of = |service: &str| groups.get(service).unwrap_or(&no_group);
+        let node_id = |service: &str| match (level, &group_of(service).system) {
+            (GraphLevel::System, Some(system)) => system.clone(),
+            _ => service.to_string(),
+        };
+
+        let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+        let mut edges: BTreeMap<(String, String), (bool, BTreeSet<Interaction>)> = BTreeMap::new();
+        for (service, dependencies) in &graph.adjacency_list {
+            let id = node_id(service);
+            let group = group_of(service);
+            let node = nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+                id: id.clone(),
+                system: group.system.clone(),
+                domain: None,
+                services: Vec::new(),
+                resource: group.resource.filter(|_| id == *service),
+            });
+            node.services.push(service.clone());
+            if node.domain.is_none() {
+                node.domain = group.domain.clone();
+            }
+
+            for (dependency, metadata) in dependencies {
+                let to = node_id(dependency);
+                if to != id {
+                    let (required, interactions) = edges.entry((id.clone(), to)).or_default();
+                    *required |= metadata.required;
+                    interactions.insert(metadata.interaction);
+                }
+            }
+        }
+
+        for node in nodes.values_mut() {
+            node.services.sort();
+        }
+
+        Self {
+            level,
+            nodes: nodes.into_values().collect(),
+            edges: edges
+                .into_iter()
+                .map(|((from, to), (required, interactions))| GraphEdge {
+                    from,
+                    to,
+                    required,
+                    interactions: interactions.into_iter().collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::dependency::EdgeMetadata;
+
+    fn edge(required: bool) -> EdgeMetadata {
+        EdgeMetadata { required, version_constraint: None, interaction: Default::default() }
+    }
+
+    fn group(system: &str, domain: &str) -> ServiceGroup {
+        ServiceGroup {
+            system: Some(system.to_string()),
+            domain: Some(domain.to_string()),
+            resource: None,
+        }
+    }
+
+    #[test]
+    fn test_system_level_aggregation() {
+        let mut graph = DependencyGraph::new();
+        graph.add_edge("checkout-api".into(), "checkout-db".into(), edge(true));
+        let event = EdgeMetadata { interaction: Interaction::AsyncEvent, ..edge(false) };
+        graph.add_edge("checkout-api".into(), "invoices".into(), event);
+        graph.add_edge("checkout-worker".into(), "payouts".into(), edge(true));
+        graph.add_edge("checkout-worker".into(), "audit".into(), edge(true));
+
+        let groups = HashMap::from([
+            ("checkout-api".to_string(), group("checkout", "commerce")),
+            ("checkout-db".to_string(), group("checkout", "commerce")),
+            ("checkout-worker".to_string(), group("checkout", "commerce")),
+            ("invoices".to_string(), group("billing", "finance")),
+            ("payouts".to_string(), group("billing", "finance")),
+        ]);
+
+        let export = GraphExport::build(&graph, &groups, GraphLevel::System);
+        let ids: Vec<_> = export.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["audit", "billing", "checkout"]);
+        assert_eq!(export.nodes[1].domain.as_deref(), Some("finance"));
+        assert_eq!(
+            export.nodes[2].services,
+            vec!["checkout-api", "checkout-db", "checkout-worker"]
+        );
+
+        // Service edges collapse into one system edge, required if any of them is
+        let sync = Interaction::SyncCall;
+        assert_eq!(
+            export.edges,
+            vec![
+                GraphEdge {
+                    from: "checkout".into(),
+                    to: "audit".into(),
+                    required: true,
+                    interactions: vec![sync],
+                },
+                GraphEdge {
+                    from: "checkout".into(),
+                    to: "billing".into(),
+                    required: true,
+                    interactions: vec![sync, Interaction::AsyncEvent],
+                },
+            ]
+        );
+
+        let export = GraphExport::build(&graph, &groups, GraphLevel::Service);
+        assert_eq!(export.nodes.len(), 6);
+        assert_eq!(export.edges.len(), 4);
+    }
+}
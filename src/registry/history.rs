@@ -0,0 +1,152 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::registry::service::{ServiceState, ServiceStatus};
+
+/// Default number of status transitions kept per service
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// What caused a service status to be recomputed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusTrigger {
+    /// The service was registered through the registry
+    Register,
+    /// The service was loaded from disk
+    Load,
+    /// The whole catalog was validated
+    Validation,
+    /// The service was re-registered to match the repository
+    Reconcile,
+    /// The service was archived
+    Archive,
+    /// The service was restored from the archive
+    Unarchive,
+}
+
+impl fmt::Display for StatusTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusTrigger::Register => write!(f, "register"),
+            StatusTrigger::Load => write!(f, "load"),
+            StatusTrigger::Validation => write!(f, "validation"),
+            StatusTrigger::Reconcile => write!(f, "reconcile"),
+            StatusTrigger::Archive => write!(f, "archive"),
+            StatusTrigger::Unarchive => write!(f, "unarchive"),
+        }
+    }
+}
+
+/// A recorded change of a service's status
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusTransition {
+    /// State the service moved into
+    pub state: ServiceState,
+    /// When the transition was recorded
+    pub timestamp: DateTime<Utc>,
+    /// What caused the status to be recomputed
+    pub trigger: StatusTrigger,
+    /// Error message of the new status, if any
+    pub error_message: Option<String>,
+}
+
+/// Bounded per-service history of status transitions
+#[derive(Debug, Clone)]
+pub struct StatusHistory {
+    limit: usize,
+    entries: HashMap<String, VecDeque<StatusTransition>>,
+}
+
+impl Default for StatusHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl StatusHistory {
+    /// Creates a history keeping at most `limit` transitions per service
+    pub fn new(limit: usize) -> Self {
+        Self { limit: limit.max(1), entries: HashMap::new() }
+    }
+
+    /// Records a status if it differs from the last recorded one for the service
+    ///
+    /// Returns whether a transition was recorded.
+    pub fn record(
+        &mut self,
+        service: &str,
+        status: &ServiceStatus,
+        trigger: StatusTrigger,
+    ) -> bool {
+        let entries = self.entries.entry(service.to_string()).or_default();
+        if let Some(last) = entries.back() {
+            if last.state == status.state && last.error_message == status.error_message {
+                return false;
+            }
+        }
+
+        if entries.len() == self.limit {
+            entries.pop_front();
+        }
+        entries.push_back(StatusTransition {
+            state: status.state.clone(),
+            timestamp: status.last_checked,
+            trigger,
+            error_message: status.error_message.clone(),
+        });
+        true
+    }
+
+    /// Gets the transitions of a service, oldest first
+    pub fn get(&self, service: &str) -> Vec<StatusTransition> {
+        self.entries.get(service).map(|e| e.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Drops the history of a service
+    pub fn remove(&mut self, service: &str) {
+        self.entries.remove(service);
+    }
+
+    /// Keeps only the histories of the services `keep` accepts
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.entries.retain(|service, _| keep(service));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_records_only_transitions() {
+        let mut history = StatusHistory::default();
+        let active = ServiceStatus::new(ServiceState::Active);
+        let failing = ServiceStatus::new(ServiceState::Error).with_error("boom".to_string());
+
+        assert!(history.record("billing", &active, StatusTrigger::Register));
+        assert!(!history.record("billing", &active, StatusTrigger::Validation));
+        assert!(history.record("billing", &failing, StatusTrigger::Validation));
+
+        let transitions = history.get("billing");
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[1].state, ServiceState::Error);
+        assert_eq!(transitions[1].trigger, StatusTrigger::Validation);
+        assert_eq!(transitions[1].error_message.as_deref(), Some("boom"));
+        assert!(history.get("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = StatusHistory::new(2);
+        for i in 0..5 {
+            let status = ServiceStatus::new(ServiceState::Error).with_error(format!("error {}", i));
+            history.record("billing", &status, StatusTrigger::Validation);
+        }
+
+        let transitions = history.get("billing");
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].error_message.as_deref(), Some("error 3"));
+        assert_eq!(transitions[1].error_message.as_deref(), Some("error 4"));
+    }
+}
@@ -0,0 +1,82 @@
+//! Stable machine IDs of services, surviving renames
+
+use std::fmt;
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::error::{AureaCoreError, Result};
+
+/// How a registry identifies services besides their names
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdStrategy {
+    /// Services are identified by name only; IDs given in catalog configs are kept
+    #[default]
+    Names,
+    /// Services without an ID get a UUID v7 when registered
+    UuidV7,
+}
+
+impl fmt::Display for IdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdStrategy::Names => write!(f, "names"),
+            IdStrategy::UuidV7 => write!(f, "uuid-v7"),
+        }
+    }
+}
+
+impl FromStr for IdStrategy {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "names" => Ok(IdStrategy::Names),
+            "uuid-v7" | "uuid" => Ok(IdStrategy::UuidV7),
+            other => Err(AureaCoreError::Config(format!(
+                "Unknown ID strategy '{}', expected names or uuid-v7",
+                other
+            ))),
+        }
+    }
+}
+
+/// Reads the ID of a catalog config, if it has a valid one
+pub(crate) fn config_id(config: &serde_json::Value) -> Result<Option<Uuid>> {
+    match config.get("id") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(id) => serde_json::from_value(id.clone())
+            .map(Some)
+            .map_err(|e| AureaCoreError::Config(format!("Invalid service ID {}: {}", id, e))),
+    }
+}
+
+/// Sets the ID of a catalog config, keeping the rest of it as written
+pub(crate) fn with_config_id(mut config: serde_json::Value, id: Uuid) -> String {
+    if let Some(object) = config.as_object_mut() {
+        object.insert("id".to_string(), serde_json::Value::String(id.to_string()));
+    }
+    config.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_ids() {
+        let config = serde_json::json!({"config_path": "orders.yaml"});
+        assert_eq!(config_id(&config).unwrap(), None);
+
+        let id = Uuid::now_v7();
+        let with_id: serde_json::Value = serde_json::from_str(&with_config_id(config, id)).unwrap();
+        assert_eq!(with_id["config_path"], "orders.yaml");
+        assert_eq!(config_id(&with_id).unwrap(), Some(id));
+        assert_eq!(id.get_version_num(), 7);
+
+        let invalid = serde_json::json!({"id": "not-a-uuid"});
+        assert!(config_id(&invalid).unwrap_err().to_string().contains("Invalid service ID"));
+        assert_eq!("uuid-v7".parse::<IdStrategy>().unwrap(), IdStrategy::UuidV7);
+        assert!("serial".parse::<IdStrategy>().is_err());
+    }
+}
@@ -0,0 +1,733 @@
+//! Tracker issues for validation problems that persist, one per service
+//!
+//! Issues are filed with the [`ISSUE_LABEL`] label and recognized by their title, so
+//! later runs update or close them instead of filing duplicates.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::ValidationSummary;
+use crate::schema::suppress::WarningCode;
+
+/// Base URL of the public GitHub API
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Base URL of gitlab.com
+const GITLAB_URL: &str = "https://gitlab.com";
+
+/// Label of the issues filed for validation problems
+pub const ISSUE_LABEL: &str = "aureacore";
+
+/// Validations in a row a problem must be reported in before an issue is filed
+pub const DEFAULT_ISSUE_AFTER_RUNS: u32 = 3;
+
+/// File inside the `.git` directory counting how long each problem has been reported
+pub(crate) const ISSUE_STREAKS_FILE: &str = "aureacore-issue-streaks.json";
+
+/// Start of the title of every filed issue, followed by the service name
+const TITLE_PREFIX: &str = "Validation failing for ";
+
+/// Key grouping the errors failing a service, next to the warning codes
+const FAILURE_KEY: &str = "failure";
+
+/// Issues listed per page
+const ISSUES_PER_PAGE: usize = 100;
+
+/// An open issue filed for validation problems
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerIssue {
+    /// Number of the issue within its repository or project
+    pub id: u64,
+    /// Title of the issue
+    pub title: String,
+    /// Description of the issue
+    pub body: String,
+}
+
+/// Content of an issue to file or update
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueDraft {
+    /// Title of the issue
+    pub title: String,
+    /// Description of the issue, in Markdown
+    pub body: String,
+    /// Usernames the issue is assigned to
+    pub assignees: Vec<String>,
+}
+
+/// Files and closes issues on an issue tracker
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Lists the open issues labeled [`ISSUE_LABEL`]
+    async fn open_issues(&self) -> Result<Vec<TrackerIssue>>;
+
+    /// Files an issue labeled [`ISSUE_LABEL`], returning its number
+    async fn create_issue(&self, draft: &IssueDraft) -> Result<u64>;
+
+    /// Replaces the title, description and assignees of an issue
+    async fn update_issue(&self, id: u64, draft: &IssueDraft) -> Result<()>;
+
+    /// Closes an issue
+    async fn close_issue(&self, id: u64) -> Result<()>;
+}
+
+/// Services whose issue was filed, updated or closed by a sync
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IssueSyncReport {
+    /// Services an issue was filed for
+    pub opened: Vec<String>,
+    /// Services whose issue now lists other problems
+    pub updated: Vec<String>,
+    /// Services whose issue was closed
+    pub closed: Vec<String>,
+}
+
+/// Validations in a row each problem of a service was reported in, by service and problem
+pub(crate) type Streaks = BTreeMap<String, BTreeMap<String, u32>>;
+
+/// Reads the problem streaks; a missing or unreadable file starts over
+pub(crate) fn read_streaks(path: &Path) -> Streaks {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Streaks::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|err| {
+        tracing::warn!("Ignoring unreadable issue streaks {}: {}", path.display(), err);
+        Streaks::new()
+    })
+}
+
+/// Writes the problem streaks
+pub(crate) fn write_streaks(path: &Path, streaks: &Streaks) -> Result<()> {
+    let content = serde_json::to_string_pretty(streaks)
+        .map_err(|e| AureaCoreError::Internal(format!("Failed to encode issue streaks: {}", e)))?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Groups the problems a summary reports for a service by warning code
+///
+/// Errors failing the service are grouped under their own key. Messages are deduplicated.
+pub(crate) fn problems(
+    summary: &ValidationSummary,
+    service: &str,
+) -> BTreeMap<String, Vec<String>> {
+    let mut problems: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let failures = summary.failed.iter().filter(|(name, _)| name == service);
+    let failures = failures.map(|(_, error)| (FAILURE_KEY.to_string(), error));
+    let warnings = summary.warnings.get(service).into_iter().flatten();
+    let warnings = warnings.map(|warning| (WarningCode::of(warning).code(), warning));
+    for (key, message) in failures.chain(warnings) {
+        let messages = problems.entry(key).or_default();
+        if !messages.contains(message) {
+            messages.push(message.clone());
+        }
+    }
+    problems
+}
+
+/// Gets the title of the issue filed for a service
+pub(crate) fn issue_title(service: &str) -> String {
+    format!("{}{}", TITLE_PREFIX, service)
+}
+
+/// Gets the service an issue was filed for, if it was filed for validation problems
+pub(crate) fn issue_service(title: &str) -> Option<&str> {
+    title.strip_prefix(TITLE_PREFIX).filter(|service| !service.is_empty())
+}
+
+/// Describes the persistent problems of a service, errors first
+pub(crate) fn issue_body(
+    service: &str,
+    problems: &BTreeMap<String, Vec<String>>,
+    after_runs: u32,
+) -> String {
+    let mut body = format!(
+        "Validation of `{}` reported these problems in {} or more runs in a row:\n",
+        service, after_runs
+    );
+    let failures = problems.get_key_value(FAILURE_KEY);
+    let warnings = problems.iter().filter(|(key, _)| key.as_str() != FAILURE_KEY);
+    for (key, messages) in failures.into_iter().chain(warnings) {
+        let heading = if key == FAILURE_KEY { "Errors" } else { key.as_str() };
+        body.push_str(&format!("\n**{}**\n", heading));
+        for message in messages {
+            body.push_str(&format!("- {}\n", message));
+        }
+    }
+    body.push_str(&format!(
+        "\nThis issue is maintained by aureacore and closes once `{}` validates without \
+         warnings.\n",
+        service
+    ));
+    body
+}
+
+/// Gets the user to assign the issues of a service to from its owner
+///
+/// Only owners naming a single user, e.g. `@jdoe`, are assigned; team names are not.
+pub(crate) fn assignee(owner: &str) -> Option<String> {
+    let user = owner.trim().trim_start_matches('@');
+    (!user.is_empty() && !user.contains(char::is_whitespace)).then(|| user.to_string())
+}
+
+/// Sends a request, failing on answers other than 2xx
+async fn send(request: reqwest::RequestBuilder, tracker: &str, url: &str) -> Result<Value> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AureaCoreError::Service(format!("{} {}: {}", tracker, url, e)))?;
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(AureaCoreError::Service(format!(
+            "{} {} returned HTTP {}: {}",
+            tracker,
+            url,
+            status.as_u16(),
+            message.trim()
+        )));
+    }
+    response.json().await.map_err(|e| {
+        AureaCoreError::Service(format!("Invalid {} response from {}: {}", tracker, url, e))
+    })
+}
+
+/// Reads the issues of a page listing issues
+fn listed_issues(page: &Value, id: &str, body: &str) -> Vec<TrackerIssue> {
+    page.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|issue| issue.get("pull_request").is_none())
+        .filter_map(|issue| {
+            Some(TrackerIssue {
+                id: issue[id].as_u64()?,
+                title: issue["title"].as_str()?.to_string(),
+                body: issue[body].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Files issues in a GitHub repository through the REST API
+pub struct GitHubIssues {
+    client: reqwest::Client,
+    api_url: String,
+    owner: String,
+    repo: String,
+    token: String,
+}
+
+impl GitHubIssues {
+    /// Creates a tracker for a `https://github.com/<owner>/<repo>` URL or `<owner>/<repo>`
+    /// slug, authenticating with a token allowed to write issues
+    pub fn new(repository: &str, token: impl Into<String>) -> Result<Self> {
+        let slug = repository
+            .trim_start_matches("https://")
+            .trim_start_matches("github.com/")
+            .trim_end_matches('/')
+            .trim_end_matches(".git");
+        let (owner, repo) = slug
+            .split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty() && !repo.contains('/'))
+            .ok_or_else(|| {
+                AureaCoreError::Config(format!("Invalid GitHub repository '{}'", repository))
+            })?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_url: GITHUB_API_URL.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            token: token.into(),
+        })
+    }
+
+    /// Talks to a GitHub Enterprise server, or a test double, instead of github.com
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sends a request to an endpoint of the repository, e.g. `issues/42`
+    async fn request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+        body: Option<Value>,
+    ) -> Result<Value> {
+        let url = format!("{}/repos/{}/{}/{}", self.api_url, self.owner, self.repo, endpoint);
+        let mut request = self
+            .client
+            .request(method, &url)
+            .query(query)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header(reqwest::header::USER_AGENT, "aureacore")
+            .bearer_auth(&self.token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        send(request, "GitHub", &url).await
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GitHubIssues {
+    async fn open_issues(&self) -> Result<Vec<TrackerIssue>> {
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let query = [
+                ("labels", ISSUE_LABEL.to_string()),
+                ("state", "open".to_string()),
+                ("per_page", ISSUES_PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ];
+            let listed = self.request(Method::GET, "issues", &query, None).await?;
+            let count = listed.as_array().map_or(0, Vec::len);
+            // Pull requests count towards the page size but are left out
+            issues.extend(listed_issues(&listed, "number", "body"));
+            if count < ISSUES_PER_PAGE {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn create_issue(&self, draft: &IssueDraft) -> Result<u64> {
+        let body = json!({
+            "title": draft.title,
+            "body": draft.body,
+            "labels": [ISSUE_LABEL],
+            "assignees": draft.assignees,
+        });
+        let issue = self.request(Method::POST, "issues", &[], Some(body)).await?;
+        issue["number"].as_u64().ok_or_else(|| {
+            AureaCoreError::Service("GitHub didn't return the number of the issue".to_string())
+        })
+    }
+
+    async fn update_issue(&self, id: u64, draft: &IssueDraft) -> Result<()> {
+        let body =
+            json!({ "title": draft.title, "body": draft.body, "assignees": draft.assignees });
+        self.request(Method::PATCH, &format!("issues/{}", id), &[], Some(body)).await?;
+        Ok(())
+    }
+
+    async fn close_issue(&self, id: u64) -> Result<()> {
+        let body = json!({ "state": "closed", "state_reason": "completed" });
+        self.request(Method::PATCH, &format!("issues/{}", id), &[], Some(body)).await?;
+        Ok(())
+    }
+}
+
+/// Files issues in a GitLab project through the REST API
+pub struct GitLabIssues {
+    client: reqwest::Client,
+    base_url: String,
+    project: String,
+    token: String,
+}
+
+impl GitLabIssues {
+    /// Creates a tracker for a project, given by path such as `group/catalog` or by id,
+    /// authenticating with a token with the `api` scope
+    pub fn new(project: &str, token: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: GITLAB_URL.to_string(),
+            project: project.trim_matches('/').replace('/', "%2F"),
+            token: token.into(),
+        }
+    }
+
+    /// Talks to a self-managed instance, or a test double, instead of gitlab.com
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sends a request to an endpoint of the API, e.g. `users`
+    async fn request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+        body: Option<Value>,
+    ) -> Result<Value> {
+        let url = format!("{}/api/v4/{}", self.base_url, endpoint);
+        let mut request =
+            self.client.request(method, &url).query(query).header("PRIVATE-TOKEN", &self.token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        send(request, "GitLab", &url).await
+    }
+
+    /// Looks up the ids of users by username, leaving out unknown users
+    async fn user_ids(&self, usernames: &[String]) -> Result<Vec<u64>> {
+        let mut ids = Vec::new();
+        for username in usernames {
+            let users =
+                self.request(Method::GET, "users", &[("username", username.clone())], None).await?;
+            match users.get(0).and_then(|user| user["id"].as_u64()) {
+                Some(id) => ids.push(id),
+                None => tracing::warn!("Not assigning unknown GitLab user '{}'", username),
+            }
+        }
+        Ok(ids)
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GitLabIssues {
+    async fn open_issues(&self) -> Result<Vec<TrackerIssue>> {
+        let endpoint = format!("projects/{}/issues", self.project);
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let query = [
+                ("labels", ISSUE_LABEL.to_string()),
+                ("state", "opened".to_string()),
+                ("per_page", ISSUES_PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ];
+            let listed = self.request(Method::GET, &endpoint, &query, None).await?;
+            let count = listed.as_array().map_or(0, Vec::len);
+            issues.extend(listed_issues(&listed, "iid", "description"));
+            if count < ISSUES_PER_PAGE {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn create_issue(&self, draft: &IssueDraft) -> Result<u64> {
+        let body = json!({
+            "title": draft.title,
+            "description": draft.body,
+            "labels": ISSUE_LABEL,
+            "assignee_ids": self.user_ids(&draft.assignees).await?,
+        });
+        let endpoint = format!("projects/{}/issues", self.project);
+        let issue = self.request(Method::POST, &endpoint, &[], Some(body)).await?;
+        issue["iid"].as_u64().ok_or_else(|| {
+            AureaCoreError::Service("GitLab didn't return the number of the issue".to_string())
+        })
+    }
+
+    async fn update_issue(&self, id: u64, draft: &IssueDraft) -> Result<()> {
+        let body = json!({
+            "title": draft.title,
+            "description": draft.body,
+            "assignee_ids": self.user_ids(&draft.assignees).await?,
+        });
+        let endpoint = format!("projects/{}/issues/{}", self.project, id);
+        self.request(Method::PUT, &endpoint, &[], Some(body)).await?;
+        Ok(())
+    }
+
+    async fn close_issue(&self, id: u64) -> Result<()> {
+        let endpoint = format!("projects/{}/issues/{}", self.project, id);
+        self.request(Method::PUT, &endpoint, &[], Some(json!({ "state_event": "close" }))).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use axum::extract::{Path as UrlPath, Query, State};
+    use axum::http::HeaderMap;
+    use axum::routing::{get, patch, put};
+    use axum::{Json, Router};
+
+    use super::*;
+
+    #[test]
+    fn test_problems_and_body() {
+        let mut summary = ValidationSummary::new();
+        summary.failed.push(("billing".to_string(), "Missing dependency 'ledger'".to_string()));
+        let eol = "Runtime java 8 is past its end of life".to_string();
+        summary.warnings.insert("billing".to_string(), vec![eol.clone(), eol.clone()]);
+
+        assert!(problems(&summary, "search").is_empty());
+        let found = problems(&summary, "billing");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found["W004"], [eol]);
+
+        let body = issue_body("billing", &found, 3);
+        assert!(body.starts_with("Validation of `billing` reported these problems in 3"));
+        let errors = body.find("**Errors**\n- Missing dependency 'ledger'").unwrap();
+        assert!(errors < body.find("**W004**").unwrap());
+
+        assert_eq!(issue_service(&issue_title("billing")), Some("billing"));
+        assert_eq!(issue_service("Validation failing for "), None);
+        assert_eq!(assignee(" @jdoe "), Some("jdoe".to_string()));
+        assert_eq!(assignee("Platform Team"), None);
+    }
+
+    /// Requests received by the test double, as `<method> <issue> <body>`
+    type Requests = Arc<Mutex<Vec<String>>>;
+
+    fn record(requests: &Requests, method: &str, id: &str, body: &Value) {
+        requests.lock().unwrap().push(format!("{} {} {}", method, id, body));
+    }
+
+    #[tokio::test]
+    async fn test_trackers() {
+        let requests = Requests::default();
+        let listed = json!([
+            {
+                "number": 7,
+                "iid": 7,
+                "title": "Validation failing for billing",
+                "body": "old",
+                "description": "old",
+            },
+            { "number": 8, "title": "Bump deps", "pull_request": {} },
+        ]);
+        let router = Router::new()
+            .route(
+                "/repos/acme/catalog/issues",
+                get({
+                    let listed = listed.clone();
+                    move |headers: HeaderMap| async move {
+                        assert_eq!(headers["authorization"], "Bearer s3cret");
+                        Json(listed)
+                    }
+                })
+                .post(
+                    |State(requests): State<Requests>, Json(body): Json<Value>| async move {
+                        record(&requests, "POST", "-", &body);
+                        Json(json!({ "number": 9 }))
+                    },
+                ),
+            )
+            .route(
+                "/repos/acme/catalog/issues/{id}",
+                patch(
+                    |State(requests): State<Requests>,
+                     UrlPath(id): UrlPath<String>,
+                     Json(body): Json<Value>| async move {
+                        record(&requests, "PATCH", &id, &body);
+                        Json(json!({}))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/acme%2Fcatalog/issues",
+                get(move |headers: HeaderMap| async move {
+                    assert_eq!(headers["private-token"], "s3cret");
+                    Json(listed)
+                })
+                .post(
+                    |State(requests): State<Requests>, Json(body): Json<Value>| async move {
+                        record(&requests, "POST", "-", &body);
+                        Json(json!({ "iid": 9 }))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/acme%2Fcatalog/issues/{id}",
+                put(
+                    |State(requests): State<Requests>,
+                     UrlPath(id): UrlPath<String>,
+                     Json(body): Json<Value>| async move {
+                        record(&requests, "PUT", &id, &body);
+                        Json(json!({}))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/users",
+                get(|axum::extract::RawQuery(query): axum::extract::RawQuery| async move {
+                    match query.as_deref() {
+                        Some("username=jdoe") => Json(json!([{ "id": 42 }])),
+                        _ => Json(json!([])),
+                    }
+                }),
+            )
+            .with_state(requests.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let github = GitHubIssues::new("https://github.com/acme/catalog", "s3cret")
+            .unwrap()
+            .with_api_url(&base);
+        let gitlab = GitLabIssues::new("acme/catalog", "s3cret").with_base_url(&base);
+        let draft = IssueDraft {
+            title: issue_title("billing"),
+            body: "new".to_string(),
+            assignees: vec!["jdoe".to_string(), "ghost".to_string()],
+        };
+        for tracker in [&github as &dyn IssueTracker, &gitlab] {
+            let issues = tracker.open_issues().await.unwrap();
+            assert_eq!(issues.len(), 1);
+            assert_eq!((issues[0].id, issues[0].body.as_str()), (7, "old"));
+            assert_eq!(tracker.create_issue(&draft).await.unwrap(), 9);
+            tracker.update_issue(7, &draft).await.unwrap();
+            tracker.close_issue(7).await.unwrap();
+        }
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 6);
+        assert!(requests[0].starts_with("POST - "));
+        assert!(requests[0].contains(r#""assignees":["jdoe","ghost"]"#));
+        assert!(requests[0].contains(r#""labels":["aureacore"]"#));
+        assert!(requests[2].contains(r#""state":"closed""#));
+        // GitLab assigns by user id, leaving out unknown users
+        assert!(requests[3].contains(r#""assignee_ids":[42]"#));
+        assert!(requests[4].starts_with("PUT 7 "));
+        assert!(requests[5].contains(r#""state_event":"close""#));
+        assert!(GitHubIssues::new("acme", "s3cret").is_err());
+    }
+
+    /// Issues of the GitLab test double, in the order they were filed
+    type GitLabState = Arc<Mutex<Vec<Value>>>;
+
+    /// Serves the issues and users endpoints of a GitLab project, keeping issues in memory
+    async fn gitlab_double(issues: GitLabState) -> String {
+        let router = Router::new()
+            .route(
+                "/api/v4/projects/acme%2Fcatalog/issues",
+                get(
+                    |State(issues): State<GitLabState>,
+                     Query(query): Query<HashMap<String, String>>| async move {
+                        assert_eq!(query["labels"], ISSUE_LABEL);
+                        let issues = issues.lock().unwrap();
+                        let listed: Vec<&Value> = issues
+                            .iter()
+                            .filter(|issue| issue["state"] == query["state"])
+                            .collect();
+                        Json(json!(listed))
+                    },
+                )
+                .post(
+                    |State(issues): State<GitLabState>, Json(mut body): Json<Value>| async move {
+                        let mut issues = issues.lock().unwrap();
+                        body["iid"] = json!(issues.len() + 1);
+                        body["state"] = json!("opened");
+                        issues.push(body.clone());
+                        Json(body)
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/acme%2Fcatalog/issues/{iid}",
+                put(
+                    |State(issues): State<GitLabState>,
+                     UrlPath(iid): UrlPath<usize>,
+                     Json(body): Json<Value>| async move {
+                        let mut issues = issues.lock().unwrap();
+                        let issue = &mut issues[iid - 1];
+                        for (key, value) in body.as_object().unwrap() {
+                            match (key.as_str(), value.as_str()) {
+                                ("state_event", Some("close")) => issue["state"] = json!("closed"),
+                                _ => issue[key] = value.clone(),
+                            }
+                        }
+                        Json(issue.clone())
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/users",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    match query["username"].as_str() {
+                        "jdoe" => Json(json!([{ "id": 42, "username": "jdoe" }])),
+                        _ => Json(json!([])),
+                    }
+                }),
+            )
+            .with_state(issues);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        base
+    }
+
+    #[tokio::test]
+    async fn test_registry_files_gitlab_issues() {
+        let issues = GitLabState::default();
+        let base = gitlab_double(issues.clone()).await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let work_dir = temp_dir.path().join("config");
+        std::fs::create_dir_all(work_dir.join(".git")).unwrap();
+        let mut registry =
+            crate::registry::ServiceRegistry::new(String::new(), "main".to_string(), work_dir)
+                .unwrap()
+                .with_issue_tracker(Box::new(
+                    GitLabIssues::new("acme/catalog", "s3cret").with_base_url(&base),
+                ))
+                .with_issue_after_runs(2);
+        for (name, owner) in [("billing", "\"@jdoe\""), ("search", "\"@ghost\"")] {
+            let path = temp_dir.path().join(format!("{}.yaml", name));
+            std::fs::write(
+                &path,
+                format!(
+                    "name: {}\nversion: 1.0.0\nowner: {}\nservice_type:\n  type: rest\nendpoints: []\n",
+                    name, owner
+                ),
+            )
+            .unwrap();
+            registry
+                .register_service(name, &format!(r#"{{"config_path": "{}"}}"#, path.display()))
+                .unwrap();
+        }
+
+        let eol = "Runtime java 8 is past its end of life".to_string();
+        let mut failing = ValidationSummary::new();
+        failing.successful = vec!["billing".to_string()];
+        failing.failed = vec![("search".to_string(), "Missing dependency 'ledger'".to_string())];
+        failing.add_warning("billing".to_string(), eol.clone());
+        let mut green = ValidationSummary::new();
+        green.successful = vec!["billing".to_string(), "search".to_string()];
+
+        // Filed on the second failing run, assigned to owners known to GitLab
+        assert_eq!(registry.sync_issues(&failing).await.unwrap(), IssueSyncReport::default());
+        let report = registry.sync_issues(&failing).await.unwrap();
+        assert_eq!(report.opened, ["billing", "search"]);
+        {
+            let issues = issues.lock().unwrap();
+            assert_eq!(issues.len(), 2);
+            assert_eq!(issues[0]["title"], "Validation failing for billing");
+            assert_eq!(issues[0]["labels"], ISSUE_LABEL);
+            assert!(issues[0]["description"].as_str().unwrap().contains(&eol));
+            assert_eq!(issues[0]["assignee_ids"], json!([42]));
+            assert_eq!(issues[1]["assignee_ids"], json!([]));
+        }
+
+        // Problems changing update the open issue instead of filing another
+        let mut changed = failing.clone();
+        changed.add_warning("billing".to_string(), "No runbook".to_string());
+        registry.sync_issues(&changed).await.unwrap();
+        let report = registry.sync_issues(&changed).await.unwrap();
+        assert_eq!(report.updated, ["billing"]);
+        assert!(report.opened.is_empty());
+        assert!(issues.lock().unwrap()[0]["description"].as_str().unwrap().contains("No runbook"));
+
+        // Green runs close the issues
+        let report = registry.sync_issues(&green).await.unwrap();
+        assert_eq!(report.closed, ["billing", "search"]);
+        assert!(issues.lock().unwrap().iter().all(|issue| issue["state"] == "closed"));
+
+        // Problems coming back are filed again once they persist, not left on the closed issue
+        assert_eq!(registry.sync_issues(&failing).await.unwrap(), IssueSyncReport::default());
+        let report = registry.sync_issues(&failing).await.unwrap();
+        assert_eq!(report.opened, ["billing", "search"]);
+        let issues = issues.lock().unwrap();
+        assert_eq!(issues.len(), 4);
+        assert_eq!(issues[2]["state"], "opened");
+        assert_eq!(issues[2]["title"], "Validation failing for billing");
+        assert_eq!(issues[2]["assignee_ids"], json!([42]));
+    }
+}
@@ -0,0 +1,163 @@
+//! Append-only journal of mutating registry operations, replayable to rebuild the catalog
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+
+/// A mutating registry operation, with the input needed to apply it again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum JournalOperation {
+    /// A service was registered or its catalog config replaced
+    Register { name: String, config: String },
+    /// A batch of services was upserted, by name
+    Sync { services: BTreeMap<String, String>, prune: bool },
+    /// A service was deleted
+    Delete { name: String, force: bool },
+    /// A service was moved to the archive
+    Archive { name: String },
+    /// A service was restored from the archive
+    Unarchive { name: String },
+    /// A service was renamed, keeping its ID
+    Rename { from: String, to: String },
+    /// The definition file of a service was rewritten, e.g. by lint fixes or constraint bumps
+    Rewrite { name: String, definition: String },
+}
+
+impl fmt::Display for JournalOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalOperation::Register { name, .. } => write!(f, "register '{}'", name),
+            JournalOperation::Sync { services, prune } => {
+                write!(f, "sync {} service(s)", services.len())?;
+                if *prune {
+                    write!(f, ", pruning the rest")?;
+                }
+                Ok(())
+            }
+            JournalOperation::Delete { name, force } => {
+                write!(f, "delete '{}'{}", name, if *force { " (forced)" } else { "" })
+            }
+            JournalOperation::Archive { name } => write!(f, "archive '{}'", name),
+            JournalOperation::Unarchive { name } => write!(f, "unarchive '{}'", name),
+            JournalOperation::Rename { from, to } => write!(f, "rename '{}' to '{}'", from, to),
+            JournalOperation::Rewrite { name, .. } => write!(f, "rewrite '{}'", name),
+        }
+    }
+}
+
+/// A journaled operation and when it was applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When the operation was applied
+    pub timestamp: DateTime<Utc>,
+    /// The operation
+    #[serde(flatten)]
+    pub operation: JournalOperation,
+}
+
+/// Journal of the operations applied to a registry, one JSON entry per line
+#[derive(Debug, Clone)]
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Creates a journal stored at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates the journal of the given work directory
+    ///
+    /// The journal lives next to the work directory so it survives re-cloning the
+    /// repository and never ends up in it.
+    pub fn for_work_dir(work_dir: &Path) -> Self {
+        let dir_name = work_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "aureacore".to_string());
+        let parent = work_dir.parent().unwrap_or_else(|| Path::new("."));
+        Self::new(parent.join(format!(".{}.journal", dir_name)))
+    }
+
+    /// Gets the path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends an operation, timestamped now
+    pub fn append(&self, operation: JournalOperation) -> Result<()> {
+        let entry = JournalEntry { timestamp: Utc::now(), operation };
+        let line = serde_json::to_string(&entry).map_err(|e| {
+            AureaCoreError::Internal(format!("Failed to serialize journal entry: {}", e))
+        })?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Reads all entries, oldest first; a missing journal has none
+    pub fn read(&self) -> Result<Vec<JournalEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        fs::read_to_string(&self.path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    AureaCoreError::invalid_config(
+                        format!("Invalid journal entry at {}:{}", self.path.display(), index + 1),
+                        e,
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_journal_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = Journal::for_work_dir(&temp_dir.path().join("config"));
+        assert_eq!(journal.path(), temp_dir.path().join(".config.journal"));
+        assert!(journal.read().unwrap().is_empty());
+
+        let register = JournalOperation::Register { name: "billing".into(), config: "{}".into() };
+        let sync = JournalOperation::Sync {
+            services: BTreeMap::from([("ledger".to_string(), "{}".to_string())]),
+            prune: true,
+        };
+        journal.append(register.clone()).unwrap();
+        journal.append(sync.clone()).unwrap();
+        let entries = journal.read().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation, register);
+        assert_eq!(entries[1].operation, sync);
+        assert!(entries[0].timestamp <= entries[1].timestamp);
+        assert_eq!(sync.to_string(), "sync 1 service(s), pruning the rest");
+
+        let line = fs::read_to_string(journal.path()).unwrap();
+        assert!(line.starts_with(r#"{"timestamp":"#));
+        assert!(line.contains(r#""op":"register","name":"billing""#));
+
+        fs::write(journal.path(), "not json\n").unwrap();
+        assert!(journal.read().unwrap_err().to_string().contains(":1:"));
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::registry::{CancellationToken, ServiceRegistry, ValidationSummary};
+use crate::schema::service::ServiceSchema;
+
+/// A link in a service definition that did not answer with a 2xx status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLink {
+    /// Field holding the link, e.g. `documentation_url` or `metadata.runbook`
+    pub field: String,
+    /// The link
+    pub url: String,
+    /// Why the link is considered dead
+    pub reason: String,
+}
+
+/// Checks that documentation, dashboard and runbook links of services are alive
+///
+/// Links are checked concurrently up to a limit, and results are cached so repeated
+/// runs and links shared between services are only requested once per cache period.
+pub struct LinkChecker {
+    client: reqwest::Client,
+    concurrency: usize,
+    cache_ttl: Duration,
+    /// Outcome per URL, `None` for live links, with the time it was checked
+    cache: Mutex<HashMap<String, (Option<String>, Instant)>>,
+    /// Token stopping further requests once cancelled
+    cancellation: CancellationToken,
+}
+
+impl Default for LinkChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkChecker {
+    /// Creates a checker with 8 concurrent requests, 10 second timeouts and a 10 minute cache
+    pub fn new() -> Self {
+        Self {
+            client: Self::client(Duration::from_secs(10)),
+            concurrency: 8,
+            cache_ttl: Duration::from_secs(600),
+            cache: Mutex::new(HashMap::new()),
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// Sets how many links are checked at the same time
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets how long a link check result is reused
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Sets how long to wait for a link to answer
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = Self::client(timeout);
+        self
+    }
+
+    /// Sets the token stopping the check; links not requested by then are left unchecked
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    fn client(timeout: Duration) -> reqwest::Client {
+        reqwest::Client::builder().timeout(timeout).build().unwrap_or_default()
+    }
+
+    /// Checks URLs, returning the reason each dead one failed
+    pub async fn check(&self, urls: &[String]) -> HashMap<String, String> {
+        let mut outcomes = HashMap::new();
+        let mut pending = Vec::new();
+        {
+            let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            for url in urls {
+                match cache.get(url) {
+                    Some((outcome, checked)) if checked.elapsed() < self.cache_ttl => {
+                        outcomes.insert(url.clone(), outcome.clone());
+                    }
+                    _ if !pending.contains(url) => pending.push(url.clone()),
+                    _ => {}
+                }
+            }
+        }
+
+        let checked: Vec<Option<(String, Option<String>)>> = stream::iter(pending)
+            .map(|url| async move {
+                if self.cancellation.is_cancelled() {
+                    return None;
+                }
+                let outcome = self.check_one(&url).await;
+                Some((url, outcome))
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        for (url, outcome) in checked.into_iter().flatten() {
+            cache.insert(url.clone(), (outcome.clone(), Instant::now()));
+            outcomes.insert(url, outcome);
+        }
+        outcomes.into_iter().filter_map(|(url, outcome)| Some((url, outcome?))).collect()
+    }
+
+    /// Requests a link, returning why it is dead or `None` if it is alive
+    async fn check_one(&self, url: &str) -> Option<String> {
+        // Some servers do not implement HEAD, so fall back to GET
+        let mut response = self.client.head(url).send().await;
+        if let Ok(head) = &response {
+            let status = head.status();
+            if status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+                || status == reqwest::StatusCode::NOT_IMPLEMENTED
+            {
+                response = self.client.get(url).send().await;
+            }
+        }
+        match response {
+            Ok(response) if response.status().is_success() => None,
+            Ok(response) => Some(format!("HTTP {}", response.status().as_u16())),
+            Err(err) if err.is_timeout() => Some("timed out".to_string()),
+            Err(err) => Some(format!("unreachable: {}", err)),
+        }
+    }
+
+    /// Checks the links of the services in a validation summary, recording dead ones in it
+    ///
+    /// The summary is marked as timed out if the check was cancelled before every link
+    /// was requested.
+    pub async fn check_summary(&self, registry: &ServiceRegistry, summary: &mut ValidationSummary) {
+        let services: Vec<(String, Vec<(String, String)>)> = summary
+            .successful
+            .iter()
+            .chain(summary.failed.iter().map(|(name, _)| name))
+            .filter_map(|name| {
+                let definition = registry.get_service(name).ok()?.definition()?;
+                Some((name.clone(), service_links(&definition)))
+            })
+            .collect();
+        let urls: Vec<String> = services
+            .iter()
+            .flat_map(|(_, links)| links.iter().map(|(_, url)| url.clone()))
+            .collect();
+
+        let dead = self.check(&urls).await;
+        if self.cancellation.is_cancelled() {
+            summary.timed_out = true;
+        }
+        for (service, links) in services {
+            let dead_links: Vec<DeadLink> = links
+                .into_iter()
+                .filter_map(|(field, url)| {
+                    let reason = dead.get(&url)?.clone();
+                    Some(DeadLink { field, url, reason })
+                })
+                .collect();
+            if !dead_links.is_empty() {
+                summary.dead_links.insert(service, dead_links);
+            }
+        }
+    }
+}
+
+/// Lists the links of a service as (field, URL) pairs
+///
+/// Covers the documentation URL and metadata entries whose key mentions a dashboard,
+/// runbook or URL, holding an HTTP(S) link or a list of them.
+pub fn service_links(definition: &ServiceSchema) -> Vec<(String, String)> {
+    let is_link = |url: &str| url.starts_with("http://") || url.starts_with("https://");
+    let mut links = Vec::new();
+    if let Some(url) = definition.documentation_url.as_deref().filter(|url| is_link(url)) {
+        links.push(("documentation_url".to_string(), url.to_string()));
+    }
+
+    let mut keys: Vec<&String> = definition
+        .metadata
+        .keys()
+        .filter(|key| {
+            let key = key.to_lowercase();
+            key.contains("dashboard") || key.contains("runbook") || key.contains("url")
+        })
+        .collect();
+    keys.sort();
+    for key in keys {
+        let value = &definition.metadata[key];
+        let urls: Vec<&str> = match value {
+            serde_json::Value::String(url) => vec![url.as_str()],
+            serde_json::Value::Array(items) => items.iter().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        for url in urls.into_iter().filter(|url| is_link(url)) {
+            links.push((format!("metadata.{}", key), url.to_string()));
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+
+    use super::*;
+
+    #[test]
+    fn test_service_links() {
+        let definition: ServiceSchema = serde_yaml::from_str(
+            "name: billing\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n\
+             documentation_url: https://docs.example.com/billing\n\
+             metadata:\n  runbook: https://wiki.example.com/billing\n  dashboards: [https://grafana.example.com/d/1, n/a]\n  tier: https://not-a-link-key.example.com\n",
+        )
+        .unwrap();
+        assert_eq!(
+            service_links(&definition),
+            vec![
+                ("documentation_url".to_string(), "https://docs.example.com/billing".to_string()),
+                ("metadata.dashboards".to_string(), "https://grafana.example.com/d/1".to_string()),
+                ("metadata.runbook".to_string(), "https://wiki.example.com/billing".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_with_cache() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let router = Router::new()
+            .route(
+                "/ok",
+                get(move || async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    "ok"
+                }),
+            )
+            .route("/gone", get(|| async { StatusCode::GONE }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let checker = LinkChecker::new().with_concurrency(2);
+        let urls = vec![format!("{}/ok", base), format!("{}/gone", base), format!("{}/ok", base)];
+        let dead = checker.check(&urls).await;
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[&format!("{}/gone", base)], "HTTP 410");
+
+        // Cached results are reused until they expire
+        checker.check(&urls).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let checker = checker.with_cache_ttl(Duration::ZERO);
+        checker.check(&urls).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let dead = checker.check(&["http://127.0.0.1:1/".to_string()]).await;
+        assert!(dead["http://127.0.0.1:1/"].starts_with("unreachable"));
+    }
+}
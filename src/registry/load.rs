@@ -0,0 +1,156 @@
+//! Reading service configs and definitions from disk in parallel
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+
+use crate::error::AureaCoreError;
+use crate::registry::store::ConfigStore;
+use crate::registry::{Service, ServiceConfig};
+
+/// Most threads reading files at once
+pub const MAX_LOAD_WORKERS: usize = 8;
+
+/// Outcome of loading the services of the catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LoadReport {
+    /// Services loaded, including those whose definitions failed to parse
+    pub loaded: Vec<String>,
+    /// Files that could not be read or parsed
+    pub failed: Vec<LoadFailure>,
+}
+
+impl LoadReport {
+    /// Checks whether every file was read and parsed
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// A config or definition file that could not be read or parsed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoadFailure {
+    /// Service the file belongs to
+    pub service: String,
+    /// Path of the file
+    pub path: PathBuf,
+    /// Why the file could not be loaded
+    pub error: String,
+}
+
+/// A service read from disk with the failure parsing its definition, or the failure
+/// reading its catalog config
+pub(crate) type Prefetched =
+    (String, std::result::Result<(Service, Option<LoadFailure>), LoadFailure>);
+
+/// Reads the catalog configs of services and their definitions on a bounded pool of threads
+///
+/// `rebase` maps definition paths, e.g. into the worktree of a preview. Results are in the
+/// order of `names`.
+pub(crate) fn prefetch_services(
+    store: &ConfigStore,
+    names: &[String],
+    rebase: &(dyn Fn(&str) -> Option<String> + Sync),
+) -> Vec<Prefetched> {
+    let workers = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(MAX_LOAD_WORKERS)
+        .min(names.len())
+        .max(1);
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, Prefetched)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(name) = names.get(index) else {
+                            break;
+                        };
+                        let service = prefetch_service(store, name, rebase);
+                        results.push((index, (name.clone(), service)));
+                    }
+                    results
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap_or_default()).collect()
+    });
+
+    // Services claimed by a worker that panicked have no result; report them as failed
+    let mut loaded = vec![false; names.len()];
+    for (index, _) in &results {
+        loaded[*index] = true;
+    }
+    for (index, name) in names.iter().enumerate().filter(|(index, _)| !loaded[*index]) {
+        let failure = LoadFailure {
+            service: name.clone(),
+            path: store.config_dir().join(super::config_file(name)),
+            error: "Loading the service panicked".to_string(),
+        };
+        results.push((index, (name.clone(), Err(failure))));
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, prefetched)| prefetched).collect()
+}
+
+fn prefetch_service(
+    store: &ConfigStore,
+    name: &str,
+    rebase: &(dyn Fn(&str) -> Option<String> + Sync),
+) -> std::result::Result<(Service, Option<LoadFailure>), LoadFailure> {
+    let file = super::config_file(name);
+    let failure = |error: AureaCoreError| LoadFailure {
+        service: name.to_string(),
+        path: store.config_dir().join(&file),
+        error: error.to_string(),
+    };
+    let config = store.load_config(&file).map_err(failure)?;
+    let mut config: ServiceConfig = serde_json::from_str(&config)
+        .map_err(|e| failure(AureaCoreError::invalid_config("Invalid service config", e)))?;
+    if let Some(path) = rebase(&config.config_path) {
+        config.config_path = path;
+    }
+    let mut service = Service::new(name.to_string(), config);
+    let definition = service.load_schema_data().err().map(|error| LoadFailure {
+        service: name.to_string(),
+        path: PathBuf::from(&service.config.config_path),
+        error: error.to_string(),
+    });
+    Ok((service, definition))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_prefetch_reports_services_of_panicking_workers() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path()).unwrap();
+        let names: Vec<String> = ["a", "b"].iter().map(|name| name.to_string()).collect();
+        for name in &names {
+            let config = format!(
+                r#"{{"namespace":null,"config_path":"{}.yaml","schema_version":"1.0.0"}}"#,
+                name
+            );
+            store.save_config(format!("{}.json", name), &config).unwrap();
+        }
+
+        let rebase = |path: &str| -> Option<String> {
+            assert_ne!(path, "b.yaml", "rebase failed");
+            None
+        };
+        let results = prefetch_services(&store, &names, &rebase);
+
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+        let failure = results[1].1.as_ref().unwrap_err();
+        assert_eq!(failure.service, "b");
+        assert_eq!(failure.error, "Loading the service panicked");
+    }
+}
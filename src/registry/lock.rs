@@ -0,0 +1,407 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+
+/// Default time after which a lock without heartbeat is considered abandoned
+pub const DEFAULT_LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// Advisory lock deciding which registry instance may write to a shared catalog
+///
+/// Implementations may be backed by a local lockfile or by an external
+/// coordination service (e.g. Redis or etcd).
+pub trait RegistryLock: Send + Sync {
+    /// Attempts to acquire the lock, returning whether this instance now holds it
+    fn try_acquire(&self) -> Result<bool>;
+
+    /// Refreshes the lock to signal that the holder is still alive
+    ///
+    /// Returns `false` if the lock is no longer held by this instance.
+    fn heartbeat(&self) -> Result<bool>;
+
+    /// Releases the lock if it is held by this instance
+    fn release(&self) -> Result<()>;
+
+    /// Checks whether this instance currently holds the lock
+    fn is_held(&self) -> bool;
+}
+
+/// Contents of a lockfile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockRecord {
+    /// Identifier of the instance holding the lock
+    owner: String,
+    /// Last time the holder refreshed the lock
+    heartbeat: DateTime<Utc>,
+}
+
+/// Lockfile as observed by an instance
+enum Observed {
+    /// No lockfile exists
+    Missing,
+    /// The lockfile holds a record, read from `raw`
+    Record { record: LockRecord, raw: String },
+    /// The lockfile can't be parsed, last modified at the given time
+    Unreadable { raw: String, modified: SystemTime },
+}
+
+/// Lockfile-based [`RegistryLock`] with heartbeat expiry
+///
+/// The lock is taken by linking a fully written record to the lockfile path, which
+/// fails if the lockfile exists. A lock whose heartbeat is older than the TTL is
+/// treated as abandoned and may be taken over; a lockfile that can't be parsed counts
+/// as held until it was last modified longer than the TTL ago.
+pub struct FileLock {
+    path: PathBuf,
+    owner: String,
+    ttl: Duration,
+    held: AtomicBool,
+}
+
+impl FileLock {
+    /// Creates a new lock at the given lockfile path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let owner = format!(
+            "{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        );
+        Self { path: path.into(), owner, ttl: DEFAULT_LOCK_TTL, held: AtomicBool::new(false) }
+    }
+
+    /// Creates a lock guarding the given work directory
+    ///
+    /// The lockfile lives next to the work directory so it never ends up in the
+    /// cloned repository.
+    pub fn for_work_dir(work_dir: &Path) -> Self {
+        let dir_name = work_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "aureacore".to_string());
+        let parent = work_dir.parent().unwrap_or_else(|| Path::new("."));
+        Self::new(parent.join(format!(".{}.lock", dir_name)))
+    }
+
+    /// Sets the time after which a lock without heartbeat is considered abandoned
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Gets the path of the lockfile
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the identifier this instance writes into the lockfile
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    fn read_record(&self) -> Result<Option<LockRecord>> {
+        match self.observe()? {
+            Observed::Record { record, .. } => Ok(Some(record)),
+            Observed::Missing | Observed::Unreadable { .. } => Ok(None),
+        }
+    }
+
+    fn observe(&self) -> Result<Observed> {
+        let raw = match fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Observed::Missing),
+            Err(e) => return Err(AureaCoreError::Io(e)),
+        };
+        match serde_json::from_str(&raw) {
+            Ok(record) => Ok(Observed::Record { record, raw }),
+            Err(_) => {
+                let modified = match fs::metadata(&self.path).and_then(|m| m.modified()) {
+                    Ok(modified) => modified,
+                    Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Observed::Missing),
+                    Err(e) => return Err(AureaCoreError::Io(e)),
+                };
+                Ok(Observed::Unreadable { raw, modified })
+            }
+        }
+    }
+
+    /// Gets a path next to the lockfile that is unique to this instance
+    fn scratch_path(&self, purpose: &str) -> PathBuf {
+        self.path.with_extension(format!("{}.{}", self.owner, purpose))
+    }
+
+    fn record(&self) -> Result<String> {
+        serde_json::to_string(&LockRecord { owner: self.owner.clone(), heartbeat: Utc::now() })
+            .map_err(|e| AureaCoreError::Internal(format!("Failed to serialize lock: {}", e)))
+    }
+
+    /// Writes this instance's record through a temporary file renamed into place
+    ///
+    /// Readers never see a partially written record, which they would take as abandoned.
+    fn write_record(&self) -> Result<()> {
+        let temp = self.scratch_path("tmp");
+        fs::write(&temp, self.record()?)?;
+        fs::rename(&temp, &self.path).map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            AureaCoreError::Io(e)
+        })
+    }
+
+    /// Creates the lockfile with this instance's record, unless it exists
+    ///
+    /// The record is written to a temporary file first and linked into place, so other
+    /// instances never see the lockfile without a complete record.
+    fn publish(&self) -> Result<bool> {
+        let temp = self.scratch_path("tmp");
+        fs::write(&temp, self.record()?)?;
+        let linked = fs::hard_link(&temp, &self.path);
+        let _ = fs::remove_file(&temp);
+        match linked {
+            Ok(()) => {
+                self.held.store(true, Ordering::SeqCst);
+                Ok(true)
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(AureaCoreError::Io(e)),
+        }
+    }
+
+    /// Replaces an abandoned lockfile with one naming this instance
+    ///
+    /// Compares and swaps on the abandoned lockfile's content `raw`: the lockfile is
+    /// moved aside, checked to still hold `raw` and only then replaced. Of several
+    /// instances taking over at once, only the one that moved the abandoned lockfile
+    /// holds the lock.
+    fn take_over(&self, raw: &str) -> Result<bool> {
+        let aside = self.scratch_path("stale");
+        match fs::rename(&self.path, &aside) {
+            Ok(()) => {}
+            // Another instance took it over first
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(AureaCoreError::Io(e)),
+        }
+        let moved = fs::read_to_string(&aside);
+        if !moved.as_deref().is_ok_and(|moved| moved == raw) {
+            // The lockfile was replaced since it was read: put the new one back
+            let restored = fs::hard_link(&aside, &self.path);
+            let _ = fs::remove_file(&aside);
+            if let Err(e) = restored {
+                if e.kind() != ErrorKind::AlreadyExists {
+                    return Err(AureaCoreError::Io(e));
+                }
+            }
+            return Ok(false);
+        }
+        let _ = fs::remove_file(&aside);
+        self.publish()
+    }
+
+    fn is_expired(&self, record: &LockRecord) -> bool {
+        let age = Utc::now().signed_duration_since(record.heartbeat);
+        age.to_std().map(|age| age > self.ttl).unwrap_or(false)
+    }
+
+    fn is_untouched_past_ttl(&self, modified: SystemTime) -> bool {
+        modified.elapsed().is_ok_and(|age| age > self.ttl)
+    }
+}
+
+impl RegistryLock for FileLock {
+    fn try_acquire(&self) -> Result<bool> {
+        if self.is_held() {
+            return self.heartbeat();
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if self.publish()? {
+            return Ok(true);
+        }
+        let raw = match self.observe()? {
+            // Released meanwhile; try again on the next attempt
+            Observed::Missing => return Ok(false),
+            Observed::Record { record, .. } if record.owner == self.owner => {
+                self.held.store(true, Ordering::SeqCst);
+                return self.heartbeat();
+            }
+            Observed::Record { record, .. } if !self.is_expired(&record) => return Ok(false),
+            Observed::Unreadable { modified, .. } if !self.is_untouched_past_ttl(modified) => {
+                return Ok(false)
+            }
+            Observed::Record { raw, .. } | Observed::Unreadable { raw, .. } => raw,
+        };
+        tracing::warn!("Taking over stale registry lock {}", self.path.display());
+        self.take_over(&raw)
+    }
+
+    fn heartbeat(&self) -> Result<bool> {
+        if !self.is_held() {
+            return Ok(false);
+        }
+
+        match self.read_record()? {
+            Some(record) if record.owner == self.owner => {
+                self.write_record()?;
+                Ok(true)
+            }
+            _ => {
+                // Another instance took over the lock
+                self.held.store(false, Ordering::SeqCst);
+                Ok(false)
+            }
+        }
+    }
+
+    fn release(&self) -> Result<()> {
+        if !self.held.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        match self.read_record()? {
+            Some(record) if record.owner == self.owner => {
+                fs::remove_file(&self.path)?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_held(&self) -> bool {
+        self.held.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        if let Err(e) = self.release() {
+            tracing::warn!("Failed to release registry lock {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_file_lock_is_exclusive() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.lock");
+
+        let first = FileLock::new(&path);
+        let second = FileLock::new(&path);
+
+        assert!(first.try_acquire().unwrap());
+        assert!(first.is_held());
+        assert!(!second.try_acquire().unwrap());
+        assert!(!second.is_held());
+
+        first.release().unwrap();
+        assert!(!path.exists());
+        assert!(second.try_acquire().unwrap());
+    }
+
+    #[test]
+    fn test_file_lock_takes_over_stale_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.lock");
+
+        let stale = LockRecord {
+            owner: "crashed-instance".to_string(),
+            heartbeat: Utc::now() - chrono::Duration::minutes(10),
+        };
+        fs::write(&path, serde_json::to_string(&stale).unwrap()).unwrap();
+
+        let lock = FileLock::new(&path).with_ttl(Duration::from_secs(30));
+        assert!(lock.try_acquire().unwrap());
+        assert!(fs::read_to_string(&path).unwrap().contains(lock.owner()));
+    }
+
+    #[test]
+    fn test_concurrent_takeovers_leave_one_holder() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.lock");
+        let stale = LockRecord {
+            owner: "crashed-instance".to_string(),
+            heartbeat: Utc::now() - chrono::Duration::minutes(10),
+        };
+        let raw = serde_json::to_string(&stale).unwrap();
+        fs::write(&path, &raw).unwrap();
+
+        // Both instances saw the lock as stale before either replaced it
+        let first = FileLock::new(&path);
+        let second = FileLock::new(&path);
+        assert!(first.take_over(&raw).unwrap());
+        assert!(!second.take_over(&raw).unwrap());
+        assert!(!second.is_held());
+
+        // The record of the winner is left in place
+        assert!(first.heartbeat().unwrap());
+        assert!(fs::read_to_string(&path).unwrap().contains(first.owner()));
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+
+        // Racing threads taking over the same stale lock
+        fs::write(&path, &raw).unwrap();
+        let locks: Vec<FileLock> =
+            (0..8).map(|i| FileLock::new(&path).with_ttl(Duration::from_secs(30 + i))).collect();
+        let winners = std::thread::scope(|scope| {
+            let handles: Vec<_> =
+                locks.iter().map(|lock| scope.spawn(|| lock.try_acquire().unwrap())).collect();
+            handles.into_iter().filter_map(|handle| handle.join().unwrap().then_some(())).count()
+        });
+        assert_eq!(winners, 1);
+        assert_eq!(locks.iter().filter(|lock| lock.is_held()).count(), 1);
+    }
+
+    #[test]
+    fn test_unreadable_lock_is_held_until_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.lock");
+
+        // A lockfile another instance is still writing
+        fs::write(&path, "").unwrap();
+        let lock = FileLock::new(&path).with_ttl(Duration::from_secs(30));
+        assert!(!lock.try_acquire().unwrap());
+
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(60)).unwrap();
+        assert!(lock.try_acquire().unwrap());
+        assert!(fs::read_to_string(&path).unwrap().contains(lock.owner()));
+    }
+
+    #[test]
+    fn test_heartbeat_detects_lost_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.lock");
+
+        let lock = FileLock::new(&path);
+        assert!(lock.try_acquire().unwrap());
+        assert!(lock.heartbeat().unwrap());
+
+        // Simulate another instance taking over after a missed heartbeat
+        let other = LockRecord { owner: "other".to_string(), heartbeat: Utc::now() };
+        fs::write(&path, serde_json::to_string(&other).unwrap()).unwrap();
+
+        assert!(!lock.heartbeat().unwrap());
+        assert!(!lock.is_held());
+
+        // Releasing a lost lock must not remove the other owner's lockfile
+        lock.release().unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_lock_path_for_work_dir() {
+        let lock = FileLock::for_work_dir(Path::new("/srv/catalog/config"));
+        assert_eq!(lock.path(), Path::new("/srv/catalog/.config.lock"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Lockfile pinning the resolved versions and dependency edges of the catalog
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::{fmt, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::service::Service;
+
+/// Name of the lockfile, kept at the root of the catalog repository
+pub const LOCKFILE_NAME: &str = "aureacore.lock";
+
+/// Layout version of lockfiles; lockfiles with another layout are rejected
+const LOCKFILE_FORMAT: u32 = 1;
+
+/// Comment heading every lockfile
+const LOCKFILE_HEADER: &str =
+    "# This file is generated by `aureacore validate`. Do not edit it by hand.\n";
+
+/// Resolved state of the catalog, as validated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogLock {
+    format: u32,
+    /// Locked services, sorted by name
+    pub services: BTreeMap<String, LockedService>,
+}
+
+/// A service as pinned in the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedService {
+    /// Version the definition declared
+    pub version: Option<String>,
+    /// Dependency edges, sorted by the service depended on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<LockedDependency>,
+}
+
+/// A dependency edge as pinned in the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedDependency {
+    /// Service depended on
+    pub service: String,
+    /// Version constraint of the edge
+    pub version_constraint: Option<String>,
+    /// Version the service depended on was resolved to
+    pub resolved: Option<String>,
+    /// Whether the dependency is required
+    pub required: bool,
+}
+
+/// A difference between the lockfile and the catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockDrift {
+    /// The service is in the catalog but not in the lockfile
+    ServiceAdded(String),
+    /// The service is in the lockfile but no longer in the catalog
+    ServiceRemoved(String),
+    /// The service declares another version than the one locked
+    VersionChanged { service: String, locked: Option<String>, current: Option<String> },
+    /// The service gained a dependency
+    DependencyAdded { service: String, dependency: String },
+    /// The service no longer has a dependency
+    DependencyRemoved { service: String, dependency: String },
+    /// The constraint, resolved version or requiredness of a dependency changed
+    DependencyChanged { service: String, dependency: String },
+}
+
+impl fmt::Display for LockDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version = |version: &Option<String>| version.clone().unwrap_or_else(|| "none".into());
+        match self {
+            LockDrift::ServiceAdded(service) => write!(f, "'{}' is not locked", service),
+            LockDrift::ServiceRemoved(service) => {
+                write!(f, "'{}' is locked but no longer in the catalog", service)
+            }
+            LockDrift::VersionChanged { service, locked, current } => write!(
+                f,
+                "'{}' is locked at version {} but declares {}",
+                service,
+                version(locked),
+                version(current)
+            ),
+            LockDrift::DependencyAdded { service, dependency } => {
+                write!(f, "'{}' gained the dependency on '{}'", service, dependency)
+            }
+            LockDrift::DependencyRemoved { service, dependency } => {
+                write!(f, "'{}' no longer depends on '{}'", service, dependency)
+            }
+            LockDrift::DependencyChanged { service, dependency } => {
+                write!(f, "The dependency of '{}' on '{}' changed", service, dependency)
+            }
+        }
+    }
+}
+
+impl CatalogLock {
+    /// Pins the versions and dependency edges of the services
+    pub(crate) fn capture(services: &HashMap<String, Service>) -> Self {
+        let version = |name: &str| {
+            services
+                .get(name)
+                .and_then(|service| service.schema_data.as_ref())
+                .and_then(|definition| definition.get("version"))
+                .and_then(|version| version.as_str())
+                .map(str::to_string)
+        };
+        let services = services
+            .iter()
+            .map(|(name, service)| {
+                let mut dependencies: Vec<LockedDependency> = service
+                    .dependencies()
+                    .into_iter()
+                    .map(|dep| LockedDependency {
+                        resolved: version(&dep.service),
+                        service: dep.service,
+                        version_constraint: dep.version_constraint,
+                        required: dep.required,
+                    })
+                    .collect();
+                dependencies.sort_by(|a, b| a.service.cmp(&b.service));
+                (name.clone(), LockedService { version: version(name), dependencies })
+            })
+            .collect();
+        Self { format: LOCKFILE_FORMAT, services }
+    }
+
+    /// Reads a lockfile
+    pub fn load(path: &Path) -> Result<Self> {
+        let lock: Self = serde_yaml::from_str(&fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid lockfile {}", path.display()), e)
+        })?;
+        if lock.format != LOCKFILE_FORMAT {
+            return Err(AureaCoreError::Config(format!(
+                "Lockfile {} has format {}, expected {}; regenerate it with `aureacore validate`",
+                path.display(),
+                lock.format,
+                LOCKFILE_FORMAT
+            )));
+        }
+        Ok(lock)
+    }
+
+    /// Writes the lockfile
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_yaml::to_string(self).map_err(|e| {
+            AureaCoreError::Internal(format!("Failed to serialize lockfile: {}", e))
+        })?;
+        fs::write(path, format!("{}{}", LOCKFILE_HEADER, content))?;
+        Ok(())
+    }
+
+    /// Lists how `current` drifted from this lock
+    pub fn drift(&self, current: &CatalogLock) -> Vec<LockDrift> {
+        let mut drift = Vec::new();
+        for (name, locked) in &self.services {
+            let Some(service) = current.services.get(name) else {
+                drift.push(LockDrift::ServiceRemoved(name.clone()));
+                continue;
+            };
+            if locked.version != service.version {
+                drift.push(LockDrift::VersionChanged {
+                    service: name.clone(),
+                    locked: locked.version.clone(),
+                    current: service.version.clone(),
+                });
+            }
+            for dep in &locked.dependencies {
+                let dependency = dep.service.clone();
+                match service.dependencies.iter().find(|d| d.service == dep.service) {
+                    None => drift
+                        .push(LockDrift::DependencyRemoved { service: name.clone(), dependency }),
+                    Some(d) if d != dep => drift
+                        .push(LockDrift::DependencyChanged { service: name.clone(), dependency }),
+                    Some(_) => {}
+                }
+            }
+            for dep in &service.dependencies {
+                if !locked.dependencies.iter().any(|d| d.service == dep.service) {
+                    drift.push(LockDrift::DependencyAdded {
+                        service: name.clone(),
+                        dependency: dep.service.clone(),
+                    });
+                }
+            }
+        }
+        for name in current.services.keys().filter(|name| !self.services.contains_key(*name)) {
+            drift.push(LockDrift::ServiceAdded(name.clone()));
+        }
+        drift
+    }
+}
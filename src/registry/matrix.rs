@@ -0,0 +1,200 @@
+//! Provider × consumer matrix of version constraint satisfaction across the catalog
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::docs::escape_html;
+use crate::error::{AureaCoreError, Result};
+
+/// How far the version of a provider is from what a consumer's constraint expects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSkew {
+    /// The provider's version satisfies the constraint, or there is none
+    Compatible,
+    /// The versions differ in a forward-compatible way
+    Minor,
+    /// The versions differ in a breaking way
+    Major,
+    /// The provider is not registered or declares no version
+    Unknown,
+}
+
+impl fmt::Display for VersionSkew {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VersionSkew::Compatible => "compatible",
+            VersionSkew::Minor => "minor",
+            VersionSkew::Major => "major",
+            VersionSkew::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A consumer's constraint on a provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatrixCell {
+    /// Service depended upon
+    pub provider: String,
+    /// Service declaring the dependency
+    pub consumer: String,
+    /// Version constraint of the consumer, if any
+    pub constraint: Option<String>,
+    /// Current version of the provider, if known
+    pub version: Option<String>,
+    /// How far the version is from the constraint
+    pub skew: VersionSkew,
+}
+
+/// Version skew of every dependency in the catalog, providers by consumers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CompatibilityMatrix {
+    /// Services depended upon, sorted; the rows of the matrix
+    pub providers: Vec<String>,
+    /// Services with dependencies, sorted; the columns of the matrix
+    pub consumers: Vec<String>,
+    /// Dependencies, sorted by provider then consumer
+    pub cells: Vec<MatrixCell>,
+}
+
+impl CompatibilityMatrix {
+    /// Builds the matrix from its cells
+    pub(crate) fn new(mut cells: Vec<MatrixCell>) -> Self {
+        cells.sort_by(|a, b| a.provider.cmp(&b.provider).then(a.consumer.cmp(&b.consumer)));
+        let mut providers: Vec<String> = cells.iter().map(|c| c.provider.clone()).collect();
+        providers.dedup();
+        let mut consumers: Vec<String> = cells.iter().map(|c| c.consumer.clone()).collect();
+        consumers.sort();
+        consumers.dedup();
+        Self { providers, consumers, cells }
+    }
+
+    /// Gets the cell of a consumer's dependency on a provider
+    pub fn cell(&self, provider: &str, consumer: &str) -> Option<&MatrixCell> {
+        self.cells.iter().find(|c| c.provider == provider && c.consumer == consumer)
+    }
+
+    /// Counts the dependencies with the given skew
+    pub fn count(&self, skew: VersionSkew) -> usize {
+        self.cells.iter().filter(|c| c.skew == skew).count()
+    }
+
+    /// Renders the matrix as CSV, a row per provider and a column per consumer
+    ///
+    /// Cells hold the skew, or nothing where the consumer does not depend on the provider.
+    pub fn to_csv(&self) -> Result<String> {
+        let csv_error = |e: csv::Error| AureaCoreError::Config(format!("Invalid CSV: {}", e));
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer
+            .write_record(
+                std::iter::once("provider").chain(self.consumers.iter().map(String::as_str)),
+            )
+            .map_err(csv_error)?;
+        for provider in &self.providers {
+            let row = self.consumers.iter().map(|consumer| {
+                self.cell(provider, consumer).map(|c| c.skew.to_string()).unwrap_or_default()
+            });
+            writer.write_record(std::iter::once(provider.clone()).chain(row)).map_err(csv_error)?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AureaCoreError::Config(format!("Invalid CSV: {}", e)))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Renders the matrix as a standalone HTML page
+    ///
+    /// Cells carry the skew as their class and the constraint and version as their title.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Compatibility matrix</title>\n<style>\ntd, th { border: 1px solid #ccc; padding: 4px 8px; }\n.compatible { background: #d4edda; }\n.minor { background: #fff3cd; }\n.major { background: #f8d7da; }\n.unknown { background: #e2e3e5; }\n</style>\n</head>\n<body>\n<table>\n<tr><th>provider \\ consumer</th>",
+        );
+        for consumer in &self.consumers {
+            html.push_str(&format!("<th>{}</th>", escape_html(consumer)));
+        }
+        html.push_str("</tr>\n");
+        for provider in &self.providers {
+            html.push_str(&format!("<tr><th>{}</th>", escape_html(provider)));
+            for consumer in &self.consumers {
+                match self.cell(provider, consumer) {
+                    Some(cell) => html.push_str(&format!(
+                        "<td class=\"{}\" title=\"{} at {}\">{}</td>",
+                        cell.skew,
+                        escape_html(cell.constraint.as_deref().unwrap_or("any version")),
+                        escape_html(cell.version.as_deref().unwrap_or("unknown version")),
+                        cell.skew
+                    )),
+                    None => html.push_str("<td></td>"),
+                }
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::registry::{DependencyManager, InMemoryRegistry};
+    use crate::schema::validation::ValidationService;
+
+    #[test]
+    fn test_compatibility_matrix() {
+        let registry = InMemoryRegistry::new()
+            .with_service(
+                "frontend",
+                json!({
+                    "name": "frontend",
+                    "version": "1.0.0",
+                    "dependencies": [
+                        {"service": "api", "version_constraint": "2.0.0"},
+                        {"service": "search", "required": false}
+                    ]
+                }),
+            )
+            .with_service(
+                "worker",
+                json!({
+                    "name": "worker",
+                    "version": "1.0.0",
+                    "dependencies": [
+                        {"service": "api", "version_constraint": "1.4.0"},
+                        {"service": "auth"}
+                    ]
+                }),
+            )
+            .with_service("api", json!({"name": "api", "version": "2.1.0"}))
+            .with_service("auth", json!({"name": "auth", "version": "3.0.0"}));
+        let manager = DependencyManager::new(
+            Arc::new(RwLock::new(registry)),
+            Arc::new(ValidationService::new()),
+        );
+
+        let matrix = manager.compatibility_matrix().unwrap();
+        assert_eq!(matrix.providers, vec!["api", "auth", "search"]);
+        assert_eq!(matrix.consumers, vec!["frontend", "worker"]);
+        assert_eq!(matrix.cell("api", "frontend").unwrap().skew, VersionSkew::Minor);
+        assert_eq!(matrix.cell("api", "worker").unwrap().skew, VersionSkew::Major);
+        assert_eq!(matrix.cell("auth", "worker").unwrap().skew, VersionSkew::Compatible);
+        let search = matrix.cell("search", "frontend").unwrap();
+        assert_eq!((search.skew, search.version.as_deref()), (VersionSkew::Unknown, None));
+        assert!(matrix.cell("auth", "frontend").is_none());
+        assert_eq!(matrix.count(VersionSkew::Major), 1);
+
+        assert_eq!(
+            matrix.to_csv().unwrap(),
+            "provider,frontend,worker\napi,minor,major\nauth,,compatible\nsearch,unknown,\n"
+        );
+        let html = matrix.to_html();
+        assert!(html.contains("<th>provider \\ consumer</th><th>frontend</th><th>worker</th>"));
+        assert!(html.contains("<td class=\"major\" title=\"1.4.0 at 2.1.0\">major</td>"));
+    }
+}
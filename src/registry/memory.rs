@@ -0,0 +1,317 @@
+//! In-memory registry for unit testing integrations without git or files
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::checks::{alias_warnings, check_cycles, check_dependency, DependencyProblem};
+use crate::registry::service::default_schema_version;
+use crate::registry::{
+    CyclePolicy, DependencyGraph, EdgeMetadata, Registry, Service, ServiceConfig, ServiceState,
+    ServiceStatus, ValidationSummary,
+};
+use crate::schema::service::{normalize_definition, parse_dependencies, Dependency};
+use crate::schema::validation::ValidationService;
+
+/// Registry keeping service definitions in memory
+///
+/// Services are registered from their definition alone, normalized as when loaded from
+/// files; the catalog config is derived from it. Validation only covers dependencies, with
+/// the same checks as [`ServiceRegistry`](crate::registry::ServiceRegistry): missing
+/// services, dependencies on aliases, version constraints against the `version` of the
+/// dependency and cycles under the cycle policy. Definitions are not checked against the
+/// schema.
+#[derive(Default)]
+pub struct InMemoryRegistry {
+    services: BTreeMap<String, Service>,
+    aliases: BTreeMap<String, String>,
+    cycle_policy: CyclePolicy,
+    validation_service: ValidationService,
+}
+
+impl fmt::Debug for InMemoryRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryRegistry")
+            .field("services", &self.services)
+            .field("aliases", &self.aliases)
+            .field("cycle_policy", &self.cycle_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InMemoryRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service from its definition
+    pub fn with_service(mut self, name: &str, definition: serde_json::Value) -> Self {
+        self.insert_service(name, definition);
+        self
+    }
+
+    /// Sets former service names and the names now providing them
+    pub fn with_aliases(mut self, aliases: BTreeMap<String, String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Sets how validation treats circular dependencies
+    pub fn with_cycle_policy(mut self, policy: CyclePolicy) -> Self {
+        self.cycle_policy = policy;
+        self
+    }
+
+    /// Sets the validation service checking dependency versions
+    pub fn with_validation_service(mut self, validation_service: ValidationService) -> Self {
+        self.validation_service = validation_service;
+        self
+    }
+
+    /// Registers or replaces a service from its definition
+    pub fn insert_service(&mut self, name: &str, mut definition: serde_json::Value) {
+        normalize_definition(&mut definition);
+        let field = |key: &str| definition.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let config = ServiceConfig {
+            id: None,
+            namespace: field("namespace"),
+            config_path: format!("{}.json", name),
+            schema_version: field("schema_version").unwrap_or_else(default_schema_version),
+            dependencies: definition.get("dependencies").map(|_| parse_dependencies(&definition)),
+        };
+        let mut service = Service::new(name.to_string(), config);
+        service.schema_data = Some(definition);
+        self.services.insert(name.to_string(), service);
+    }
+
+    /// Removes a service, returning it if it was registered
+    pub fn remove_service(&mut self, name: &str) -> Option<Service> {
+        self.services.remove(name)
+    }
+
+    /// Resolves a former service name to the service now providing it
+    fn resolve_alias<'a>(&'a self, name: &'a str) -> &'a str {
+        let mut current = name;
+        for _ in 0..=self.aliases.len() {
+            if self.services.contains_key(current) {
+                break;
+            }
+            match self.aliases.get(current) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        current
+    }
+
+    /// Gets the dependencies of a service, naming the services now providing aliased ones
+    fn canonical_dependencies(&self, service: &Service) -> Vec<Dependency> {
+        let mut dependencies = service.dependencies();
+        for dependency in &mut dependencies {
+            dependency.service = self.resolve_alias(&dependency.service).to_string();
+        }
+        dependencies
+    }
+
+    /// Checks the dependencies of a service, returning the error failing it if any
+    fn check_dependencies(&self, service: &Service, warnings: &mut Vec<String>) -> Option<String> {
+        let dependencies = self.canonical_dependencies(service);
+        warnings.extend(alias_warnings(&service.dependencies(), &dependencies));
+
+        let mut error = None;
+        for dependency in &dependencies {
+            let provider = self.services.get(&dependency.service);
+            match check_dependency(dependency, provider, false, &self.validation_service) {
+                Some(DependencyProblem::Failing(msg)) => error = Some(msg),
+                Some(DependencyProblem::Warning(msg)) => warnings.push(msg),
+                None => {}
+            }
+        }
+        error
+    }
+
+    /// Builds the graph of dependencies between registered services
+    fn dependency_graph(&self) -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        for (name, service) in &self.services {
+            graph.add_node(name.clone());
+            for dependency in self.canonical_dependencies(service) {
+                if self.services.contains_key(&dependency.service) {
+                    graph.add_edge(
+                        name.clone(),
+                        dependency.service.clone(),
+                        EdgeMetadata {
+                            required: dependency.required,
+                            version_constraint: dependency.version_constraint.clone(),
+                            interaction: dependency.interaction,
+                        },
+                    );
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl Registry for InMemoryRegistry {
+    fn get_service(&self, name: &str) -> Result<&Service> {
+        self.services.get(name).ok_or_else(|| AureaCoreError::ServiceNotFound(name.to_string()))
+    }
+
+    fn list_services(&self) -> Result<Vec<String>> {
+        Ok(self.services.keys().cloned().collect())
+    }
+
+    /// Registers a service from its JSON definition
+    fn register_service(&mut self, name: &str, config: &str) -> Result<()> {
+        let definition = serde_json::from_str(config)
+            .map_err(|e| AureaCoreError::invalid_config("Invalid service config", e))?;
+        self.insert_service(name, definition);
+        Ok(())
+    }
+
+    fn validate_all_services(&mut self) -> Result<ValidationSummary> {
+        let mut summary = ValidationSummary::new();
+
+        let mut statuses = BTreeMap::new();
+        for (name, service) in &self.services {
+            let mut warnings = Vec::new();
+            let error = self.check_dependencies(service, &mut warnings);
+            for warning in &warnings {
+                summary.add_warning(name.clone(), warning.clone());
+            }
+            if let Some(error) = &error {
+                summary.failed.push((name.clone(), error.clone()));
+            }
+            statuses.insert(name.clone(), (error, warnings));
+        }
+
+        let cycles = check_cycles(
+            &self.dependency_graph(),
+            self.cycle_policy,
+            self.services.keys(),
+            self.services.keys(),
+        );
+        for (name, msg) in cycles.failed {
+            summary.failed.push((name.clone(), msg.clone()));
+            if let Some((error, _)) = statuses.get_mut(&name) {
+                error.get_or_insert(msg);
+            }
+        }
+        if let Some(warning) = cycles.warning {
+            summary.add_warning("system".to_string(), warning);
+        }
+
+        for (name, (error, warnings)) in statuses {
+            let status = match error {
+                Some(error) => ServiceStatus::new(ServiceState::Error).with_error(error),
+                None => {
+                    summary.successful.push(name.clone());
+                    ServiceStatus::new(ServiceState::Active)
+                }
+            };
+            if let Some(service) = self.services.get_mut(&name) {
+                service.status = status.with_warnings(warnings);
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::registry::DependencyManager;
+    use crate::schema::validation::ValidationService;
+
+    #[test]
+    fn test_in_memory_registry_with_dependency_manager() {
+        let registry = InMemoryRegistry::new()
+            .with_service(
+                "frontend",
+                json!({
+                    "name": "frontend",
+                    "version": "1.0.0",
+                    "dependencies": [
+                        {"service": "api", "version_constraint": "2.0.0", "required": true},
+                        {"service": "search", "required": false}
+                    ]
+                }),
+            )
+            .with_service("api", json!({"name": "api", "version": "2.1.0"}));
+        let registry = Arc::new(RwLock::new(registry));
+        let manager = DependencyManager::new(registry.clone(), Arc::new(ValidationService::new()));
+
+        assert_eq!(manager.analyze_impact("api").unwrap(), vec!["frontend"]);
+        assert_eq!(
+            manager.resolve_dependencies(&["frontend".to_string()]).unwrap(),
+            vec!["api".to_string(), "frontend".to_string()]
+        );
+
+        let summary = registry.write().unwrap().validate_all_services().unwrap();
+        assert!(summary.is_successful());
+        let warnings = &summary.warnings["frontend"];
+        assert!(warnings.iter().any(|w| w.contains("Minor version incompatibility")));
+        assert!(warnings.iter().any(|w| w.contains("'search' not found")));
+
+        registry.write().unwrap().remove_service("api");
+        let summary = registry.write().unwrap().validate_all_services().unwrap();
+        assert_eq!(
+            summary.failed,
+            vec![("frontend".to_string(), "Required dependency 'api' not found".to_string())]
+        );
+        let registry = registry.read().unwrap();
+        assert_eq!(registry.get_service("frontend").unwrap().status.state, ServiceState::Error);
+    }
+
+    #[test]
+    fn test_in_memory_registry_shares_registry_checks() {
+        let mut registry = InMemoryRegistry::new()
+            .with_service(
+                "frontend",
+                json!({
+                    "name": "frontend",
+                    "version": "1.0.0",
+                    "dependencies": [{"service": "legacy-api", "required": true}]
+                }),
+            )
+            .with_service(
+                "api",
+                json!({
+                    "name": "api",
+                    "version": "1.0.0",
+                    "dependencies": [{"service": "frontend", "required": true}]
+                }),
+            )
+            .with_aliases(BTreeMap::from([("legacy-api".to_string(), "api".to_string())]));
+
+        let summary = registry.validate_all_services().unwrap();
+        assert!(summary.is_successful());
+        assert_eq!(
+            summary.warnings["frontend"],
+            vec!["Dependency 'legacy-api' is an alias of 'api'; depend on 'api' instead"]
+        );
+        assert!(summary.warnings["system"][0].starts_with("Circular dependency detected"));
+
+        let mut registry = registry.with_cycle_policy(CyclePolicy::Fail);
+        let summary = registry.validate_all_services().unwrap();
+        assert_eq!(
+            summary.failed,
+            vec![
+                ("api".to_string(), "Circular dependency: api -> frontend -> api".to_string()),
+                (
+                    "frontend".to_string(),
+                    "Circular dependency: frontend -> api -> frontend".to_string()
+                ),
+            ]
+        );
+        assert!(!summary.warnings.contains_key("system"));
+        assert_eq!(registry.get_service("api").unwrap().status.state, ServiceState::Error);
+    }
+}
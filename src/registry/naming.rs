@@ -0,0 +1,233 @@
+//! Naming restrictions of the platforms services are deployed to
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{AureaCoreError, Result};
+
+/// Namespaces Kubernetes creates and manages itself
+const KUBERNETES_NAMESPACES: &[&str] =
+    &["default", "kube-system", "kube-public", "kube-node-lease"];
+
+/// Longest name Kubernetes accepts for namespaces and services (a DNS label)
+const DNS_LABEL_MAX_LENGTH: usize = 63;
+
+/// Longest service name accepted, in bytes, leaving room for the config extension
+/// within the 255 bytes file systems allow per file name
+pub const MAX_SERVICE_NAME_BYTES: usize = 128;
+
+/// Invisible characters that reorder or hide text: zero-width characters, bidi
+/// controls and the byte order mark
+const INVISIBLE_CHARS: &[(char, char)] = &[
+    ('\u{200B}', '\u{200F}'),
+    ('\u{202A}', '\u{202E}'),
+    ('\u{2060}', '\u{2064}'),
+    ('\u{2066}', '\u{2069}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+];
+
+/// Organization-wide restrictions on service names and namespaces
+///
+/// Catches names that would collide with reserved objects or be rejected by the
+/// downstream platform before anything is deployed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct NamingRules {
+    /// Apply Kubernetes restrictions: system namespaces and the `kube-` prefix are
+    /// reserved, and names and namespaces must be DNS labels
+    pub kubernetes: bool,
+    /// Namespaces services may not be placed in
+    pub reserved_namespaces: Vec<String>,
+    /// Names services may not take
+    pub reserved_names: Vec<String>,
+    /// Longest allowed service name
+    pub max_name_length: Option<usize>,
+}
+
+impl NamingRules {
+    /// Creates rules applying the Kubernetes restrictions
+    pub fn kubernetes() -> Self {
+        Self { kubernetes: true, ..Self::default() }
+    }
+
+    /// Loads naming rules from a YAML file
+    pub fn load(path: &Path) -> Result<Self> {
+        serde_yaml::from_str(&fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid naming rules {}", path.display()), e)
+        })
+    }
+
+    /// Checks a service name and namespace, describing each violation and how to fix it
+    pub fn check(&self, name: &str, namespace: Option<&str>) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if self.reserved_names.iter().any(|reserved| reserved == name) {
+            violations.push(format!(
+                "Service name '{}' is reserved by the organization; choose another name",
+                name
+            ));
+        }
+        if let Some(max) = self.max_name_length.filter(|max| name.len() > *max) {
+            violations.push(format!(
+                "Service name '{}' is {} characters long, at most {} are allowed; shorten it",
+                name,
+                name.len(),
+                max
+            ));
+        }
+        if self.kubernetes {
+            if let Some(reason) = dns_label_violation(name) {
+                violations.push(format!(
+                    "Service name '{}' {}; rename it to e.g. '{}'",
+                    name,
+                    reason,
+                    to_dns_label(name)
+                ));
+            }
+        }
+
+        let Some(namespace) = namespace else {
+            return violations;
+        };
+        if self.reserved_namespaces.iter().any(|reserved| reserved == namespace) {
+            violations.push(format!(
+                "Namespace '{}' is reserved by the organization; move the service to a team namespace",
+                namespace
+            ));
+        }
+        if self.kubernetes {
+            if KUBERNETES_NAMESPACES.contains(&namespace) || namespace.starts_with("kube-") {
+                violations.push(format!(
+                    "Namespace '{}' is reserved by Kubernetes; move the service to a team namespace",
+                    namespace
+                ));
+            } else if let Some(reason) = dns_label_violation(namespace) {
+                violations.push(format!(
+                    "Namespace '{}' {}; rename it to e.g. '{}'",
+                    namespace,
+                    reason,
+                    to_dns_label(namespace)
+                ));
+            }
+        }
+        violations
+    }
+}
+
+/// Checks a service name can safely name its config file, `<name>.json`
+///
+/// Rejects empty and overly long names, path separators, `..`, names starting with a dot,
+/// and control or invisible characters. Applies whatever the [`NamingRules`].
+pub fn check_service_name(name: &str) -> Result<()> {
+    let reason = if name.is_empty() {
+        "must not be empty".to_string()
+    } else if name.len() > MAX_SERVICE_NAME_BYTES {
+        format!("is {} bytes long, at most {} are allowed", name.len(), MAX_SERVICE_NAME_BYTES)
+    } else if name.contains(['/', '\\']) {
+        "must not contain path separators".to_string()
+    } else if name.contains("..") {
+        "must not contain '..'".to_string()
+    } else if name.starts_with('.') {
+        "must not start with '.'".to_string()
+    } else if name.chars().any(char::is_control) {
+        "must not contain control characters".to_string()
+    } else if name.chars().any(|c| INVISIBLE_CHARS.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)))
+    {
+        "must not contain invisible or bidirectional formatting characters".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(AureaCoreError::InvalidName(format!("{:?} {}", name, reason)))
+}
+
+/// Explains why a value is not an RFC 1123 DNS label, if it is not
+fn dns_label_violation(value: &str) -> Option<&'static str> {
+    if value.is_empty() {
+        Some("is empty")
+    } else if value.len() > DNS_LABEL_MAX_LENGTH {
+        Some("is longer than the 63 characters of a DNS label")
+    } else if !value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+        Some("may only contain lowercase letters, digits and '-'")
+    } else if value.starts_with('-') || value.ends_with('-') {
+        Some("must start and end with a letter or digit")
+    } else {
+        None
+    }
+}
+
+/// Converts a value into a DNS label to suggest as replacement
+fn to_dns_label(value: &str) -> String {
+    let mut label = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() {
+            label.push(c.to_ascii_lowercase());
+        } else if !label.ends_with('-') {
+            label.push('-');
+        }
+    }
+    label.truncate(DNS_LABEL_MAX_LENGTH);
+    label.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naming_rules() {
+        let rules: NamingRules = serde_yaml::from_str(
+            "kubernetes: true\nreserved_namespaces: [platform]\nreserved_names: [admin]\nmax_name_length: 20\n",
+        )
+        .unwrap();
+        assert!(rules.check("billing", Some("payments")).is_empty());
+        assert!(rules.check("billing", None).is_empty());
+
+        let violations = rules.check("Billing_API", Some("kube-system"));
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].contains("rename it to e.g. 'billing-api'"));
+        assert!(violations[1].contains("reserved by Kubernetes"));
+
+        assert!(rules.check("admin", Some("platform"))[0].contains("reserved by the organization"));
+        assert_eq!(rules.check("admin", Some("platform")).len(), 2);
+        assert!(rules.check("a-very-long-service-name", None)[0].contains("at most 20"));
+        assert!(rules.check("billing", Some("default"))[0].contains("reserved by Kubernetes"));
+
+        // Without configured rules any name is accepted
+        assert!(NamingRules::default().check("Billing_API", Some("kube-system")).is_empty());
+    }
+
+    #[test]
+    fn test_check_service_name() {
+        for name in ["billing", "billing-v2", "billing_api.internal", "café", "支付"] {
+            assert!(check_service_name(name).is_ok(), "{}", name);
+        }
+        let hostile = [
+            "",
+            "..",
+            ".git",
+            "../etc/passwd",
+            "a/b",
+            "a\\b",
+            "a..b",
+            "bill\0ing",
+            "bill\ning",
+            "bill\u{202E}gnin",
+            "bill\u{200B}ing",
+        ];
+        for name in hostile {
+            let err = check_service_name(name).unwrap_err();
+            assert!(matches!(err, AureaCoreError::InvalidName(_)), "{:?}", name);
+        }
+        assert!(check_service_name(&"a".repeat(MAX_SERVICE_NAME_BYTES)).is_ok());
+        let err = check_service_name(&"é".repeat(MAX_SERVICE_NAME_BYTES / 2 + 1)).unwrap_err();
+        assert!(err.to_string().contains("at most 128"));
+        // Control characters are escaped in the message rather than printed
+        let err = check_service_name("bill\ning").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid service name: \"bill\\ning\" must not contain control characters"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Notifying the owners of dependent services when a service changes
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::Duration;
+
+use semver::Version;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::Service;
+use crate::schema::validation::{ValidationService, VersionCompatibility};
+
+/// A value of a service definition that changed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// JSON Pointer to the value, e.g. `/endpoints`
+    pub path: String,
+    /// Value before the change, if it was set
+    pub before: Option<Value>,
+    /// Value after the change, if it is still set
+    pub after: Option<Value>,
+}
+
+/// Notice to the owner of a service that a service it depends on changed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImpactNotification {
+    /// Service that changed
+    pub service: String,
+    /// Service depending on the changed service
+    pub dependent: String,
+    /// Owner of the dependent service, if declared
+    pub owner: Option<String>,
+    /// Whether the dependent depends on the changed service directly
+    pub direct: bool,
+    /// Whether the change is likely to break the dependent
+    pub breaking: bool,
+    /// Why the change is considered breaking
+    pub reasons: Vec<String>,
+    /// Changes to the definition of the changed service; empty if it was removed
+    pub changes: Vec<FieldChange>,
+}
+
+/// Delivers impact notifications, e.g. to a chat channel or ticketing system
+pub trait Notifier: Send + Sync {
+    /// Delivers a notification
+    fn notify(&self, notification: &ImpactNotification) -> Result<()>;
+}
+
+/// Notifier posting each notification as JSON to an HTTP endpoint
+pub struct WebhookNotifier {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl WebhookNotifier {
+    /// Creates a notifier posting to `url`, waiting at most `timeout` per notification
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Self {
+        Self { url: url.into(), agent: ureq::AgentBuilder::new().timeout(timeout).build() }
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify(&self, notification: &ImpactNotification) -> Result<()> {
+        self.agent.post(&self.url).send_json(notification).map_err(|e| {
+            AureaCoreError::Internal(format!("Failed to notify {}: {}", self.url, e))
+        })?;
+        Ok(())
+    }
+}
+
+/// Computes the values that differ between two definitions
+///
+/// Objects are compared member by member; other values, including arrays, are
+/// reported as a whole.
+pub fn diff_definitions(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    diff_values("", Some(before), Some(after), &mut changes);
+    changes
+}
+
+fn diff_values(
+    path: &str,
+    before: Option<&Value>,
+    after: Option<&Value>,
+    out: &mut Vec<FieldChange>,
+) {
+    match (before, after) {
+        (Some(Value::Object(before)), Some(Value::Object(after))) => {
+            let keys: std::collections::BTreeSet<&String> =
+                before.keys().chain(after.keys()).collect();
+            for key in keys {
+                let path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                diff_values(&path, before.get(key), after.get(key), out);
+            }
+        }
+        (before, after) if before != after => out.push(FieldChange {
+            path: path.to_string(),
+            before: before.cloned(),
+            after: after.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+/// Finds the services depending on `name`, directly or transitively, sorted by name
+fn dependents(services: &HashMap<String, Service>, name: &str) -> BTreeMap<String, bool> {
+    let mut found = BTreeMap::new();
+    let mut queue = VecDeque::from([(name.to_string(), true)]);
+    while let Some((current, direct)) = queue.pop_front() {
+        for (dependent, service) in services {
+            if dependent == name || found.contains_key(dependent) {
+                continue;
+            }
+            if service.dependencies().iter().any(|dep| dep.service == current) {
+                found.insert(dependent.clone(), direct);
+                queue.push_back((dependent.clone(), false));
+            }
+        }
+    }
+    found
+}
+
+/// Explains why a change to a definition is likely to break its dependents
+fn breaking_reasons(before: &Value, after: Option<&Value>) -> Vec<String> {
+    let Some(after) = after else {
+        return vec!["The service was removed from the catalog".to_string()];
+    };
+    let mut reasons = Vec::new();
+    let version =
+        |definition: &Value| definition.get("version").and_then(|v| v.as_str()).map(str::to_string);
+    if let (Some(from), Some(to)) = (version(before), version(after)) {
+        if let (Ok(old), Ok(new)) = (Version::parse(&from), Version::parse(&to)) {
+            if new.major != old.major {
+                reasons.push(format!("Major version changed from {} to {}", from, to));
+            }
+        }
+    }
+    let endpoints = |definition: &Value| -> Vec<String> {
+        let endpoints = definition.get("endpoints").and_then(|e| e.as_array());
+        endpoints
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.get("name").and_then(|n| n.as_str()).map(str::to_string))
+            .collect()
+    };
+    let remaining = endpoints(after);
+    for endpoint in endpoints(before) {
+        if !remaining.contains(&endpoint) {
+            reasons.push(format!("Endpoint '{}' was removed", endpoint));
+        }
+    }
+    reasons
+}
+
+/// Builds the notifications for the dependents of a service whose definition changed
+///
+/// `after` is `None` if the service was removed. Direct dependents are also warned when
+/// the new version no longer satisfies their constraint.
+pub(crate) fn impact_notifications(
+    services: &HashMap<String, Service>,
+    validation_service: &ValidationService,
+    name: &str,
+    before: &Value,
+    after: Option<&Value>,
+) -> Vec<ImpactNotification> {
+    let changes = after.map(|after| diff_definitions(before, after)).unwrap_or_default();
+    let reasons = breaking_reasons(before, after);
+    let version = after.and_then(|after| after.get("version")).and_then(|v| v.as_str());
+
+    let mut notifications = Vec::new();
+    for (dependent, direct) in dependents(services, name) {
+        let service = &services[&dependent];
+        let mut reasons = reasons.clone();
+        let dependencies = service.dependencies();
+        let dependency = dependencies.iter().find(|dep| dep.service == name);
+        if let (Some(dependency), Some(version)) = (dependency, version) {
+            if let Some(constraint) = &dependency.version_constraint {
+                let compatibility =
+                    validation_service.check_dependency_version(dependency, version, constraint);
+                if compatibility == VersionCompatibility::MajorIncompatible {
+                    reasons.push(format!(
+                        "Version {} no longer satisfies the constraint {} of '{}'",
+                        version, constraint, dependent
+                    ));
+                }
+            }
+        }
+        let owner =
+            service.schema_data.as_ref().and_then(|d| d.get("owner")).and_then(|o| o.as_str());
+        notifications.push(ImpactNotification {
+            service: name.to_string(),
+            dependent: dependent.clone(),
+            owner: owner.map(str::to_string),
+            direct,
+            breaking: !reasons.is_empty(),
+            reasons,
+            changes: changes.clone(),
+        });
+    }
+    notifications
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_definitions() {
+        let before = json!({
+            "name": "ledger",
+            "version": "1.2.0",
+            "metadata": {"tier": 1, "team": "payments"},
+            "endpoints": [{"name": "api", "path": "/api"}]
+        });
+        let after = json!({
+            "name": "ledger",
+            "version": "2.0.0",
+            "metadata": {"tier": 2, "a/b": true},
+            "endpoints": []
+        });
+        let changes = diff_definitions(&before, &after);
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["/endpoints", "/metadata/a~1b", "/metadata/team", "/metadata/tier", "/version"]
+        );
+        assert_eq!(changes[2].after, None);
+        assert_eq!(changes[4].before, Some(json!("1.2.0")));
+        assert!(diff_definitions(&before, &before).is_empty());
+
+        assert_eq!(
+            breaking_reasons(&before, Some(&after)),
+            vec!["Major version changed from 1.2.0 to 2.0.0", "Endpoint 'api' was removed"]
+        );
+        assert!(breaking_reasons(&before, Some(&before)).is_empty());
+        assert_eq!(breaking_reasons(&before, None).len(), 1);
+    }
+}
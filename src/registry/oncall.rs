@@ -0,0 +1,254 @@
+//! Responders on call for services, resolved through the platforms hosting their schedules
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::oncall::OncallPlatform;
+
+/// Base URL of the PagerDuty REST API
+const PAGERDUTY_API_URL: &str = "https://api.pagerduty.com";
+
+/// Base URL of the Opsgenie REST API, in the US region
+const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+
+/// A person currently on call for a schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct OncallContact {
+    /// Display name of the responder
+    pub name: String,
+    /// Email address of the responder
+    pub email: Option<String>,
+    /// Escalation level the responder is on call for, 1 being the first responder
+    pub escalation_level: u32,
+}
+
+/// Resolves who is on call through an on-call platform's API
+#[async_trait]
+pub trait OncallProvider: Send + Sync {
+    /// Platform whose schedules this provider resolves
+    fn platform(&self) -> OncallPlatform;
+
+    /// Gets the responders currently on call for a schedule
+    async fn current_oncall(&self, schedule_id: &str) -> Result<Vec<OncallContact>>;
+}
+
+/// Resolves on-call schedules with the PagerDuty REST API
+pub struct PagerDutyProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_token: String,
+}
+
+impl PagerDutyProvider {
+    /// Creates a provider authenticating with a REST API key
+    pub fn new(api_token: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: PAGERDUTY_API_URL.to_string(),
+            api_token: api_token.into(),
+        }
+    }
+
+    /// Talks to the EU service region, or a test double, instead
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl OncallProvider for PagerDutyProvider {
+    fn platform(&self) -> OncallPlatform {
+        OncallPlatform::PagerDuty
+    }
+
+    async fn current_oncall(&self, schedule_id: &str) -> Result<Vec<OncallContact>> {
+        let url = format!("{}/oncalls", self.base_url);
+        let request = self
+            .client
+            .get(&url)
+            .query(&[("schedule_ids[]", schedule_id), ("include[]", "users")])
+            .header(reqwest::header::ACCEPT, "application/vnd.pagerduty+json;version=2")
+            .header(reqwest::header::AUTHORIZATION, format!("Token token={}", self.api_token));
+        let body = send(request, self.platform(), &url).await?;
+
+        let mut contacts: Vec<OncallContact> = body["oncalls"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|oncall| {
+                let user = &oncall["user"];
+                OncallContact {
+                    name: user["name"]
+                        .as_str()
+                        .or(user["summary"].as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    email: user["email"].as_str().map(str::to_string),
+                    escalation_level: oncall["escalation_level"].as_u64().unwrap_or(1) as u32,
+                }
+            })
+            .collect();
+        contacts.sort_by_key(|contact| contact.escalation_level);
+        contacts.dedup();
+        Ok(contacts)
+    }
+}
+
+/// Resolves on-call schedules with the Opsgenie REST API
+pub struct OpsgenieProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl OpsgenieProvider {
+    /// Creates a provider authenticating with an API integration key
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: OPSGENIE_API_URL.to_string(),
+            api_key: api_key.into(),
+        }
+    }
+
+    /// Talks to the EU region, e.g. `https://api.eu.opsgenie.com`, or a test double, instead
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl OncallProvider for OpsgenieProvider {
+    fn platform(&self) -> OncallPlatform {
+        OncallPlatform::Opsgenie
+    }
+
+    /// Opsgenie only names the users on call, which are their email addresses, and
+    /// reports them all as first responders
+    async fn current_oncall(&self, schedule_id: &str) -> Result<Vec<OncallContact>> {
+        let url = format!("{}/v2/schedules/{}/on-calls", self.base_url, schedule_id);
+        let request = self
+            .client
+            .get(&url)
+            .query(&[("scheduleIdentifierType", "id"), ("flat", "true")])
+            .header(reqwest::header::AUTHORIZATION, format!("GenieKey {}", self.api_key));
+        let body = send(request, self.platform(), &url).await?;
+
+        Ok(body["data"]["onCallRecipients"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|recipient| OncallContact {
+                name: recipient.to_string(),
+                email: recipient.contains('@').then(|| recipient.to_string()),
+                escalation_level: 1,
+            })
+            .collect())
+    }
+}
+
+/// Sends a request to an on-call platform, failing on unsuccessful answers
+async fn send(
+    request: reqwest::RequestBuilder,
+    platform: OncallPlatform,
+    url: &str,
+) -> Result<Value> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AureaCoreError::Service(format!("{} {}: {}", platform, url, e)))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AureaCoreError::Service(format!(
+            "{} {} returned HTTP {}",
+            platform,
+            url,
+            status.as_u16()
+        )));
+    }
+    response.json().await.map_err(|e| {
+        AureaCoreError::Service(format!("Invalid {} response from {}: {}", platform, url, e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::extract::{Path, Query};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::json;
+
+    use super::*;
+
+    fn authorized(headers: &HeaderMap, expected: &str) -> bool {
+        headers.get("authorization").and_then(|v| v.to_str().ok()) == Some(expected)
+    }
+
+    #[tokio::test]
+    async fn test_current_oncall() {
+        let router = Router::new()
+            .route(
+                "/oncalls",
+                get(|headers: HeaderMap, Query(query): Query<HashMap<String, String>>| async move {
+                    if !authorized(&headers, "Token token=s3cret") {
+                        return Err(StatusCode::UNAUTHORIZED);
+                    }
+                    match query.get("schedule_ids[]").map(String::as_str) {
+                        Some("PX1ABC2") => Ok(Json(json!({"oncalls": [
+                            {"escalation_level": 2, "user": {"name": "Grace", "email": "grace@example.com"}},
+                            {"escalation_level": 1, "user": {"name": "Ada", "email": "ada@example.com"}},
+                        ]}))),
+                        _ => Err(StatusCode::NOT_FOUND),
+                    }
+                }),
+            )
+            .route(
+                "/v2/schedules/{id}/on-calls",
+                get(|headers: HeaderMap, Path(id): Path<String>| async move {
+                    if !authorized(&headers, "GenieKey s3cret") {
+                        return Err(StatusCode::UNAUTHORIZED);
+                    }
+                    match id.as_str() {
+                        "d875e654-9b4e-4219-a803-0c9a8f9b1f7e" => Ok(Json(json!({
+                            "data": {"onCallRecipients": ["ada@example.com"]}
+                        }))),
+                        _ => Err(StatusCode::NOT_FOUND),
+                    }
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let pagerduty = PagerDutyProvider::new("s3cret").with_base_url(&base);
+        let contacts = pagerduty.current_oncall("PX1ABC2").await.unwrap();
+        let names: Vec<_> =
+            contacts.iter().map(|c| (c.name.as_str(), c.escalation_level)).collect();
+        assert_eq!(names, vec![("Ada", 1), ("Grace", 2)]);
+        assert_eq!(contacts[0].email.as_deref(), Some("ada@example.com"));
+        let err = pagerduty.current_oncall("PUNKNWN").await.unwrap_err();
+        assert!(err.to_string().contains("HTTP 404"));
+
+        let opsgenie = OpsgenieProvider::new("s3cret").with_base_url(&base);
+        let contacts =
+            opsgenie.current_oncall("d875e654-9b4e-4219-a803-0c9a8f9b1f7e").await.unwrap();
+        assert_eq!(
+            contacts,
+            vec![OncallContact {
+                name: "ada@example.com".to_string(),
+                email: Some("ada@example.com".to_string()),
+                escalation_level: 1,
+            }]
+        );
+
+        let unauthorized = OpsgenieProvider::new("wrong").with_base_url(&base);
+        assert!(unauthorized.current_oncall("d875e654-9b4e-4219-a803-0c9a8f9b1f7e").await.is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Compact overview of the catalog, for `aureacore status` and dashboards
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Number of distinct warnings an overview lists
+pub const TOP_WARNINGS: usize = 5;
+
+/// Days after which a service that hasn't been checked is reported as stale
+pub const DEFAULT_STALE_AFTER_DAYS: i64 = 30;
+
+/// A warning and the number of services reporting it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WarningCount {
+    /// Warning message
+    pub warning: String,
+    /// Number of services reporting the warning
+    pub services: usize,
+}
+
+/// A service whose status hasn't been checked for a while
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaleService {
+    /// Name of the service
+    pub service: String,
+    /// Last time its status was checked
+    pub last_checked: DateTime<Utc>,
+}
+
+/// One-screen summary of the state of the catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CatalogOverview {
+    /// Commit the catalog was last synced to, if checked out
+    pub revision: Option<String>,
+    /// Time of the last successful sync, if any
+    pub last_sync: Option<DateTime<Utc>>,
+    /// Number of services, archived ones included
+    pub total: usize,
+    /// Number of services per state
+    pub states: BTreeMap<String, usize>,
+    /// Most frequent warnings, most reported first
+    pub top_warnings: Vec<WarningCount>,
+    /// Dependency cycles, each starting and ending with the same service
+    pub cycles: Vec<Vec<String>>,
+    /// Services not checked within the staleness threshold, least recently checked first
+    pub stale: Vec<StaleService>,
+}
+
+impl CatalogOverview {
+    /// Counts warnings by message, keeping the `TOP_WARNINGS` most reported
+    pub(crate) fn set_top_warnings<'a>(&mut self, warnings: impl IntoIterator<Item = &'a str>) {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for warning in warnings {
+            *counts.entry(warning).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        // Stable sort keeps equally frequent warnings in alphabetical order
+        counts.sort_by_key(|(_, services)| std::cmp::Reverse(*services));
+        self.top_warnings = counts
+            .into_iter()
+            .take(TOP_WARNINGS)
+            .map(|(warning, services)| WarningCount { warning: warning.to_string(), services })
+            .collect();
+    }
+
+    /// Adds a cycle unless the same cycle, entered at another service, is known
+    pub(crate) fn add_cycle(&mut self, cycle: Vec<String>) {
+        let members = |cycle: &[String]| {
+            let mut members = cycle[..cycle.len().saturating_sub(1)].to_vec();
+            members.sort();
+            members
+        };
+        let new = members(&cycle);
+        if !self.cycles.iter().any(|known| members(known) == new) {
+            self.cycles.push(cycle);
+        }
+    }
+}
+
+impl fmt::Display for CatalogOverview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let revision = self.revision.as_deref().map(|r| &r[..r.len().min(7)]);
+        let last_sync = self.last_sync.map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+        writeln!(
+            f,
+            "Revision: {} (synced {})",
+            revision.unwrap_or("none"),
+            last_sync.as_deref().unwrap_or("never")
+        )?;
+
+        let states: Vec<String> =
+            self.states.iter().map(|(state, count)| format!("{} {}", count, state)).collect();
+        if states.is_empty() {
+            writeln!(f, "Services: {}", self.total)?;
+        } else {
+            writeln!(f, "Services: {} ({})", self.total, states.join(", "))?;
+        }
+
+        if !self.top_warnings.is_empty() {
+            writeln!(f, "Top warnings:")?;
+            for count in &self.top_warnings {
+                writeln!(f, "  {:>4}  {}", count.services, count.warning)?;
+            }
+        }
+
+        if self.cycles.is_empty() {
+            writeln!(f, "Cycles: none")?;
+        } else {
+            writeln!(f, "Cycles:")?;
+            for cycle in &self.cycles {
+                writeln!(f, "  {}", cycle.join(" -> "))?;
+            }
+        }
+
+        if self.stale.is_empty() {
+            writeln!(f, "Stale services: none")?;
+        } else {
+            writeln!(f, "Stale services:")?;
+            for stale in &self.stale {
+                writeln!(
+                    f,
+                    "  {} (last checked {})",
+                    stale.service,
+                    stale.last_checked.format("%Y-%m-%d")
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
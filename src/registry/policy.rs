@@ -0,0 +1,129 @@
+//! Policies escalating validation warnings to failures per namespace and tier,
+//! circular dependencies to failures, and windows for re-confirming dependencies
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{AureaCoreError, Result};
+
+/// Maximum number of validation warnings allowed for a set of namespaces and tiers
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct WarningPolicy {
+    /// Name of the policy, quoted in failures
+    pub name: String,
+    /// Namespaces the policy applies to, all namespaces if empty
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Service tiers (the `tier` metadata value) the policy applies to, all tiers if empty
+    #[serde(default)]
+    pub tiers: Vec<String>,
+    /// Warnings a service may have before it fails validation
+    pub max_warnings: usize,
+}
+
+impl WarningPolicy {
+    /// Checks whether the policy applies to a service in the given namespace and tier
+    pub fn applies_to(&self, namespace: Option<&str>, tier: Option<&str>) -> bool {
+        let matches = |allowed: &[String], value: Option<&str>| {
+            allowed.is_empty() || value.is_some_and(|v| allowed.iter().any(|a| a == v))
+        };
+        matches(&self.namespaces, namespace) && matches(&self.tiers, tier)
+    }
+}
+
+/// How validation treats circular dependencies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CyclePolicy {
+    /// Report cycles as a catalog-wide warning
+    #[default]
+    Warn,
+    /// Fail every service on a cycle
+    Fail,
+    /// Fail services on a cycle of required dependencies, warn about other cycles
+    FailRequired,
+}
+
+/// Warning policies enforced by a registry during validation
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WarningPolicies {
+    /// Configured policies
+    #[serde(default)]
+    pub policies: Vec<WarningPolicy>,
+    /// How circular dependencies are treated
+    #[serde(default)]
+    pub cycles: CyclePolicy,
+    /// Days after their last verification that dependencies without a `review_by` date
+    /// are due for review, never if unset
+    #[serde(default)]
+    pub dependency_review_days: Option<u32>,
+}
+
+impl WarningPolicies {
+    /// Creates a set of warning policies
+    pub fn new(policies: Vec<WarningPolicy>) -> Self {
+        Self { policies, cycles: CyclePolicy::default(), dependency_review_days: None }
+    }
+
+    /// Sets how circular dependencies are treated
+    pub fn with_cycle_policy(mut self, cycles: CyclePolicy) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Sets how many days after their last verification dependencies are due for review
+    pub fn with_dependency_review_days(mut self, days: u32) -> Self {
+        self.dependency_review_days = Some(days);
+        self
+    }
+
+    /// Loads warning policies from a YAML file
+    pub fn load(path: &Path) -> Result<Self> {
+        serde_yaml::from_str(&fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid warning policy {}", path.display()), e)
+        })
+    }
+
+    /// Gets the strictest policy applying to a service in the given namespace and tier
+    pub fn policy_for(
+        &self,
+        namespace: Option<&str>,
+        tier: Option<&str>,
+    ) -> Option<&WarningPolicy> {
+        self.policies
+            .iter()
+            .filter(|policy| policy.applies_to(namespace, tier))
+            .min_by_key(|policy| policy.max_warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strictest_policy_applies() {
+        let policies: WarningPolicies = serde_yaml::from_str(
+            "policies:\n\
+             - name: gold\n  tiers: [gold]\n  max_warnings: 0\n\
+             - name: payments\n  namespaces: [payments]\n  max_warnings: 3\n",
+        )
+        .unwrap();
+
+        assert_eq!(policies.policy_for(Some("payments"), Some("gold")).unwrap().name, "gold");
+        assert_eq!(policies.policy_for(Some("payments"), Some("bronze")).unwrap().name, "payments");
+        assert_eq!(policies.policy_for(None, Some("gold")).unwrap().max_warnings, 0);
+        assert!(policies.policy_for(Some("search"), None).is_none());
+        assert_eq!(policies.cycles, CyclePolicy::Warn);
+
+        let policies: WarningPolicies = serde_yaml::from_str("cycles: fail-required\n").unwrap();
+        assert_eq!(policies.cycles, CyclePolicy::FailRequired);
+        assert_eq!(policies.dependency_review_days, None);
+
+        let policies: WarningPolicies =
+            serde_yaml::from_str("dependency_review_days: 180\n").unwrap();
+        assert_eq!(policies.dependency_review_days, Some(180));
+    }
+}
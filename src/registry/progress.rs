@@ -0,0 +1,124 @@
+//! Progress of long-running registry operations, for progress bars and streaming clients
+
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::error::Result;
+
+/// Observes the progress of a long-running operation
+///
+/// Operations report from the thread running them, so implementations should return
+/// quickly.
+pub trait ProgressReporter: Send + Sync {
+    /// Called once when `operation` starts
+    fn started(&self, operation: &str);
+
+    /// Called as the operation advances, with the percentage done from 0 to 100
+    fn progress(&self, percent: u8, message: &str);
+
+    /// Called once when `operation` ends, successfully or not
+    fn finished(&self, operation: &str, success: bool);
+}
+
+/// Reporter ignoring all progress
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn started(&self, _operation: &str) {}
+
+    fn progress(&self, _percent: u8, _message: &str) {}
+
+    fn finished(&self, _operation: &str, _success: bool) {}
+}
+
+/// A progress report, as sent through a channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum ProgressEvent {
+    /// An operation started
+    Started { operation: String },
+    /// The running operation advanced
+    Progress { percent: u8, message: String },
+    /// An operation ended
+    Finished { operation: String, success: bool },
+}
+
+/// Forwards progress to a channel, e.g. to stream it to API clients
+///
+/// Reports are dropped once the receiver is gone.
+impl ProgressReporter for UnboundedSender<ProgressEvent> {
+    fn started(&self, operation: &str) {
+        let _ = self.send(ProgressEvent::Started { operation: operation.to_string() });
+    }
+
+    fn progress(&self, percent: u8, message: &str) {
+        let _ = self.send(ProgressEvent::Progress { percent, message: message.to_string() });
+    }
+
+    fn finished(&self, operation: &str, success: bool) {
+        let _ = self.send(ProgressEvent::Finished { operation: operation.to_string(), success });
+    }
+}
+
+/// Computes the percentage of `done` out of `total`, counting nothing to do as done
+pub(crate) fn percent(done: usize, total: usize) -> u8 {
+    if total == 0 {
+        return 100;
+    }
+    (done.min(total) * 100 / total) as u8
+}
+
+/// Runs `f` between the started and finished reports of `operation`
+pub(crate) fn report<T>(
+    reporter: &dyn ProgressReporter,
+    operation: &str,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    reporter.started(operation);
+    let result = f();
+    reporter.finished(operation, result.is_ok());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AureaCoreError;
+
+    #[test]
+    fn test_channel_reporter() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        report(&sender, "Loading services", || {
+            sender.progress(percent(1, 3), "Loading a");
+            Ok(())
+        })
+        .unwrap();
+        let failed: Result<()> = report(&sender, "Validating services", || {
+            Err(AureaCoreError::Internal("boom".to_string()))
+        });
+        assert!(failed.is_err());
+
+        let mut events = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(
+            events,
+            vec![
+                ProgressEvent::Started { operation: "Loading services".to_string() },
+                ProgressEvent::Progress { percent: 33, message: "Loading a".to_string() },
+                ProgressEvent::Finished {
+                    operation: "Loading services".to_string(),
+                    success: true
+                },
+                ProgressEvent::Started { operation: "Validating services".to_string() },
+                ProgressEvent::Finished {
+                    operation: "Validating services".to_string(),
+                    success: false
+                },
+            ]
+        );
+        assert_eq!(percent(0, 0), 100);
+    }
+}
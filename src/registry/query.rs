@@ -0,0 +1,364 @@
+//! A small query language over the dependency graph
+//!
+//! Queries are function calls naming a traversal, a start service and options:
+//!
+//! - `deps(checkout)` lists everything `checkout` transitively depends on
+//! - `dependents(billing, depth<=1)` lists the direct dependents of `billing`
+//! - `path(checkout, ledger, required_only)` finds the shortest dependency path
+//!
+//! Options are `depth<=N` (also `depth<N` and `depth=N`) to bound traversal depth,
+//! `required_only` to follow only required dependencies and `interaction=KIND` to follow
+//! only dependencies of the given kinds, e.g. `interaction=sync-call` for synchronous call
+//! chains (several kinds are separated by `|`).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::dependency::DependencyGraph;
+use crate::schema::service::Interaction;
+
+/// Traversal performed by a graph query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Traversal {
+    /// Services the start service transitively depends on
+    Dependencies,
+    /// Services transitively depending on the start service
+    Dependents,
+    /// Shortest dependency path from the start service to a target
+    Path(String),
+}
+
+/// A parsed query over the dependency graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphQuery {
+    /// Traversal to perform
+    pub traversal: Traversal,
+    /// Service the traversal starts from
+    pub service: String,
+    /// Maximum number of edges to follow
+    pub max_depth: Option<usize>,
+    /// Whether to follow only required dependencies
+    pub required_only: bool,
+    /// Interaction kinds of the dependencies to follow, all kinds if empty
+    pub interactions: Vec<Interaction>,
+}
+
+/// A service reached by a graph query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryMatch {
+    /// Name of the service
+    pub service: String,
+    /// Number of edges between the start service and this one
+    pub depth: usize,
+    /// Services on the way from the start service, both ends included
+    pub path: Vec<String>,
+}
+
+impl GraphQuery {
+    /// Creates a query listing the transitive dependencies of a service
+    pub fn deps(service: &str) -> Self {
+        Self::new(Traversal::Dependencies, service)
+    }
+
+    /// Creates a query listing the transitive dependents of a service
+    pub fn dependents(service: &str) -> Self {
+        Self::new(Traversal::Dependents, service)
+    }
+
+    /// Creates a query finding the shortest dependency path between two services
+    pub fn path(from: &str, to: &str) -> Self {
+        Self::new(Traversal::Path(to.to_string()), from)
+    }
+
+    fn new(traversal: Traversal, service: &str) -> Self {
+        Self {
+            traversal,
+            service: service.to_string(),
+            max_depth: None,
+            required_only: false,
+            interactions: Vec::new(),
+        }
+    }
+
+    /// Limits the number of edges followed
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Follows only required dependencies
+    pub fn required_only(mut self) -> Self {
+        self.required_only = true;
+        self
+    }
+
+    /// Follows dependencies of the given interaction kind, in addition to kinds added before
+    pub fn interaction(mut self, interaction: Interaction) -> Self {
+        if !self.interactions.contains(&interaction) {
+            self.interactions.push(interaction);
+        }
+        self
+    }
+
+    /// Runs the query against a dependency graph
+    ///
+    /// Matches are ordered by depth, then name. Path queries return the services
+    /// along the path, or nothing if there is no path.
+    pub fn execute(&self, graph: &DependencyGraph) -> Result<Vec<QueryMatch>> {
+        let mut services = vec![&self.service];
+        if let Traversal::Path(target) = &self.traversal {
+            services.push(target);
+        }
+        for service in services {
+            if !graph.adjacency_list.contains_key(service) {
+                return Err(AureaCoreError::ServiceNotFound(service.clone()));
+            }
+        }
+
+        let edges = self.edges(graph);
+        let mut paths: HashMap<&str, Vec<String>> = HashMap::new();
+        paths.insert(&self.service, vec![self.service.clone()]);
+        let mut queue = VecDeque::from([self.service.as_str()]);
+        let mut order = Vec::new();
+
+        while let Some(node) = queue.pop_front() {
+            let path = paths[node].clone();
+            if self.max_depth.is_some_and(|max| path.len() > max) {
+                continue;
+            }
+            let mut neighbors: Vec<&str> = edges.get(node).cloned().unwrap_or_default();
+            neighbors.sort_unstable();
+            for neighbor in neighbors {
+                if paths.contains_key(neighbor) {
+                    continue;
+                }
+                let mut next = path.clone();
+                next.push(neighbor.to_string());
+                paths.insert(neighbor, next);
+                order.push(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+
+        let to_match = |service: &str, path: &Vec<String>| QueryMatch {
+            service: service.to_string(),
+            depth: path.len() - 1,
+            path: path.clone(),
+        };
+        Ok(match &self.traversal {
+            Traversal::Path(target) => match paths.get(target.as_str()) {
+                Some(path) => {
+                    path.iter().map(|service| to_match(service, &paths[service.as_str()])).collect()
+                }
+                None => Vec::new(),
+            },
+            _ => order.into_iter().map(|service| to_match(service, &paths[service])).collect(),
+        })
+    }
+
+    /// Collects the edges the traversal follows, oriented in traversal direction
+    fn edges<'a>(&self, graph: &'a DependencyGraph) -> HashMap<&'a str, Vec<&'a str>> {
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (from, dependencies) in &graph.adjacency_list {
+            for (to, metadata) in dependencies {
+                if self.required_only && !metadata.required {
+                    continue;
+                }
+                if !self.interactions.is_empty()
+                    && !self.interactions.contains(&metadata.interaction)
+                {
+                    continue;
+                }
+                match self.traversal {
+                    Traversal::Dependents => edges.entry(to.as_str()).or_default().push(from),
+                    _ => edges.entry(from.as_str()).or_default().push(to),
+                }
+            }
+        }
+        for neighbors in edges.values_mut() {
+            let mut seen = HashSet::new();
+            neighbors.retain(|n| seen.insert(*n));
+        }
+        edges
+    }
+}
+
+impl FromStr for GraphQuery {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid =
+            |reason: String| AureaCoreError::InvalidQuery(format!("{} in '{}'", reason, s));
+
+        let s = s.trim();
+        let (function, rest) = s
+            .split_once('(')
+            .ok_or_else(|| invalid("Expected a call like deps(service)".into()))?;
+        let arguments = rest
+            .trim_end()
+            .strip_suffix(')')
+            .ok_or_else(|| invalid("Missing closing parenthesis".into()))?;
+
+        let mut positional = Vec::new();
+        let mut max_depth = None;
+        let mut required_only = false;
+        let mut interactions = Vec::new();
+        for argument in arguments.split(',').map(str::trim) {
+            if argument.is_empty() {
+                return Err(invalid("Empty argument".into()));
+            }
+            if let Some(bound) = argument
+                .strip_prefix("depth")
+                .map(str::trim_start)
+                .filter(|b| b.starts_with(['<', '=', '>']))
+            {
+                let (inclusive, value) = if let Some(v) = bound.strip_prefix("<=") {
+                    (true, v)
+                } else if let Some(v) = bound.strip_prefix('<') {
+                    (false, v)
+                } else if let Some(v) = bound.strip_prefix('=') {
+                    (true, v)
+                } else {
+                    return Err(invalid(format!("Unsupported depth bound '{}'", argument)));
+                };
+                let value: usize = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid(format!("Invalid depth '{}'", value.trim())))?;
+                max_depth = Some(if inclusive { value } else { value.saturating_sub(1) });
+            } else if argument == "required_only" || argument == "required" {
+                required_only = true;
+            } else if let Some(kinds) = argument
+                .strip_prefix("interaction")
+                .and_then(|kinds| kinds.trim_start().strip_prefix('='))
+            {
+                for kind in kinds.split('|').map(str::trim) {
+                    let kind: Interaction = kind.parse().map_err(invalid)?;
+                    if !interactions.contains(&kind) {
+                        interactions.push(kind);
+                    }
+                }
+            } else {
+                positional.push(argument.trim_matches(|c| c == '"' || c == '\'').to_string());
+            }
+        }
+
+        let query = match (function.trim(), positional.as_slice()) {
+            ("deps", [service]) => GraphQuery::deps(service),
+            ("dependents", [service]) => GraphQuery::dependents(service),
+            ("path", [from, to]) => GraphQuery::path(from, to),
+            ("deps" | "dependents", _) => {
+                return Err(invalid(format!("{}() takes one service", function.trim())))
+            }
+            ("path", _) => return Err(invalid("path() takes two services".into())),
+            (other, _) => return Err(invalid(format!("Unknown function '{}'", other))),
+        };
+        Ok(GraphQuery { max_depth, required_only, interactions, ..query })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::dependency::EdgeMetadata;
+
+    fn graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        let mut edge = |from: &str, to: &str, required: bool| {
+            graph.add_edge(
+                from.into(),
+                to.into(),
+                EdgeMetadata {
+                    required,
+                    version_constraint: None,
+                    interaction: Default::default(),
+                },
+            )
+        };
+        edge("checkout", "billing", true);
+        edge("checkout", "search", false);
+        edge("billing", "ledger", true);
+        edge("search", "ledger", true);
+        edge("ledger", "audit", true);
+        graph
+    }
+
+    fn names(matches: &[QueryMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.service.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_queries() {
+        assert_eq!(
+            "deps(\"checkout\", depth<=2, required_only)".parse::<GraphQuery>().unwrap(),
+            GraphQuery::deps("checkout").max_depth(2).required_only()
+        );
+        assert_eq!(
+            "dependents(ledger, depth < 2)".parse::<GraphQuery>().unwrap(),
+            GraphQuery::dependents("ledger").max_depth(1)
+        );
+        assert_eq!("path(a, b)".parse::<GraphQuery>().unwrap(), GraphQuery::path("a", "b"));
+        assert_eq!("deps(depth-api)".parse::<GraphQuery>().unwrap(), GraphQuery::deps("depth-api"));
+        assert_eq!(
+            "deps(checkout, interaction=sync-call|data-read)".parse::<GraphQuery>().unwrap(),
+            GraphQuery::deps("checkout")
+                .interaction(Interaction::SyncCall)
+                .interaction(Interaction::DataRead)
+        );
+
+        for invalid in [
+            "deps",
+            "deps(a",
+            "deps(a, b)",
+            "path(a)",
+            "walk(a)",
+            "deps(a, depth>2)",
+            "deps(a, interaction=rpc)",
+        ] {
+            let err = invalid.parse::<GraphQuery>().unwrap_err();
+            assert_eq!(err.code(), "invalid_query", "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_traversals() {
+        let graph = graph();
+
+        let all = GraphQuery::deps("checkout").execute(&graph).unwrap();
+        assert_eq!(names(&all), vec!["billing", "search", "ledger", "audit"]);
+        assert_eq!(all[3].path, vec!["checkout", "billing", "ledger", "audit"]);
+
+        let near = GraphQuery::deps("checkout").max_depth(1).execute(&graph).unwrap();
+        assert_eq!(names(&near), vec!["billing", "search"]);
+
+        let dependents = GraphQuery::dependents("ledger").required_only().execute(&graph).unwrap();
+        assert_eq!(names(&dependents), vec!["billing", "search", "checkout"]);
+
+        let path = GraphQuery::path("checkout", "audit").execute(&graph).unwrap();
+        assert_eq!(names(&path), vec!["checkout", "billing", "ledger", "audit"]);
+        assert!(GraphQuery::path("audit", "checkout").execute(&graph).unwrap().is_empty());
+
+        assert!(GraphQuery::deps("unknown").execute(&graph).is_err());
+    }
+
+    #[test]
+    fn test_interaction_filter() {
+        let mut graph = graph();
+        let event = EdgeMetadata {
+            required: true,
+            version_constraint: None,
+            interaction: Interaction::AsyncEvent,
+        };
+        graph.add_edge("billing".into(), "notifications".into(), event);
+
+        let all = GraphQuery::deps("checkout").execute(&graph).unwrap();
+        assert!(names(&all).contains(&"notifications"));
+        let sync = "deps(checkout, interaction=sync-call)".parse::<GraphQuery>().unwrap();
+        assert!(!names(&sync.execute(&graph).unwrap()).contains(&"notifications"));
+        let events = GraphQuery::dependents("notifications").interaction(Interaction::AsyncEvent);
+        assert_eq!(names(&events.execute(&graph).unwrap()), vec!["billing"]);
+    }
+}
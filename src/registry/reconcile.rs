@@ -0,0 +1,60 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::registry::ValidationSummary;
+
+/// Changes made to bring the registry in line with the repository
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReconcileReport {
+    /// Commit the registry was reconciled to
+    pub commit: String,
+    /// Services present in the repository but not in the registry
+    pub added: Vec<String>,
+    /// Services whose catalog config or definition changed
+    pub updated: Vec<String>,
+    /// Services no longer present in the repository
+    pub removed: Vec<String>,
+    /// Services already matching the repository
+    pub unchanged: Vec<String>,
+}
+
+impl ReconcileReport {
+    /// Checks whether reconciliation changed anything
+    pub fn is_noop(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for ReconcileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Reconciled to {}", self.commit)?;
+        for (label, names) in [
+            ("Added", &self.added),
+            ("Updated", &self.updated),
+            ("Removed", &self.removed),
+            ("Unchanged", &self.unchanged),
+        ] {
+            writeln!(f, "{}: {}", label, names.len())?;
+            if label != "Unchanged" {
+                for name in names {
+                    writeln!(f, "  - {}", name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Changes made by a batch sync of services, with the validation of the result
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchSyncReport {
+    /// Services that were not registered before
+    pub added: Vec<String>,
+    /// Registered services whose config was replaced
+    pub updated: Vec<String>,
+    /// Services removed because they were absent from the batch
+    pub removed: Vec<String>,
+    /// Validation of the synced services and their dependents
+    pub validation: ValidationSummary,
+}
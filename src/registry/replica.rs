@@ -0,0 +1,257 @@
+//! Read replicas serving the catalog from snapshots a writer publishes
+//!
+//! The writer periodically exports its validated catalog with
+//! [`export_snapshot`](crate::registry::ServiceRegistry::export_snapshot) and publishes it
+//! to a [`SnapshotStore`]. Readers fetch the latest snapshot and
+//! [`hydrate`](crate::registry::ServiceRegistry::hydrate) from it, serving the catalog
+//! read-only without a checkout of their own.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::{AureaCoreError, Result};
+
+/// Name of the snapshot file in a directory store
+pub const SNAPSHOT_OBJECT: &str = "catalog.snapshot";
+
+/// Largest snapshot fetched over HTTP
+const MAX_SNAPSHOT_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Where a writer publishes catalog snapshots and readers fetch them from
+pub trait SnapshotStore: Send + Sync {
+    /// Replaces the published snapshot
+    fn publish(&self, snapshot: &[u8]) -> Result<()>;
+
+    /// Fetches the published snapshot
+    ///
+    /// Returns `None` if nothing was published yet, or the snapshot is unchanged since
+    /// the last fetch.
+    fn fetch(&self) -> Result<Option<Vec<u8>>>;
+}
+
+/// Store keeping the snapshot in a directory, e.g. a shared volume or mounted bucket
+///
+/// Snapshots are written to a temporary file first and renamed, so readers never see a
+/// partial snapshot.
+pub struct DirectorySnapshotStore {
+    dir: PathBuf,
+    /// Hash of the snapshot fetched last
+    fetched: Mutex<Option<u64>>,
+}
+
+impl DirectorySnapshotStore {
+    /// Creates a store publishing to `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), fetched: Mutex::new(None) }
+    }
+}
+
+impl SnapshotStore for DirectorySnapshotStore {
+    fn publish(&self, snapshot: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let temp = self.dir.join(format!(".{}.{}.tmp", SNAPSHOT_OBJECT, std::process::id()));
+        std::fs::write(&temp, snapshot)?;
+        std::fs::rename(&temp, self.dir.join(SNAPSHOT_OBJECT))?;
+        Ok(())
+    }
+
+    fn fetch(&self) -> Result<Option<Vec<u8>>> {
+        let snapshot = match std::fs::read(self.dir.join(SNAPSHOT_OBJECT)) {
+            Ok(snapshot) => snapshot,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(unseen(&self.fetched, snapshot))
+    }
+}
+
+/// Store publishing the snapshot with HTTP `PUT` and fetching it with `GET`
+///
+/// Works with object stores accepting uploads over HTTP, e.g. pre-signed S3 or GCS URLs
+/// and WebDAV servers. Fetches are revalidated with the `ETag` of the last snapshot.
+pub struct HttpSnapshotStore {
+    url: String,
+    agent: ureq::Agent,
+    authorization: Option<String>,
+    /// ETag and hash of the snapshot fetched last
+    fetched: Mutex<(Option<String>, Option<u64>)>,
+}
+
+impl HttpSnapshotStore {
+    /// Creates a store for the snapshot at `url`, waiting at most `timeout` per request
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+            authorization: None,
+            fetched: Mutex::new((None, None)),
+        }
+    }
+
+    /// Sends an `Authorization` header, e.g. `Bearer <token>`, with every request
+    pub fn with_authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = Some(authorization.into());
+        self
+    }
+
+    fn request(&self, method: &str) -> ureq::Request {
+        let request = self.agent.request(method, &self.url);
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+}
+
+impl SnapshotStore for HttpSnapshotStore {
+    fn publish(&self, snapshot: &[u8]) -> Result<()> {
+        self.request("PUT")
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(snapshot)
+            .map_err(|e| {
+                AureaCoreError::Service(format!(
+                    "Failed to publish snapshot to {}: {}",
+                    self.url, e
+                ))
+            })?;
+        Ok(())
+    }
+
+    fn fetch(&self) -> Result<Option<Vec<u8>>> {
+        let etag = self.fetched.lock().unwrap_or_else(|e| e.into_inner()).0.clone();
+        let mut request = self.request("GET");
+        if let Some(etag) = &etag {
+            request = request.set("If-None-Match", etag);
+        }
+        let response = match request.call() {
+            Ok(response) if response.status() == 304 => return Ok(None),
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(err) => {
+                return Err(AureaCoreError::Service(format!(
+                    "Failed to fetch snapshot from {}: {}",
+                    self.url, err
+                )))
+            }
+        };
+
+        let etag = response.header("ETag").map(str::to_string);
+        let mut snapshot = Vec::new();
+        response.into_reader().take(MAX_SNAPSHOT_BYTES).read_to_end(&mut snapshot)?;
+        let mut fetched = self.fetched.lock().unwrap_or_else(|e| e.into_inner());
+        fetched.0 = etag;
+        let hash = hash(&snapshot);
+        if fetched.1 == Some(hash) {
+            return Ok(None);
+        }
+        fetched.1 = Some(hash);
+        Ok(Some(snapshot))
+    }
+}
+
+/// Passes a fetched snapshot on unless it is the one fetched last
+fn unseen(fetched: &Mutex<Option<u64>>, snapshot: Vec<u8>) -> Option<Vec<u8>> {
+    let hash = hash(&snapshot);
+    let mut fetched = fetched.lock().unwrap_or_else(|e| e.into_inner());
+    if *fetched == Some(hash) {
+        return None;
+    }
+    *fetched = Some(hash);
+    Some(snapshot)
+}
+
+fn hash(snapshot: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    snapshot.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Bytes;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_directory_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let writer = DirectorySnapshotStore::new(temp_dir.path().join("snapshots"));
+        let reader = DirectorySnapshotStore::new(temp_dir.path().join("snapshots"));
+        assert_eq!(reader.fetch().unwrap(), None);
+
+        writer.publish(b"v1").unwrap();
+        assert_eq!(reader.fetch().unwrap(), Some(b"v1".to_vec()));
+        assert_eq!(reader.fetch().unwrap(), None);
+        writer.publish(b"v2").unwrap();
+        assert_eq!(reader.fetch().unwrap(), Some(b"v2".to_vec()));
+        assert_eq!(std::fs::read_dir(temp_dir.path().join("snapshots")).unwrap().count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_http_store() {
+        let stored: Arc<Mutex<Option<Bytes>>> = Arc::default();
+        let router = Router::new().route(
+            "/catalog.snapshot",
+            get({
+                let stored = stored.clone();
+                move |headers: HeaderMap| {
+                    let stored = stored.lock().unwrap().clone();
+                    async move {
+                        let Some(body) = stored else {
+                            return StatusCode::NOT_FOUND.into_response();
+                        };
+                        let etag = format!("\"{}\"", body.len());
+                        if headers.get("if-none-match").is_some_and(|tag| *tag == *etag) {
+                            return StatusCode::NOT_MODIFIED.into_response();
+                        }
+                        ([("etag", etag)], body).into_response()
+                    }
+                }
+            })
+            .put({
+                let stored = stored.clone();
+                move |headers: HeaderMap, body: Bytes| {
+                    let stored = stored.clone();
+                    async move {
+                        if headers.get("authorization").is_none_or(|auth| auth != "Bearer w") {
+                            return StatusCode::FORBIDDEN;
+                        }
+                        *stored.lock().unwrap() = Some(body);
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/catalog.snapshot", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        tokio::task::spawn_blocking(move || {
+            let reader = HttpSnapshotStore::new(&url, Duration::from_secs(5));
+            assert_eq!(reader.fetch().unwrap(), None);
+            assert!(reader.publish(b"v1").is_err());
+
+            let writer =
+                HttpSnapshotStore::new(&url, Duration::from_secs(5)).with_authorization("Bearer w");
+            writer.publish(b"v1").unwrap();
+            assert_eq!(reader.fetch().unwrap(), Some(b"v1".to_vec()));
+            // Revalidated with the ETag
+            assert_eq!(reader.fetch().unwrap(), None);
+            writer.publish(b"v22").unwrap();
+            assert_eq!(reader.fetch().unwrap(), Some(b"v22".to_vec()));
+        })
+        .await
+        .unwrap();
+    }
+}
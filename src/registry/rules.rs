@@ -0,0 +1,133 @@
+//! Validation rules and policies loaded from the catalog repository, swapped as a whole
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::eol::EolDatabase;
+use crate::registry::freeze::FreezeCalendar;
+use crate::registry::naming::NamingRules;
+use crate::registry::policy::WarningPolicies;
+use crate::registry::webhook::ValidationWebhook;
+use crate::schema::root::Layout;
+
+/// Policy files read from the policies directory of the layout
+pub const POLICY_FILES: &[&str] =
+    &["freeze.yaml", "webhooks.yaml", "warning-policies.yaml", "naming-rules.yaml", "eol.yaml"];
+
+/// Rules and policies of a catalog, versioned by the content they were loaded from
+///
+/// Missing files leave the respective rules at their defaults.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    /// Freeze windows, from `freeze.yaml`
+    pub freeze: FreezeCalendar,
+    /// External validators, from `webhooks.yaml`
+    pub webhooks: Vec<ValidationWebhook>,
+    /// Warning escalation policies, from `warning-policies.yaml`
+    pub warning_policies: WarningPolicies,
+    /// Naming conventions, from `naming-rules.yaml`
+    pub naming_rules: NamingRules,
+    /// Runtime end-of-life dates, from `eol.yaml`
+    pub eol: EolDatabase,
+    /// Definition schema generations by version, from `<version>.json` in the schemas directory
+    pub schemas: BTreeMap<String, serde_json::Value>,
+    /// Digest of the files the rules were loaded from
+    pub version: String,
+}
+
+impl RuleSet {
+    /// Loads the rules from the policies and schemas directories of a layout
+    ///
+    /// Fails without partial results if any file is invalid.
+    pub fn load(work_dir: &Path, layout: &Layout) -> Result<Self> {
+        let mut rules = Self::default();
+        let mut hasher = Sha256::new();
+
+        let policies_dir = layout.policies_path(work_dir);
+        for file in POLICY_FILES {
+            let path = policies_dir.join(file);
+            if !path.exists() {
+                continue;
+            }
+            hasher.update(file.as_bytes());
+            hasher.update(fs::read(&path)?);
+            match *file {
+                "freeze.yaml" => rules.freeze = FreezeCalendar::load(&path)?,
+                "webhooks.yaml" => rules.webhooks = ValidationWebhook::load_all(&path)?,
+                "warning-policies.yaml" => rules.warning_policies = WarningPolicies::load(&path)?,
+                "naming-rules.yaml" => rules.naming_rules = NamingRules::load(&path)?,
+                _ => rules.eol = EolDatabase::load(&path)?,
+            }
+        }
+
+        let schemas_dir = layout.schemas_path(work_dir);
+        if schemas_dir.is_dir() {
+            let mut paths: Vec<_> = fs::read_dir(&schemas_dir)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect();
+            paths.sort();
+            for path in paths {
+                let version = path.file_stem().unwrap().to_string_lossy().into_owned();
+                let content = fs::read_to_string(&path)?;
+                let schema = serde_json::from_str(&content).map_err(|e| {
+                    AureaCoreError::invalid_config(
+                        format!("Invalid definition schema {}", path.display()),
+                        e,
+                    )
+                })?;
+                hasher.update(version.as_bytes());
+                hasher.update(content.as_bytes());
+                rules.schemas.insert(version, schema);
+            }
+        }
+
+        let digest = hasher.finalize();
+        rules.version = digest.iter().take(6).map(|byte| format!("{:02x}", byte)).collect();
+        Ok(rules)
+    }
+}
+
+/// The rule set a registry validates with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActiveRules {
+    /// Version of the rule set
+    pub version: String,
+    /// When the rule set was applied
+    pub loaded_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_load_rule_set() {
+        let temp_dir = TempDir::new().unwrap();
+        let work_dir = temp_dir.path();
+        let empty = RuleSet::load(work_dir, &Layout::default()).unwrap();
+        assert_eq!(empty.version.len(), 12);
+
+        fs::write(work_dir.join("naming-rules.yaml"), "kubernetes: true\n").unwrap();
+        let named = RuleSet::load(work_dir, &Layout::default()).unwrap();
+        assert_ne!(named.version, empty.version);
+        assert_eq!(RuleSet::load(work_dir, &Layout::default()).unwrap().version, named.version);
+
+        fs::create_dir_all(work_dir.join("schemas")).unwrap();
+        fs::write(work_dir.join("schemas/2.0.json"), r#"{"type": "object"}"#).unwrap();
+        let rules = RuleSet::load(work_dir, &Layout::default()).unwrap();
+        assert!(rules.schemas.contains_key("2.0"));
+
+        fs::write(work_dir.join("schemas/2.1.json"), "not json").unwrap();
+        let err = RuleSet::load(work_dir, &Layout::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid definition schema"));
+    }
+}
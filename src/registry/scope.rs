@@ -0,0 +1,118 @@
+//! Selection of the services a consumer may see in a scoped view of the catalog
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::registry::service::Service;
+use crate::schema::service::Interaction;
+
+/// Selects the services of a consumer scope by namespace, team and tag
+///
+/// A service is selected if it matches every kind of criterion given: one of the
+/// namespaces, one of the teams (owners) and one of the tags (the `tags` metadata
+/// list). An empty selector selects every service.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeSelector {
+    /// Namespaces to include
+    pub namespaces: Vec<String>,
+    /// Owning teams to include
+    pub teams: Vec<String>,
+    /// Tags to include
+    pub tags: Vec<String>,
+}
+
+impl ScopeSelector {
+    /// Creates a selector selecting every service
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also selects services in `namespace`
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespaces.push(namespace.into());
+        self
+    }
+
+    /// Also selects services owned by `team`
+    pub fn with_team(mut self, team: impl Into<String>) -> Self {
+        self.teams.push(team.into());
+        self
+    }
+
+    /// Also selects services tagged `tag`
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Checks whether a service is in scope
+    pub fn matches(&self, service: &Service) -> bool {
+        let definition = service.schema_data.as_ref();
+        let namespace = service.config.namespace.as_deref();
+        let owner = definition.and_then(|d| d.get("owner")).and_then(|owner| owner.as_str());
+        let tags: Vec<&str> = definition
+            .and_then(|d| d.get("metadata"))
+            .and_then(|metadata| metadata.get("tags"))
+            .and_then(|tags| tags.as_array())
+            .map(|tags| tags.iter().filter_map(|tag| tag.as_str()).collect())
+            .unwrap_or_default();
+
+        (self.namespaces.is_empty()
+            || namespace.is_some_and(|n| self.namespaces.iter().any(|s| s == n)))
+            && (self.teams.is_empty() || owner.is_some_and(|o| self.teams.iter().any(|t| t == o)))
+            && (self.tags.is_empty() || tags.iter().any(|tag| self.tags.iter().any(|t| t == tag)))
+    }
+}
+
+impl fmt::Display for ScopeSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let criteria: Vec<String> =
+            [("namespace", &self.namespaces), ("team", &self.teams), ("tag", &self.tags)]
+                .into_iter()
+                .filter(|(_, values)| !values.is_empty())
+                .map(|(kind, values)| format!("{}={}", kind, values.join(",")))
+                .collect();
+        if criteria.is_empty() {
+            write!(f, "all services")
+        } else {
+            write!(f, "{}", criteria.join(" "))
+        }
+    }
+}
+
+/// Which side of a boundary edge lies outside the scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoundaryDirection {
+    /// A service in scope depends on a service outside of it
+    Outgoing,
+    /// A service outside of the scope depends on a service in it
+    Incoming,
+}
+
+/// A dependency crossing the boundary of a scoped view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BoundaryEdge {
+    /// Dependent service
+    pub from: String,
+    /// Service depended upon
+    pub to: String,
+    /// Which end is external to the scope
+    pub direction: BoundaryDirection,
+    /// Whether the dependency is required
+    pub required: bool,
+    /// Kind of interaction
+    pub interaction: Interaction,
+}
+
+impl BoundaryEdge {
+    /// Gets the service at the end of the edge outside of the scope
+    pub fn external(&self) -> &str {
+        match self.direction {
+            BoundaryDirection::Outgoing => &self.to,
+            BoundaryDirection::Incoming => &self.from,
+        }
+    }
+}
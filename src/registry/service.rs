@@ -4,33 +4,98 @@ use std::{fmt, fs};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use {serde_json, tracing};
 
 use crate::error::{AureaCoreError, Result};
-use crate::schema::service::Dependency;
+use crate::registry::contracts::ContractStatus;
+use crate::schema::position::{Diagnostic, Position, PositionIndex};
+use crate::schema::service::{normalize_definition, parse_dependencies, Dependency, ServiceSchema};
+use crate::schema::suppress::{parse_annotations, Suppression};
 use crate::schema::validation::ValidationService;
 
-/// Configuration for a service
+/// Catalog reference to a service
+///
+/// Locates the service definition ([`ServiceSchema`]) in the repository and carries
+/// catalog-level overrides. Everything describing the service itself belongs in the
+/// definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceConfig {
+    /// Stable machine ID of the service, kept when the service is renamed
+    #[serde(default)]
+    pub id: Option<Uuid>,
     /// Optional namespace for the service
     pub namespace: Option<String>,
-    /// Path to the service configuration file
+    /// Path to the service definition file
     pub config_path: String,
     /// Schema version for validation
     #[serde(default = "default_schema_version")]
     pub schema_version: String,
-    /// Dependencies on other services
+    /// Catalog-level dependencies, overriding those in the service definition
     #[serde(default)]
     pub dependencies: Option<Vec<Dependency>>,
 }
 
-fn default_schema_version() -> String {
+impl ServiceConfig {
+    /// Resolves the effective dependencies of a service
+    ///
+    /// Dependencies declared in the catalog reference take precedence over those in
+    /// the raw service definition.
+    pub fn resolve_dependencies(&self, definition: Option<&serde_json::Value>) -> Vec<Dependency> {
+        match &self.dependencies {
+            Some(dependencies) => dependencies.clone(),
+            None => definition.map(parse_dependencies).unwrap_or_default(),
+        }
+    }
+}
+
+pub(crate) fn default_schema_version() -> String {
     "1.0.0".to_string()
 }
 
+/// Parses service schema content as JSON or YAML based on the file extension
+///
+/// The definition is normalized, with documented defaults of omitted fields injected.
+pub(crate) fn parse_schema_content(path: &Path, content: &str) -> Result<serde_json::Value> {
+    let mut definition = parse_raw_schema_content(path, content)?;
+    normalize_definition(&mut definition);
+    Ok(definition)
+}
+
+/// Parses service schema content like [`parse_schema_content`], indexing the positions
+/// of its fields alongside
+pub(crate) fn parse_spanned_schema_content(
+    path: &Path,
+    content: &str,
+) -> Result<(serde_json::Value, PositionIndex)> {
+    let definition = parse_schema_content(path, content)?;
+    Ok((definition, PositionIndex::parse(content)))
+}
+
+/// Parses service schema content as written, without injecting defaults
+pub(crate) fn parse_raw_schema_content(path: &Path, content: &str) -> Result<serde_json::Value> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str::<serde_json::Value>(content).map_err(|e| {
+            AureaCoreError::Service(format!("Failed to parse JSON configuration: {}", e))
+        })
+    } else if path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml") {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str(content).map_err(|e| {
+            AureaCoreError::Service(format!("Failed to parse YAML configuration: {}", e))
+        })?;
+
+        // Convert YAML to JSON value
+        serde_json::to_value(yaml_value)
+            .map_err(|e| AureaCoreError::Service(format!("Failed to convert YAML to JSON: {}", e)))
+    } else {
+        Err(AureaCoreError::Service(format!(
+            "Unsupported configuration file format: {}",
+            path.display()
+        )))
+    }
+}
+
 /// Status of a service
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServiceStatus {
     /// Current state of the service
     pub state: ServiceState,
@@ -40,10 +105,16 @@ pub struct ServiceStatus {
     pub error_message: Option<String>,
     /// Warning messages (e.g., missing dependencies or minor version issues)
     pub warnings: Vec<String>,
+    /// Outcome of the last contract verification, kept until the definition changes
+    #[serde(default)]
+    pub contracts: Option<ContractStatus>,
+    /// Schema violations of a failed validation, located in the definition file
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 /// State of a service
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ServiceState {
     /// Service is active and running
     Active,
@@ -53,6 +124,8 @@ pub enum ServiceState {
     Validating,
     /// Service is in an error state
     Error,
+    /// Service is archived: out of the active catalog, kept for reference
+    Archived,
 }
 
 impl fmt::Display for ServiceState {
@@ -62,6 +135,7 @@ impl fmt::Display for ServiceState {
             ServiceState::Inactive => write!(f, "Inactive"),
             ServiceState::Validating => write!(f, "Validating"),
             ServiceState::Error => write!(f, "Error"),
+            ServiceState::Archived => write!(f, "Archived"),
         }
     }
 }
@@ -69,7 +143,14 @@ impl fmt::Display for ServiceState {
 impl ServiceStatus {
     /// Creates a new service status
     pub fn new(state: ServiceState) -> Self {
-        Self { state, last_checked: Utc::now(), error_message: None, warnings: Vec::new() }
+        Self {
+            state,
+            last_checked: Utc::now(),
+            error_message: None,
+            warnings: Vec::new(),
+            contracts: None,
+            diagnostics: Vec::new(),
+        }
     }
 
     /// Updates the status with an error
@@ -87,6 +168,18 @@ impl ServiceStatus {
         self
     }
 
+    /// Keeps the outcome of a contract verification
+    pub fn with_contracts(mut self, contracts: Option<ContractStatus>) -> Self {
+        self.contracts = contracts;
+        self
+    }
+
+    /// Keeps the located schema violations of a failed validation
+    pub fn with_diagnostics(mut self, diagnostics: Vec<Diagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
     /// Updates the status state
     pub fn with_state(mut self, state: ServiceState) -> Self {
         self.state = state;
@@ -109,6 +202,8 @@ pub struct Service {
     pub last_updated: DateTime<Utc>,
     /// Cached service schema data
     pub schema_data: Option<serde_json::Value>,
+    /// Positions of the fields of the definition file, indexed when it is loaded
+    pub positions: Option<PositionIndex>,
 }
 
 impl Service {
@@ -121,15 +216,72 @@ impl Service {
             status: ServiceStatus::new(ServiceState::Inactive),
             last_updated: now,
             schema_data: None,
+            positions: None,
+        }
+    }
+
+    /// Gets the stable machine ID of the service, if it has one
+    pub fn id(&self) -> Option<Uuid> {
+        self.config.id
+    }
+
+    /// Reads the service definition file as currently stored on disk
+    ///
+    /// Unlike the cached schema data this is not parsed, and reflects edits made since
+    /// the service was last loaded.
+    pub fn raw_definition(&self) -> Result<String> {
+        fs::read_to_string(&self.config.config_path).map_err(|e| {
+            AureaCoreError::Service(format!(
+                "Failed to read configuration file {}: {}",
+                self.config.config_path, e
+            ))
+        })
+    }
+
+    /// Gets the typed service definition, if it is loaded and well-formed
+    pub fn definition(&self) -> Option<ServiceSchema> {
+        self.schema_data.as_ref().and_then(|data| serde_json::from_value(data.clone()).ok())
+    }
+
+    /// Gets the warning suppressions of the service, from its `ignores` block and the
+    /// `aureacore:ignore` comments in its definition file
+    ///
+    /// Returns warnings about invalid comments alongside.
+    pub fn suppressions(&self) -> (Vec<Suppression>, Vec<String>) {
+        let mut suppressions = self.definition().map(|d| d.ignores).unwrap_or_default();
+        let (annotated, invalid) = parse_annotations(&self.raw_definition().unwrap_or_default());
+        suppressions.extend(annotated);
+        (suppressions, invalid)
+    }
+
+    /// Gets the tier of the service from the `tier` metadata value, if set
+    pub fn tier(&self) -> Option<String> {
+        match self.schema_data.as_ref()?.get("metadata")?.get("tier")? {
+            serde_json::Value::String(tier) => Some(tier.clone()),
+            serde_json::Value::Number(tier) => Some(tier.to_string()),
+            _ => None,
         }
     }
 
+    /// Gets the owner from the service definition, if set
+    pub fn owner(&self) -> Option<&str> {
+        self.schema_data.as_ref()?.get("owner")?.as_str()
+    }
+
+    /// Gets the effective dependencies of the service
+    ///
+    /// See [`ServiceConfig::resolve_dependencies`].
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        self.config.resolve_dependencies(self.schema_data.as_ref())
+    }
+
     /// Updates the service configuration
     pub fn update_config(&mut self, config: ServiceConfig) -> Result<()> {
         self.config = config;
         self.last_updated = Utc::now();
         self.status = ServiceStatus::new(ServiceState::Validating);
         self.schema_data = None;
+        self.positions = None;
         Ok(())
     }
 
@@ -149,32 +301,10 @@ impl Service {
                 AureaCoreError::Service(format!("Failed to read configuration file: {}", e))
             })?;
 
-            // Parse the configuration content based on file extension
-            let data = if config_path.extension().is_some_and(|ext| ext == "json") {
-                serde_json::from_str::<serde_json::Value>(&config_content).map_err(|e| {
-                    AureaCoreError::Service(format!("Failed to parse JSON configuration: {}", e))
-                })?
-            } else if config_path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml") {
-                let yaml_value: serde_yaml::Value =
-                    serde_yaml::from_str(&config_content).map_err(|e| {
-                        AureaCoreError::Service(format!(
-                            "Failed to parse YAML configuration: {}",
-                            e
-                        ))
-                    })?;
-
-                // Convert YAML to JSON value
-                serde_json::to_value(yaml_value).map_err(|e| {
-                    AureaCoreError::Service(format!("Failed to convert YAML to JSON: {}", e))
-                })?
-            } else {
-                return Err(AureaCoreError::Service(format!(
-                    "Unsupported configuration file format: {}",
-                    self.config.config_path
-                )));
-            };
+            let (data, positions) = parse_spanned_schema_content(config_path, &config_content)?;
 
             self.schema_data = Some(data);
+            self.positions = Some(positions);
         }
 
         Ok(self.schema_data.as_ref().unwrap())
@@ -192,6 +322,7 @@ impl Service {
         validation_service: &mut ValidationService,
         available_services: &HashSet<String>,
     ) -> Result<()> {
+        let contracts = self.status.contracts.take();
         self.status = ServiceStatus::new(ServiceState::Validating);
 
         // Avoid borrow checker issues by cloning values we need for logging
@@ -199,30 +330,21 @@ impl Service {
 
         // Load the schema data
         let schema_data = match self.load_schema_data() {
-            Ok(data) => {
-                let mut data_value = data.clone();
-
-                // Synchronize dependencies from ServiceConfig to schema_data if present in config
-                if let Some(dependencies) = &self.config.dependencies {
-                    // Create a mutable version of the schema data
-                    if let Some(obj) = data_value.as_object_mut() {
-                        // Convert dependencies to serde_json::Value
-                        let deps_value = serde_json::to_value(dependencies)
-                            .unwrap_or(serde_json::Value::Array(vec![]));
-                        // Update schema data with dependencies
-                        obj.insert("dependencies".to_string(), deps_value);
-                    }
-                }
-
-                data_value
+            Ok(schema_data) => schema_data.clone(),
+            Err(err) => {
+                self.status = ServiceStatus::new(ServiceState::Error)
+                    .with_error(err.to_string())
+                    .with_contracts(contracts);
+                return Err(err);
             }
-            Err(err) => return Err(err),
         };
+        let dependencies = self.dependencies();
 
         // Validate the schema with context for dependency validation
-        let (result, warnings) = validation_service.validate_service_with_context(
+        let (result, warnings) = validation_service.validate_service_with_dependencies(
             &service_name,
             &schema_data,
+            &dependencies,
             available_services,
         );
 
@@ -230,8 +352,9 @@ impl Service {
         match result {
             Ok(_) => {
                 // Service validated successfully but may have warnings
-                self.status =
-                    ServiceStatus::new(ServiceState::Active).with_warnings(warnings.clone());
+                self.status = ServiceStatus::new(ServiceState::Active)
+                    .with_warnings(warnings.clone())
+                    .with_contracts(contracts);
 
                 // Log any warnings
                 for warning in &warnings {
@@ -241,16 +364,63 @@ impl Service {
                 Ok(())
             }
             Err(err) => {
-                let error_message = format!("Schema validation failed: {}", err);
+                let (message, diagnostics) = self.describe_failure(&err, validation_service);
+                let error_message = format!("Schema validation failed: {}", message);
                 self.status = ServiceStatus::new(ServiceState::Error)
                     .with_error(error_message)
-                    .with_warnings(warnings.clone());
+                    .with_warnings(warnings.clone())
+                    .with_contracts(contracts)
+                    .with_diagnostics(diagnostics);
 
                 Err(err)
             }
         }
     }
 
+    /// Locates the schema violations of the definition in its file
+    ///
+    /// Violations of fields missing from the file are reported at their closest
+    /// ancestor, and at the start of the file if it wasn't indexed.
+    pub fn schema_diagnostics(
+        &self,
+        validation_service: &mut ValidationService,
+    ) -> Vec<Diagnostic> {
+        let Some(data) = &self.schema_data else {
+            return Vec::new();
+        };
+        validation_service
+            .schema_violations(data)
+            .into_iter()
+            .map(|violation| Diagnostic {
+                file: self.config.config_path.clone(),
+                position: self
+                    .positions
+                    .as_ref()
+                    .map_or(Position::START, |positions| positions.nearest(&violation.pointer)),
+                pointer: violation.pointer,
+                message: violation.message,
+            })
+            .collect()
+    }
+
+    /// Describes a failed validation, with schema violations located in the file
+    pub(crate) fn describe_failure(
+        &self,
+        err: &AureaCoreError,
+        validation_service: &mut ValidationService,
+    ) -> (String, Vec<Diagnostic>) {
+        if !matches!(err, AureaCoreError::ValidationError(_)) {
+            return (err.to_string(), Vec::new());
+        }
+        let diagnostics = self.schema_diagnostics(validation_service);
+        if diagnostics.is_empty() {
+            return (err.to_string(), diagnostics);
+        }
+        let located: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+        let message = format!("Schema validation failed: {}", located.join("; "));
+        (AureaCoreError::ValidationError(message).to_string(), diagnostics)
+    }
+
     /// Gets the current service status
     pub fn status(&self) -> &ServiceStatus {
         &self.status
@@ -272,6 +442,7 @@ mod tests {
 
     fn create_test_config(config_path: &str) -> ServiceConfig {
         ServiceConfig {
+            id: None,
             namespace: None,
             config_path: config_path.to_string(),
             schema_version: "1.0.0".to_string(),
@@ -414,6 +585,7 @@ mod tests {
             service: "config-dependency".to_string(),
             version_constraint: Some("1.0.0".to_string()),
             required: true,
+            ..Default::default()
         }]);
 
         let mut service = Service::new("test-service".to_string(), config);
@@ -443,4 +615,60 @@ mod tests {
         assert!(!service.status.warnings.is_empty());
         assert!(service.status.warnings[0].contains("config-dependency"));
     }
+
+    #[test]
+    fn test_dependencies_resolve_from_reference_or_definition() {
+        let definition = json!({
+            "name": "test-service",
+            "dependencies": [{"service": "from-definition", "required": false}]
+        });
+
+        // Without catalog-level dependencies the definition is used
+        let mut service = Service::new("test-service".to_string(), create_test_config("t.json"));
+        service.mock_schema_data(definition.clone());
+        let dependencies = service.dependencies();
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].service, "from-definition");
+        assert!(!dependencies[0].required);
+
+        // Catalog-level dependencies override the definition without rewriting it
+        service.config.dependencies = Some(vec![Dependency {
+            service: "from-reference".to_string(),
+            required: true,
+            ..Default::default()
+        }]);
+        let dependencies = service.dependencies();
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].service, "from-reference");
+        assert_eq!(service.schema_data, Some(definition));
+    }
+
+    #[test]
+    fn test_locate_schema_violations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("orders.yaml");
+        let definition = "\
+name: orders
+version: 1.0.0
+service_type:
+  type: rest
+endpoints: []
+dependencies:
+  - service: billing
+    required: sometimes
+";
+        fs::write(&path, definition).unwrap();
+        let mut service =
+            Service::new("orders".to_string(), create_test_config(&path.to_string_lossy()));
+        let mut validation_service = ValidationService::new();
+        let available: HashSet<String> = ["billing".to_string()].into();
+        assert!(service.validate(&mut validation_service, &available).is_err());
+
+        let diagnostics = &service.status.diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pointer, "/dependencies/0/required");
+        assert_eq!(diagnostics[0].position, Position { line: 8, column: 5 });
+        let location = format!("{}:8:5: ", path.display());
+        assert!(service.status.error_message.as_ref().unwrap().contains(&location));
+    }
 }
@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::service::{Service, ServiceConfig, ServiceStatus};
+use crate::registry::ValidationSummary;
+use crate::schema::ResourceSchema;
+
+/// File inside the `.git` directory holding the warm-start snapshot
+pub(crate) const SNAPSHOT_FILE: &str = "aureacore-snapshot.bin";
+
+/// Layout version of snapshots; snapshots written with another layout are ignored
+const SNAPSHOT_FORMAT: u32 = 8;
+
+/// Binary snapshot of a validated registry, keyed by the commit it was taken at and the
+/// fingerprint of what validated it
+///
+/// Used to warm-start the registry and to hydrate read replicas.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegistrySnapshot {
+    format: u32,
+    /// Commit the catalog was checked out at
+    pub commit: String,
+    /// Crate version and rule set the services were validated with
+    pub fingerprint: String,
+    services: Vec<SnapshotService>,
+    archived: Vec<SnapshotService>,
+    /// Resource definitions as JSON text, for the same reason as service schema data
+    resources: Vec<String>,
+    /// Summary of the validation the snapshot reflects
+    pub last_validation: Option<ValidationSummary>,
+}
+
+/// A service as stored in a snapshot
+///
+/// Schema data is kept as JSON text because bincode cannot decode self-describing values.
+#[derive(Serialize, Deserialize)]
+struct SnapshotService {
+    name: String,
+    config: ServiceConfig,
+    schema_data: Option<String>,
+    status: ServiceStatus,
+    last_updated: DateTime<Utc>,
+}
+
+impl RegistrySnapshot {
+    /// Captures the active and archived services, resources and last validation of a
+    /// registry
+    pub fn capture(
+        commit: String,
+        fingerprint: String,
+        services: &HashMap<String, Service>,
+        archived: &HashMap<String, Service>,
+        resources: &BTreeMap<String, ResourceSchema>,
+        last_validation: Option<&ValidationSummary>,
+    ) -> Result<Self> {
+        let entries = |services: &HashMap<String, Service>| {
+            services
+                .values()
+                .map(|service| SnapshotService {
+                    name: service.name.clone(),
+                    config: service.config.clone(),
+                    schema_data: service.schema_data.as_ref().map(|data| data.to_string()),
+                    status: service.status.clone(),
+                    last_updated: service.last_updated,
+                })
+                .collect()
+        };
+
+        let resources = resources
+            .values()
+            .map(|resource| {
+                serde_json::to_string(resource).map_err(|e| {
+                    AureaCoreError::Internal(format!(
+                        "Failed to encode resource '{}': {}",
+                        resource.name, e
+                    ))
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            format: SNAPSHOT_FORMAT,
+            commit,
+            fingerprint,
+            services: entries(services),
+            archived: entries(archived),
+            resources,
+            last_validation: last_validation.cloned(),
+        })
+    }
+
+    /// Rebuilds the active services captured in the snapshot
+    pub fn services(&self) -> Result<HashMap<String, Service>> {
+        rebuild(&self.services)
+    }
+
+    /// Rebuilds the archived services captured in the snapshot
+    pub fn archived(&self) -> Result<HashMap<String, Service>> {
+        rebuild(&self.archived)
+    }
+
+    /// Rebuilds the resources captured in the snapshot
+    pub fn resources(&self) -> Result<BTreeMap<String, ResourceSchema>> {
+        let mut resources = BTreeMap::new();
+        for entry in &self.resources {
+            let resource: ResourceSchema = serde_json::from_str(entry).map_err(|e| {
+                AureaCoreError::Internal(format!("Corrupt snapshot data for a resource: {}", e))
+            })?;
+            resources.insert(resource.name.clone(), resource);
+        }
+        Ok(resources)
+    }
+
+    /// Encodes the snapshot
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self)
+            .map_err(|e| AureaCoreError::Internal(format!("Failed to encode snapshot: {}", e)))
+    }
+
+    /// Decodes a snapshot, failing if it is corrupt or of another layout
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let snapshot: Self = bincode::deserialize(bytes)
+            .map_err(|e| AureaCoreError::Internal(format!("Unreadable snapshot: {}", e)))?;
+        if snapshot.format != SNAPSHOT_FORMAT {
+            return Err(AureaCoreError::IncompatibleVersion(format!(
+                "snapshot has layout {}, expected {}",
+                snapshot.format, SNAPSHOT_FORMAT
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes the snapshot to a file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Reads a snapshot from a file
+    ///
+    /// Returns `None` if there is no snapshot, or it is unreadable or of another layout.
+    pub fn read(path: &Path) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        match Self::from_bytes(&bytes) {
+            Ok(snapshot) => Some(snapshot),
+            Err(AureaCoreError::IncompatibleVersion(_)) => {
+                tracing::debug!("Ignoring snapshot {} with another layout", path.display());
+                None
+            }
+            Err(err) => {
+                tracing::warn!("Ignoring unreadable snapshot {}: {}", path.display(), err);
+                None
+            }
+        }
+    }
+}
+
+/// Rebuilds services from their snapshot entries
+fn rebuild(entries: &[SnapshotService]) -> Result<HashMap<String, Service>> {
+    let mut services = HashMap::with_capacity(entries.len());
+    for entry in entries {
+        let schema_data = match &entry.schema_data {
+            Some(data) => Some(serde_json::from_str(data).map_err(|e| {
+                AureaCoreError::Internal(format!(
+                    "Corrupt snapshot data for service '{}': {}",
+                    entry.name, e
+                ))
+            })?),
+            None => None,
+        };
+
+        let mut service = Service::new(entry.name.clone(), entry.config.clone());
+        service.status = entry.status.clone();
+        service.last_updated = entry.last_updated;
+        service.schema_data = schema_data;
+        services.insert(entry.name.clone(), service);
+    }
+    Ok(services)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::registry::service::ServiceState;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SNAPSHOT_FILE);
+
+        let config: ServiceConfig =
+            serde_json::from_value(json!({"config_path": "billing.yaml"})).unwrap();
+        let mut service = Service::new("billing".to_string(), config);
+        service.schema_data = Some(json!({"name": "billing", "metadata": {"tier": 1}}));
+        service.status = ServiceStatus::new(ServiceState::Active).with_warnings(vec!["w".into()]);
+        let services = HashMap::from([("billing".to_string(), service)]);
+
+        let archived = HashMap::new();
+        let resource: ResourceSchema = serde_json::from_value(
+            json!({"name": "orders-db", "kind": "database", "metadata": {"replicas": 2}}),
+        )
+        .unwrap();
+        let resources = BTreeMap::from([("orders-db".to_string(), resource.clone())]);
+        RegistrySnapshot::capture(
+            "abc123".to_string(),
+            "0.1.0:".to_string(),
+            &services,
+            &archived,
+            &resources,
+            None,
+        )
+        .unwrap()
+        .write(&path)
+        .unwrap();
+
+        let snapshot = RegistrySnapshot::read(&path).unwrap();
+        assert_eq!(snapshot.commit, "abc123");
+        assert_eq!(snapshot.fingerprint, "0.1.0:");
+        let restored = &snapshot.services().unwrap()["billing"];
+        assert_eq!(restored.status.state, ServiceState::Active);
+        assert_eq!(restored.status.warnings, vec!["w"]);
+        assert_eq!(restored.schema_data.as_ref().unwrap()["metadata"]["tier"], 1);
+        assert_eq!(snapshot.resources().unwrap()["orders-db"], resource);
+
+        fs::write(&path, b"garbage").unwrap();
+        assert!(RegistrySnapshot::read(&path).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Effective startup waits of services, for orchestrators starting services in order
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::schema::service::Dependency;
+
+/// Seconds a service waits for a dependency without a startup timeout
+pub const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 60;
+
+/// A dependency a service waits for before starting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StartupWait {
+    /// Service or resource waited for
+    pub dependency: String,
+    /// Whether the dependency is required, so the service must not start without it
+    pub required: bool,
+    /// Seconds to wait for the dependency before giving up
+    pub timeout_secs: u64,
+}
+
+impl StartupWait {
+    /// Gets the timeout as a duration
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
+/// How a service waits for its dependencies when starting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StartupPlan {
+    /// Service to start
+    pub service: String,
+    /// Dependencies to wait for, in declaration order
+    pub waits: Vec<StartupWait>,
+}
+
+impl StartupPlan {
+    /// Resolves the effective waits of a service from its dependencies
+    ///
+    /// Dependencies without a startup policy are waited for if required, with the
+    /// default timeout.
+    pub(crate) fn resolve(service: &str, dependencies: &[Dependency]) -> Self {
+        let waits = dependencies
+            .iter()
+            .filter_map(|dependency| {
+                let wait = dependency.startup.map_or(dependency.required, |policy| policy.wait);
+                wait.then(|| StartupWait {
+                    dependency: dependency.service.clone(),
+                    required: dependency.required,
+                    timeout_secs: dependency
+                        .startup
+                        .and_then(|policy| policy.timeout_secs)
+                        .unwrap_or(DEFAULT_STARTUP_TIMEOUT_SECS),
+                })
+            })
+            .collect();
+        Self { service: service.to_string(), waits }
+    }
+
+    /// Gets the longest time the service may wait for its dependencies
+    pub fn max_wait(&self) -> Duration {
+        self.waits.iter().map(StartupWait::timeout).max().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::service::StartupPolicy;
+
+    fn dependency(service: &str, required: bool, startup: Option<StartupPolicy>) -> Dependency {
+        Dependency { service: service.to_string(), required, startup, ..Default::default() }
+    }
+
+    #[test]
+    fn test_resolve_startup_plan() {
+        let plan = StartupPlan::resolve(
+            "orders",
+            &[
+                dependency("users", true, None),
+                dependency("search", false, None),
+                dependency(
+                    "cache",
+                    false,
+                    Some(StartupPolicy { wait: true, timeout_secs: Some(5) }),
+                ),
+                dependency("audit", true, Some(StartupPolicy { wait: false, timeout_secs: None })),
+            ],
+        );
+        assert_eq!(plan.service, "orders");
+        assert_eq!(
+            plan.waits,
+            vec![
+                StartupWait {
+                    dependency: "users".into(),
+                    required: true,
+                    timeout_secs: DEFAULT_STARTUP_TIMEOUT_SECS,
+                },
+                StartupWait { dependency: "cache".into(), required: false, timeout_secs: 5 },
+            ]
+        );
+        assert_eq!(plan.max_wait(), Duration::from_secs(DEFAULT_STARTUP_TIMEOUT_SECS));
+        assert_eq!(StartupPlan::resolve("users", &[]).max_wait(), Duration::ZERO);
+    }
+}
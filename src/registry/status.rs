@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::service::{Service, ServiceStatus};
+
+/// File inside the `.git` directory holding the last known service statuses
+pub(crate) const STATUS_FILE: &str = "aureacore-status.json";
+
+/// A persisted status, tied to the config and definition it was computed for
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredStatus {
+    digest: String,
+    status: ServiceStatus,
+}
+
+/// Hashes the catalog config and loaded definition of a service
+///
+/// A stored status is only restored while this digest is unchanged.
+fn service_digest(service: &Service) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_string(&service.config).unwrap_or_default());
+    hasher.update([0]);
+    if let Some(data) = &service.schema_data {
+        hasher.update(data.to_string());
+    }
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Writes the statuses of services to a file
+pub(crate) fn write_statuses(path: &Path, services: &HashMap<String, Service>) -> Result<()> {
+    let stored: BTreeMap<&String, StoredStatus> = services
+        .iter()
+        .map(|(name, service)| {
+            (name, StoredStatus { digest: service_digest(service), status: service.status.clone() })
+        })
+        .collect();
+    let content = serde_json::to_string_pretty(&stored)
+        .map_err(|e| AureaCoreError::Internal(format!("Failed to encode statuses: {}", e)))?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Restores statuses from a file onto services whose config and definition are unchanged
+///
+/// Returns the names of the services whose status was restored. A missing or unreadable
+/// file restores nothing.
+pub(crate) fn restore_statuses(
+    path: &Path,
+    services: &mut HashMap<String, Service>,
+) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let stored: HashMap<String, StoredStatus> = match serde_json::from_str(&content) {
+        Ok(stored) => stored,
+        Err(err) => {
+            tracing::warn!("Ignoring unreadable status file {}: {}", path.display(), err);
+            return Vec::new();
+        }
+    };
+
+    let mut restored = Vec::new();
+    for (name, entry) in stored {
+        if let Some(service) = services.get_mut(&name) {
+            if service_digest(service) == entry.digest {
+                service.status = entry.status;
+                restored.push(name);
+            }
+        }
+    }
+    restored.sort();
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::registry::service::{ServiceConfig, ServiceState};
+
+    #[test]
+    fn test_statuses_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(STATUS_FILE);
+
+        let service = |path: &str| {
+            let config: ServiceConfig =
+                serde_json::from_value(json!({ "config_path": path })).unwrap();
+            let mut service = Service::new("billing".to_string(), config);
+            service.schema_data = Some(json!({"name": "billing"}));
+            service
+        };
+        let mut failing = service("billing.yaml");
+        failing.status = ServiceStatus::new(ServiceState::Error)
+            .with_error("missing dependency".to_string())
+            .with_warnings(vec!["deprecated field".to_string()]);
+        let expected = failing.status.clone();
+        write_statuses(&path, &HashMap::from([("billing".to_string(), failing)])).unwrap();
+
+        let mut services = HashMap::from([("billing".to_string(), service("billing.yaml"))]);
+        assert_eq!(restore_statuses(&path, &mut services), vec!["billing"]);
+        assert_eq!(services["billing"].status, expected);
+
+        // A changed config invalidates the stored status
+        let mut services = HashMap::from([("billing".to_string(), service("other.yaml"))]);
+        assert!(restore_statuses(&path, &mut services).is_empty());
+        assert_eq!(services["billing"].status.state, ServiceState::Inactive);
+
+        fs::write(&path, "garbage").unwrap();
+        assert!(restore_statuses(&path, &mut services).is_empty());
+    }
+}
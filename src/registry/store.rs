@@ -1,8 +1,19 @@
+use std::collections::HashSet;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use crate::error::{AureaCoreError, Result};
 
+/// Files removed, or found removable in a dry run, by [`ConfigStore::gc`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoreGc {
+    /// Configuration files not in the set to keep
+    pub orphaned_configs: Vec<PathBuf>,
+    /// Temporary files left behind by interrupted writes or editors
+    pub temp_files: Vec<PathBuf>,
+}
+
 /// Manages service configuration storage
 #[derive(Debug, Clone)]
 pub struct ConfigStore {
@@ -16,66 +27,107 @@ impl ConfigStore {
         let config_dir = config_dir.into();
         if !config_dir.exists() {
             fs::create_dir_all(&config_dir).map_err(|e| {
-                AureaCoreError::Config(format!("Failed to create config directory: {}", e))
+                AureaCoreError::config_store(
+                    format!("Failed to create config directory {}", config_dir.display()),
+                    e,
+                )
             })?;
         }
         Ok(Self { config_dir })
     }
 
+    /// Opens a ConfigStore without creating its directory until a config is saved
+    pub fn open(config_dir: impl Into<PathBuf>) -> Self {
+        Self { config_dir: config_dir.into() }
+    }
+
+    /// Gets the base directory for configuration files
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// Resolves a path relative to the config directory
+    ///
+    /// Fails for empty and absolute paths, `..` components and control characters, so
+    /// no path resolves outside the config directory.
+    fn resolve(&self, path: &Path) -> Result<PathBuf> {
+        let unsafe_path = |reason: &str| {
+            Err(AureaCoreError::UnsafePath(format!("{:?} {}", path.as_os_str(), reason)))
+        };
+        if path.as_os_str().is_empty() {
+            return unsafe_path("is empty");
+        }
+        for component in path.components() {
+            match component {
+                Component::Normal(part) => {
+                    if part.to_string_lossy().chars().any(char::is_control) {
+                        return unsafe_path("contains control characters");
+                    }
+                }
+                Component::CurDir => {}
+                Component::ParentDir => return unsafe_path("leaves the config directory"),
+                Component::RootDir | Component::Prefix(_) => return unsafe_path("is absolute"),
+            }
+        }
+        Ok(self.config_dir.join(path))
+    }
+
     /// Loads a configuration file
     pub fn load_config(&self, path: impl AsRef<Path>) -> Result<String> {
-        let path = self.config_dir.join(path);
+        let path = self.resolve(path.as_ref())?;
         if !path.exists() {
-            return Err(AureaCoreError::Config(format!(
-                "Configuration file not found: {}",
-                path.display()
-            )));
+            return Err(AureaCoreError::ConfigStore {
+                message: format!("Configuration file not found: {}", path.display()),
+                source: None,
+            });
         }
 
         fs::read_to_string(&path).map_err(|e| {
-            AureaCoreError::Config(format!(
-                "Failed to read configuration file {}: {}",
-                path.display(),
-                e
-            ))
+            AureaCoreError::config_store(
+                format!("Failed to read configuration file {}", path.display()),
+                e,
+            )
         })
     }
 
     /// Saves a configuration file
     pub fn save_config(&self, path: impl AsRef<Path>, content: &str) -> Result<()> {
-        let path = self.config_dir.join(path);
+        let path = self.resolve(path.as_ref())?;
         if let Some(parent) = path.parent() {
             if !parent.exists() {
                 fs::create_dir_all(parent).map_err(|e| {
-                    AureaCoreError::Config(format!(
-                        "Failed to create directory {}: {}",
-                        parent.display(),
-                        e
-                    ))
+                    AureaCoreError::config_store(
+                        format!("Failed to create directory {}", parent.display()),
+                        e,
+                    )
                 })?;
             }
         }
 
         fs::write(&path, content).map_err(|e| {
-            AureaCoreError::Config(format!(
-                "Failed to write configuration file {}: {}",
-                path.display(),
-                e
-            ))
+            AureaCoreError::config_store(
+                format!("Failed to write configuration file {}", path.display()),
+                e,
+            )
         })
     }
 
-    /// Lists all configuration files
+    /// Lists all configuration files; a missing config directory has none
     pub fn list_configs(&self) -> Result<Vec<PathBuf>> {
         let mut configs = Vec::new();
+        if !self.config_dir.is_dir() {
+            return Ok(configs);
+        }
         let dir = fs::read_dir(&self.config_dir).map_err(|e| {
-            AureaCoreError::Config(format!("Failed to read config directory: {}", e))
+            AureaCoreError::config_store(
+                format!("Failed to read config directory {}", self.config_dir.display()),
+                e,
+            )
         })?;
 
         for entry in dir {
-            let entry = entry.map_err(|e| {
-                AureaCoreError::Config(format!("Failed to read directory entry: {}", e))
-            })?;
+            let entry = entry
+                .map_err(|e| AureaCoreError::config_store("Failed to read directory entry", e))?;
             let path = entry.path();
             if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
                 configs.push(path.strip_prefix(&self.config_dir).unwrap().to_path_buf());
@@ -85,24 +137,103 @@ impl ConfigStore {
         Ok(configs)
     }
 
+    /// Lists files saved without an extension, as earlier versions stored service configs
+    ///
+    /// Hidden files are left out.
+    pub fn list_legacy_configs(&self) -> Result<Vec<PathBuf>> {
+        let mut configs = Vec::new();
+        if !self.config_dir.is_dir() {
+            return Ok(configs);
+        }
+        let dir = fs::read_dir(&self.config_dir).map_err(|e| {
+            AureaCoreError::config_store(
+                format!("Failed to read config directory {}", self.config_dir.display()),
+                e,
+            )
+        })?;
+
+        for entry in dir {
+            let entry = entry
+                .map_err(|e| AureaCoreError::config_store("Failed to read directory entry", e))?;
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if path.is_file() && path.extension().is_none() && !hidden {
+                configs.push(PathBuf::from(entry.file_name()));
+            }
+        }
+        configs.sort();
+        Ok(configs)
+    }
+
     /// Removes a configuration file
     pub fn remove_config(&self, path: impl AsRef<Path>) -> Result<()> {
-        let path = self.config_dir.join(path);
+        let path = self.resolve(path.as_ref())?;
         if !path.exists() {
-            return Err(AureaCoreError::Config(format!(
-                "Configuration file not found: {}",
-                path.display()
-            )));
+            return Err(AureaCoreError::ConfigStore {
+                message: format!("Configuration file not found: {}", path.display()),
+                source: None,
+            });
         }
 
         fs::remove_file(&path).map_err(|e| {
-            AureaCoreError::Config(format!(
-                "Failed to remove configuration file {}: {}",
-                path.display(),
-                e
-            ))
+            AureaCoreError::config_store(
+                format!("Failed to remove configuration file {}", path.display()),
+                e,
+            )
         })
     }
+
+    /// Removes configuration files not in `keep` and temporary files older than `temp_age`
+    ///
+    /// With `dry_run` nothing is removed; the report lists what would be.
+    pub fn gc(
+        &self,
+        keep: &HashSet<PathBuf>,
+        temp_age: Duration,
+        dry_run: bool,
+    ) -> Result<StoreGc> {
+        let mut report = StoreGc::default();
+        for config in self.list_configs()? {
+            if !keep.contains(&config) {
+                report.orphaned_configs.push(config);
+            }
+        }
+
+        let dir = fs::read_dir(&self.config_dir).map_err(|e| {
+            AureaCoreError::config_store(
+                format!("Failed to read config directory {}", self.config_dir.display()),
+                e,
+            )
+        })?;
+        let now = SystemTime::now();
+        for entry in dir {
+            let entry = entry
+                .map_err(|e| AureaCoreError::config_store("Failed to read directory entry", e))?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !(name.ends_with(".tmp") || name.ends_with(".swp") || name.ends_with('~')) {
+                continue;
+            }
+            // Recent temp files may belong to a write still in progress
+            let age = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            if entry.path().is_file() && age >= temp_age {
+                report.temp_files.push(PathBuf::from(name));
+            }
+        }
+        report.orphaned_configs.sort();
+        report.temp_files.sort();
+
+        if !dry_run {
+            for path in report.orphaned_configs.iter().chain(&report.temp_files) {
+                self.remove_config(path)?;
+            }
+        }
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
@@ -135,7 +266,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let store = ConfigStore::new(temp_dir.path()).unwrap();
         let result = store.load_config("nonexistent.json");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(AureaCoreError::ConfigStore { source: None, .. })));
     }
 
     #[test]
@@ -153,4 +284,83 @@ mod tests {
         assert!(configs.contains(&config1));
         assert!(configs.contains(&config2));
     }
+
+    #[test]
+    fn test_gc() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path()).unwrap();
+        for file in ["keep.json", "orphan.json", "keep.json.tmp", "notes.txt"] {
+            store.save_config(file, "{}").unwrap();
+        }
+        let keep = HashSet::from([PathBuf::from("keep.json")]);
+
+        let report = store.gc(&keep, Duration::from_secs(3600), false).unwrap();
+        assert_eq!(report.orphaned_configs, vec![PathBuf::from("orphan.json")]);
+        assert!(report.temp_files.is_empty());
+
+        let report = store.gc(&keep, Duration::ZERO, true).unwrap();
+        assert_eq!(report.temp_files, vec![PathBuf::from("keep.json.tmp")]);
+        assert!(temp_dir.path().join("keep.json.tmp").exists());
+
+        store.gc(&keep, Duration::ZERO, false).unwrap();
+        assert!(!temp_dir.path().join("keep.json.tmp").exists());
+        assert!(temp_dir.path().join("keep.json").exists());
+        assert!(temp_dir.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_rejects_unsafe_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path().join("configs")).unwrap();
+        std::fs::write(temp_dir.path().join("secret.json"), "{}").unwrap();
+
+        for path in ["", "../secret.json", "nested/../../secret.json", "/etc/passwd", "a\nb.json"] {
+            let err = store.load_config(path).unwrap_err();
+            assert!(matches!(err, AureaCoreError::UnsafePath(_)), "{:?}", path);
+            assert!(store.save_config(path, "{}").is_err());
+            assert!(store.remove_config(path).is_err());
+        }
+        assert!(temp_dir.path().join("secret.json").exists());
+        store.save_config("./nested/ok.json", "{}").unwrap();
+        assert_eq!(store.load_config("nested/ok.json").unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_list_legacy_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path()).unwrap();
+        store.save_config("billing", "{}").unwrap();
+        store.save_config("search.json", "{}").unwrap();
+        store.save_config(".last-sync", "").unwrap();
+        std::fs::create_dir(temp_dir.path().join("archived")).unwrap();
+
+        assert_eq!(store.list_legacy_configs().unwrap(), vec![PathBuf::from("billing")]);
+        assert_eq!(store.list_configs().unwrap(), vec![PathBuf::from("search.json")]);
+    }
+
+    #[test]
+    fn test_fuzz_paths_stay_in_config_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path().join("configs")).unwrap();
+        let alphabet = ["a", "é", ".", "..", "/", "\\", "\0", "\n", "\u{202E}", "~", ":", "json"];
+        // A fixed xorshift sequence keeps failures reproducible
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..5_000 {
+            let mut path = String::new();
+            for _ in 0..(state % 8 + 1) {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                path.push_str(alphabet[(state % alphabet.len() as u64) as usize]);
+            }
+            if let Ok(resolved) = store.resolve(Path::new(&path)) {
+                assert!(resolved.starts_with(store.config_dir()), "{:?}", path);
+                assert!(!resolved.components().any(|c| c == Component::ParentDir), "{:?}", path);
+            }
+            if crate::registry::check_service_name(&path).is_ok() {
+                let resolved = store.resolve(Path::new(&format!("{}.json", path))).unwrap();
+                assert_eq!(resolved.parent(), Some(store.config_dir()), "{:?}", path);
+            }
+        }
+    }
 }
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Freshness of the catalog relative to the remote repository
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SyncStatus {
+    /// No sync has been attempted by this instance
+    #[default]
+    Unsynced,
+    /// The last sync succeeded
+    Fresh { last_sync: DateTime<Utc> },
+    /// The last sync failed and the catalog is served from local state
+    Stale { last_sync: Option<DateTime<Utc>>, error: String },
+}
+
+impl SyncStatus {
+    /// Checks whether the catalog is served from possibly outdated local state
+    pub fn is_stale(&self) -> bool {
+        matches!(self, SyncStatus::Stale { .. })
+    }
+
+    /// Gets the time of the last successful sync, if known
+    pub fn last_sync(&self) -> Option<DateTime<Utc>> {
+        match self {
+            SyncStatus::Unsynced => None,
+            SyncStatus::Fresh { last_sync } => Some(*last_sync),
+            SyncStatus::Stale { last_sync, .. } => *last_sync,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_status_last_sync() {
+        let now = Utc::now();
+        assert_eq!(SyncStatus::default().last_sync(), None);
+        assert_eq!(SyncStatus::Fresh { last_sync: now }.last_sync(), Some(now));
+
+        let stale = SyncStatus::Stale { last_sync: Some(now), error: "offline".to_string() };
+        assert!(stale.is_stale());
+        assert_eq!(stale.last_sync(), Some(now));
+    }
+
+    #[test]
+    fn test_sync_status_serialization() {
+        let stale = SyncStatus::Stale { last_sync: None, error: "offline".to_string() };
+        let json = serde_json::to_value(&stale).unwrap();
+        assert_eq!(json["state"], "stale");
+        assert_eq!(json["error"], "offline");
+    }
+}
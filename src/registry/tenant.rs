@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::ServiceRegistry;
+
+/// Resource limits enforced for one tenant
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TenantLimits {
+    /// Maximum number of services in the tenant's catalog
+    pub max_services: Option<usize>,
+    /// Maximum number of API requests served concurrently for the tenant
+    pub max_concurrent_requests: Option<usize>,
+    /// Deepest nesting of selections an API query may have
+    pub max_query_depth: Option<usize>,
+    /// Highest complexity an API query may have, each selected field counting one
+    pub max_query_complexity: Option<usize>,
+    /// Number of persisted API queries to keep; persisted queries are disabled if unset
+    pub persisted_queries: Option<usize>,
+}
+
+/// An isolated catalog served alongside others in one process
+///
+/// A tenant is a repository and branch, optionally restricted to a set of namespaces.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TenantConfig {
+    /// Identifier requests are routed by
+    pub id: String,
+    /// URL of the tenant's catalog repository
+    pub repo_url: String,
+    /// Branch of the repository
+    #[serde(default = "default_branch")]
+    pub branch: String,
+    /// Namespaces services may be registered in, all namespaces if empty
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Resource limits of the tenant
+    #[serde(default)]
+    pub limits: TenantLimits,
+}
+
+impl TenantConfig {
+    /// Loads a list of tenants from a YAML file
+    ///
+    /// Fails if a tenant id is empty, not path-safe or used twice.
+    pub fn load_all(path: &Path) -> Result<Vec<Self>> {
+        let tenants: Vec<Self> =
+            serde_yaml::from_str(&std::fs::read_to_string(path)?).map_err(|e| {
+                AureaCoreError::invalid_config(
+                    format!("Invalid tenant config {}", path.display()),
+                    e,
+                )
+            })?;
+
+        let mut ids = std::collections::HashSet::new();
+        for tenant in &tenants {
+            if tenant.id.is_empty()
+                || !tenant.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(AureaCoreError::Config(format!("Invalid tenant id '{}'", tenant.id)));
+            }
+            if !ids.insert(tenant.id.as_str()) {
+                return Err(AureaCoreError::Config(format!("Duplicate tenant id '{}'", tenant.id)));
+            }
+        }
+        Ok(tenants)
+    }
+
+    /// Checks whether services in the given namespace belong to the tenant
+    pub fn allows_namespace(&self, namespace: Option<&str>) -> bool {
+        self.namespaces.is_empty()
+            || namespace.is_some_and(|ns| self.namespaces.iter().any(|n| n == ns))
+    }
+
+    /// Creates the tenant's registry, working in its own directory below `root`
+    pub fn registry(&self, root: &Path) -> Result<ServiceRegistry> {
+        Ok(ServiceRegistry::new(self.repo_url.clone(), self.branch.clone(), root.join(&self.id))?
+            .with_tenant(self.clone()))
+    }
+}
+
+/// Default function to serve the main branch
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_load_tenants() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tenants.yaml");
+        std::fs::write(
+            &path,
+            "- id: payments\n  repo_url: https://example.com/payments.git\n  namespaces: [billing]\n  limits:\n    max_services: 2\n- id: search\n  repo_url: https://example.com/search.git\n  branch: prod\n",
+        )
+        .unwrap();
+
+        let tenants = TenantConfig::load_all(&path).unwrap();
+        assert_eq!(tenants[0].branch, "main");
+        assert_eq!(tenants[0].limits.max_services, Some(2));
+        assert!(tenants[0].allows_namespace(Some("billing")));
+        assert!(!tenants[0].allows_namespace(Some("search")));
+        assert!(!tenants[0].allows_namespace(None));
+        assert!(tenants[1].allows_namespace(None));
+        assert_eq!(tenants[1].limits, TenantLimits::default());
+
+        std::fs::write(&path, "- id: a\n  repo_url: x\n- id: a\n  repo_url: y\n").unwrap();
+        assert!(TenantConfig::load_all(&path).is_err());
+        std::fs::write(&path, "- id: ../a\n  repo_url: x\n").unwrap();
+        assert!(TenantConfig::load_all(&path).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::root::{Environment, RootConfig};
+use crate::schema::service::Exposure;
+
+/// Environments of a catalog and the services placed in each of them
+#[derive(Debug, Clone, Default)]
+pub struct Topology {
+    environments: Vec<Environment>,
+    /// Explicit placement per service; services without an entry run everywhere
+    placement: HashMap<String, Vec<String>>,
+}
+
+impl Topology {
+    /// Creates a topology without any explicit placement
+    pub fn new(environments: Vec<Environment>) -> Self {
+        Self { environments, placement: HashMap::new() }
+    }
+
+    /// Builds the topology declared in a root configuration
+    pub fn from_root_config(config: &RootConfig) -> Result<Self> {
+        let mut topology = Self::new(config.environments.clone());
+        for service in &config.services {
+            if let Some(environments) = &service.environments {
+                topology.place(&service.name, environments.clone())?;
+            }
+        }
+        Ok(topology)
+    }
+
+    /// Restricts a service to the given environments
+    pub fn place(&mut self, service: &str, environments: Vec<String>) -> Result<()> {
+        if let Some(unknown) = environments.iter().find(|e| self.environment(e).is_none()) {
+            return Err(AureaCoreError::Config(format!(
+                "Service '{}' is placed in unknown environment '{}'",
+                service, unknown
+            )));
+        }
+        self.placement.insert(service.to_string(), environments);
+        Ok(())
+    }
+
+    /// Gets all environments
+    pub fn environments(&self) -> &[Environment] {
+        &self.environments
+    }
+
+    /// Gets an environment by name
+    pub fn environment(&self, name: &str) -> Option<&Environment> {
+        self.environments.iter().find(|e| e.name == name)
+    }
+
+    /// Checks whether a service is deployed to an environment
+    pub fn is_placed(&self, service: &str, environment: &str) -> bool {
+        match self.placement.get(service) {
+            Some(environments) => environments.iter().any(|e| e == environment),
+            None => self.environment(environment).is_some(),
+        }
+    }
+
+    /// Builds the base URL of a service in an environment
+    ///
+    /// The host comes from the environment's hostname pattern, falling back to the first
+    /// hostname the service exposes. Fails if the service is not placed in the
+    /// environment or no host can be determined.
+    pub fn base_url(
+        &self,
+        service: &str,
+        namespace: Option<&str>,
+        exposure: Option<&Exposure>,
+        environment: &str,
+    ) -> Result<String> {
+        let env = self.environment(environment).ok_or_else(|| {
+            AureaCoreError::Config(format!("Unknown environment '{}'", environment))
+        })?;
+        if !self.is_placed(service, environment) {
+            return Err(AureaCoreError::Config(format!(
+                "Service '{}' is not deployed to environment '{}'",
+                service, environment
+            )));
+        }
+
+        let host = match &env.hostname_pattern {
+            Some(pattern) => pattern
+                .replace("{service}", service)
+                .replace("{namespace}", namespace.unwrap_or("default"))
+                .replace("{environment}", environment),
+            None => exposure.and_then(|e| e.hostnames.first()).cloned().ok_or_else(|| {
+                AureaCoreError::Config(format!(
+                    "Environment '{}' has no hostname pattern and service '{}' exposes no hostname",
+                    environment, service
+                ))
+            })?,
+        };
+        let scheme = env.scheme.as_deref().unwrap_or("https");
+        match env.ports.get(service).or(env.default_port.as_ref()) {
+            Some(port) => Ok(format!("{}://{}:{}", scheme, host, port)),
+            None => Ok(format!("{}://{}", scheme, host)),
+        }
+    }
+
+    /// Gets the environments a service is deployed to but its dependency is not
+    pub fn missing_placements(&self, service: &str, dependency: &str) -> Vec<&Environment> {
+        self.environments
+            .iter()
+            .filter(|e| self.is_placed(service, &e.name) && !self.is_placed(dependency, &e.name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn topology() -> Topology {
+        let config: RootConfig = serde_json::from_value(json!({
+            "version": "1.0.0",
+            "global": {"config_dir": "configs", "default_namespace": "default"},
+            "environments": [
+                {"name": "dev"},
+                {"name": "prod", "production": true}
+            ],
+            "services": [
+                {"name": "api", "config_path": "api.yaml"},
+                {"name": "mock-billing", "config_path": "mock.yaml", "environments": ["dev"]}
+            ]
+        }))
+        .unwrap();
+        Topology::from_root_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_placement() {
+        let topology = topology();
+
+        assert!(topology.is_placed("api", "prod"));
+        assert!(topology.is_placed("mock-billing", "dev"));
+        assert!(!topology.is_placed("mock-billing", "prod"));
+        assert!(!topology.is_placed("api", "qa"));
+
+        let missing = topology.missing_placements("api", "mock-billing");
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].production);
+        assert!(topology.missing_placements("mock-billing", "api").is_empty());
+    }
+
+    #[test]
+    fn test_base_url() {
+        let config: RootConfig = serde_json::from_value(json!({
+            "version": "1.0.0",
+            "global": {"config_dir": "configs", "default_namespace": "default"},
+            "environments": [
+                {"name": "dev", "scheme": "http", "default_port": 8080},
+                {
+                    "name": "prod",
+                    "hostname_pattern": "{service}.{namespace}.{environment}.example.com",
+                    "ports": {"payments": 8443}
+                }
+            ],
+            "services": []
+        }))
+        .unwrap();
+        let topology = Topology::from_root_config(&config).unwrap();
+        let exposure =
+            Exposure { hostnames: vec!["payments.local".to_string()], ..Default::default() };
+
+        assert_eq!(
+            topology.base_url("payments", Some("billing"), None, "prod").unwrap(),
+            "https://payments.billing.prod.example.com:8443"
+        );
+        assert_eq!(
+            topology.base_url("search", None, None, "prod").unwrap(),
+            "https://search.default.prod.example.com"
+        );
+        assert_eq!(
+            topology.base_url("payments", None, Some(&exposure), "dev").unwrap(),
+            "http://payments.local:8080"
+        );
+        assert!(topology.base_url("payments", None, None, "dev").is_err());
+        assert!(topology.base_url("payments", None, None, "qa").is_err());
+    }
+
+    #[test]
+    fn test_unknown_environment_rejected() {
+        let mut topology = topology();
+        let err = topology.place("api", vec!["qa".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("unknown environment 'qa'"));
+    }
+}
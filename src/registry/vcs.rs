@@ -0,0 +1,316 @@
+//! Version control backends the catalog repository is read and written through
+
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::cancel::CancellationToken;
+use crate::registry::git::{CommitInfo, GitProvider};
+use crate::registry::progress::ProgressReporter;
+
+/// Backends a catalog repository can be accessed with
+///
+/// A gitoxide backend is not implemented yet; asking for one fails with a config error
+/// saying so, rather than as an unknown backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum VcsBackend {
+    /// A local clone managed with libgit2
+    #[default]
+    Git,
+    /// The GitHub REST API, without a local clone or libgit2 transport
+    GitHub,
+}
+
+impl fmt::Display for VcsBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VcsBackend::Git => write!(f, "git"),
+            VcsBackend::GitHub => write!(f, "github"),
+        }
+    }
+}
+
+impl FromStr for VcsBackend {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "git" | "git2" => Ok(VcsBackend::Git),
+            "github" => Ok(VcsBackend::GitHub),
+            "gitoxide" | "gix" => Err(AureaCoreError::Config(
+                "The gitoxide VCS backend is not available yet, use git or github".to_string(),
+            )),
+            other => Err(AureaCoreError::Config(format!(
+                "Unknown VCS backend '{}', expected git or github",
+                other
+            ))),
+        }
+    }
+}
+
+impl VcsBackend {
+    /// Creates a provider for a repository, checked out to `work_dir`
+    ///
+    /// The GitHub backend takes a `https://github.com/<owner>/<repo>` URL or an
+    /// `<owner>/<repo>` slug, authenticates with `GITHUB_TOKEN` if set, and is only
+    /// available when built with the `github` feature.
+    pub fn open(
+        self,
+        repo_url: &str,
+        branch: &str,
+        work_dir: PathBuf,
+    ) -> Result<Box<dyn VcsProvider>> {
+        match self {
+            VcsBackend::Git => {
+                Ok(Box::new(GitProvider::new(repo_url.to_string(), branch.to_string(), work_dir)))
+            }
+            #[cfg(feature = "github")]
+            VcsBackend::GitHub => {
+                let mut provider = crate::registry::github::GitHubProvider::new(
+                    repo_url,
+                    branch.to_string(),
+                    work_dir,
+                )?;
+                if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                    provider = provider.with_token(token);
+                }
+                Ok(Box::new(provider))
+            }
+            #[cfg(not(feature = "github"))]
+            VcsBackend::GitHub => Err(AureaCoreError::Config(
+                "The github VCS backend requires building with the `github` feature".to_string(),
+            )),
+        }
+    }
+}
+
+/// Clones, updates and commits to the repository holding a catalog
+///
+/// Commits are local until pushed, whatever the backend, so a batch of changes lands on
+/// the remote branch at once. Operations that need a local clone, such as reading past
+/// revisions or previewing branches, are only available through [`as_git`](Self::as_git).
+#[async_trait]
+pub trait VcsProvider: Send {
+    /// Backend the provider accesses the repository with
+    fn backend(&self) -> VcsBackend;
+
+    /// Directory the repository is checked out to
+    fn work_dir(&self) -> &Path;
+
+    /// Checks out the branch to the working directory, if not done yet
+    async fn clone_repo(&mut self) -> Result<()>;
+
+    /// Updates the working directory to the latest commit of the remote branch
+    async fn pull(&mut self) -> Result<()>;
+
+    /// Clones the repository, or pulls the latest changes if it was cloned before
+    async fn sync(&mut self) -> Result<()>;
+
+    /// Lists commits after `since` up to the local head, newest first
+    async fn history(&mut self, since: &str) -> Result<Vec<CommitInfo>>;
+
+    /// Writes files, relative to the repository root, and commits them locally
+    ///
+    /// Returns the id of the new commit.
+    async fn commit(&mut self, message: &str, files: &[(PathBuf, String)]) -> Result<String>;
+
+    /// Publishes local commits to the remote branch
+    async fn push(&mut self) -> Result<()>;
+
+    /// Gets the id of the commit the working directory is at
+    fn current_commit(&self) -> Result<String>;
+
+    /// Gets the time of the last successful sync, if recorded
+    fn last_sync(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    /// Gets the path of an AureaCore bookkeeping file kept next to the checkout
+    fn metadata_path(&self, name: &str) -> PathBuf {
+        self.work_dir().join(format!(".aureacore-{}", name))
+    }
+
+    /// Sets the token aborting clones and pulls
+    fn set_cancellation(&mut self, _cancellation: CancellationToken) {}
+
+    /// Reports the transfer progress of clones and pulls, or stops reporting it
+    fn set_progress_reporter(&mut self, _reporter: Option<Arc<dyn ProgressReporter>>) {}
+
+    /// Creates a provider for the same checkout, e.g. for a read-only view
+    fn reopen(&self) -> Box<dyn VcsProvider>;
+
+    /// Gets the local git clone behind the provider, if it has one
+    fn as_git(&self) -> Option<&GitProvider> {
+        None
+    }
+
+    /// Takes the local git clone behind the provider, if it has one
+    fn into_git(self: Box<Self>) -> Option<GitProvider> {
+        None
+    }
+}
+
+/// Runs a provider operation to completion from synchronous code
+///
+/// The operation gets a runtime of its own on a scoped thread, so this works whether or
+/// not the caller is on a Tokio runtime.
+pub(crate) fn block_on<T: Send>(operation: impl Future<Output = Result<T>> + Send) -> Result<T> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?
+                    .block_on(operation)
+            })
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+/// Runs libgit2 on the calling task; use `spawn_blocking` around the provider for large
+/// repositories.
+#[async_trait]
+impl VcsProvider for GitProvider {
+    fn backend(&self) -> VcsBackend {
+        VcsBackend::Git
+    }
+
+    fn work_dir(&self) -> &Path {
+        GitProvider::work_dir(self)
+    }
+
+    async fn clone_repo(&mut self) -> Result<()> {
+        if self.open_existing()? {
+            return Ok(());
+        }
+        GitProvider::clone_repo(self)
+    }
+
+    async fn pull(&mut self) -> Result<()> {
+        self.open_existing()?;
+        GitProvider::pull(self)
+    }
+
+    async fn sync(&mut self) -> Result<()> {
+        GitProvider::sync(self)
+    }
+
+    async fn history(&mut self, since: &str) -> Result<Vec<CommitInfo>> {
+        self.history_since(since)
+    }
+
+    async fn commit(&mut self, message: &str, files: &[(PathBuf, String)]) -> Result<String> {
+        self.commit_files(message, files)
+    }
+
+    async fn push(&mut self) -> Result<()> {
+        GitProvider::push(self)
+    }
+
+    fn current_commit(&self) -> Result<String> {
+        self.head_commit()
+    }
+
+    fn last_sync(&self) -> Option<DateTime<Utc>> {
+        GitProvider::last_sync(self)
+    }
+
+    fn metadata_path(&self, name: &str) -> PathBuf {
+        GitProvider::metadata_path(self, name)
+    }
+
+    fn set_cancellation(&mut self, cancellation: CancellationToken) {
+        GitProvider::set_cancellation(self, cancellation)
+    }
+
+    fn set_progress_reporter(&mut self, reporter: Option<Arc<dyn ProgressReporter>>) {
+        GitProvider::set_progress_reporter(self, reporter)
+    }
+
+    fn reopen(&self) -> Box<dyn VcsProvider> {
+        Box::new(GitProvider::reopen(self))
+    }
+
+    fn as_git(&self) -> Option<&GitProvider> {
+        Some(self)
+    }
+
+    fn into_git(self: Box<Self>) -> Option<GitProvider> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use git2::{Repository, Signature};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_vcs_backend() {
+        assert_eq!("git2".parse::<VcsBackend>().unwrap(), VcsBackend::Git);
+        assert_eq!("GitHub".parse::<VcsBackend>().unwrap(), VcsBackend::GitHub);
+        assert_eq!(VcsBackend::GitHub.to_string(), "github");
+        assert!("svn".parse::<VcsBackend>().is_err());
+        let err = "gitoxide".parse::<VcsBackend>().unwrap_err();
+        assert!(err.to_string().contains("not available yet"));
+    }
+
+    #[tokio::test]
+    async fn test_git_backend_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let seed_path = temp_dir.path().join("seed");
+        let seed = Repository::init(&seed_path).unwrap();
+        fs::write(seed_path.join("README.md"), "# Catalog").unwrap();
+        let mut index = seed.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        let tree = seed.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        let initial = seed
+            .commit(Some("refs/heads/main"), &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        seed.set_head("refs/heads/main").unwrap();
+        let origin_path = temp_dir.path().join("origin.git");
+        git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(seed_path.to_str().unwrap(), &origin_path)
+            .unwrap();
+
+        let mut provider = VcsBackend::Git
+            .open(origin_path.to_str().unwrap(), "main", temp_dir.path().join("work"))
+            .unwrap();
+        assert_eq!(provider.backend(), VcsBackend::Git);
+        provider.clone_repo().await.unwrap();
+        assert!(provider.work_dir().join("README.md").exists());
+
+        let files = vec![(PathBuf::from("services/orders.yaml"), "name: orders\n".to_string())];
+        let id = provider.commit("Add orders", &files).await.unwrap();
+        assert_eq!(
+            fs::read_to_string(provider.work_dir().join("services/orders.yaml")).unwrap(),
+            "name: orders\n"
+        );
+        let history = provider.history(&initial.to_string()).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, id);
+        assert_eq!(history[0].summary, "Add orders");
+
+        provider.push().await.unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        let pushed = origin.find_reference("refs/heads/main").unwrap().target().unwrap();
+        assert_eq!(pushed.to_string(), id);
+        provider.pull().await.unwrap();
+
+        let outside = vec![(PathBuf::from("../escape.yaml"), String::new())];
+        assert!(provider.commit("Escape", &outside).await.is_err());
+    }
+}
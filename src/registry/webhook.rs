@@ -0,0 +1,231 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+
+/// What to do when a webhook cannot deliver a verdict
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailurePolicy {
+    /// Fail validation of the service
+    #[default]
+    Fail,
+    /// Report a warning and continue
+    Warn,
+    /// Continue silently
+    Ignore,
+}
+
+/// An HTTP endpoint that validates service definitions out of process
+///
+/// The endpoint receives `{"service": name, "definition": {...}}` as a JSON POST and
+/// answers with `{"verdict": "pass" | "warn" | "fail", "messages": [...]}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidationWebhook {
+    /// Name of the webhook, used in messages
+    pub name: String,
+    /// URL the definition is posted to
+    pub url: String,
+    /// Milliseconds to wait for a verdict
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// What to do if the endpoint fails, times out or answers malformed verdicts
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+}
+
+impl ValidationWebhook {
+    /// Loads a list of webhooks from a YAML file
+    pub fn load_all(path: &Path) -> Result<Vec<Self>> {
+        serde_yaml::from_str(&std::fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid webhook config {}", path.display()), e)
+        })
+    }
+}
+
+/// Default function to wait five seconds for a verdict
+fn default_timeout_ms() -> u64 {
+    5000
+}
+
+/// Verdict returned by a validation webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    /// The definition passed the checks
+    Pass,
+    /// The definition passed with warnings
+    Warn,
+    /// The definition was rejected
+    Fail,
+}
+
+/// Response body of a validation webhook
+#[derive(Debug, Deserialize)]
+struct WebhookResponse {
+    verdict: Verdict,
+    #[serde(default)]
+    messages: Vec<String>,
+}
+
+/// Warnings and failures reported by the webhooks for one service
+#[derive(Debug, Default)]
+pub(crate) struct WebhookOutcome {
+    pub warnings: Vec<String>,
+    pub failures: Vec<String>,
+}
+
+impl WebhookOutcome {
+    /// Converts reported failures into a validation error
+    pub fn into_result(self) -> Result<()> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(AureaCoreError::ValidationError(self.failures.join("; ")))
+        }
+    }
+}
+
+/// Runs service definitions through the configured validation webhooks
+#[derive(Default)]
+pub(crate) struct WebhookValidator {
+    webhooks: Vec<(ValidationWebhook, ureq::Agent)>,
+}
+
+impl WebhookValidator {
+    /// Creates a validator calling the given webhooks in order
+    pub fn new(webhooks: Vec<ValidationWebhook>) -> Self {
+        let webhooks = webhooks
+            .into_iter()
+            .map(|webhook| {
+                let agent = ureq::AgentBuilder::new()
+                    .timeout(Duration::from_millis(webhook.timeout_ms))
+                    .build();
+                (webhook, agent)
+            })
+            .collect();
+        Self { webhooks }
+    }
+
+    /// Collects the verdicts of all webhooks on a service definition
+    pub fn check(&self, service: &str, definition: &serde_json::Value) -> WebhookOutcome {
+        let mut outcome = WebhookOutcome::default();
+        let body = serde_json::json!({ "service": service, "definition": definition });
+
+        for (webhook, agent) in &self.webhooks {
+            let response = agent
+                .post(&webhook.url)
+                .send_json(&body)
+                .map_err(|e| e.to_string())
+                .and_then(|r| r.into_json::<WebhookResponse>().map_err(|e| e.to_string()));
+
+            match response {
+                Ok(WebhookResponse { verdict, messages }) => {
+                    let messages = if messages.is_empty() {
+                        vec!["no details given".into()]
+                    } else {
+                        messages
+                    };
+                    let target = match verdict {
+                        Verdict::Pass => continue,
+                        Verdict::Warn => &mut outcome.warnings,
+                        Verdict::Fail => &mut outcome.failures,
+                    };
+                    target.extend(
+                        messages.into_iter().map(|m| format!("Webhook '{}': {}", webhook.name, m)),
+                    );
+                }
+                Err(err) => {
+                    let message = format!("Webhook '{}' gave no verdict: {}", webhook.name, err);
+                    match webhook.on_failure {
+                        FailurePolicy::Fail => outcome.failures.push(message),
+                        FailurePolicy::Warn => outcome.warnings.push(message),
+                        FailurePolicy::Ignore => tracing::debug!("{}", message),
+                    }
+                }
+            }
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Serves the given JSON response bodies to one request each, returning the URL
+    pub(crate) fn serve(bodies: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/validate", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for body in bodies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                // Read the headers, then as much body as announced
+                let complete = |request: &[u8]| {
+                    let text = String::from_utf8_lossy(request);
+                    let Some((headers, body)) = text.split_once("\r\n\r\n") else {
+                        return false;
+                    };
+                    let length = headers
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().to_string())
+                        })
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    body.len() >= length
+                };
+                while !complete(&request) {
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    fn webhook(name: &str, url: String, on_failure: FailurePolicy) -> ValidationWebhook {
+        ValidationWebhook { name: name.to_string(), url, timeout_ms: 2000, on_failure }
+    }
+
+    #[test]
+    fn test_webhook_verdicts() {
+        let url = serve(vec![
+            r#"{"verdict": "warn", "messages": ["no runbook"]}"#,
+            r#"{"verdict": "fail", "messages": ["unapproved region"]}"#,
+        ]);
+        let validator = WebhookValidator::new(vec![
+            webhook("ops", url.clone(), FailurePolicy::Fail),
+            webhook("compliance", url, FailurePolicy::Fail),
+            webhook("offline", "http://127.0.0.1:1/validate".into(), FailurePolicy::Warn),
+        ]);
+
+        let outcome = validator.check("billing", &serde_json::json!({"name": "billing"}));
+        assert_eq!(outcome.warnings[0], "Webhook 'ops': no runbook");
+        assert!(outcome.warnings[1].starts_with("Webhook 'offline' gave no verdict"));
+        assert_eq!(outcome.failures, vec!["Webhook 'compliance': unapproved region"]);
+        assert_eq!(
+            outcome.into_result().unwrap_err().to_string(),
+            "Validation error: Webhook 'compliance': unapproved region"
+        );
+    }
+}
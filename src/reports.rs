@@ -0,0 +1,187 @@
+//! Catalog-wide rollups for consumers such as FinOps
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::registry::ServiceRegistry;
+
+/// Key services without a value for the grouping dimension are rolled up under
+pub const UNASSIGNED: &str = "unassigned";
+
+/// What to group services by in a cost rollup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostDimension {
+    /// The owner of the service
+    Team,
+    /// The system the service belongs to
+    System,
+    /// The namespace the service is registered in
+    Namespace,
+}
+
+impl FromStr for CostDimension {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "team" | "owner" => Ok(CostDimension::Team),
+            "system" => Ok(CostDimension::System),
+            "namespace" => Ok(CostDimension::Namespace),
+            other => Err(format!(
+                "Unknown cost dimension '{}', expected 'team', 'system' or 'namespace'",
+                other
+            )),
+        }
+    }
+}
+
+/// Estimated cost of the services sharing a team, system or namespace
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CostGroup {
+    /// Team, system or namespace, or [`UNASSIGNED`]
+    pub key: String,
+    /// Sum of the monthly cost estimates of the services
+    pub monthly_cost: f64,
+    /// Services in the group, sorted by name
+    pub services: Vec<String>,
+    /// Cost centers the services book to, sorted
+    pub cost_centers: Vec<String>,
+    /// Services in the group without a cost estimate
+    pub unestimated: Vec<String>,
+}
+
+/// Estimated monthly cost of the catalog, grouped along one dimension
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostRollup {
+    /// What the services are grouped by
+    pub dimension: CostDimension,
+    /// Groups, most expensive first
+    pub groups: Vec<CostGroup>,
+}
+
+impl CostRollup {
+    /// Sums the monthly cost estimates of all groups
+    pub fn total(&self) -> f64 {
+        self.groups.iter().map(|group| group.monthly_cost).sum()
+    }
+}
+
+impl fmt::Display for CostRollup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for group in &self.groups {
+            write!(
+                f,
+                "{:<24} {:>12.2}  ({} service(s)",
+                group.key,
+                group.monthly_cost,
+                group.services.len()
+            )?;
+            if !group.unestimated.is_empty() {
+                write!(f, ", {} without estimate", group.unestimated.len())?;
+            }
+            writeln!(f, ")")?;
+        }
+        writeln!(f, "{:<24} {:>12.2}", "total", self.total())
+    }
+}
+
+/// Rolls up the monthly cost estimates of the loaded services by team, system or namespace
+pub fn cost_rollup(registry: &ServiceRegistry, dimension: CostDimension) -> Result<CostRollup> {
+    let mut groups: BTreeMap<String, CostGroup> = BTreeMap::new();
+    let mut names = registry.list_services()?;
+    names.sort();
+    for name in names {
+        let service = registry.get_service(&name)?;
+        let definition = service.definition();
+        let key = match dimension {
+            CostDimension::Team => definition.as_ref().and_then(|d| d.owner.clone()),
+            CostDimension::System => definition.as_ref().and_then(|d| d.system.clone()),
+            CostDimension::Namespace => service.config.namespace.clone(),
+        }
+        .unwrap_or_else(|| UNASSIGNED.to_string());
+
+        let group =
+            groups.entry(key.clone()).or_insert_with(|| CostGroup { key, ..CostGroup::default() });
+        group.services.push(name.clone());
+        match definition.as_ref().and_then(|d| d.monthly_cost_estimate) {
+            Some(cost) => group.monthly_cost += cost,
+            None => group.unestimated.push(name.clone()),
+        }
+        if let Some(cost_center) = definition.and_then(|d| d.cost_center) {
+            if !group.cost_centers.contains(&cost_center) {
+                group.cost_centers.push(cost_center);
+            }
+        }
+    }
+
+    let mut groups: Vec<CostGroup> = groups.into_values().collect();
+    for group in &mut groups {
+        group.cost_centers.sort();
+    }
+    groups.sort_by(|a, b| b.monthly_cost.total_cmp(&a.monthly_cost).then(a.key.cmp(&b.key)));
+    Ok(CostRollup { dimension, groups })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_cost_rollup() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), temp_dir.path().join("config"))
+                .unwrap();
+        let services = [
+            (
+                "billing",
+                "payments",
+                "owner: payments\ncost_center: CC-1042\nmonthly_cost_estimate: 1200.5\n",
+            ),
+            (
+                "ledger",
+                "payments",
+                "owner: payments\ncost_center: CC-1043\nmonthly_cost_estimate: 300\n",
+            ),
+            ("search", "discovery", "owner: discovery\nsystem: web\nmonthly_cost_estimate: 2000\n"),
+            ("relay", "default", "system: web\n"),
+        ];
+        for (name, namespace, body) in services {
+            let path = temp_dir.path().join(format!("{}.yaml", name));
+            std::fs::write(
+                &path,
+                format!(
+                    "name: {}\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n{}",
+                    name, body
+                ),
+            )
+            .unwrap();
+            let config =
+                format!(r#"{{"config_path": "{}", "namespace": "{}"}}"#, path.display(), namespace);
+            registry.register_service(name, &config).unwrap();
+        }
+
+        let by_team = cost_rollup(&registry, CostDimension::Team).unwrap();
+        let summary: Vec<(&str, f64)> =
+            by_team.groups.iter().map(|g| (g.key.as_str(), g.monthly_cost)).collect();
+        assert_eq!(summary, vec![("discovery", 2000.0), ("payments", 1500.5), (UNASSIGNED, 0.0)]);
+        assert_eq!(by_team.groups[1].cost_centers, vec!["CC-1042", "CC-1043"]);
+        assert_eq!(by_team.groups[2].unestimated, vec!["relay"]);
+        assert_eq!(by_team.total(), 3500.5);
+
+        let by_system = cost_rollup(&registry, CostDimension::System).unwrap();
+        assert_eq!(by_system.groups[0].key, "web");
+        assert_eq!(by_system.groups[0].services, vec!["relay", "search"]);
+
+        let by_namespace = cost_rollup(&registry, "namespace".parse().unwrap()).unwrap();
+        assert_eq!(by_namespace.groups.len(), 3);
+        assert!(by_namespace.to_string().contains("total"));
+    }
+}
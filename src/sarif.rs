@@ -0,0 +1,247 @@
+//! Validation results as SARIF, so code-review UIs show catalog problems inline
+//!
+//! GitHub code scanning and GitLab code quality annotate the lines of changed files
+//! with the results of a SARIF log. Problems are reported at the definition field they
+//! concern, located with a [`PositionIndex`] of the definition file.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::registry::{ServiceRegistry, ValidationSummary};
+use crate::schema::{Position, PositionIndex, SchemaViolation};
+
+/// Version of the SARIF format written
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// Schema of the SARIF format written
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Kind of problem, reported as a SARIF rule
+struct Rule {
+    id: &'static str,
+    description: &'static str,
+    level: &'static str,
+}
+
+const INVALID_DEFINITION: usize = 0;
+const DEPENDENCY: usize = 1;
+const LINT: usize = 2;
+const DEAD_LINK: usize = 3;
+const WARNING: usize = 4;
+
+const RULES: &[Rule] = &[
+    Rule {
+        id: "invalid-definition",
+        description: "The service definition fails validation",
+        level: "error",
+    },
+    Rule {
+        id: "dependency",
+        description: "A dependency is missing, incompatible or not deployed alongside",
+        level: "warning",
+    },
+    Rule {
+        id: "lint",
+        description: "The service definition doesn't follow catalog conventions",
+        level: "warning",
+    },
+    Rule {
+        id: "dead-link",
+        description: "A documentation, dashboard or runbook link is dead",
+        level: "warning",
+    },
+    Rule { id: "warning", description: "Other problem with the service", level: "warning" },
+];
+
+/// Renders a validation summary as a SARIF log
+///
+/// Schema violations are reported where validation located them, other problems at
+/// the field they mention. Warnings left out by suppressions are included as suppressed results, so code-review
+/// UIs can show them as dismissed. Paths are relative to the repository root where the
+/// definitions are inside it.
+pub fn sarif_log(registry: &ServiceRegistry, summary: &ValidationSummary) -> Value {
+    let mut services: BTreeSet<&str> = summary.warnings.keys().map(String::as_str).collect();
+    services.extend(summary.failed.iter().map(|(name, _)| name.as_str()));
+    services.extend(summary.dead_links.keys().map(String::as_str));
+    services.extend(summary.suppressed.keys().map(String::as_str));
+
+    let mut results = Vec::new();
+    for name in services {
+        let definition = Definition::open(registry, name);
+        let fixes = summary.fixes.get(name);
+        let diagnostics = registry.get_service(name).ok().map(|s| s.status.diagnostics.as_slice());
+        for (_, message) in summary.failed.iter().filter(|(service, _)| service == name) {
+            // Schema violations were located when validating
+            let located = diagnostics
+                .filter(|diagnostics| diagnostics.iter().any(|d| message.contains(&d.to_string())));
+            for diagnostic in located.into_iter().flatten() {
+                let violation = SchemaViolation {
+                    pointer: diagnostic.pointer.clone(),
+                    message: diagnostic.message.clone(),
+                };
+                let position = Some(diagnostic.position);
+                let message = violation.to_string();
+                results.push(definition.result(INVALID_DEFINITION, &message, position, false));
+            }
+            if located.is_none() {
+                let position = definition.locate(name, message, None);
+                results.push(definition.result(INVALID_DEFINITION, message, position, false));
+            }
+        }
+        let warnings = summary.warnings.get(name).into_iter().flatten().map(|w| (w, false));
+        let suppressed = summary.suppressed.get(name).into_iter().flatten().map(|w| (w, true));
+        for (message, suppressed) in warnings.chain(suppressed) {
+            let fix = fixes.into_iter().flatten().find(|fix| fix.warning == *message);
+            let rule = if fix.is_some() {
+                LINT
+            } else if message.to_ascii_lowercase().contains("dependency") {
+                DEPENDENCY
+            } else {
+                WARNING
+            };
+            let pointer = fix.and_then(|fix| fix.patch.first()).map(|op| op.path());
+            let position = definition.locate(name, message, pointer);
+            results.push(definition.result(rule, message, position, suppressed));
+        }
+        for link in summary.dead_links.get(name).into_iter().flatten() {
+            let message = format!("Dead link {} in {}: {}", link.url, link.field, link.reason);
+            let pointer = format!("/{}", link.field.replace('.', "/"));
+            let position = definition.index.as_ref().map(|index| index.nearest(&pointer));
+            results.push(definition.result(DEAD_LINK, &message, position, false));
+        }
+    }
+
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": rule.level },
+            })
+        })
+        .collect();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "aureacore",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/spiralhouse/aureacore",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Definition file of a service, if it could be read
+struct Definition {
+    uri: Option<String>,
+    index: Option<PositionIndex>,
+}
+
+impl Definition {
+    fn open(registry: &ServiceRegistry, name: &str) -> Self {
+        let Ok(service) = registry.get_service(name) else {
+            return Self { uri: None, index: None };
+        };
+        let config_path = &service.config.config_path;
+        let path = registry.definition_path(name).ok().flatten();
+        let path = path.as_deref().unwrap_or(Path::new(config_path));
+        let uri = path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>();
+        let index = std::fs::read_to_string(config_path).ok().map(|c| PositionIndex::parse(&c));
+        Self { uri: Some(uri.join("/")), index }
+    }
+
+    /// Finds the field a problem concerns, by the JSON pointer of its fix if it has one
+    fn locate(&self, service: &str, message: &str, pointer: Option<&str>) -> Option<Position> {
+        let index = self.index.as_ref()?;
+        Some(match pointer {
+            Some(pointer) => index.nearest(pointer),
+            None => index.locate_message(service, message),
+        })
+    }
+
+    fn result(
+        &self,
+        rule: usize,
+        message: &str,
+        position: Option<Position>,
+        suppressed: bool,
+    ) -> Value {
+        let mut result = json!({
+            "ruleId": RULES[rule].id,
+            "ruleIndex": rule,
+            "level": RULES[rule].level,
+            "message": { "text": message },
+        });
+        if let Some(uri) = &self.uri {
+            let mut location = json!({ "artifactLocation": { "uri": uri } });
+            if let Some(position) = position {
+                location["region"] =
+                    json!({ "startLine": position.line, "startColumn": position.column });
+            }
+            result["locations"] = json!([{ "physicalLocation": location }]);
+        }
+        if suppressed {
+            result["suppressions"] = json!([{ "kind": "external" }]);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_sarif_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let work_dir = temp_dir.path().join("config");
+        let mut registry =
+            ServiceRegistry::new(String::new(), "main".to_string(), work_dir.clone()).unwrap();
+        std::fs::create_dir_all(work_dir.join("services")).unwrap();
+        let definition = "\
+name: orders
+version: 1.0.0
+service_type:
+  type: rest
+endpoints:
+  - name: list
+    path: /orders
+dependencies:
+  - service: ghost
+    required: false
+";
+        let path = work_dir.join("services/orders.yaml");
+        std::fs::write(&path, definition).unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.register_service("orders", &config).unwrap();
+        let mut summary = registry.validate_all_services().unwrap();
+        summary.failed.push(("orders".to_string(), "Required dependency 'stock' not found".into()));
+
+        let log = sarif_log(&registry, &summary);
+        assert_eq!(log["version"], SARIF_VERSION);
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        let find = |rule: &str| results.iter().find(|r| r["ruleId"] == rule).unwrap();
+        let at = |result: &Value| {
+            let location = &result["locations"][0]["physicalLocation"];
+            assert_eq!(location["artifactLocation"]["uri"], "services/orders.yaml");
+            (location["region"]["startLine"].clone(), location["region"]["startColumn"].clone())
+        };
+        let failure = find("invalid-definition");
+        assert_eq!(failure["level"], "error");
+        // The missing dependency isn't in the file, so the service name is pointed at
+        assert_eq!(at(failure), (json!(1), json!(1)));
+        assert_eq!(at(find("dependency")), (json!(9), json!(5)));
+        assert_eq!(at(find("lint")), (json!(6), json!(3)));
+    }
+}
@@ -0,0 +1,579 @@
+//! In-process scheduler running periodic jobs such as git sync and revalidation
+
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::{LinkChecker, ServiceRegistry, Severity, SharedRegistry, SnapshotStore};
+
+/// Future of a single job run
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// A job run periodically by the [`Scheduler`]
+pub struct Job {
+    name: String,
+    interval: Duration,
+    jitter: Duration,
+    run: Arc<dyn Fn() -> JobFuture + Send + Sync>,
+}
+
+impl Job {
+    /// Creates a job running `run` every `interval`
+    pub fn new<F, Fut>(name: impl Into<String>, interval: Duration, run: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            interval,
+            jitter: Duration::ZERO,
+            run: Arc::new(move || Box::pin(run())),
+        }
+    }
+
+    /// Delays each run by a random duration up to `jitter`
+    ///
+    /// Spreads the load of instances started at the same time.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Gets the name of the job
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Picks the delay before the next run
+    fn next_delay(&self, run: u64) -> Duration {
+        if self.jitter.is_zero() {
+            return self.interval;
+        }
+        let random = RandomState::new().hash_one((&self.name, run));
+        self.interval + Duration::from_nanos(random % self.jitter.as_nanos().max(1) as u64)
+    }
+}
+
+/// Outcome of the runs of a scheduled job
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobStatus {
+    /// Name of the job
+    pub name: String,
+    /// Time between runs, before jitter
+    pub interval: Duration,
+    /// Whether a run is in progress
+    pub running: bool,
+    /// When the last run started
+    pub last_started: Option<DateTime<Utc>>,
+    /// When the last run finished
+    pub last_finished: Option<DateTime<Utc>>,
+    /// How long the last finished run took
+    pub last_duration: Option<Duration>,
+    /// Error of the last finished run, if it failed
+    pub last_error: Option<String>,
+    /// Number of finished runs
+    pub runs: u64,
+    /// Number of finished runs that failed
+    pub failures: u64,
+    /// Number of runs skipped because the previous run was still in progress
+    pub skipped: u64,
+}
+
+impl JobStatus {
+    fn new(job: &Job) -> Self {
+        Self {
+            name: job.name.clone(),
+            interval: job.interval,
+            running: false,
+            last_started: None,
+            last_finished: None,
+            last_duration: None,
+            last_error: None,
+            runs: 0,
+            failures: 0,
+            skipped: 0,
+        }
+    }
+}
+
+/// Shared view of the status of scheduled jobs, e.g. for the API
+#[derive(Clone, Default)]
+pub struct JobStatuses(Arc<Mutex<BTreeMap<String, JobStatus>>>);
+
+impl JobStatuses {
+    /// Gets the status of every job, ordered by name
+    pub fn all(&self) -> Vec<JobStatus> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect()
+    }
+
+    /// Gets the status of a job
+    pub fn get(&self, name: &str) -> Option<JobStatus> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+    }
+
+    fn update(&self, name: &str, update: impl FnOnce(&mut JobStatus)) {
+        if let Some(status) = self.0.lock().unwrap_or_else(|e| e.into_inner()).get_mut(name) {
+            update(status);
+        }
+    }
+}
+
+/// Runs jobs periodically on the tokio runtime
+///
+/// Each job runs at its own interval plus jitter. A run that is still in progress when
+/// the next one is due causes that run to be skipped, so runs of a job never overlap.
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    statuses: JobStatuses,
+}
+
+impl Scheduler {
+    /// Creates a scheduler without jobs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a job, replacing any job with the same name
+    pub fn with_job(mut self, job: Job) -> Self {
+        self.jobs.retain(|existing| existing.name != job.name);
+        self.jobs.push(job);
+        self
+    }
+
+    /// Gets a view of the status of the jobs that stays valid once started
+    pub fn statuses(&self) -> JobStatuses {
+        self.statuses.clone()
+    }
+
+    /// Starts running the jobs, the first run of each after one interval
+    pub fn start(self) -> SchedulerHandle {
+        let mut tasks = Vec::with_capacity(self.jobs.len());
+        for job in self.jobs {
+            self.statuses
+                .0
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(job.name.clone(), JobStatus::new(&job));
+            tasks.push(tokio::spawn(run_job(job, self.statuses.clone())));
+        }
+        SchedulerHandle { statuses: self.statuses, tasks }
+    }
+}
+
+/// Schedules the runs of a job until aborted
+async fn run_job(job: Job, statuses: JobStatuses) {
+    let mut current: Option<JoinHandle<()>> = None;
+    for run in 0u64.. {
+        tokio::time::sleep(job.next_delay(run)).await;
+        if current.as_ref().is_some_and(|task| !task.is_finished()) {
+            tracing::warn!(
+                "Skipping run of job '{}', the previous run is still in progress",
+                job.name
+            );
+            statuses.update(&job.name, |status| status.skipped += 1);
+            continue;
+        }
+
+        statuses.update(&job.name, |status| {
+            status.running = true;
+            status.last_started = Some(Utc::now());
+        });
+        let future = (job.run)();
+        let name = job.name.clone();
+        let statuses = statuses.clone();
+        current = Some(tokio::spawn(async move {
+            let started = Instant::now();
+            let result = future.await;
+            if let Err(err) = &result {
+                tracing::warn!("Job '{}' failed: {}", name, err);
+            }
+            statuses.update(&name, |status| {
+                status.running = false;
+                status.last_finished = Some(Utc::now());
+                status.last_duration = Some(started.elapsed());
+                status.runs += 1;
+                status.last_error = result.err().map(|err| err.to_string());
+                if status.last_error.is_some() {
+                    status.failures += 1;
+                }
+            });
+        }));
+    }
+}
+
+/// Handle of a started [`Scheduler`]; dropping it stops scheduling further runs
+pub struct SchedulerHandle {
+    statuses: JobStatuses,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Gets a view of the status of the jobs
+    pub fn statuses(&self) -> JobStatuses {
+        self.statuses.clone()
+    }
+
+    /// Stops scheduling further runs; runs in progress are left to finish
+    pub fn shutdown(self) {}
+}
+
+impl Drop for SchedulerHandle {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Runs `run` with the registry locked on the blocking thread pool
+///
+/// Syncing and validating make git and HTTP calls (webhooks, schema registries) that
+/// would otherwise stall the runtime workers serving the API.
+async fn run_blocking<T, F>(registry: SharedRegistry, job: &'static str, run: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ServiceRegistry) -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || run(&mut registry.blocking_lock()))
+        .await
+        .map_err(|e| AureaCoreError::Internal(format!("Job '{}' panicked: {}", job, e)))?
+}
+
+/// Creates a job pulling the repository and reloading services when the sync succeeds
+///
+/// A catalog left stale by a failed sync keeps being served from local state, and
+/// the next run retries the sync.
+pub fn sync_job(registry: SharedRegistry, interval: Duration) -> Job {
+    Job::new("sync", interval, move || {
+        run_blocking(registry.clone(), "sync", |registry| {
+            if registry.is_read_only() {
+                return Ok(());
+            }
+            if !registry.sync()?.is_stale() {
+                registry.load_services()?;
+                // Rules changed in the repository take effect without a restart
+                if let Err(err) = registry.reload_rules() {
+                    tracing::warn!("Keeping the current rules, failed to reload them: {}", err);
+                }
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Creates a job refreshing the registry lock, or acquiring it while read-only
+///
+/// The interval should be well inside the lock's TTL, so other instances never see
+/// the lock of a live writer as abandoned.
+pub fn heartbeat_job(registry: SharedRegistry, interval: Duration) -> Job {
+    Job::new("heartbeat", interval, move || {
+        let registry = registry.clone();
+        async move {
+            if registry.lock().await.heartbeat()? {
+                tracing::debug!("Holding the registry lock");
+            }
+            Ok(())
+        }
+    })
+}
+
+/// Creates a job revalidating all services
+///
+/// Read replicas are skipped, since they serve the validation of their writer.
+pub fn revalidate_job(registry: SharedRegistry, interval: Duration) -> Job {
+    Job::new("revalidate", interval, move || {
+        run_blocking(registry.clone(), "revalidate", |registry| {
+            if registry.replica_of().is_none() {
+                registry.validate_all_services()?;
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Creates a job running the doctor's consistency checks
+///
+/// Findings are reported in the log; the run fails if any is an error, so the
+/// catalog's health shows in the job's status.
+pub fn health_check_job(registry: SharedRegistry, interval: Duration) -> Job {
+    Job::new("health-check", interval, move || {
+        run_blocking(registry.clone(), "health-check", |registry| {
+            let report = registry.doctor(None)?;
+            for finding in &report.findings {
+                tracing::warn!(
+                    "[{}] {}: {}{}",
+                    finding.severity,
+                    finding.check,
+                    finding.service.as_ref().map(|s| format!("{}: ", s)).unwrap_or_default(),
+                    finding.message
+                );
+            }
+            if !report.is_healthy() {
+                return Err(AureaCoreError::ValidationError(format!(
+                    "Catalog is unhealthy, {} error(s) found",
+                    report.count(Severity::Error)
+                )));
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Creates a job detecting drift of the loaded services from the lockfile
+///
+/// Catalogs without a lockfile are skipped. The run fails if the services drifted,
+/// so the drift shows in the job's status.
+pub fn drift_detection_job(registry: SharedRegistry, interval: Duration) -> Job {
+    Job::new("drift-detection", interval, move || {
+        run_blocking(registry.clone(), "drift-detection", |registry| {
+            if !registry.lockfile_path().exists() {
+                return Ok(());
+            }
+            let drift = registry.check_lockfile()?;
+            for change in &drift {
+                tracing::warn!("Catalog drifted from the lockfile: {}", change);
+            }
+            if !drift.is_empty() {
+                return Err(AureaCoreError::ValidationError(format!(
+                    "Catalog drifted from the lockfile in {} place(s)",
+                    drift.len()
+                )));
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Creates a job publishing snapshots of the catalog for read replicas
+///
+/// Nothing is published while another instance holds the registry lock, since that
+/// instance is the writer. The registry lock is only held while encoding the snapshot.
+pub fn publish_snapshot_job(
+    registry: SharedRegistry,
+    store: Arc<dyn SnapshotStore>,
+    interval: Duration,
+) -> Job {
+    Job::new("publish-snapshot", interval, move || {
+        let registry = registry.clone();
+        let store = store.clone();
+        async move {
+            let snapshot = {
+                let registry = registry.lock().await;
+                if registry.is_read_only() {
+                    return Ok(());
+                }
+                registry.export_snapshot()?
+            };
+            tokio::task::spawn_blocking(move || store.publish(&snapshot))
+                .await
+                .map_err(|e| AureaCoreError::Internal(format!("Publishing panicked: {}", e)))?
+        }
+    })
+}
+
+/// Creates a job hydrating a read replica from the latest published snapshot
+pub fn follow_snapshots_job(
+    registry: SharedRegistry,
+    store: Arc<dyn SnapshotStore>,
+    interval: Duration,
+) -> Job {
+    Job::new("follow-snapshots", interval, move || {
+        let registry = registry.clone();
+        let store = store.clone();
+        async move {
+            let snapshot = tokio::task::spawn_blocking(move || store.fetch())
+                .await
+                .map_err(|e| AureaCoreError::Internal(format!("Fetching panicked: {}", e)))??;
+            if let Some(snapshot) = snapshot {
+                registry.lock().await.hydrate(&snapshot)?;
+            }
+            Ok(())
+        }
+    })
+}
+
+/// Creates a job checking that documentation, dashboard and runbook links are alive
+///
+/// Dead links are reported in the log; the registry lock is only held while
+/// revalidating, not while links are requested.
+pub fn link_check_job(registry: SharedRegistry, interval: Duration) -> Job {
+    let checker = Arc::new(LinkChecker::new());
+    Job::new("link-check", interval, move || {
+        let registry = registry.clone();
+        let checker = checker.clone();
+        async move {
+            let urls: Vec<String> = {
+                let registry = registry.lock().await;
+                registry
+                    .list_services()?
+                    .iter()
+                    .filter_map(|name| registry.get_service(name).ok()?.definition())
+                    .flat_map(|definition| crate::registry::service_links(&definition))
+                    .map(|(_, url)| url)
+                    .collect()
+            };
+            for (url, reason) in checker.check(&urls).await {
+                tracing::warn!("Dead link {}: {}", url, reason);
+            }
+            Ok(())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scheduler_runs_jobs_without_overlap() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let slow = Job::new("slow", Duration::from_millis(10), move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(35)).await;
+                Ok(())
+            }
+        });
+        let failing = Job::new("failing", Duration::from_millis(10), || async {
+            Err(AureaCoreError::Internal("boom".to_string()))
+        })
+        .with_jitter(Duration::from_millis(5));
+
+        let handle = Scheduler::new().with_job(slow).with_job(failing).start();
+        let statuses = handle.statuses();
+        assert_eq!(statuses.all().len(), 2);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        handle.shutdown();
+
+        let slow = statuses.get("slow").unwrap();
+        assert!(slow.runs >= 1);
+        assert!(slow.skipped >= 1);
+        assert!(calls.load(Ordering::SeqCst) as u64 <= slow.runs + 1);
+        assert!(slow.last_started.is_some());
+
+        let failing = statuses.get("failing").unwrap();
+        assert!(failing.failures >= 1);
+        assert_eq!(failing.failures, failing.runs);
+        assert!(failing.last_error.as_deref().unwrap().contains("boom"));
+
+        // No further runs are scheduled after shutdown
+        let runs = statuses.get("failing").unwrap().runs;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(statuses.get("failing").unwrap().runs, runs);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_jobs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("orders.yaml");
+        std::fs::write(
+            &path,
+            "name: orders\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let registry = |dir: &str| {
+            let registry = crate::registry::ServiceRegistry::new(
+                String::new(),
+                "main".to_string(),
+                temp_dir.path().join(dir),
+            )
+            .unwrap();
+            Arc::new(tokio::sync::Mutex::new(registry))
+        };
+        let writer = registry("writer");
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        writer.lock().await.register_service("orders", &config).unwrap();
+        let replica = registry("replica");
+        let store: Arc<dyn SnapshotStore> = Arc::new(crate::registry::DirectorySnapshotStore::new(
+            temp_dir.path().join("snapshots"),
+        ));
+
+        let follow = follow_snapshots_job(replica.clone(), store.clone(), Duration::from_secs(1));
+        (follow.run)().await.unwrap();
+        assert!(replica.lock().await.replica_of().is_none());
+
+        let publish = publish_snapshot_job(writer, store, Duration::from_secs(1));
+        (publish.run)().await.unwrap();
+        (follow.run)().await.unwrap();
+        let replica = replica.lock().await;
+        assert!(replica.replica_of().is_some());
+        assert!(replica.get_service("orders").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_job_acquires_released_lock() {
+        use crate::registry::{FileLock, RegistryLock};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let work_dir = temp_dir.path().join("config");
+        let writer = FileLock::for_work_dir(&work_dir);
+        assert!(writer.try_acquire().unwrap());
+
+        let registry = crate::registry::ServiceRegistry::new(
+            String::new(),
+            "main".to_string(),
+            work_dir.clone(),
+        )
+        .unwrap()
+        .with_lock(Box::new(FileLock::for_work_dir(&work_dir)))
+        .unwrap();
+        let registry = Arc::new(tokio::sync::Mutex::new(registry));
+        assert!(registry.lock().await.is_read_only());
+
+        let heartbeat = heartbeat_job(registry.clone(), Duration::from_secs(1));
+        (heartbeat.run)().await.unwrap();
+        assert!(registry.lock().await.is_read_only());
+
+        writer.release().unwrap();
+        (heartbeat.run)().await.unwrap();
+        assert!(!registry.lock().await.is_read_only());
+    }
+
+    #[tokio::test]
+    async fn test_health_and_drift_jobs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("orders.yaml");
+        std::fs::write(
+            &path,
+            "name: orders\nversion: 1.0.0\nservice_type:\n  type: rest\nendpoints: []\n",
+        )
+        .unwrap();
+        let mut registry = crate::registry::ServiceRegistry::new(
+            String::new(),
+            "main".to_string(),
+            temp_dir.path().join("config"),
+        )
+        .unwrap();
+        let config = format!(r#"{{"config_path": "{}"}}"#, path.display());
+        registry.register_service("orders", &config).unwrap();
+        let registry = Arc::new(tokio::sync::Mutex::new(registry));
+
+        let health = health_check_job(registry.clone(), Duration::from_secs(1));
+        (health.run)().await.unwrap();
+
+        // Without a lockfile there is nothing to drift from
+        let drift = drift_detection_job(registry.clone(), Duration::from_secs(1));
+        (drift.run)().await.unwrap();
+
+        registry.lock().await.write_lockfile().unwrap();
+        (drift.run)().await.unwrap();
+        registry.lock().await.register_service("payments", &config).unwrap();
+        let err = (drift.run)().await.unwrap_err();
+        assert!(err.to_string().contains("drifted from the lockfile"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Request capacity providers declare, checked against the load their consumers expect
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::service::{Dependency, ServiceSchema};
+
+/// Requests per second a provider can serve, in total and per consumer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Capacity {
+    /// Requests per second the service can serve across all consumers
+    pub requests_per_sec: Option<u64>,
+    /// Requests per second individual consumers may send
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quotas: Vec<Quota>,
+}
+
+impl Capacity {
+    /// Gets the quota granted to a consumer, if it has one
+    pub fn quota_for(&self, consumer: &str) -> Option<u64> {
+        self.quotas.iter().find(|q| q.consumer == consumer).map(|q| q.requests_per_sec)
+    }
+}
+
+/// Requests per second a provider allows one consumer to send
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Quota {
+    /// Name of the consuming service
+    pub consumer: String,
+    /// Requests per second the consumer may send
+    pub requests_per_sec: u64,
+}
+
+/// Declared load that a provider's capacity doesn't cover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadViolation {
+    /// A consumer expects to send more than the quota the provider grants it
+    QuotaExceeded { provider: String, consumer: String, expected: u64, quota: u64 },
+    /// Consumers together expect to send more than the provider can serve
+    CapacityExceeded { load: u64, consumers: usize, capacity: u64 },
+}
+
+impl fmt::Display for LoadViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadViolation::QuotaExceeded { provider, consumer, expected, quota } => write!(
+                f,
+                "Expected load of {} requests/sec on '{}' exceeds its quota of {} for '{}'",
+                expected, provider, quota, consumer
+            ),
+            LoadViolation::CapacityExceeded { load, consumers, capacity } => write!(
+                f,
+                "Expected load of {} requests/sec from {} consumers exceeds the capacity of {}",
+                load, consumers, capacity
+            ),
+        }
+    }
+}
+
+/// Checks the load a consumer expects on a dependency against the provider's quota for it
+///
+/// Consumers without a quota, or not declaring their load, are not checked.
+pub fn check_quota(
+    consumer: &str,
+    dependency: &Dependency,
+    provider: &ServiceSchema,
+) -> Option<LoadViolation> {
+    let expected = dependency.expected_requests_per_sec?;
+    let quota = provider.capacity.as_ref()?.quota_for(consumer)?;
+    (expected > quota).then(|| LoadViolation::QuotaExceeded {
+        provider: dependency.service.clone(),
+        consumer: consumer.to_string(),
+        expected,
+        quota,
+    })
+}
+
+/// Checks the aggregate load consumers expect on a provider against its capacity
+///
+/// `loads` holds the requests per second each consumer declared.
+pub fn check_capacity(provider: &ServiceSchema, loads: &[(String, u64)]) -> Option<LoadViolation> {
+    let capacity = provider.capacity.as_ref()?.requests_per_sec?;
+    let load = loads.iter().map(|(_, load)| load).sum();
+    (load > capacity).then_some(LoadViolation::CapacityExceeded {
+        load,
+        consumers: loads.len(),
+        capacity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_check_load() {
+        let provider: ServiceSchema = serde_json::from_value(json!({
+            "name": "billing",
+            "version": "1.0.0",
+            "service_type": {"type": "rest"},
+            "endpoints": [],
+            "capacity": {
+                "requests_per_sec": 500,
+                "quotas": [{"consumer": "orders", "requests_per_sec": 200}]
+            }
+        }))
+        .unwrap();
+        let dependency = |expected: Option<u64>| -> Dependency {
+            serde_json::from_value(
+                json!({"service": "billing", "expected_requests_per_sec": expected}),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            check_quota("orders", &dependency(Some(250)), &provider).unwrap().to_string(),
+            "Expected load of 250 requests/sec on 'billing' exceeds its quota of 200 for 'orders'"
+        );
+        assert!(check_quota("orders", &dependency(Some(200)), &provider).is_none());
+        assert!(check_quota("orders", &dependency(None), &provider).is_none());
+        assert!(check_quota("search", &dependency(Some(1000)), &provider).is_none());
+
+        let loads = vec![("orders".to_string(), 250), ("search".to_string(), 300)];
+        assert_eq!(
+            check_capacity(&provider, &loads),
+            Some(LoadViolation::CapacityExceeded { load: 550, consumers: 2, capacity: 500 })
+        );
+        assert!(check_capacity(&provider, &loads[..1]).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+//! Strategies deciding whether a dependency's version satisfies a constraint
+
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::validation::VersionCompatibility;
+
+/// Decides whether the version found for a dependency satisfies its constraint
+///
+/// Unparseable versions are reported as [`VersionCompatibility::MajorIncompatible`].
+pub trait CompatibilityPolicy: Send + Sync {
+    /// Checks the version of a dependency against the version it is expected at
+    fn check(&self, version: &str, constraint: &str) -> VersionCompatibility;
+}
+
+/// Semantic versioning where only major bumps break; minor differences are warned about
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SemverLoose;
+
+impl CompatibilityPolicy for SemverLoose {
+    fn check(&self, version: &str, constraint: &str) -> VersionCompatibility {
+        let (Ok(found), Ok(expected)) = (Version::parse(version), Version::parse(constraint))
+        else {
+            return VersionCompatibility::MajorIncompatible;
+        };
+        if found.major != expected.major {
+            VersionCompatibility::MajorIncompatible
+        } else if found.minor != expected.minor {
+            VersionCompatibility::MinorIncompatible
+        } else {
+            VersionCompatibility::Compatible
+        }
+    }
+}
+
+/// Semantic versioning where minor bumps break as well, e.g. for internal APIs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SemverStrict;
+
+impl CompatibilityPolicy for SemverStrict {
+    fn check(&self, version: &str, constraint: &str) -> VersionCompatibility {
+        let (Ok(found), Ok(expected)) = (Version::parse(version), Version::parse(constraint))
+        else {
+            return VersionCompatibility::MajorIncompatible;
+        };
+        if (found.major, found.minor) != (expected.major, expected.minor) {
+            VersionCompatibility::MajorIncompatible
+        } else {
+            VersionCompatibility::Compatible
+        }
+    }
+}
+
+/// Any difference from the expected version breaks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExactMatch;
+
+impl CompatibilityPolicy for ExactMatch {
+    fn check(&self, version: &str, constraint: &str) -> VersionCompatibility {
+        let equal = match (Version::parse(version), Version::parse(constraint)) {
+            (Ok(found), Ok(expected)) => found == expected,
+            _ => version.trim() == constraint.trim(),
+        };
+        if equal {
+            VersionCompatibility::Compatible
+        } else {
+            VersionCompatibility::MajorIncompatible
+        }
+    }
+}
+
+/// Calendar versioning such as `2024.05.1`
+///
+/// A version older than the expected one breaks; a newer one from another year (the
+/// first segment) is warned about.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CalVer;
+
+impl CalVer {
+    /// Parses the numeric segments of a calendar version, ignoring any `-` or `+` suffix
+    fn segments(version: &str) -> Option<Vec<u64>> {
+        let version = version.trim().trim_start_matches('v');
+        let release = version.split(['-', '+']).next()?;
+        let segments: Vec<u64> =
+            release.split('.').map(|segment| segment.parse().ok()).collect::<Option<_>>()?;
+        (segments.len() >= 2).then_some(segments)
+    }
+}
+
+impl CompatibilityPolicy for CalVer {
+    fn check(&self, version: &str, constraint: &str) -> VersionCompatibility {
+        let (Some(found), Some(expected)) = (Self::segments(version), Self::segments(constraint))
+        else {
+            return VersionCompatibility::MajorIncompatible;
+        };
+        let len = found.len().max(expected.len());
+        let padded = |segments: &[u64]| {
+            let mut segments = segments.to_vec();
+            segments.resize(len, 0);
+            segments
+        };
+        if padded(&found).cmp(&padded(&expected)) == Ordering::Less {
+            VersionCompatibility::MajorIncompatible
+        } else if found[0] != expected[0] {
+            VersionCompatibility::MinorIncompatible
+        } else {
+            VersionCompatibility::Compatible
+        }
+    }
+}
+
+/// Built-in compatibility policy, as named in service definitions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompatibilityStrategy {
+    /// See [`SemverLoose`]
+    #[default]
+    SemverLoose,
+    /// See [`SemverStrict`]
+    SemverStrict,
+    /// See [`ExactMatch`]
+    ExactMatch,
+    /// See [`CalVer`]
+    #[serde(rename = "calver")]
+    CalVer,
+}
+
+impl CompatibilityPolicy for CompatibilityStrategy {
+    fn check(&self, version: &str, constraint: &str) -> VersionCompatibility {
+        match self {
+            CompatibilityStrategy::SemverLoose => SemverLoose.check(version, constraint),
+            CompatibilityStrategy::SemverStrict => SemverStrict.check(version, constraint),
+            CompatibilityStrategy::ExactMatch => ExactMatch.check(version, constraint),
+            CompatibilityStrategy::CalVer => CalVer.check(version, constraint),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::validation::VersionCompatibility::*;
+
+    #[test]
+    fn test_compatibility_policies() {
+        assert_eq!(SemverLoose.check("1.2.3", "1.2.0"), Compatible);
+        assert_eq!(SemverLoose.check("1.3.0", "1.2.0"), MinorIncompatible);
+        assert_eq!(SemverLoose.check("2.0.0", "1.2.0"), MajorIncompatible);
+        assert_eq!(SemverLoose.check("latest", "1.2.0"), MajorIncompatible);
+
+        assert_eq!(SemverStrict.check("1.2.3", "1.2.0"), Compatible);
+        assert_eq!(SemverStrict.check("1.3.0", "1.2.0"), MajorIncompatible);
+
+        assert_eq!(ExactMatch.check("1.2.0", "1.2.0"), Compatible);
+        assert_eq!(ExactMatch.check("1.2.1", "1.2.0"), MajorIncompatible);
+        assert_eq!(ExactMatch.check("main", "main"), Compatible);
+
+        assert_eq!(CalVer.check("2024.05.1", "2024.05"), Compatible);
+        assert_eq!(CalVer.check("2024.04", "2024.05"), MajorIncompatible);
+        assert_eq!(CalVer.check("2025.01", "2024.05"), MinorIncompatible);
+        assert_eq!(CalVer.check("v24.5-rc1", "24.5"), Compatible);
+        assert_eq!(CalVer.check("2024", "2024.05"), MajorIncompatible);
+
+        let strategy: CompatibilityStrategy = serde_json::from_str("\"calver\"").unwrap();
+        assert_eq!(strategy, CompatibilityStrategy::CalVer);
+        assert_eq!(
+            serde_json::to_string(&CompatibilityStrategy::SemverStrict).unwrap(),
+            "\"semver-strict\""
+        );
+        assert_eq!(CompatibilityStrategy::default().check("1.3.0", "1.2.0"), MinorIncompatible);
+    }
+}
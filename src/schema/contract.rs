@@ -0,0 +1,312 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::service::ServiceSchema;
+
+/// An API version a service consumes from one of its dependencies
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ConsumedApi {
+    /// Name of the providing service
+    pub service: String,
+    /// Name of the provider endpoint
+    pub endpoint: String,
+    /// API version, or semver requirement, the consumer is pinned to
+    pub version: String,
+}
+
+/// An endpoint a provider publishes for other services to consume
+///
+/// Once a provider lists any consumables, its other endpoints are private.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Consumable {
+    /// Name of the endpoint offered
+    pub endpoint: String,
+    /// Services allowed to consume the endpoint, any service if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumers: Vec<String>,
+    /// What consumers may rely on, e.g. stability or rate limits
+    pub description: Option<String>,
+}
+
+impl Consumable {
+    /// Checks whether the endpoint is offered to a consumer
+    pub fn offered_to(&self, consumer: &str) -> bool {
+        self.consumers.is_empty() || self.consumers.iter().any(|c| c == consumer)
+    }
+}
+
+/// Contract test suite a service declares, run by `aureacore verify`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ContractTest {
+    /// Verification results published to a Pact broker
+    Pact {
+        /// Name of the suite
+        name: String,
+        /// Base URL of the Pact broker
+        broker_url: String,
+        /// Name of the service in the broker, defaulting to the service name
+        pacticipant: Option<String>,
+        /// Environment the service has to be deployable to, defaulting to the latest versions
+        environment: Option<String>,
+    },
+    /// Shell command exiting with 0 when the contracts hold
+    Command {
+        /// Name of the suite
+        name: String,
+        /// Command to run with `sh -c`
+        command: String,
+        /// Directory to run the command in, relative to the definition file
+        working_dir: Option<String>,
+    },
+}
+
+impl ContractTest {
+    /// Gets the name of the suite
+    pub fn name(&self) -> &str {
+        match self {
+            ContractTest::Pact { name, .. } | ContractTest::Command { name, .. } => name,
+        }
+    }
+}
+
+/// A consumed API that the provider does not offer
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractViolation {
+    /// The provider has no endpoint with the consumed name
+    MissingEndpoint { provider: String, endpoint: String },
+    /// The provider endpoint offers none of the versions the consumer is pinned to
+    UnsupportedVersion { provider: String, endpoint: String, version: String, offered: Vec<String> },
+    /// The provider doesn't publish the endpoint as a consumable
+    PrivateEndpoint { provider: String, endpoint: String },
+    /// The provider publishes the endpoint to other consumers only
+    NotOffered { provider: String, endpoint: String, consumer: String },
+    /// The provider publishes none of its consumables to the dependent
+    NoConsumables { provider: String, consumer: String },
+}
+
+impl fmt::Display for ContractViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractViolation::MissingEndpoint { provider, endpoint } => {
+                write!(f, "Consumed endpoint '{}' is not provided by '{}'", endpoint, provider)
+            }
+            ContractViolation::UnsupportedVersion { provider, endpoint, version, offered } => {
+                write!(
+                    f,
+                    "Consumed API version {} of '{}.{}' is not offered (provided: {})",
+                    version,
+                    provider,
+                    endpoint,
+                    offered.join(", ")
+                )
+            }
+            ContractViolation::PrivateEndpoint { provider, endpoint } => {
+                write!(f, "Consumed endpoint '{}' is private to '{}'", endpoint, provider)
+            }
+            ContractViolation::NotOffered { provider, endpoint, consumer } => write!(
+                f,
+                "Consumed endpoint '{}' of '{}' is not offered to '{}'",
+                endpoint, provider, consumer
+            ),
+            ContractViolation::NoConsumables { provider, consumer } => {
+                write!(f, "'{}' offers no consumable to '{}'", provider, consumer)
+            }
+        }
+    }
+}
+
+/// Parses an API version leniently, accepting a `v` prefix and missing components
+fn parse_api_version(version: &str) -> Option<Version> {
+    let trimmed = version.trim_start_matches('v');
+    let padded = match trimmed.matches('.').count() {
+        0 => format!("{}.0.0", trimmed),
+        1 => format!("{}.0", trimmed),
+        _ => trimmed.to_string(),
+    };
+    Version::parse(&padded).ok()
+}
+
+/// Checks whether an offered API version satisfies the version a consumer is pinned to
+///
+/// Versions match if they are equal, or if the pinned version is a semver requirement
+/// matched by the offered version.
+pub fn version_satisfies(offered: &str, wanted: &str) -> bool {
+    if offered == wanted {
+        return true;
+    }
+
+    match (parse_api_version(offered), VersionReq::parse(wanted.trim_start_matches('v'))) {
+        (Some(offered), Ok(requirement)) => requirement.matches(&offered),
+        _ => false,
+    }
+}
+
+/// Checks a consumed API against the provider's definition
+///
+/// Provider endpoints that do not declare any versions accept every version.
+pub fn check_consumption(
+    consumed: &ConsumedApi,
+    provider: &ServiceSchema,
+) -> Option<ContractViolation> {
+    let Some(endpoint) = provider.endpoints.iter().find(|e| e.name == consumed.endpoint) else {
+        return Some(ContractViolation::MissingEndpoint {
+            provider: consumed.service.clone(),
+            endpoint: consumed.endpoint.clone(),
+        });
+    };
+
+    if endpoint.provides.is_empty()
+        || endpoint.provides.iter().any(|offered| version_satisfies(offered, &consumed.version))
+    {
+        return None;
+    }
+
+    Some(ContractViolation::UnsupportedVersion {
+        provider: consumed.service.clone(),
+        endpoint: consumed.endpoint.clone(),
+        version: consumed.version.clone(),
+        offered: endpoint.provides.clone(),
+    })
+}
+
+/// Checks that a provider publishing consumables offers the consumed endpoint to `consumer`
+///
+/// Providers without consumables offer all their endpoints to everyone.
+pub fn check_consumable(
+    consumer: &str,
+    consumed: &ConsumedApi,
+    provider: &ServiceSchema,
+) -> Option<ContractViolation> {
+    if provider.consumables.is_empty() {
+        return None;
+    }
+    let consumables: Vec<&Consumable> =
+        provider.consumables.iter().filter(|c| c.endpoint == consumed.endpoint).collect();
+    if consumables.is_empty() {
+        return Some(ContractViolation::PrivateEndpoint {
+            provider: consumed.service.clone(),
+            endpoint: consumed.endpoint.clone(),
+        });
+    }
+    if consumables.iter().any(|c| c.offered_to(consumer)) {
+        return None;
+    }
+    Some(ContractViolation::NotOffered {
+        provider: consumed.service.clone(),
+        endpoint: consumed.endpoint.clone(),
+        consumer: consumer.to_string(),
+    })
+}
+
+/// Checks that a provider publishing consumables offers at least one of them to `consumer`
+pub fn check_dependency_offered(
+    consumer: &str,
+    provider: &ServiceSchema,
+) -> Option<ContractViolation> {
+    if provider.consumables.is_empty()
+        || provider.consumables.iter().any(|c| c.offered_to(consumer))
+    {
+        return None;
+    }
+    Some(ContractViolation::NoConsumables {
+        provider: provider.name.clone(),
+        consumer: consumer.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn provider() -> ServiceSchema {
+        serde_json::from_value(json!({
+            "name": "billing",
+            "version": "2.3.0",
+            "service_type": {"type": "rest"},
+            "endpoints": [
+                {"name": "invoices", "path": "/invoices", "provides": ["v1", "2.1.0"]},
+                {"name": "health", "path": "/health"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn consumed(endpoint: &str, version: &str) -> ConsumedApi {
+        ConsumedApi {
+            service: "billing".to_string(),
+            endpoint: endpoint.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_version_satisfies() {
+        assert!(version_satisfies("v1", "v1"));
+        assert!(version_satisfies("2.1.0", "^2.0"));
+        assert!(version_satisfies("v2", ">=2"));
+        assert!(!version_satisfies("v1", "v2"));
+        assert!(!version_satisfies("2.1.0", "^3"));
+        assert!(!version_satisfies("beta", "^1"));
+    }
+
+    #[test]
+    fn test_check_consumable() {
+        let mut provider = provider();
+        assert_eq!(check_consumable("orders", &consumed("health", "v1"), &provider), None);
+        assert_eq!(check_dependency_offered("orders", &provider), None);
+
+        provider.consumables = vec![Consumable {
+            endpoint: "invoices".to_string(),
+            consumers: vec!["checkout".to_string()],
+            description: None,
+        }];
+        assert_eq!(check_consumable("checkout", &consumed("invoices", "v1"), &provider), None);
+        assert_eq!(
+            check_consumable("orders", &consumed("invoices", "v1"), &provider),
+            Some(ContractViolation::NotOffered {
+                provider: "billing".to_string(),
+                endpoint: "invoices".to_string(),
+                consumer: "orders".to_string(),
+            })
+        );
+        assert_eq!(
+            check_consumable("checkout", &consumed("health", "v1"), &provider).unwrap().to_string(),
+            "Consumed endpoint 'health' is private to 'billing'"
+        );
+        assert_eq!(check_dependency_offered("checkout", &provider), None);
+        assert!(matches!(
+            check_dependency_offered("orders", &provider),
+            Some(ContractViolation::NoConsumables { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_consumption() {
+        let provider = provider();
+
+        assert_eq!(check_consumption(&consumed("invoices", "v1"), &provider), None);
+        assert_eq!(check_consumption(&consumed("invoices", "^2"), &provider), None);
+        // Unversioned endpoints accept any version
+        assert_eq!(check_consumption(&consumed("health", "v9"), &provider), None);
+
+        assert_eq!(
+            check_consumption(&consumed("invoices", "v3"), &provider),
+            Some(ContractViolation::UnsupportedVersion {
+                provider: "billing".to_string(),
+                endpoint: "invoices".to_string(),
+                version: "v3".to_string(),
+                offered: vec!["v1".to_string(), "2.1.0".to_string()],
+            })
+        );
+        assert!(matches!(
+            check_consumption(&consumed("refunds", "v1"), &provider),
+            Some(ContractViolation::MissingEndpoint { .. })
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+
+/// Platform hosting a feature flag or experiment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagPlatform {
+    /// LaunchDarkly
+    LaunchDarkly,
+    /// Unleash
+    Unleash,
+}
+
+impl fmt::Display for FlagPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagPlatform::LaunchDarkly => write!(f, "LaunchDarkly"),
+            FlagPlatform::Unleash => write!(f, "Unleash"),
+        }
+    }
+}
+
+impl FromStr for FlagPlatform {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "launchdarkly" => Ok(FlagPlatform::LaunchDarkly),
+            "unleash" => Ok(FlagPlatform::Unleash),
+            other => Err(AureaCoreError::Config(format!(
+                "Unknown flag platform '{}', expected launchdarkly or unleash",
+                other
+            ))),
+        }
+    }
+}
+
+/// A feature flag or experiment gating behavior of a service
+///
+/// Flags are soft dependencies: the service keeps working whatever their value, so a
+/// missing flag is reported as a warning.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureFlag {
+    /// Platform hosting the flag
+    pub provider: FlagPlatform,
+    /// Key of the flag on the platform
+    #[schemars(regex(pattern = r"^[A-Za-z0-9][A-Za-z0-9._-]*$"))]
+    pub key: String,
+    /// Project the flag belongs to, `default` if unset
+    pub project: Option<String>,
+    /// What the flag gates
+    pub description: Option<String>,
+}
+
+impl FeatureFlag {
+    /// Gets the project the flag belongs to
+    pub fn project(&self) -> &str {
+        self.project.as_deref().unwrap_or("default")
+    }
+}
@@ -0,0 +1,247 @@
+//! Lint rules for service definitions and the fixes that resolve them
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AureaCoreError, Result};
+
+/// Service types as written in definitions
+const SERVICE_TYPES: &[&str] = &["rest", "grpc", "graphql", "eventdriven", "other"];
+
+/// A lint warning about a service definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    /// Warning describing the problem
+    pub message: String,
+    /// Change resolving the problem, if one can be generated
+    pub fix: Option<LintFix>,
+}
+
+/// Machine-applicable fix for a lint warning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintFix {
+    /// Warning the fix resolves
+    pub warning: String,
+    /// What the fix changes
+    pub description: String,
+    /// Whether the fix only changes spelling, never meaning
+    ///
+    /// Unsafe fixes fill in placeholder values a maintainer should review.
+    pub safe: bool,
+    /// Changes to the definition as a JSON Patch (RFC 6902)
+    pub patch: Vec<PatchOperation>,
+}
+
+impl LintFix {
+    /// Creates a fix that only corrects spelling
+    fn safe(warning: &str, description: String, operation: PatchOperation) -> Self {
+        Self { warning: warning.to_string(), description, safe: true, patch: vec![operation] }
+    }
+
+    /// Creates a fix filling in a value a maintainer should review
+    fn placeholder(warning: &str, description: String, operation: PatchOperation) -> Self {
+        Self { warning: warning.to_string(), description, safe: false, patch: vec![operation] }
+    }
+}
+
+/// Operation of a JSON Patch (RFC 6902)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Adds a member to an object or inserts an element into an array
+    Add { path: String, value: Value },
+    /// Replaces an existing value
+    Replace { path: String, value: Value },
+}
+
+impl PatchOperation {
+    /// Gets the JSON Pointer the operation targets
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. } | PatchOperation::Replace { path, .. } => path,
+        }
+    }
+}
+
+/// Lints a raw service definition
+pub fn lint(service_name: &str, definition: &Value) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let service_type =
+        definition.get("service_type").and_then(|st| st.get("type")).and_then(|t| t.as_str());
+
+    if let Some(service_type) = service_type {
+        let lowercase = service_type.to_ascii_lowercase();
+        if lowercase != service_type && SERVICE_TYPES.contains(&lowercase.as_str()) {
+            let message = format!(
+                "Service '{}' spells its service type '{}' instead of '{}'",
+                service_name, service_type, lowercase
+            );
+            let fix = LintFix::safe(
+                &message,
+                format!("Lowercase service type to '{}'", lowercase),
+                PatchOperation::Replace {
+                    path: "/service_type/type".to_string(),
+                    value: lowercase.into(),
+                },
+            );
+            findings.push(LintFinding { message, fix: Some(fix) });
+        }
+    }
+
+    let endpoints = definition.get("endpoints").and_then(|e| e.as_array());
+    for (i, endpoint) in endpoints.into_iter().flatten().enumerate() {
+        let path = format!("/endpoints/{}/method", i);
+        match endpoint.get("method").and_then(|m| m.as_str()) {
+            None if service_type == Some("rest") && endpoint.get("method").is_none() => {
+                let message = format!(
+                    "Service '{}' is a REST service but endpoint #{} doesn't specify an HTTP method",
+                    service_name,
+                    i + 1
+                );
+                let fix = LintFix::placeholder(
+                    &message,
+                    format!("Set the method of endpoint #{} to GET", i + 1),
+                    PatchOperation::Add { path, value: "GET".into() },
+                );
+                findings.push(LintFinding { message, fix: Some(fix) });
+            }
+            Some(method) if method != method.to_ascii_uppercase() => {
+                let uppercase = method.to_ascii_uppercase();
+                let message = format!(
+                    "Service '{}' spells the method of endpoint #{} '{}' instead of '{}'",
+                    service_name,
+                    i + 1,
+                    method,
+                    uppercase
+                );
+                let fix = LintFix::safe(
+                    &message,
+                    format!("Uppercase the method of endpoint #{} to {}", i + 1, uppercase),
+                    PatchOperation::Replace { path, value: uppercase.into() },
+                );
+                findings.push(LintFinding { message, fix: Some(fix) });
+            }
+            _ => {}
+        }
+    }
+
+    let is_custom = service_type.is_some_and(|t| {
+        !matches!(t.to_ascii_lowercase().as_str(), "rest" | "grpc" | "graphql" | "eventdriven")
+    });
+    if is_custom && definition.get("description").is_none() {
+        let service_type = service_type.unwrap_or_default();
+        let message = format!(
+            "Service '{}' uses a custom service type '{}' but doesn't provide a description",
+            service_name, service_type
+        );
+        let fix = LintFix::placeholder(
+            &message,
+            "Add a placeholder description".to_string(),
+            PatchOperation::Add {
+                path: "/description".to_string(),
+                value: format!("TODO: describe the {} service", service_name).into(),
+            },
+        );
+        findings.push(LintFinding { message, fix: Some(fix) });
+    }
+
+    findings
+}
+
+/// Applies a JSON Patch to a YAML (or JSON) document, keeping the order of its keys
+pub fn apply_patch(document: &mut serde_yaml::Value, patch: &[PatchOperation]) -> Result<()> {
+    for operation in patch {
+        let invalid =
+            || AureaCoreError::Config(format!("Cannot apply patch at '{}'", operation.path()));
+        let (parent, key) = operation.path().rsplit_once('/').ok_or_else(invalid)?;
+        let mut target = &mut *document;
+        for token in parent.split('/').skip(1) {
+            target = match target {
+                serde_yaml::Value::Mapping(mapping) => mapping.get_mut(unescape(token).as_str()),
+                serde_yaml::Value::Sequence(items) => {
+                    token.parse::<usize>().ok().and_then(|i| items.get_mut(i))
+                }
+                _ => None,
+            }
+            .ok_or_else(invalid)?;
+        }
+
+        let (PatchOperation::Add { value, .. } | PatchOperation::Replace { value, .. }) = operation;
+        let value = serde_yaml::to_value(value).map_err(|_| invalid())?;
+        let key = unescape(key);
+        match (target, operation) {
+            (serde_yaml::Value::Mapping(mapping), PatchOperation::Add { .. }) => {
+                mapping.insert(key.into(), value);
+            }
+            (serde_yaml::Value::Mapping(mapping), PatchOperation::Replace { .. }) => {
+                *mapping.get_mut(key.as_str()).ok_or_else(invalid)? = value;
+            }
+            (serde_yaml::Value::Sequence(items), PatchOperation::Replace { .. }) => {
+                let index = key.parse::<usize>().map_err(|_| invalid())?;
+                *items.get_mut(index).ok_or_else(invalid)? = value;
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a JSON Pointer reference token
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_lint_fixes() {
+        let definition = json!({
+            "name": "billing",
+            "service_type": {"type": "REST"},
+            "endpoints": [{"name": "api", "path": "/api"}, {"name": "ui", "path": "/", "method": "get"}]
+        });
+        let findings = lint("billing", &definition);
+        assert_eq!(findings.len(), 2);
+        assert!(findings[0].message.contains("'REST' instead of 'rest'"));
+        assert!(findings[1].message.contains("'get' instead of 'GET'"));
+        assert!(findings.iter().all(|f| f.fix.as_ref().unwrap().safe));
+
+        let mut document: serde_yaml::Value =
+            serde_yaml::from_str(&serde_json::to_string(&definition).unwrap()).unwrap();
+        for finding in &findings {
+            apply_patch(&mut document, &finding.fix.as_ref().unwrap().patch).unwrap();
+        }
+        let fixed = serde_json::to_value(&document).unwrap();
+        assert_eq!(fixed["service_type"]["type"], "rest");
+        assert_eq!(fixed["endpoints"][1]["method"], "GET");
+
+        // Once the type is fixed, the endpoint without a method needs a placeholder
+        let findings = lint("billing", &fixed);
+        assert_eq!(findings.len(), 1);
+        let fix = findings[0].fix.as_ref().unwrap();
+        assert!(!fix.safe);
+        assert_eq!(
+            fix.patch,
+            vec![PatchOperation::Add {
+                path: "/endpoints/0/method".to_string(),
+                value: json!("GET")
+            }]
+        );
+        assert_eq!(
+            serde_json::to_value(&fix.patch).unwrap(),
+            json!([{"op": "add", "path": "/endpoints/0/method", "value": "GET"}])
+        );
+
+        let custom = json!({"name": "relay", "service_type": {"type": "other"}, "endpoints": []});
+        assert!(lint("relay", &custom)[0].message.contains("doesn't provide a description"));
+        assert!(apply_patch(
+            &mut document,
+            &[PatchOperation::Replace { path: "/missing/field".to_string(), value: json!(1) }]
+        )
+        .is_err());
+    }
+}
@@ -1,7 +1,40 @@
+pub mod capacity;
+pub mod compatibility;
+pub mod contract;
+pub mod flags;
+pub mod lint;
+pub mod oncall;
+pub mod position;
+pub mod remote;
+pub mod resource;
 pub mod root;
+pub mod runtime;
 pub mod service;
+pub mod suppress;
 pub mod validation;
 
-pub use root::{GlobalConfig, RootConfig, ServiceRef};
-pub use service::{Dependency, Endpoint, ServiceSchema, ServiceType};
-pub use validation::{CompiledSchema, SchemaType, ValidationService, VersionCompatibility};
+pub use capacity::{check_capacity, check_quota, Capacity, LoadViolation, Quota};
+pub use compatibility::{
+    CalVer, CompatibilityPolicy, CompatibilityStrategy, ExactMatch, SemverLoose, SemverStrict,
+};
+pub use contract::{
+    check_consumable, check_consumption, check_dependency_offered, Consumable, ConsumedApi,
+    ContractTest, ContractViolation,
+};
+pub use flags::{FeatureFlag, FlagPlatform};
+pub use lint::{LintFinding, LintFix, PatchOperation};
+pub use oncall::{Oncall, OncallPlatform};
+pub use position::{Diagnostic, Position, PositionIndex};
+pub use remote::{ConfluentSchemaRegistry, HttpSchemaRegistry, SchemaSource};
+pub use resource::{ResourceKind, ResourceSchema};
+pub use root::{AccessGrant, Environment, GlobalConfig, Layout, RootConfig, ServiceRef};
+pub use runtime::Runtime;
+pub use service::{
+    redact_metadata, Dependency, Endpoint, Exposure, ServiceSchema, ServiceType, StartupPolicy,
+};
+pub use suppress::{
+    apply_suppressions, parse_annotations, Suppression, SuppressionOutcome, WarningCode,
+};
+pub use validation::{
+    CompiledSchema, SchemaType, SchemaViolation, ValidationService, VersionCompatibility,
+};
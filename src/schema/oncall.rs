@@ -0,0 +1,90 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// On-call platform hosting a service's schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OncallPlatform {
+    /// PagerDuty
+    PagerDuty,
+    /// Atlassian Opsgenie
+    Opsgenie,
+}
+
+impl fmt::Display for OncallPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OncallPlatform::PagerDuty => write!(f, "PagerDuty"),
+            OncallPlatform::Opsgenie => write!(f, "Opsgenie"),
+        }
+    }
+}
+
+/// On-call routing for a service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Oncall {
+    /// Platform hosting the schedule
+    pub platform: OncallPlatform,
+    /// Identifier of the on-call schedule on the platform
+    pub schedule_id: String,
+    /// Identifier of the escalation policy on the platform
+    pub escalation_policy: Option<String>,
+}
+
+impl Oncall {
+    /// Checks that the schedule ID has the format used by the platform
+    ///
+    /// PagerDuty IDs are seven uppercase alphanumerics starting with `P`, Opsgenie
+    /// IDs are UUIDs.
+    pub fn check_schedule_id(&self) -> std::result::Result<(), String> {
+        let id = self.schedule_id.as_str();
+        let valid = match self.platform {
+            OncallPlatform::PagerDuty => {
+                id.len() == 7
+                    && id.starts_with('P')
+                    && id.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            }
+            OncallPlatform::Opsgenie => is_uuid(id),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("'{}' is not a valid {} schedule ID", id, self.platform))
+        }
+    }
+}
+
+/// Checks whether a string is a hyphenated UUID
+fn is_uuid(id: &str) -> bool {
+    let groups: Vec<&str> = id.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oncall(platform: OncallPlatform, schedule_id: &str) -> Oncall {
+        Oncall { platform, schedule_id: schedule_id.to_string(), escalation_policy: None }
+    }
+
+    #[test]
+    fn test_check_schedule_id() {
+        assert!(oncall(OncallPlatform::PagerDuty, "PX1ABC2").check_schedule_id().is_ok());
+        assert!(oncall(OncallPlatform::PagerDuty, "px1abc2").check_schedule_id().is_err());
+        assert!(oncall(OncallPlatform::PagerDuty, "QX1ABC2").check_schedule_id().is_err());
+        assert!(oncall(OncallPlatform::Opsgenie, "d875e654-9b4e-4219-a803-0c9a8f9b1f7e")
+            .check_schedule_id()
+            .is_ok());
+
+        let err = oncall(OncallPlatform::Opsgenie, "PX1ABC2").check_schedule_id().unwrap_err();
+        assert_eq!(err, "'PX1ABC2' is not a valid Opsgenie schedule ID");
+    }
+}
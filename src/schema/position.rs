@@ -0,0 +1,558 @@
+//! Line and column of the fields of service definitions
+//!
+//! Definitions are parsed into plain values, losing where their fields were written.
+//! A [`PositionIndex`] is built from the text of a definition alongside, so problems
+//! with a field can be reported at its line, e.g. inline in code-review UIs.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Where a field starts in a file, both 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Start of the file
+    pub const START: Position = Position { line: 1, column: 1 };
+}
+
+/// Fields problems name, and the definition field they concern
+const KEYWORDS: &[(&str, &str)] = &[
+    ("graphql_schema", "/service_type"),
+    ("proto_files", "/service_type"),
+    ("topics", "/service_type"),
+    ("schema version", "/schema_version"),
+    ("runtime", "/runtime"),
+    ("end of life", "/runtime"),
+    ("capacity", "/capacity"),
+];
+
+/// Problem with a field of a definition file, e.g. a schema violation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Definition file, as referenced by the service config
+    pub file: String,
+    /// JSON pointer of the offending field
+    pub pointer: String,
+    /// Where the field, or its closest ancestor in the file, starts
+    pub position: Position,
+    /// What is wrong with the field
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}: {}", self.file, self.position.line, self.position.column, self.message)
+    }
+}
+
+/// A field found in a definition
+#[derive(Debug, Clone)]
+struct Entry {
+    pointer: String,
+    position: Position,
+    /// Text of the field, if it is a scalar
+    value: Option<String>,
+}
+
+/// Positions of the fields of a YAML or JSON document, by JSON pointer
+///
+/// Mapping entries are located at their key, sequence items at their first character.
+/// Parsing never fails: the index holds the fields found up to the first construct it
+/// doesn't understand, such as multi-line flow collections in YAML.
+#[derive(Debug, Clone, Default)]
+pub struct PositionIndex {
+    entries: Vec<Entry>,
+    by_pointer: HashMap<String, usize>,
+}
+
+impl PositionIndex {
+    /// Indexes a YAML or JSON document
+    pub fn parse(content: &str) -> Self {
+        let mut index = Self::default();
+        let trimmed = content.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            JsonScanner::new(content, &mut index).scan();
+        } else {
+            scan_yaml(content, &mut index);
+        }
+        index
+    }
+
+    /// Gets the position of the field at a JSON pointer such as `/dependencies/0/service`
+    pub fn locate(&self, pointer: &str) -> Option<Position> {
+        self.by_pointer.get(pointer).map(|i| self.entries[*i].position)
+    }
+
+    /// Gets the position of the field at a pointer, or of its closest indexed ancestor
+    ///
+    /// Fields missing from the document, e.g. ones a fix would add, are reported where
+    /// they would be added.
+    pub fn nearest(&self, pointer: &str) -> Position {
+        let mut pointer = pointer;
+        loop {
+            if let Some(position) = self.locate(pointer) {
+                return position;
+            }
+            match pointer.rfind('/') {
+                Some(end) => pointer = &pointer[..end],
+                None => return Position::START,
+            }
+        }
+    }
+
+    /// Gets the scalar text of the field at a pointer, without quotes
+    pub fn value(&self, pointer: &str) -> Option<&str> {
+        self.by_pointer.get(pointer).and_then(|i| self.entries[*i].value.as_deref())
+    }
+
+    /// Lists the pointers of scalar fields with the given text, in document order
+    pub fn find_value<'a>(&'a self, value: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.value.as_deref() == Some(value))
+            .map(|entry| entry.pointer.as_str())
+    }
+
+    /// Finds the field a validation message of a service concerns
+    ///
+    /// Fields are found by the endpoint number or the quoted names the message mentions,
+    /// or the keywords it contains, falling back to the name of the service.
+    pub fn locate_message(&self, service: &str, message: &str) -> Position {
+        if let Some(number) = message
+            .split("endpoint #")
+            .nth(1)
+            .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|number| number.parse::<usize>().ok())
+        {
+            return self.nearest(&format!("/endpoints/{}", number.saturating_sub(1)));
+        }
+        let quoted = message.split('\'').skip(1).step_by(2).filter(|name| *name != service);
+        for name in quoted {
+            if let Some(pointer) = self.find_value(name).find(|pointer| *pointer != "/name") {
+                return self.nearest(pointer);
+            }
+        }
+        let lowercase = message.to_ascii_lowercase();
+        let keyword = KEYWORDS.iter().find(|(keyword, _)| lowercase.contains(keyword));
+        self.nearest(keyword.map(|(_, pointer)| *pointer).unwrap_or("/name"))
+    }
+
+    /// Gets the pointer of the field written at a position
+    ///
+    /// That is the last field starting on its line at or before its column.
+    pub fn field_at(&self, position: Position) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| {
+                entry.position.line == position.line && entry.position.column <= position.column
+            })
+            .map(|entry| entry.pointer.as_str())
+    }
+
+    /// Gets the pointer of the collection a field written at a position belongs to
+    ///
+    /// Follows the indentation of block YAML: the parent is the last field on an earlier
+    /// line starting left of the position.
+    pub fn parent_at(&self, position: Position) -> String {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| {
+                entry.position.line < position.line && entry.position.column < position.column
+            })
+            .map(|entry| entry.pointer.clone())
+            .unwrap_or_default()
+    }
+
+    fn insert(&mut self, pointer: String, position: Position) {
+        self.by_pointer.insert(pointer.clone(), self.entries.len());
+        self.entries.push(Entry { pointer, position, value: None });
+    }
+
+    fn set_value(&mut self, pointer: &str, value: String) {
+        if let Some(i) = self.by_pointer.get(pointer) {
+            self.entries[*i].value = Some(value);
+        }
+    }
+}
+
+/// Appends a key or index to a JSON pointer
+pub fn child_pointer(pointer: &str, token: &str) -> String {
+    format!("{}/{}", pointer, token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Block collection the YAML scanner is in
+struct Frame {
+    indent: usize,
+    pointer: String,
+    next_item: usize,
+    /// Whether the frame is a sequence of `-` items
+    sequence: bool,
+}
+
+/// Indexes block-style YAML, the style definitions are written in
+fn scan_yaml(content: &str, index: &mut PositionIndex) {
+    let mut frames =
+        vec![Frame { indent: 0, pointer: String::new(), next_item: 0, sequence: false }];
+    // A key or item whose value starts on a later line, and its indentation
+    let mut pending: Option<(usize, String)> = None;
+    // Indentation of the key of a block scalar being skipped
+    let mut block_scalar: Option<usize> = None;
+
+    for (line_index, raw) in content.lines().enumerate() {
+        let line = line_index + 1;
+        let text = strip_comment(raw);
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let mut indent = text.len() - trimmed.len();
+        if let Some(key_indent) = block_scalar {
+            if indent > key_indent {
+                continue;
+            }
+            block_scalar = None;
+        }
+        if indent == 0 && (trimmed.starts_with("---") || trimmed.starts_with("...")) {
+            continue;
+        }
+        if trimmed.starts_with('%') {
+            continue;
+        }
+
+        if let Some((pending_indent, pointer)) = pending.take() {
+            // Sequences may be indented as deep as the key holding them
+            let sequence = trimmed.starts_with('-');
+            if indent > pending_indent || (indent == pending_indent && sequence) {
+                frames.push(Frame { indent, pointer, next_item: 0, sequence });
+            }
+        }
+        let is_item = trimmed.starts_with('-');
+        while frames.len() > 1
+            && frames.last().is_some_and(|frame| {
+                frame.indent > indent || (frame.indent == indent && frame.sequence && !is_item)
+            })
+        {
+            frames.pop();
+        }
+
+        let mut rest = trimmed;
+        while rest == "-" || rest.starts_with("- ") {
+            let Some(frame) = frames.last_mut() else { break };
+            let pointer = child_pointer(&frame.pointer, &frame.next_item.to_string());
+            frame.next_item += 1;
+            index.insert(pointer.clone(), Position { line, column: indent + 1 });
+            let after = rest[1..].trim_start();
+            if after.is_empty() {
+                pending = Some((indent, pointer));
+                rest = after;
+                break;
+            }
+            indent += rest.len() - after.len();
+            frames.push(Frame { indent, pointer, next_item: 0, sequence: false });
+            rest = after;
+        }
+        if rest.is_empty() {
+            continue;
+        }
+
+        let Some(frame) = frames.last() else { break };
+        match split_key(rest) {
+            Some((key, value)) => {
+                let pointer = child_pointer(&frame.pointer, &key);
+                index.insert(pointer.clone(), Position { line, column: indent + 1 });
+                if value.is_empty() {
+                    pending = Some((indent, pointer));
+                } else if value.starts_with('|') || value.starts_with('>') {
+                    block_scalar = Some(indent);
+                } else {
+                    index_flow(index, &pointer, value, line, raw);
+                }
+            }
+            None => {
+                let pointer = frame.pointer.clone();
+                index_flow(index, &pointer, rest, line, raw);
+            }
+        }
+    }
+}
+
+/// Records an inline value, indexing the scalars of a single-line flow sequence
+fn index_flow(index: &mut PositionIndex, pointer: &str, value: &str, line: usize, raw: &str) {
+    let inner = value.strip_prefix('[').and_then(|v| v.strip_suffix(']'));
+    let Some(inner) = inner else {
+        index.set_value(pointer, unquote(value));
+        return;
+    };
+    // The value is a slice of the line, so its offset is the distance between them
+    let mut column = inner.as_ptr() as usize - raw.as_ptr() as usize + 1;
+    for (i, item) in inner.split(',').enumerate() {
+        let trimmed = item.trim();
+        if !trimmed.is_empty() {
+            let item_pointer = child_pointer(pointer, &i.to_string());
+            let position = Position { line, column: column + item.len() - item.trim_start().len() };
+            index.insert(item_pointer.clone(), position);
+            index.set_value(&item_pointer, unquote(trimmed));
+        }
+        column += item.len() + 1;
+    }
+}
+
+/// Removes a trailing comment, leaving `#` inside quotes alone
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') if previous.is_whitespace() => return line[..i].trim_end(),
+            _ => {}
+        }
+        previous = c;
+    }
+    line.trim_end()
+}
+
+/// Splits `key: value` into the unquoted key and the value
+fn split_key(text: &str) -> Option<(String, &str)> {
+    if text.starts_with('[') || text.starts_with('{') {
+        return None;
+    }
+    let end = match text.chars().next() {
+        Some(q @ ('\'' | '"')) => text[1..].find(q).map(|i| i + 2)?,
+        _ => 0,
+    };
+    let colon = text[end..]
+        .char_indices()
+        .find(|(i, c)| {
+            *c == ':' && text[end + i + 1..].chars().next().is_none_or(char::is_whitespace)
+        })
+        .map(|(i, _)| end + i)?;
+    Some((unquote(text[..colon].trim()), text[colon + 1..].trim()))
+}
+
+fn unquote(text: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner.to_string();
+        }
+    }
+    text.to_string()
+}
+
+/// Indexes a JSON document
+struct JsonScanner<'a> {
+    chars: Vec<char>,
+    at: usize,
+    line: usize,
+    column: usize,
+    index: &'a mut PositionIndex,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn new(content: &str, index: &'a mut PositionIndex) -> Self {
+        Self { chars: content.chars().collect(), at: 0, line: 1, column: 1, index }
+    }
+
+    fn scan(&mut self) {
+        self.value("");
+    }
+
+    fn position(&self) -> Position {
+        Position { line: self.line, column: self.column }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.at += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Scans a value, returning `None` on malformed input
+    fn value(&mut self, pointer: &str) -> Option<()> {
+        self.skip_whitespace();
+        match self.peek()? {
+            '{' => {
+                self.bump();
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        '}' => {
+                            self.bump();
+                            return Some(());
+                        }
+                        ',' => {
+                            self.bump();
+                        }
+                        '"' => {
+                            let position = self.position();
+                            let key = self.string()?;
+                            let child = child_pointer(pointer, &key);
+                            self.index.insert(child.clone(), position);
+                            self.skip_whitespace();
+                            (self.bump()? == ':').then_some(())?;
+                            self.value(&child)?;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            '[' => {
+                self.bump();
+                let mut item = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        ']' => {
+                            self.bump();
+                            return Some(());
+                        }
+                        ',' => {
+                            self.bump();
+                        }
+                        _ => {
+                            let child = child_pointer(pointer, &item.to_string());
+                            self.index.insert(child.clone(), self.position());
+                            item += 1;
+                            self.value(&child)?;
+                        }
+                    }
+                }
+            }
+            '"' => {
+                let value = self.string()?;
+                self.index.set_value(pointer, value);
+                Some(())
+            }
+            _ => {
+                let mut literal = String::new();
+                while let Some(c) = self.peek().filter(|c| !matches!(c, ',' | '}' | ']')) {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    literal.push(c);
+                    self.bump();
+                }
+                (!literal.is_empty()).then_some(())?;
+                self.index.set_value(pointer, literal);
+                Some(())
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.bump();
+        let mut text = String::new();
+        loop {
+            match self.bump()? {
+                '"' => return Some(text),
+                '\\' => match self.bump()? {
+                    'n' => text.push('\n'),
+                    't' => text.push('\t'),
+                    'r' => text.push('\r'),
+                    'b' => text.push('\u{8}'),
+                    'f' => text.push('\u{c}'),
+                    'u' => {
+                        let hex: String = (0..4).filter_map(|_| self.bump()).collect();
+                        let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                        text.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    other => text.push(other),
+                },
+                c => text.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_yaml() {
+        let yaml = "\
+# Orders
+name: orders
+version: \"1.0.0\"
+service_type:
+  type: rest
+description: |
+  Takes orders.
+  key: not a field
+dependencies:
+- service: billing
+  required: true
+-   service: 'stock' # inventory
+    version_constraint: 2.0.0
+tags: [shop, 'core']
+endpoints:
+  - name: list
+    path: /orders
+";
+        let index = PositionIndex::parse(yaml);
+        let at = |pointer| index.locate(pointer).map(|p| (p.line, p.column));
+        assert_eq!(at("/name"), Some((2, 1)));
+        assert_eq!(index.value("/version"), Some("1.0.0"));
+        assert_eq!(at("/service_type/type"), Some((5, 3)));
+        assert_eq!(at("/description/key"), None);
+        assert_eq!(at("/dependencies/0"), Some((10, 1)));
+        assert_eq!(at("/dependencies/0/required"), Some((11, 3)));
+        assert_eq!(at("/dependencies/1/service"), Some((12, 5)));
+        assert_eq!(index.value("/dependencies/1/service"), Some("stock"));
+        assert_eq!(at("/dependencies/1/version_constraint"), Some((13, 5)));
+        assert_eq!(at("/tags/1"), Some((14, 14)));
+        assert_eq!(index.value("/tags/1"), Some("core"));
+        assert_eq!(at("/endpoints/0/path"), Some((17, 5)));
+        assert_eq!(index.find_value("stock").collect::<Vec<_>>(), vec!["/dependencies/1/service"]);
+        assert_eq!(index.nearest("/endpoints/0/method"), Position { line: 16, column: 3 });
+        assert_eq!(index.nearest("/owner"), Position::START);
+        assert_eq!(
+            index.field_at(Position { line: 12, column: 9 }),
+            Some("/dependencies/1/service")
+        );
+        assert_eq!(index.parent_at(Position { line: 18, column: 5 }), "/endpoints/0");
+        assert_eq!(
+            index.locate_message("orders", "Optional dependency 'stock' not found").line,
+            12
+        );
+    }
+
+    #[test]
+    fn test_index_json() {
+        let json = "{\n  \"name\": \"orders\",\n  \"dependencies\": [\n    {\"service\": \"a/b\", \"required\": false}\n  ]\n}\n";
+        let index = PositionIndex::parse(json);
+        assert_eq!(index.locate("/name"), Some(Position { line: 2, column: 3 }));
+        assert_eq!(index.locate("/dependencies/0"), Some(Position { line: 4, column: 5 }));
+        assert_eq!(
+            index.locate("/dependencies/0/required"),
+            Some(Position { line: 4, column: 24 })
+        );
+        assert_eq!(index.value("/dependencies/0/service"), Some("a/b"));
+        assert_eq!(index.value("/dependencies/0/required"), Some("false"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Schemas fetched from a central schema registry, so organizations can manage the
+//! service schema independently of the aureacore version they run
+//!
+//! Schemas are keyed by schema type and generation, the major and minor version of the
+//! schema, e.g. `service` and `1.2`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::validation::SchemaType;
+
+/// Subject prefix used with Confluent-style registries unless configured otherwise
+pub const DEFAULT_SUBJECT_PREFIX: &str = "aureacore";
+
+/// Looks up JSON Schemas in a schema registry
+pub trait SchemaSource: Send + Sync {
+    /// Fetches the schema of a type for a generation such as `1.2`
+    ///
+    /// Returns `None` if the registry doesn't hold one, so the built-in schema is used.
+    fn fetch(&self, schema_type: &SchemaType, generation: &str) -> Result<Option<Value>>;
+}
+
+/// Registry serving schemas over plain HTTP at `<base URL>/<type>/<generation>`
+///
+/// Responses are cached with their `ETag` and revalidated on every fetch, so unchanged
+/// schemas aren't downloaded again. The cached schema is used while the registry is
+/// unreachable.
+pub struct HttpSchemaRegistry {
+    base_url: String,
+    agent: ureq::Agent,
+    authorization: Option<String>,
+    /// Last schema fetched per URL, with its ETag
+    cache: Mutex<HashMap<String, (String, Value)>>,
+}
+
+impl HttpSchemaRegistry {
+    /// Creates a registry client for `base_url`, waiting at most `timeout` per request
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+            authorization: None,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sends an `Authorization` header, e.g. `Bearer <token>`, with every request
+    pub fn with_authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = Some(authorization.into());
+        self
+    }
+}
+
+impl SchemaSource for HttpSchemaRegistry {
+    fn fetch(&self, schema_type: &SchemaType, generation: &str) -> Result<Option<Value>> {
+        let url = format!("{}/{}/{}", self.base_url, schema_type.name(), generation);
+        let cached = self.cache.lock().unwrap_or_else(|e| e.into_inner()).get(&url).cloned();
+
+        let mut request = self.agent.get(&url).set("Accept", "application/schema+json");
+        if let Some(authorization) = &self.authorization {
+            request = request.set("Authorization", authorization);
+        }
+        if let Some((etag, _)) = &cached {
+            request = request.set("If-None-Match", etag);
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(ureq::Error::Transport(err)) if cached.is_some() => {
+                tracing::warn!("Schema registry unreachable, using cached {}: {}", url, err);
+                return Ok(cached.map(|(_, schema)| schema));
+            }
+            Err(err) => {
+                return Err(AureaCoreError::SchemaCompilationError(format!(
+                    "Failed to fetch schema {}: {}",
+                    url, err
+                )))
+            }
+        };
+        if response.status() == 304 {
+            if let Some((_, schema)) = cached {
+                return Ok(Some(schema));
+            }
+        }
+
+        let etag = response.header("ETag").map(str::to_string);
+        let schema: Value = response.into_json().map_err(|e| {
+            AureaCoreError::SchemaCompilationError(format!("Invalid schema {}: {}", url, e))
+        })?;
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        match etag {
+            Some(etag) => cache.insert(url, (etag, schema.clone())),
+            None => cache.remove(&url),
+        };
+        Ok(Some(schema))
+    }
+}
+
+/// Latest version of a subject, as answered by a Confluent-style registry
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubjectVersion {
+    schema: String,
+    /// Absent for Avro schemas, the registry's default
+    schema_type: Option<String>,
+}
+
+/// Registry speaking the Confluent Schema Registry API
+///
+/// Schemas are read from the latest version of the subject
+/// `<prefix>-<type>-<generation>`, e.g. `aureacore-service-1.2`, and must be of type
+/// `JSON`.
+pub struct ConfluentSchemaRegistry {
+    base_url: String,
+    agent: ureq::Agent,
+    authorization: Option<String>,
+    subject_prefix: String,
+}
+
+impl ConfluentSchemaRegistry {
+    /// Creates a registry client for `base_url`, waiting at most `timeout` per request
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+            authorization: None,
+            subject_prefix: DEFAULT_SUBJECT_PREFIX.to_string(),
+        }
+    }
+
+    /// Sends an `Authorization` header, e.g. `Basic <credentials>`, with every request
+    pub fn with_authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = Some(authorization.into());
+        self
+    }
+
+    /// Reads subjects starting with `prefix` instead of `aureacore`
+    pub fn with_subject_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.subject_prefix = prefix.into();
+        self
+    }
+
+    /// Gets the subject holding the schema of a type for a generation
+    pub fn subject(&self, schema_type: &SchemaType, generation: &str) -> String {
+        format!("{}-{}-{}", self.subject_prefix, schema_type.name(), generation)
+    }
+}
+
+impl SchemaSource for ConfluentSchemaRegistry {
+    fn fetch(&self, schema_type: &SchemaType, generation: &str) -> Result<Option<Value>> {
+        let subject = self.subject(schema_type, generation);
+        let url = format!("{}/subjects/{}/versions/latest", self.base_url, subject);
+        let mut request =
+            self.agent.get(&url).set("Accept", "application/vnd.schemaregistry.v1+json");
+        if let Some(authorization) = &self.authorization {
+            request = request.set("Authorization", authorization);
+        }
+        let version: SubjectVersion = match request.call() {
+            Ok(response) => response.into_json().map_err(|e| {
+                AureaCoreError::SchemaCompilationError(format!(
+                    "Invalid answer for subject {}: {}",
+                    subject, e
+                ))
+            })?,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(err) => {
+                return Err(AureaCoreError::SchemaCompilationError(format!(
+                    "Failed to fetch subject {}: {}",
+                    subject, err
+                )))
+            }
+        };
+
+        if version.schema_type.as_deref() != Some("JSON") {
+            return Err(AureaCoreError::SchemaCompilationError(format!(
+                "Subject {} holds a schema of type {}, not JSON",
+                subject,
+                version.schema_type.as_deref().unwrap_or("AVRO")
+            )));
+        }
+        serde_json::from_str(&version.schema).map(Some).map_err(|e| {
+            AureaCoreError::SchemaCompilationError(format!(
+                "Invalid schema in subject {}: {}",
+                subject, e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use axum::extract::Path;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fetch_schemas() {
+        let downloads = Arc::new(AtomicUsize::new(0));
+        let schema = json!({ "type": "object", "required": ["name", "team"] });
+        let router = Router::new()
+            .route(
+                "/schemas/{kind}/{generation}",
+                get({
+                    let downloads = downloads.clone();
+                    let schema = schema.clone();
+                    move |Path((kind, generation)): Path<(String, String)>, headers: HeaderMap| {
+                        let downloads = downloads.clone();
+                        let schema = schema.clone();
+                        async move {
+                            if (kind.as_str(), generation.as_str()) != ("service", "1.0") {
+                                return StatusCode::NOT_FOUND.into_response();
+                            }
+                            if headers.get("if-none-match").is_some_and(|tag| tag == "\"v1\"") {
+                                return StatusCode::NOT_MODIFIED.into_response();
+                            }
+                            downloads.fetch_add(1, Ordering::SeqCst);
+                            ([("etag", "\"v1\"")], Json(schema)).into_response()
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/subjects/{subject}/versions/latest",
+                get(move |Path(subject): Path<String>| async move {
+                    let schema = json!({ "type": "object" }).to_string();
+                    match subject.as_str() {
+                        "acme-service-1.0" => {
+                            Json(json!({ "schema": schema, "schemaType": "JSON" })).into_response()
+                        }
+                        "acme-resource-1.0" => Json(json!({ "schema": "{}" })).into_response(),
+                        _ => StatusCode::NOT_FOUND.into_response(),
+                    }
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let downloads_seen = downloads.clone();
+        tokio::task::spawn_blocking(move || {
+            let http =
+                HttpSchemaRegistry::new(format!("{}/schemas/", base), Duration::from_secs(5));
+            assert_eq!(http.fetch(&SchemaType::Service, "1.0").unwrap(), Some(schema.clone()));
+            // Revalidated with the ETag rather than downloaded again
+            assert_eq!(http.fetch(&SchemaType::Service, "1.0").unwrap(), Some(schema));
+            assert_eq!(downloads_seen.load(Ordering::SeqCst), 1);
+            assert_eq!(http.fetch(&SchemaType::Service, "2.0").unwrap(), None);
+
+            let confluent = ConfluentSchemaRegistry::new(&base, Duration::from_secs(5))
+                .with_subject_prefix("acme");
+            let fetched = confluent.fetch(&SchemaType::Service, "1.0").unwrap();
+            assert_eq!(fetched, Some(json!({ "type": "object" })));
+            assert_eq!(confluent.fetch(&SchemaType::Service, "1.1").unwrap(), None);
+            let err = confluent.fetch(&SchemaType::Resource, "1.0").unwrap_err();
+            assert!(err.to_string().contains("of type AVRO"));
+        })
+        .await
+        .unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+//! Definitions of infrastructure resources, such as databases and message queues, that
+//! services depend on
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+
+/// Kinds of resources
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceKind {
+    /// Relational or document database
+    Database,
+    /// Message queue or event stream
+    Queue,
+    /// Cache such as Redis or Memcached
+    Cache,
+    /// Object storage bucket
+    Bucket,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ResourceKind::Database => "database",
+            ResourceKind::Queue => "queue",
+            ResourceKind::Cache => "cache",
+            ResourceKind::Bucket => "bucket",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ResourceKind {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "database" => Ok(ResourceKind::Database),
+            "queue" => Ok(ResourceKind::Queue),
+            "cache" => Ok(ResourceKind::Cache),
+            "bucket" => Ok(ResourceKind::Bucket),
+            other => Err(AureaCoreError::Config(format!(
+                "Unknown resource kind '{}', expected database, queue, cache or bucket",
+                other
+            ))),
+        }
+    }
+}
+
+/// Schema for a resource definition
+///
+/// Services depend on a resource by naming it in a dependency, like a service. Resource
+/// names share one namespace with service names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ResourceSchema {
+    /// Name of the resource
+    pub name: String,
+    /// Kind of resource
+    pub kind: ResourceKind,
+    /// Description of the resource
+    pub description: Option<String>,
+    /// Owner of the resource
+    pub owner: Option<String>,
+    /// System the resource belongs to
+    pub system: Option<String>,
+    /// Business domain the resource's system belongs to
+    pub domain: Option<String>,
+    /// Technology backing the resource, e.g. `postgres 15` or `kafka`
+    pub engine: Option<String>,
+    /// Extensible metadata for additional attributes
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+}
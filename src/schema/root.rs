@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{AureaCoreError, Result};
+
 /// Root configuration schema for AureaCore
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RootConfig {
@@ -10,6 +15,153 @@ pub struct RootConfig {
     pub global: GlobalConfig,
     /// List of services managed by AureaCore
     pub services: Vec<ServiceRef>,
+    /// Environments services can be deployed to
+    #[serde(default)]
+    pub environments: Vec<Environment>,
+    /// Where the catalog keeps its files inside the work directory
+    #[serde(default)]
+    pub layout: Layout,
+    /// Who may modify which services; everyone may modify every service if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub access: Vec<AccessGrant>,
+    /// Former names of renamed or merged services, mapped to the services now providing them
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl RootConfig {
+    /// Loads a root configuration from a YAML or JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let config: Self = serde_yaml::from_str(&std::fs::read_to_string(path)?).map_err(|e| {
+            AureaCoreError::invalid_config(format!("Invalid root config {}", path.display()), e)
+        })?;
+        config.layout.validate()?;
+        Ok(config)
+    }
+}
+
+/// Directories of the catalog, relative to the work directory
+///
+/// Lets the registry adopt the structure of an existing repository instead of keeping
+/// everything at its root. Catalogs with different config directories can share one
+/// work directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Layout {
+    /// Service configs, one `<service>.json` each
+    pub configs_dir: String,
+    /// JSON Schemas of additional definition schema versions, one `<version>.json` each
+    pub schemas_dir: String,
+    /// Policy files such as `freeze.yaml` and `naming-rules.yaml`
+    pub policies_dir: String,
+    /// Configs of archived services
+    pub archived_dir: String,
+    /// Resource definitions such as databases and queues, one `<resource>.yaml` each
+    pub resources_dir: String,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            configs_dir: ".".to_string(),
+            schemas_dir: "schemas".to_string(),
+            policies_dir: ".".to_string(),
+            archived_dir: "archived".to_string(),
+            resources_dir: "resources".to_string(),
+        }
+    }
+}
+
+impl Layout {
+    /// Checks that every directory stays inside the work directory
+    pub fn validate(&self) -> Result<()> {
+        for (key, dir) in [
+            ("configs_dir", &self.configs_dir),
+            ("schemas_dir", &self.schemas_dir),
+            ("policies_dir", &self.policies_dir),
+            ("archived_dir", &self.archived_dir),
+            ("resources_dir", &self.resources_dir),
+        ] {
+            let outside = Path::new(dir)
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+            if outside {
+                return Err(AureaCoreError::Config(format!(
+                    "Layout {} '{}' must be relative to the work directory",
+                    key, dir
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the directory of service configs in `work_dir`
+    pub fn configs_path(&self, work_dir: &Path) -> PathBuf {
+        resolve(work_dir, &self.configs_dir)
+    }
+
+    /// Resolves the directory of definition schemas in `work_dir`
+    pub fn schemas_path(&self, work_dir: &Path) -> PathBuf {
+        resolve(work_dir, &self.schemas_dir)
+    }
+
+    /// Resolves the directory of policy files in `work_dir`
+    pub fn policies_path(&self, work_dir: &Path) -> PathBuf {
+        resolve(work_dir, &self.policies_dir)
+    }
+
+    /// Resolves the directory of archived service configs in `work_dir`
+    pub fn archived_path(&self, work_dir: &Path) -> PathBuf {
+        resolve(work_dir, &self.archived_dir)
+    }
+
+    /// Resolves the directory of resource definitions in `work_dir`
+    pub fn resources_path(&self, work_dir: &Path) -> PathBuf {
+        resolve(work_dir, &self.resources_dir)
+    }
+}
+
+/// Joins a layout directory to the work directory, keeping `.` out of the result
+fn resolve(work_dir: &Path, dir: &str) -> PathBuf {
+    Path::new(dir).components().fold(work_dir.to_path_buf(), |path, component| match component {
+        Component::Normal(name) => path.join(name),
+        _ => path,
+    })
+}
+
+/// A runtime environment such as dev, staging or prod
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Environment {
+    /// Name of the environment
+    pub name: String,
+    /// Description of the environment
+    pub description: Option<String>,
+    /// Whether the environment serves production traffic
+    #[serde(default)]
+    pub production: bool,
+    /// Hostname of services in the environment, with `{service}`, `{namespace}` and
+    /// `{environment}` placeholders, e.g. `{service}.{namespace}.prod.example.com`
+    pub hostname_pattern: Option<String>,
+    /// URL scheme of services in the environment, `https` if unset
+    pub scheme: Option<String>,
+    /// Port services listen on, omitted from URLs if unset
+    pub default_port: Option<u16>,
+    /// Ports of individual services, overriding the default port
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ports: BTreeMap<String, u16>,
+}
+
+/// Write permission on the services of some namespaces and teams
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AccessGrant {
+    /// Identities granted the permission, e.g. user emails or names of API tokens
+    pub principals: Vec<String>,
+    /// Namespaces whose services may be modified, `*` for every namespace
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Teams whose services may be modified, matched against the owners of services
+    #[serde(default)]
+    pub teams: Vec<String>,
 }
 
 /// Global configuration settings
@@ -30,6 +182,8 @@ pub struct ServiceRef {
     pub config_path: String,
     /// Optional namespace override for the service
     pub namespace: Option<String>,
+    /// Environments the service is deployed to, all environments if unset
+    pub environments: Option<Vec<String>>,
 }
 
 #[cfg(test)]
@@ -65,6 +219,26 @@ mod tests {
         assert!(result.is_ok(), "Validation failed: {:?}", result);
     }
 
+    #[test]
+    fn test_layout() {
+        let config: RootConfig = serde_yaml::from_str(
+            "version: 1.0.0\nglobal:\n  config_dir: .\n  default_namespace: default\n\
+             services: []\nlayout:\n  configs_dir: catalog/services\n",
+        )
+        .unwrap();
+        let work_dir = Path::new("/repo");
+        assert_eq!(config.layout.configs_path(work_dir), Path::new("/repo/catalog/services"));
+        assert_eq!(config.layout.policies_path(work_dir), work_dir);
+        assert_eq!(config.layout.archived_path(work_dir), Path::new("/repo/archived"));
+        assert_eq!(config.layout.resources_path(work_dir), Path::new("/repo/resources"));
+        assert!(config.layout.validate().is_ok());
+
+        let outside = Layout { archived_dir: "../archived".to_string(), ..Layout::default() };
+        assert!(outside.validate().unwrap_err().to_string().contains("archived_dir"));
+        let absolute = Layout { schemas_dir: "/etc/schemas".to_string(), ..Layout::default() };
+        assert!(absolute.validate().is_err());
+    }
+
     #[test]
     fn test_invalid_root_config_missing_required() {
         let schema =
@@ -0,0 +1,28 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Language runtime a service runs on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Runtime {
+    /// Programming language or runtime, e.g. `node`, `python` or `java`
+    pub language: String,
+    /// Application framework, e.g. `express` or `spring-boot`
+    pub framework: Option<String>,
+    /// Version of the language runtime, e.g. `18.19.0` or `3.11`
+    pub version: Option<String>,
+}
+
+impl fmt::Display for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language)?;
+        if let Some(version) = &self.version {
+            write!(f, " {}", version)?;
+        }
+        if let Some(framework) = &self.framework {
+            write!(f, " ({})", framework)?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
+use chrono::NaiveDate;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::schema::capacity::Capacity;
+use crate::schema::compatibility::CompatibilityStrategy;
+use crate::schema::contract::{Consumable, ConsumedApi, ContractTest};
+use crate::schema::flags::FeatureFlag;
+use crate::schema::oncall::Oncall;
+use crate::schema::runtime::Runtime;
+use crate::schema::suppress::Suppression;
+
 /// Schema for a service configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServiceSchema {
@@ -14,17 +23,55 @@ pub struct ServiceSchema {
     pub description: Option<String>,
     /// Owner of the service
     pub owner: Option<String>,
+    /// System (group of services delivering one capability) the service belongs to
+    pub system: Option<String>,
+    /// Business domain the service's system belongs to
+    pub domain: Option<String>,
+    /// On-call schedule responsible for the service
+    pub oncall: Option<Oncall>,
+    /// Cost center the service's spending is booked to, e.g. `CC-1042`
+    #[schemars(regex(pattern = r"^[A-Za-z0-9][A-Za-z0-9._-]{0,63}$"))]
+    pub cost_center: Option<String>,
+    /// Estimated monthly cost of running the service, in the organization's currency
+    #[schemars(range(min = 0))]
+    pub monthly_cost_estimate: Option<f64>,
     /// Documentation URL for the service
     pub documentation_url: Option<String>,
     /// Service type
     pub service_type: ServiceType,
+    /// Language runtime the service runs on
+    pub runtime: Option<Runtime>,
     /// Service endpoints
     pub endpoints: Vec<Endpoint>,
+    /// Network addresses the service claims
+    pub exposure: Option<Exposure>,
     /// Dependencies on other services
     pub dependencies: Option<Vec<Dependency>>,
+    /// API versions consumed from dependencies
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes: Vec<ConsumedApi>,
+    /// Endpoints published for other services to consume, all of them if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumables: Vec<Consumable>,
+    /// Requests per second the service can serve, in total and per consumer
+    pub capacity: Option<Capacity>,
+    /// Contract test suites verifying the service's APIs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contract_tests: Vec<ContractTest>,
+    /// Feature flags and experiments gating behavior of the service
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_flags: Vec<FeatureFlag>,
     /// Extensible metadata for additional attributes
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Metadata keys only internal consumers may read, e.g. internal URLs or cost data
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive_metadata: Vec<String>,
+    /// Name of the archetype (service template) the service follows
+    pub archetype: Option<String>,
+    /// Validation warnings suppressed for the service, each until it expires
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignores: Vec<Suppression>,
 }
 
 /// Types of services
@@ -56,10 +103,27 @@ pub struct Endpoint {
     pub method: Option<String>,
     /// Documentation about the endpoint
     pub description: Option<String>,
+    /// API versions offered by the endpoint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provides: Vec<String>,
+}
+
+/// Network addresses a service claims, which must be unique across the catalog
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Exposure {
+    /// Hostnames routed to the service
+    #[serde(default)]
+    pub hostnames: Vec<String>,
+    /// Ports the service listens on
+    #[serde(default)]
+    pub ports: Vec<u16>,
+    /// Public route prefixes routed to the service
+    #[serde(default)]
+    pub routes: Vec<String>,
 }
 
 /// Dependency on another service
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Dependency {
     /// Name of the service dependency
     pub service: String,
@@ -68,6 +132,102 @@ pub struct Dependency {
     /// Whether this dependency is required
     #[serde(default = "default_true")]
     pub required: bool,
+    /// How the service interacts with the dependency
+    #[serde(default)]
+    pub interaction: Interaction,
+    /// Policy checking the dependency's version, overriding the registry's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compatibility: Option<CompatibilityStrategy>,
+    /// How the service waits for the dependency when starting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup: Option<StartupPolicy>,
+    /// Date by which the team re-confirms the dependency is still needed, as `YYYY-MM-DD`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub review_by: Option<NaiveDate>,
+    /// Requests per second the service expects to send to the dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_requests_per_sec: Option<u64>,
+}
+
+/// Startup behavior of a dependency, for orchestrators starting services in order
+///
+/// Without a policy, services wait for their required dependencies only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StartupPolicy {
+    /// Whether the service waits for the dependency to be ready before starting
+    #[serde(default = "default_true")]
+    pub wait: bool,
+    /// Seconds to wait for the dependency before giving up, 60 if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Kind of interaction along a dependency, separating control flow from data flow
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Interaction {
+    /// Synchronous request/response call, on the caller's latency path
+    #[default]
+    SyncCall,
+    /// Asynchronous event or message
+    AsyncEvent,
+    /// Reads data owned by the dependency, e.g. from its database or cache
+    DataRead,
+    /// Writes data owned by the dependency
+    DataWrite,
+}
+
+impl Interaction {
+    /// All interaction kinds
+    pub const ALL: [Interaction; 4] = [
+        Interaction::SyncCall,
+        Interaction::AsyncEvent,
+        Interaction::DataRead,
+        Interaction::DataWrite,
+    ];
+
+    /// Gets the name of the interaction as written in definitions, e.g. `sync-call`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interaction::SyncCall => "sync-call",
+            Interaction::AsyncEvent => "async-event",
+            Interaction::DataRead => "data-read",
+            Interaction::DataWrite => "data-write",
+        }
+    }
+}
+
+impl std::fmt::Display for Interaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Interaction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Interaction::ALL.into_iter().find(|kind| kind.as_str() == s).ok_or_else(|| {
+            format!(
+                "Unknown interaction '{}', expected sync-call, async-event, data-read or data-write",
+                s
+            )
+        })
+    }
 }
 
 /// Default function to set dependency as required by default
@@ -75,6 +235,62 @@ fn default_true() -> bool {
     true
 }
 
+/// Extracts the dependencies declared in a raw service definition
+///
+/// Entries that do not parse as a [`Dependency`] are skipped.
+pub(crate) fn parse_dependencies(definition: &serde_json::Value) -> Vec<Dependency> {
+    definition
+        .get("dependencies")
+        .and_then(|d| d.as_array())
+        .map(|deps| deps.iter().filter_map(|d| serde_json::from_value(d.clone()).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Removes sensitive metadata from a raw service definition, returning the removed keys
+///
+/// Keys the definition lists in `sensitive_metadata` are removed along with the
+/// catalog-wide `sensitive` keys.
+pub fn redact_metadata(
+    definition: &mut serde_json::Value,
+    sensitive: &BTreeSet<String>,
+) -> Vec<String> {
+    let own: Vec<String> = definition
+        .get("sensitive_metadata")
+        .and_then(|keys| keys.as_array())
+        .map(|keys| keys.iter().filter_map(|key| key.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    let Some(metadata) = definition.get_mut("metadata").and_then(|m| m.as_object_mut()) else {
+        return Vec::new();
+    };
+    let mut removed: Vec<String> = own
+        .into_iter()
+        .chain(sensitive.iter().cloned())
+        .filter(|key| metadata.remove(key).is_some())
+        .collect();
+    removed.sort();
+    removed
+}
+
+/// Injects the documented defaults of optional fields into a raw service definition
+///
+/// Dependencies become required `sync-call` edges unless stated otherwise and a missing
+/// `metadata` becomes an empty map, so definitions spelling out defaults compare, hash
+/// and diff equal to those omitting them.
+pub(crate) fn normalize_definition(definition: &mut serde_json::Value) {
+    let Some(object) = definition.as_object_mut() else {
+        return;
+    };
+    let metadata = object.entry("metadata").or_insert(serde_json::Value::Null);
+    if metadata.is_null() {
+        *metadata = serde_json::Value::Object(Default::default());
+    }
+    let dependencies = object.get_mut("dependencies").and_then(|deps| deps.as_array_mut());
+    for dependency in dependencies.into_iter().flatten().filter_map(|dep| dep.as_object_mut()) {
+        dependency.entry("required").or_insert(serde_json::Value::Bool(true));
+        dependency.entry("interaction").or_insert_with(|| Interaction::default().as_str().into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use jsonschema::validator_for;
@@ -138,6 +354,29 @@ mod tests {
         assert!(validation.is_err(), "Expected validation to fail");
     }
 
+    #[test]
+    fn test_cost_attribution_formats() {
+        let schema = serde_json::to_value(schema_for!(ServiceSchema)).unwrap();
+        let validator = validator_for(&schema).unwrap();
+        let config = |cost_center: serde_json::Value, estimate: serde_json::Value| {
+            json!({
+                "name": "billing",
+                "version": "1.0.0",
+                "service_type": {"type": "rest"},
+                "endpoints": [],
+                "cost_center": cost_center,
+                "monthly_cost_estimate": estimate
+            })
+        };
+
+        assert!(validator.validate(&config(json!("CC-1042"), json!(1200.5))).is_ok());
+        assert!(validator.validate(&config(json!(null), json!(null))).is_ok());
+        assert!(validator.validate(&config(json!("cost center 7"), json!(10))).is_err());
+        assert!(validator.validate(&config(json!(""), json!(10))).is_err());
+        assert!(validator.validate(&config(json!("CC-1042"), json!(-5))).is_err());
+        assert!(validator.validate(&config(json!("CC-1042"), json!("1200"))).is_err());
+    }
+
     #[test]
     fn test_service_schema_with_custom_service_type() {
         let schema = serde_json::to_value(schema_for!(ServiceSchema)).unwrap();
@@ -188,4 +427,43 @@ mod tests {
         let validation = validator.validate(&config);
         assert!(validation.is_ok(), "Validation failed");
     }
+
+    #[test]
+    fn test_redact_metadata() {
+        let mut definition = json!({
+            "name": "billing",
+            "metadata": {"tier": 1, "cost": 1200, "admin_url": "http://billing.internal"},
+            "sensitive_metadata": ["admin_url", "missing"]
+        });
+        let sensitive = BTreeSet::from(["cost".to_string()]);
+        assert_eq!(redact_metadata(&mut definition, &sensitive), vec!["admin_url", "cost"]);
+        assert_eq!(definition["metadata"], json!({"tier": 1}));
+        assert!(redact_metadata(&mut definition, &sensitive).is_empty());
+        assert!(redact_metadata(&mut json!({"name": "ledger"}), &sensitive).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_definition() {
+        let mut omitted = json!({
+            "name": "billing",
+            "dependencies": [{"service": "ledger"}, {"service": "audit", "required": false}]
+        });
+        let mut explicit = json!({
+            "name": "billing",
+            "metadata": {},
+            "dependencies": [
+                {"service": "ledger", "required": true, "interaction": "sync-call"},
+                {"service": "audit", "required": false, "interaction": "sync-call"}
+            ]
+        });
+        normalize_definition(&mut omitted);
+        normalize_definition(&mut explicit);
+        assert_eq!(omitted, explicit);
+
+        let mut data_read = json!({"name": "billing", "metadata": null,
+            "dependencies": [{"service": "ledger", "interaction": "data-read"}]});
+        normalize_definition(&mut data_read);
+        assert_eq!(data_read["metadata"], json!({}));
+        assert_eq!(data_read["dependencies"][0]["interaction"], "data-read");
+    }
 }
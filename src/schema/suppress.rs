@@ -0,0 +1,345 @@
+//! Suppression of validation warnings by code, each with a justification and expiry date
+//!
+//! Suppressions are declared in the `ignores` block of a definition or in comments such
+//! as `# aureacore:ignore W004 reason="legacy runtime" expires=2026-12-31`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+
+/// Prefix of comments declaring a suppression
+const ANNOTATION: &str = "aureacore:ignore";
+
+/// Kind of validation warning, identified by a code such as `W004`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WarningCode {
+    /// W000: warnings of no other kind, e.g. from validation webhooks
+    Other,
+    /// W001: a dependency's version doesn't satisfy the declared constraint
+    DependencyVersion,
+    /// W002: an optional dependency is not in the catalog
+    MissingDependency,
+    /// W003: a dependency is not deployed to an environment the service is
+    Placement,
+    /// W004: the runtime is past or near its end of life
+    RuntimeEol,
+    /// W005: an API is consumed from a service not declared as a dependency
+    UndeclaredConsumption,
+    /// W006: a consumed endpoint or API version is not provided
+    ApiContract,
+    /// W007: a network address is claimed by another service too
+    AddressConflict,
+    /// W008: the definition targets another minor schema version
+    SchemaVersion,
+    /// W009: type-specific metadata such as `proto_files` is missing
+    TypeMetadata,
+    /// W010: a lint finding about spelling or placeholders
+    Lint,
+    /// W011: a suppression is invalid, expired or unused; cannot be suppressed itself
+    Suppression,
+    /// W012: a dependency hasn't been re-confirmed within its review window
+    DependencyReview,
+    /// W013: declared consumer load exceeds a provider's quota or capacity
+    Load,
+    /// W014: a dependency names a former service, now an alias of another
+    AliasedDependency,
+}
+
+/// Message fragments identifying each kind of warning, checked in order
+const PATTERNS: &[(&str, WarningCode)] = &[
+    ("Suppression of W", WarningCode::Suppression),
+    ("Invalid suppression", WarningCode::Suppression),
+    ("version incompatibility for dependency", WarningCode::DependencyVersion),
+    ("has incompatible version", WarningCode::DependencyVersion),
+    ("Optional dependency '", WarningCode::MissingDependency),
+    ("which is not registered in the catalog", WarningCode::MissingDependency),
+    ("is not deployed to environment", WarningCode::Placement),
+    ("its end of life", WarningCode::RuntimeEol),
+    ("without declaring it as a dependency", WarningCode::UndeclaredConsumption),
+    ("Consumed endpoint", WarningCode::ApiContract),
+    ("Consumed API version", WarningCode::ApiContract),
+    ("is also claimed by service", WarningCode::AddressConflict),
+    ("schema version", WarningCode::SchemaVersion),
+    ("in metadata", WarningCode::TypeMetadata),
+    ("spells ", WarningCode::Lint),
+    ("doesn't specify an HTTP method", WarningCode::Lint),
+    ("doesn't provide a description", WarningCode::Lint),
+    ("was due for review on", WarningCode::DependencyReview),
+    ("hasn't been re-confirmed since", WarningCode::DependencyReview),
+    ("exceeds its quota of", WarningCode::Load),
+    ("exceeds the capacity of", WarningCode::Load),
+    ("is an alias of", WarningCode::AliasedDependency),
+];
+
+impl WarningCode {
+    /// Every warning code, in order
+    pub const ALL: [WarningCode; 15] = [
+        WarningCode::Other,
+        WarningCode::DependencyVersion,
+        WarningCode::MissingDependency,
+        WarningCode::Placement,
+        WarningCode::RuntimeEol,
+        WarningCode::UndeclaredConsumption,
+        WarningCode::ApiContract,
+        WarningCode::AddressConflict,
+        WarningCode::SchemaVersion,
+        WarningCode::TypeMetadata,
+        WarningCode::Lint,
+        WarningCode::Suppression,
+        WarningCode::DependencyReview,
+        WarningCode::Load,
+        WarningCode::AliasedDependency,
+    ];
+
+    /// Classifies a warning by its message
+    pub fn of(message: &str) -> Self {
+        PATTERNS
+            .iter()
+            .find(|(fragment, _)| message.contains(fragment))
+            .map_or(WarningCode::Other, |(_, code)| *code)
+    }
+
+    /// Gets the code, e.g. `W004`
+    pub fn code(&self) -> String {
+        let index = Self::ALL.iter().position(|code| code == self).unwrap_or_default();
+        format!("W{:03}", index)
+    }
+}
+
+impl fmt::Display for WarningCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for WarningCode {
+    type Err = AureaCoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let code = s.trim().to_ascii_uppercase();
+        Self::ALL
+            .into_iter()
+            .find(|known| known.code() == code)
+            .ok_or_else(|| AureaCoreError::Config(format!("Unknown warning code '{}'", s)))
+    }
+}
+
+impl Serialize for WarningCode {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for WarningCode {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Suppression of a kind of warning for one service until it expires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Suppression {
+    /// Code of the suppressed warnings, e.g. `W004`
+    #[schemars(with = "String", regex(pattern = r"^[Ww][0-9]{3}$"))]
+    pub code: WarningCode,
+    /// Why the warnings are acceptable for now
+    #[schemars(length(min = 1))]
+    pub reason: String,
+    /// Last day the suppression applies, as `YYYY-MM-DD`
+    #[schemars(with = "String")]
+    pub expires: NaiveDate,
+}
+
+impl Suppression {
+    /// Checks whether the suppression still applies on `today`
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        today <= self.expires
+    }
+}
+
+/// Warnings of a service after applying its suppressions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuppressionOutcome {
+    /// Warnings left, including notices about expired and unused suppressions
+    pub kept: Vec<String>,
+    /// Warnings that were suppressed
+    pub suppressed: Vec<String>,
+}
+
+/// Applies active suppressions to the warnings of a service
+///
+/// Expired suppressions and active ones matching no warning are reported as W011
+/// warnings, so suppressions don't outlive their purpose unnoticed.
+pub fn apply_suppressions(
+    suppressions: &[Suppression],
+    warnings: Vec<String>,
+    today: NaiveDate,
+) -> SuppressionOutcome {
+    let mut outcome = SuppressionOutcome::default();
+    let mut used = vec![false; suppressions.len()];
+    for warning in warnings {
+        let code = WarningCode::of(&warning);
+        let matching = suppressions.iter().position(|suppression| {
+            code != WarningCode::Suppression
+                && suppression.code == code
+                && suppression.is_active(today)
+        });
+        match matching {
+            Some(index) => {
+                used[index] = true;
+                outcome.suppressed.push(warning);
+            }
+            None => outcome.kept.push(warning),
+        }
+    }
+    for (suppression, used) in suppressions.iter().zip(used) {
+        if !suppression.is_active(today) {
+            outcome.kept.push(format!(
+                "Suppression of {} expired on {} ({})",
+                suppression.code, suppression.expires, suppression.reason
+            ));
+        } else if !used {
+            outcome.kept.push(format!(
+                "Suppression of {} matches no warning and can be removed ({})",
+                suppression.code, suppression.reason
+            ));
+        }
+    }
+    outcome
+}
+
+/// Parses the suppression comments of a definition file
+///
+/// Returns the valid suppressions and a W011 warning for each invalid comment.
+pub fn parse_annotations(content: &str) -> (Vec<Suppression>, Vec<String>) {
+    let mut suppressions = Vec::new();
+    let mut invalid = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let Some(annotation) =
+            line.split_once('#').and_then(|(_, comment)| comment.trim().strip_prefix(ANNOTATION))
+        else {
+            continue;
+        };
+        match parse_annotation(annotation) {
+            Ok(suppression) => suppressions.push(suppression),
+            Err(reason) => invalid.push(format!(
+                "Invalid suppression comment on line {}: {}",
+                index + 1,
+                reason
+            )),
+        }
+    }
+    (suppressions, invalid)
+}
+
+/// Parses `W004 reason="..." expires=YYYY-MM-DD`
+fn parse_annotation(annotation: &str) -> std::result::Result<Suppression, String> {
+    let annotation = annotation.trim();
+    let (code, mut rest) = annotation.split_once(char::is_whitespace).unwrap_or((annotation, ""));
+    let code = code.parse::<WarningCode>().map_err(|e| e.to_string())?;
+
+    let mut reason = None;
+    let mut expires = None;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let (key, value) =
+            rest.split_once('=').ok_or_else(|| format!("expected key=value at '{}'", rest))?;
+        let value_rest = value.trim_start();
+        let (value, remainder) = match value_rest.strip_prefix('"') {
+            Some(quoted) => quoted
+                .split_once('"')
+                .ok_or_else(|| format!("unterminated quote in {}", key.trim()))?,
+            None => value_rest.split_once(char::is_whitespace).unwrap_or((value_rest, "")),
+        };
+        match key.trim() {
+            "reason" => reason = Some(value.to_string()),
+            "expires" => {
+                expires = Some(
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .map_err(|_| format!("invalid expiry date '{}'", value))?,
+                )
+            }
+            other => return Err(format!("unknown key '{}'", other)),
+        }
+        rest = remainder;
+    }
+
+    let reason = reason.filter(|reason| !reason.trim().is_empty()).ok_or("missing reason")?;
+    let expires = expires.ok_or("missing expiry date")?;
+    Ok(Suppression { code, reason, expires })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_codes() {
+        assert_eq!(WarningCode::RuntimeEol.code(), "W004");
+        assert_eq!("w004".parse::<WarningCode>().unwrap(), WarningCode::RuntimeEol);
+        assert!("W999".parse::<WarningCode>().is_err());
+        assert_eq!(
+            WarningCode::of("Runtime node 16 reached its end of life on 2023-09-11"),
+            WarningCode::RuntimeEol
+        );
+        assert_eq!(
+            WarningCode::of("Optional dependency 'audit' not found"),
+            WarningCode::MissingDependency
+        );
+        assert_eq!(
+            WarningCode::of("Optional dependency 'audit' has incompatible version: ..."),
+            WarningCode::DependencyVersion
+        );
+        assert_eq!(WarningCode::of("slow"), WarningCode::Other);
+    }
+
+    #[test]
+    fn test_apply_suppressions() {
+        let (suppressions, invalid) = parse_annotations(
+            "name: billing # aureacore:ignore W004 reason=\"migrating in Q4\" expires=2026-12-31\n\
+             # aureacore:ignore W002 expires=2026-01-31 reason=\"audit is optional\"\n\
+             # aureacore:ignore W006 reason=\"legacy\"\n\
+             # aureacore:ignore W010 reason=unquoted expires=2026-12-31\n",
+        );
+        assert_eq!(suppressions.len(), 3);
+        assert_eq!(suppressions[0].reason, "migrating in Q4");
+        assert_eq!(invalid, vec!["Invalid suppression comment on line 3: missing expiry date"]);
+
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let outcome = apply_suppressions(
+            &suppressions,
+            vec![
+                "Runtime node 16 reached its end of life on 2023-09-11".to_string(),
+                "Optional dependency 'audit' not found".to_string(),
+            ],
+            today,
+        );
+        assert_eq!(outcome.suppressed.len(), 1);
+        assert_eq!(
+            outcome.kept,
+            vec![
+                "Optional dependency 'audit' not found",
+                "Suppression of W002 expired on 2026-01-31 (audit is optional)",
+                "Suppression of W010 matches no warning and can be removed (unquoted)",
+            ]
+        );
+
+        let suppression: Suppression =
+            serde_yaml::from_str("code: W004\nreason: legacy\nexpires: 2026-12-31\n").unwrap();
+        assert_eq!(suppression.code, WarningCode::RuntimeEol);
+        assert!(serde_yaml::from_str::<Suppression>("code: W004\nexpires: 2026-12-31\n").is_err());
+    }
+}
@@ -1,16 +1,95 @@
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use jsonschema::{validator_for, Validator};
+use once_cell::sync::Lazy;
 use schemars::schema_for;
 use semver::Version;
+use sha2::{Digest, Sha256};
 
 use crate::error::{AureaCoreError as Error, Result};
-use crate::schema::service::ServiceSchema;
-
-/// Current schema version used by the system
+use crate::schema::compatibility::{CompatibilityPolicy, SemverLoose};
+use crate::schema::lint::lint;
+use crate::schema::oncall::Oncall;
+use crate::schema::remote::SchemaSource;
+use crate::schema::resource::ResourceSchema;
+use crate::schema::service::{parse_dependencies, Dependency, ServiceSchema};
+
+/// Default schema version used by the system, see [`SchemaVersionPolicy`]
 pub const CURRENT_SCHEMA_VERSION: &str = "1.0.0";
 
+/// Identifies a compiled schema by type and version, plus the SHA-256 digest of the
+/// content of schemas not derived from the code
+type SchemaKey = (SchemaType, String, Option<[u8; 32]>);
+
+/// Most compiled schemas kept in the process-wide cache
+const MAX_COMPILED_SCHEMAS: usize = 64;
+
+/// Compiled schemas shared by every validation service in the process, so registries
+/// created for tests or tenants compile each schema only once
+static COMPILED_SCHEMAS: Lazy<Mutex<SchemaCache>> =
+    Lazy::new(|| Mutex::new(SchemaCache::new(MAX_COMPILED_SCHEMAS)));
+
+/// Compiled schemas by key, evicting the least recently used beyond a capacity
+///
+/// Schemas fetched from a registry change content over time; bounding the cache keeps
+/// superseded versions from piling up in long-running processes.
+struct SchemaCache {
+    capacity: usize,
+    schemas: HashMap<SchemaKey, (CompiledSchema, u64)>,
+    clock: u64,
+}
+
+impl SchemaCache {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, schemas: HashMap::new(), clock: 0 }
+    }
+
+    fn get(&mut self, key: &SchemaKey) -> Option<CompiledSchema> {
+        self.clock += 1;
+        let (schema, last_used) = self.schemas.get_mut(key)?;
+        *last_used = self.clock;
+        Some(schema.clone())
+    }
+
+    /// Adds a schema unless one was cached for the key meanwhile, returning the cached one
+    fn insert(&mut self, key: SchemaKey, schema: CompiledSchema) -> CompiledSchema {
+        if let Some(schema) = self.get(&key) {
+            return schema;
+        }
+        if self.schemas.len() >= self.capacity {
+            let oldest = self
+                .schemas
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.schemas.remove(&oldest);
+            }
+        }
+        self.schemas.insert(key, (schema.clone(), self.clock));
+        schema
+    }
+}
+
+/// Hashes the content of a schema for its cache key
+fn content_digest(schema: &serde_json::Value) -> [u8; 32] {
+    Sha256::digest(schema.to_string().as_bytes()).into()
+}
+
+/// Gets a compiled schema from the process-wide cache, compiling it on first use
+fn shared_schema(
+    key: SchemaKey,
+    compile: impl FnOnce() -> Result<CompiledSchema>,
+) -> Result<CompiledSchema> {
+    if let Some(schema) = COMPILED_SCHEMAS.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return Ok(schema);
+    }
+    // Compile without holding the lock; a concurrent compilation of the same key is harmless
+    let schema = compile()?;
+    Ok(COMPILED_SCHEMAS.lock().unwrap_or_else(|e| e.into_inner()).insert(key, schema))
+}
+
 /// Type of schema to validate against
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum SchemaType {
@@ -18,10 +97,24 @@ pub enum SchemaType {
     Root,
     /// Service configuration schema
     Service,
+    /// Resource definition schema
+    Resource,
     /// Custom schema with specified name
     Custom(String),
 }
 
+impl SchemaType {
+    /// Gets the name of the schema type, e.g. `service`, as used by schema registries
+    pub fn name(&self) -> &str {
+        match self {
+            SchemaType::Root => "root",
+            SchemaType::Service => "service",
+            SchemaType::Resource => "resource",
+            SchemaType::Custom(name) => name,
+        }
+    }
+}
+
 /// Result of version compatibility check
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum VersionCompatibility {
@@ -34,28 +127,95 @@ pub enum VersionCompatibility {
 }
 
 /// Checks compatibility between versions (standalone function)
+///
+/// Applies the default [`SemverLoose`] policy.
 pub fn check_version_compatibility(version: &str, current: &str) -> VersionCompatibility {
-    // Parse versions
-    let v1 = match Version::parse(version) {
-        Ok(v) => v,
-        Err(_) => return VersionCompatibility::MajorIncompatible,
-    };
+    SemverLoose.check(version, current)
+}
 
-    let v2 = match Version::parse(current) {
-        Ok(v) => v,
-        Err(_) => return VersionCompatibility::MajorIncompatible,
-    };
+/// Range of schema versions accepted by a validation service
+///
+/// Versions outside `min_supported..=max_supported` are rejected. Versions inside the
+/// range but on a different minor version than `current` validate with a warning,
+/// unless a schema generation was registered for them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaVersionPolicy {
+    current: Version,
+    min_supported: Version,
+    max_supported: Option<Version>,
+}
+
+impl Default for SchemaVersionPolicy {
+    fn default() -> Self {
+        Self::new(CURRENT_SCHEMA_VERSION).expect("CURRENT_SCHEMA_VERSION is valid semver")
+    }
+}
+
+impl SchemaVersionPolicy {
+    /// Creates a policy accepting every version with the same major version as `current`
+    pub fn new(current: &str) -> Result<Self> {
+        let current = parse_policy_version(current)?;
+        let min_supported = Version::new(current.major, 0, 0);
+        Ok(Self { current, min_supported, max_supported: None })
+    }
+
+    /// Sets the oldest supported schema version (inclusive)
+    pub fn with_min_supported(mut self, version: &str) -> Result<Self> {
+        self.min_supported = parse_policy_version(version)?;
+        Ok(self)
+    }
+
+    /// Sets the newest supported schema version (inclusive)
+    ///
+    /// Without a maximum, versions up to the end of the current major version are accepted.
+    pub fn with_max_supported(mut self, version: &str) -> Result<Self> {
+        self.max_supported = Some(parse_policy_version(version)?);
+        Ok(self)
+    }
 
-    // Compare major and minor versions
-    if v1.major != v2.major {
-        VersionCompatibility::MajorIncompatible
-    } else if v1.minor != v2.minor {
-        VersionCompatibility::MinorIncompatible
-    } else {
-        VersionCompatibility::Compatible
+    /// Gets the schema version new definitions are expected to target
+    pub fn current(&self) -> &Version {
+        &self.current
+    }
+
+    /// Checks whether a version falls inside the supported range
+    pub fn is_supported(&self, version: &Version) -> bool {
+        let key = |v: &Version| (v.major, v.minor, v.patch);
+        let above_min = key(version) >= key(&self.min_supported);
+        let below_max = match &self.max_supported {
+            Some(max) => key(version) <= key(max),
+            None => version.major <= self.current.major,
+        };
+        above_min && below_max
+    }
+
+    /// Checks a schema version against the policy
+    pub fn check(&self, version: &str) -> VersionCompatibility {
+        match Version::parse(version) {
+            Ok(v) if !self.is_supported(&v) => VersionCompatibility::MajorIncompatible,
+            Ok(v) if v.major == self.current.major && v.minor == self.current.minor => {
+                VersionCompatibility::Compatible
+            }
+            Ok(_) => VersionCompatibility::MinorIncompatible,
+            Err(_) => VersionCompatibility::MajorIncompatible,
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaVersionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.max_supported {
+            Some(max) => write!(f, ">={}, <={}", self.min_supported, max),
+            None => write!(f, ">={}, <{}.0.0", self.min_supported, self.current.major + 1),
+        }
     }
 }
 
+fn parse_policy_version(version: &str) -> Result<Version> {
+    Version::parse(version)
+        .map_err(|e| Error::Config(format!("Invalid schema version '{}': {}", version, e)))
+}
+
 /// A compiled JSON schema validator
 #[derive(Clone)]
 pub struct CompiledSchema {
@@ -70,13 +230,40 @@ impl CompiledSchema {
 
     /// Validates a value against the schema
     pub fn validate(&self, value: &serde_json::Value) -> std::result::Result<(), Vec<String>> {
-        match self.schema.validate(value) {
-            Ok(_) => Ok(()),
-            Err(error) => {
-                // In the new version, errors are not directly iterable
-                // We need to convert a single error to a Vec
-                Err(vec![format!("{}", error)])
-            }
+        let violations = self.violations(value);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(violations.iter().map(ToString::to_string).collect())
+    }
+
+    /// Lists every violation of the schema, with the field it concerns
+    pub fn violations(&self, value: &serde_json::Value) -> Vec<SchemaViolation> {
+        self.schema
+            .iter_errors(value)
+            .map(|error| SchemaViolation {
+                pointer: error.instance_path.as_str().to_string(),
+                message: error.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Violation of a JSON schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// JSON pointer of the offending field, empty for the document itself
+    pub pointer: String,
+    /// What is wrong with the field
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} at {}", self.message, self.pointer)
         }
     }
 }
@@ -85,6 +272,16 @@ impl CompiledSchema {
 #[derive(Clone)]
 pub struct ValidationService {
     schema_cache: HashMap<SchemaType, CompiledSchema>,
+    /// Accepted range of schema versions
+    policy: SchemaVersionPolicy,
+    /// Service schemas for older or newer generations, keyed by (major, minor)
+    generations: HashMap<(u64, u64), CompiledSchema>,
+    /// Policy checking dependency versions that don't name their own
+    compatibility: Arc<dyn CompatibilityPolicy>,
+    /// Registry schemas are fetched from before falling back to the built-in ones
+    remote: Option<Arc<dyn SchemaSource>>,
+    /// Generations looked up in the remote registry, and whether it had a schema
+    remote_generations: HashMap<(u64, u64), bool>,
 }
 
 impl Default for ValidationService {
@@ -96,13 +293,177 @@ impl Default for ValidationService {
 impl ValidationService {
     /// Creates a new validation service
     pub fn new() -> Self {
-        Self { schema_cache: HashMap::new() }
+        Self {
+            schema_cache: HashMap::new(),
+            policy: SchemaVersionPolicy::default(),
+            generations: HashMap::new(),
+            compatibility: Arc::new(SemverLoose),
+            remote: None,
+            remote_generations: HashMap::new(),
+        }
+    }
+
+    /// Fetches schemas from a schema registry, using the built-in ones where it has none
+    ///
+    /// Definitions of another schema generation are validated against the registry's
+    /// schema for that generation, if any.
+    pub fn with_schema_source(mut self, source: impl SchemaSource + 'static) -> Self {
+        self.remote = Some(Arc::new(source));
+        self
+    }
+
+    /// Forgets the schemas fetched from the schema registry, so they are fetched again
+    pub fn refresh_remote_schemas(&mut self) {
+        if self.remote.is_none() {
+            return;
+        }
+        self.schema_cache.clear();
+        for (generation, found) in self.remote_generations.drain() {
+            if found {
+                self.generations.remove(&generation);
+            }
+        }
+    }
+
+    /// Fetches and compiles the schema of a type for a generation from the schema registry
+    fn remote_schema(
+        &self,
+        schema_type: &SchemaType,
+        major: u64,
+        minor: u64,
+    ) -> Result<Option<CompiledSchema>> {
+        let Some(remote) = &self.remote else {
+            return Ok(None);
+        };
+        let generation = format!("{}.{}", major, minor);
+        let Some(schema) = remote.fetch(schema_type, &generation)? else {
+            return Ok(None);
+        };
+        let key = (schema_type.clone(), generation.clone(), Some(content_digest(&schema)));
+        shared_schema(key, || {
+            let compiled = validator_for(&schema).map_err(|e| {
+                Error::SchemaCompilationError(format!(
+                    "Failed to compile {} schema {} from the schema registry: {}",
+                    schema_type.name(),
+                    generation,
+                    e
+                ))
+            })?;
+            Ok(CompiledSchema::new(compiled))
+        })
+        .map(Some)
+    }
+
+    /// Sets the policy checking dependency versions that don't name their own
+    pub fn with_compatibility_policy(mut self, policy: impl CompatibilityPolicy + 'static) -> Self {
+        self.compatibility = Arc::new(policy);
+        self
+    }
+
+    /// Sets the accepted range of schema versions
+    pub fn with_policy(mut self, policy: SchemaVersionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Gets the accepted range of schema versions
+    pub fn policy(&self) -> &SchemaVersionPolicy {
+        &self.policy
+    }
+
+    /// Registers the service schema used for definitions of another schema generation
+    ///
+    /// Definitions whose `schema_version` shares the generation's major and minor version
+    /// are validated against `schema` instead of the current service schema, which lets a
+    /// catalog migrate between generations gradually.
+    pub fn register_schema_generation(
+        &mut self,
+        version: &str,
+        schema: &serde_json::Value,
+    ) -> Result<()> {
+        let version = parse_policy_version(version)?;
+        let key = (SchemaType::Service, version.to_string(), Some(content_digest(schema)));
+        let compiled = shared_schema(key, || {
+            let compiled = validator_for(schema).map_err(|e| {
+                Error::SchemaCompilationError(format!(
+                    "Failed to compile schema generation {}: {}",
+                    version, e
+                ))
+            })?;
+            Ok(CompiledSchema::new(compiled))
+        })?;
+        self.generations.insert((version.major, version.minor), compiled);
+        Ok(())
+    }
+
+    /// Removes a schema generation registered before, if any
+    pub fn unregister_schema_generation(&mut self, version: &str) -> Result<()> {
+        let version = parse_policy_version(version)?;
+        self.generations.remove(&(version.major, version.minor));
+        Ok(())
+    }
+
+    /// Checks a definition's schema version against the policy and registered generations
+    fn check_schema_version(&self, version: &str) -> VersionCompatibility {
+        match self.policy.check(version) {
+            VersionCompatibility::MinorIncompatible if self.generation_for(version).is_some() => {
+                VersionCompatibility::Compatible
+            }
+            compatibility => compatibility,
+        }
+    }
+
+    fn generation_for(&self, version: &str) -> Option<&CompiledSchema> {
+        let version = Version::parse(version).ok()?;
+        self.generations.get(&(version.major, version.minor))
+    }
+
+    /// Gets the service schema matching a definition's schema version
+    fn service_schema_for(&mut self, version: &str) -> Result<CompiledSchema> {
+        if let Ok(parsed) = Version::parse(version) {
+            let generation = (parsed.major, parsed.minor);
+            let current = (self.policy.current().major, self.policy.current().minor);
+            let known = self.generations.contains_key(&generation)
+                || self.remote_generations.contains_key(&generation);
+            if self.remote.is_some() && generation != current && !known {
+                let schema =
+                    self.remote_schema(&SchemaType::Service, parsed.major, parsed.minor)?;
+                self.remote_generations.insert(generation, schema.is_some());
+                if let Some(schema) = schema {
+                    self.generations.insert(generation, schema);
+                }
+            }
+        }
+        match self.generation_for(version) {
+            Some(schema) => Ok(schema.clone()),
+            None => self.get_or_compile_schema(SchemaType::Service).cloned(),
+        }
+    }
+
+    fn incompatible_version_error(&self, version: &str) -> Error {
+        Error::IncompatibleVersion(format!(
+            "Schema version {} is incompatible with current version {} (supported: {})",
+            version,
+            self.policy.current(),
+            self.policy
+        ))
     }
 
     /// Gets or compiles a schema of the specified type
+    ///
+    /// The schema registry's schema for the current generation is preferred, if one is
+    /// configured. Compiled schemas are shared with the other validation services of the
+    /// process.
     pub fn get_or_compile_schema(&mut self, schema_type: SchemaType) -> Result<&CompiledSchema> {
         if !self.schema_cache.contains_key(&schema_type) {
-            let compiled = self.compile_schema(&schema_type)?;
+            let current = self.policy.current();
+            let compiled = match self.remote_schema(&schema_type, current.major, current.minor)? {
+                Some(compiled) => compiled,
+                None => {
+                    let key = (schema_type.clone(), CURRENT_SCHEMA_VERSION.to_string(), None);
+                    shared_schema(key, || self.compile_schema(&schema_type))?
+                }
+            };
             self.schema_cache.insert(schema_type.clone(), compiled);
         }
 
@@ -117,6 +478,11 @@ impl ValidationService {
                     Error::SchemaCompilationError(format!("Failed to generate schema: {}", e))
                 })?
             }
+            SchemaType::Resource => {
+                serde_json::to_value(schema_for!(ResourceSchema)).map_err(|e| {
+                    Error::SchemaCompilationError(format!("Failed to generate schema: {}", e))
+                })?
+            }
             SchemaType::Root => {
                 // Root schema will be implemented later
                 return Err(Error::NotImplemented("Root schema not yet implemented".to_string()));
@@ -136,19 +502,24 @@ impl ValidationService {
         Ok(CompiledSchema::new(schema))
     }
 
+    /// Validates a resource definition
+    pub fn validate_resource(&mut self, definition: &serde_json::Value) -> Result<()> {
+        let schema = self.get_or_compile_schema(SchemaType::Resource)?;
+        schema.validate(definition).map_err(|errors| {
+            Error::ValidationError(format!("Schema validation failed: {}", errors.join(", ")))
+        })
+    }
+
     /// Validates a service configuration
     pub fn validate_service(&mut self, config: &serde_json::Value) -> Result<()> {
-        // Get the service schema
-        let schema = self.get_or_compile_schema(SchemaType::Service)?;
-
         // Extract version from config for compatibility check
         let config_version =
             config.get("schema_version").and_then(|v| v.as_str()).unwrap_or("1.0.0");
 
-        // Check using the standalone function
-        let compatibility = check_version_compatibility(config_version, CURRENT_SCHEMA_VERSION);
+        // Get the service schema for the declared generation
+        let schema = self.service_schema_for(config_version)?;
 
-        match compatibility {
+        match self.check_schema_version(config_version) {
             VersionCompatibility::Compatible => {
                 // Perform validation
                 match schema.validate(config) {
@@ -164,7 +535,7 @@ impl ValidationService {
                 tracing::warn!(
                     "Minor schema version incompatibility: config version {} vs current {}",
                     config_version,
-                    CURRENT_SCHEMA_VERSION
+                    self.policy.current()
                 );
 
                 match schema.validate(config) {
@@ -175,37 +546,34 @@ impl ValidationService {
                     ))),
                 }
             }
-            VersionCompatibility::MajorIncompatible => Err(Error::IncompatibleVersion(format!(
-                "Schema version {} is incompatible with current version {}",
-                config_version, CURRENT_SCHEMA_VERSION
-            ))),
+            VersionCompatibility::MajorIncompatible => {
+                Err(self.incompatible_version_error(config_version))
+            }
         }
     }
 
-    /// Checks compatibility between versions
+    /// Checks compatibility between versions with the configured policy
     pub fn check_version_compatibility(
         &self,
         version: &str,
         current: &str,
     ) -> VersionCompatibility {
-        // Parse versions
-        let v1 = match Version::parse(version) {
-            Ok(v) => v,
-            Err(_) => return VersionCompatibility::MajorIncompatible,
-        };
-
-        let v2 = match Version::parse(current) {
-            Ok(v) => v,
-            Err(_) => return VersionCompatibility::MajorIncompatible,
-        };
+        self.compatibility.check(version, current)
+    }
 
-        // Compare major and minor versions
-        if v1.major != v2.major {
-            VersionCompatibility::MajorIncompatible
-        } else if v1.minor != v2.minor {
-            VersionCompatibility::MinorIncompatible
-        } else {
-            VersionCompatibility::Compatible
+    /// Checks the version found for a dependency against its constraint
+    ///
+    /// Uses the compatibility policy named by the dependency, if any, and the
+    /// configured policy otherwise.
+    pub fn check_dependency_version(
+        &self,
+        dependency: &Dependency,
+        version: &str,
+        constraint: &str,
+    ) -> VersionCompatibility {
+        match dependency.compatibility {
+            Some(strategy) => strategy.check(version, constraint),
+            None => self.compatibility.check(version, constraint),
         }
     }
 
@@ -216,46 +584,55 @@ impl ValidationService {
         config: &serde_json::Value,
         available_services: &HashSet<String>,
     ) -> Result<Vec<String>> {
-        let mut warnings = Vec::new();
-
-        // Extract dependencies from the configuration
-        if let Some(dependencies) = config.get("dependencies").and_then(|d| d.as_array()) {
-            for dep in dependencies {
-                if let Some(name) = dep.get("service").and_then(|n| n.as_str()) {
-                    if !available_services.contains(name) {
-                        let warning = format!(
-                            "Service '{}' depends on '{}', which is not registered in the catalog",
-                            service_name, name
-                        );
-                        warnings.push(warning);
-                    }
-                }
-            }
-        }
+        Ok(self.check_dependencies(service_name, &parse_dependencies(config), available_services))
+    }
 
-        Ok(warnings)
+    /// Checks that each dependency refers to a service registered in the catalog
+    pub fn check_dependencies(
+        &self,
+        service_name: &str,
+        dependencies: &[Dependency],
+        available_services: &HashSet<String>,
+    ) -> Vec<String> {
+        dependencies
+            .iter()
+            .filter(|dep| !available_services.contains(&dep.service))
+            .map(|dep| {
+                format!(
+                    "Service '{}' depends on '{}', which is not registered in the catalog",
+                    service_name, dep.service
+                )
+            })
+            .collect()
     }
 
     /// Gets schema and performs validation with version compatibility check
+    /// Lists the violations of the service schema of a definition's generation
+    ///
+    /// Definitions of unsupported generations have none; validating reports those.
+    pub fn schema_violations(&mut self, config: &serde_json::Value) -> Vec<SchemaViolation> {
+        let version = config.get("schema_version").and_then(|v| v.as_str()).unwrap_or("1.0.0");
+        match self.service_schema_for(version) {
+            Ok(schema) => schema.violations(config),
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn perform_schema_validation(
         &mut self,
         config: &serde_json::Value,
     ) -> (Result<()>, Option<String>) {
-        // Get the service schema
-        let schema_result = self.get_or_compile_schema(SchemaType::Service);
-        if let Err(err) = schema_result {
-            return (Err(err), None);
-        }
-        let schema = schema_result.unwrap();
-
         // Extract version from config for compatibility check
         let config_version =
             config.get("schema_version").and_then(|v| v.as_str()).unwrap_or("1.0.0");
 
-        // Check version compatibility
-        let compatibility = check_version_compatibility(config_version, CURRENT_SCHEMA_VERSION);
+        // Get the service schema for the declared generation
+        let schema = match self.service_schema_for(config_version) {
+            Ok(schema) => schema,
+            Err(err) => return (Err(err), None),
+        };
 
-        match compatibility {
+        match self.check_schema_version(config_version) {
             VersionCompatibility::Compatible => {
                 // Perform validation
                 match schema.validate(config) {
@@ -273,7 +650,8 @@ impl ValidationService {
                 // Create warning but continue with validation
                 let warning = format!(
                     "Minor schema version incompatibility: config version {} vs current {}",
-                    config_version, CURRENT_SCHEMA_VERSION
+                    config_version,
+                    self.policy.current()
                 );
 
                 match schema.validate(config) {
@@ -287,13 +665,9 @@ impl ValidationService {
                     ),
                 }
             }
-            VersionCompatibility::MajorIncompatible => (
-                Err(Error::IncompatibleVersion(format!(
-                    "Schema version {} is incompatible with current version {}",
-                    config_version, CURRENT_SCHEMA_VERSION
-                ))),
-                None,
-            ),
+            VersionCompatibility::MajorIncompatible => {
+                (Err(self.incompatible_version_error(config_version)), None)
+            }
         }
     }
 
@@ -311,20 +685,6 @@ impl ValidationService {
 
         // Validate based on service type
         match service_type {
-            "rest" => {
-                // Validate REST-specific requirements
-                if let Some(endpoints) = config.get("endpoints").and_then(|e| e.as_array()) {
-                    for (i, endpoint) in endpoints.iter().enumerate() {
-                        // Check if REST endpoints have method specified
-                        if endpoint.get("method").is_none() {
-                            warnings.push(format!(
-                                "Service '{}' is a REST service but endpoint #{} doesn't specify an HTTP method",
-                                service_name, i+1
-                            ));
-                        }
-                    }
-                }
-            }
             "graphql" => {
                 // Validate GraphQL-specific requirements
                 let has_schema =
@@ -359,17 +719,11 @@ impl ValidationService {
                     ));
                 }
             }
-            _ => {
-                // For custom types, just verify they have a description
-                if config.get("description").is_none() {
-                    warnings.push(format!(
-                        "Service '{}' uses a custom service type '{}' but doesn't provide a description",
-                        service_name, service_type
-                    ));
-                }
-            }
+            _ => {}
         }
 
+        // Missing HTTP methods, descriptions of custom types and casing
+        warnings.extend(lint(service_name, config).into_iter().map(|finding| finding.message));
         warnings
     }
 
@@ -380,6 +734,24 @@ impl ValidationService {
         service_name: &str,
         config: &serde_json::Value,
         available_services: &HashSet<String>,
+    ) -> (Result<()>, Vec<String>) {
+        let dependencies = parse_dependencies(config);
+        self.validate_service_with_dependencies(
+            service_name,
+            config,
+            &dependencies,
+            available_services,
+        )
+    }
+
+    /// Validates a service definition against an explicitly resolved dependency list
+    /// Returns a tuple of (Result, Vec<Warnings>)
+    pub fn validate_service_with_dependencies(
+        &mut self,
+        service_name: &str,
+        config: &serde_json::Value,
+        dependencies: &[Dependency],
+        available_services: &HashSet<String>,
     ) -> (Result<()>, Vec<String>) {
         let mut warnings = Vec::new();
 
@@ -388,9 +760,7 @@ impl ValidationService {
             config.get("schema_version").and_then(|v| v.as_str()).unwrap_or("1.0.0");
 
         // Check version compatibility
-        let compatibility = check_version_compatibility(config_version, CURRENT_SCHEMA_VERSION);
-
-        match compatibility {
+        match self.check_schema_version(config_version) {
             VersionCompatibility::Compatible => {
                 // Compatible, proceed with validation
             }
@@ -400,7 +770,7 @@ impl ValidationService {
                     "Service '{}' uses schema version {} which has minor differences from the current version {}. Some features may not be validated correctly.",
                     service_name,
                     config_version,
-                    CURRENT_SCHEMA_VERSION
+                    self.policy.current()
                 );
                 warnings.push(warning_msg);
                 // Log the warning
@@ -408,33 +778,24 @@ impl ValidationService {
                     "Minor schema version incompatibility for service '{}': config version {} vs current {}",
                     service_name,
                     config_version,
-                    CURRENT_SCHEMA_VERSION
+                    self.policy.current()
                 );
             }
             VersionCompatibility::MajorIncompatible => {
                 // Major incompatibility, return error
-                return (
-                    Err(Error::IncompatibleVersion(format!(
-                        "Schema version {} is incompatible with current version {}",
-                        config_version, CURRENT_SCHEMA_VERSION
-                    ))),
-                    warnings,
-                );
+                return (Err(self.incompatible_version_error(config_version)), warnings);
             }
         }
 
         // Validate dependencies
-        if let Ok(dependency_warnings) =
-            self.validate_dependencies(service_name, config, available_services)
-        {
-            warnings.extend(dependency_warnings);
-        }
+        warnings.extend(self.check_dependencies(service_name, dependencies, available_services));
 
         // Validate service-specific fields
         warnings.extend(self.validate_service_type(service_name, config));
 
         // Get schema and perform validation
         let (validation_result, schema_warning) = self.perform_schema_validation(config);
+        let validation_result = validation_result.and_then(|_| check_oncall(config));
 
         // If we have a schema warning, add it
         if let Some(warning) = schema_warning {
@@ -446,6 +807,16 @@ impl ValidationService {
     }
 }
 
+/// Checks the on-call schedule declared in a service definition, if any
+fn check_oncall(config: &serde_json::Value) -> Result<()> {
+    let Some(oncall) = config.get("oncall").filter(|o| !o.is_null()) else {
+        return Ok(());
+    };
+    let oncall: Oncall = serde_json::from_value(oncall.clone())
+        .map_err(|e| Error::ValidationError(format!("Invalid on-call configuration: {}", e)))?;
+    oncall.check_schedule_id().map_err(Error::ValidationError)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -492,6 +863,55 @@ mod tests {
         assert!(schema_result.is_ok());
     }
 
+    #[test]
+    fn test_compiled_schemas_are_shared() {
+        let first =
+            ValidationService::new().get_or_compile_schema(SchemaType::Service).unwrap().clone();
+        let second =
+            ValidationService::new().get_or_compile_schema(SchemaType::Service).unwrap().clone();
+        assert!(Arc::ptr_eq(&first.schema, &second.schema));
+
+        // Generations are shared only if their content matches
+        let generation = |schema: serde_json::Value| {
+            let mut service = ValidationService::new();
+            service.register_schema_generation("1.1.0", &schema).unwrap();
+            service.generation_for("1.1.0").unwrap().clone()
+        };
+        let strict = generation(serde_json::json!({"type": "object", "required": ["tier"]}));
+        let same = generation(serde_json::json!({"type": "object", "required": ["tier"]}));
+        let loose = generation(serde_json::json!({"type": "object"}));
+        assert!(Arc::ptr_eq(&strict.schema, &same.schema));
+        assert!(!Arc::ptr_eq(&strict.schema, &loose.schema));
+        assert!(loose.validate(&serde_json::json!({})).is_ok());
+        assert!(strict.validate(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_schema_cache_evicts_least_recently_used() {
+        let compile = |schema: serde_json::Value| {
+            let key = (SchemaType::Service, "1.1".to_string(), Some(content_digest(&schema)));
+            (key, CompiledSchema::new(validator_for(&schema).unwrap()))
+        };
+        let (first_key, first) = compile(json!({"type": "object"}));
+        let (second_key, second) = compile(json!({"type": "object", "required": ["tier"]}));
+        let (third_key, third) = compile(json!({"type": "object", "required": ["owner"]}));
+
+        let mut cache = SchemaCache::new(2);
+        cache.insert(first_key.clone(), first.clone());
+        cache.insert(second_key.clone(), second);
+        assert!(cache.get(&first_key).is_some());
+
+        // Inserting a schema already cached keeps the cached one
+        let (_, again) = compile(json!({"type": "object"}));
+        assert!(Arc::ptr_eq(&cache.insert(first_key.clone(), again).schema, &first.schema));
+
+        cache.insert(third_key.clone(), third);
+        assert_eq!(cache.schemas.len(), 2);
+        assert!(cache.get(&second_key).is_none());
+        assert!(cache.get(&first_key).is_some());
+        assert!(cache.get(&third_key).is_some());
+    }
+
     #[test]
     fn test_service_validation_success() {
         let mut service = ValidationService::new();
@@ -848,4 +1268,107 @@ mod tests {
             "Expected warning about minor version differences"
         );
     }
+
+    #[test]
+    fn test_schema_version_policy_range() {
+        let policy = SchemaVersionPolicy::default();
+        assert_eq!(policy.check("1.0.3"), VersionCompatibility::Compatible);
+        assert_eq!(policy.check("1.4.0"), VersionCompatibility::MinorIncompatible);
+        assert_eq!(policy.check("2.0.0"), VersionCompatibility::MajorIncompatible);
+        assert_eq!(policy.check("garbage"), VersionCompatibility::MajorIncompatible);
+
+        let policy = SchemaVersionPolicy::new("2.0.0")
+            .unwrap()
+            .with_min_supported("1.2.0")
+            .unwrap()
+            .with_max_supported("2.1.0")
+            .unwrap();
+        assert_eq!(policy.check("1.1.0"), VersionCompatibility::MajorIncompatible);
+        assert_eq!(policy.check("1.2.0"), VersionCompatibility::MinorIncompatible);
+        assert_eq!(policy.check("2.0.1"), VersionCompatibility::Compatible);
+        assert_eq!(policy.check("2.2.0"), VersionCompatibility::MajorIncompatible);
+        assert_eq!(policy.to_string(), ">=1.2.0, <=2.1.0");
+
+        assert!(SchemaVersionPolicy::new("not-a-version").is_err());
+    }
+
+    #[test]
+    fn test_schema_generations_validate_side_by_side() {
+        let policy =
+            SchemaVersionPolicy::new("2.0.0").unwrap().with_min_supported("1.0.0").unwrap();
+        let mut validator = ValidationService::new().with_policy(policy);
+
+        // Generation 1.0 only required a name
+        validator
+            .register_schema_generation("1.0.0", &json!({"type": "object", "required": ["name"]}))
+            .unwrap();
+
+        let legacy = json!({"name": "legacy-service", "schema_version": "1.0.0"});
+        let (result, warnings) =
+            validator.validate_service_with_context("legacy-service", &legacy, &HashSet::new());
+        assert!(result.is_ok(), "Legacy generation failed: {:?}", result);
+        assert!(warnings.is_empty());
+
+        // The same definition targeting the current generation is held to the current schema
+        let current = json!({"name": "new-service", "schema_version": "2.0.0"});
+        let (result, _) =
+            validator.validate_service_with_context("new-service", &current, &HashSet::new());
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+
+        // Unsupported versions report the accepted range
+        let too_new = json!({"name": "future-service", "schema_version": "3.0.0"});
+        let err = validator.validate_service(&too_new).unwrap_err();
+        assert!(err.to_string().contains("supported: >=1.0.0, <3.0.0"), "{}", err);
+    }
+
+    /// Schema registry holding schemas by type name and generation, counting fetches
+    struct FakeRegistry {
+        schemas: HashMap<(&'static str, &'static str), serde_json::Value>,
+        fetches: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl SchemaSource for FakeRegistry {
+        fn fetch(
+            &self,
+            schema_type: &SchemaType,
+            generation: &str,
+        ) -> Result<Option<serde_json::Value>> {
+            self.fetches.lock().unwrap().push(format!("{}/{}", schema_type.name(), generation));
+            let key =
+                self.schemas.keys().find(|(t, g)| *t == schema_type.name() && *g == generation);
+            Ok(key.map(|key| self.schemas[key].clone()))
+        }
+    }
+
+    #[test]
+    fn test_remote_schemas() {
+        let fetches = Arc::new(Mutex::new(Vec::new()));
+        let registry = FakeRegistry {
+            schemas: HashMap::from([
+                (("service", "1.0"), json!({"type": "object", "required": ["name", "team"]})),
+                (("service", "1.1"), json!({"type": "object", "required": ["name"]})),
+            ]),
+            fetches: fetches.clone(),
+        };
+        let mut validator = ValidationService::new().with_schema_source(registry);
+
+        // The registry's schema replaces the built-in one
+        let err = validator.validate_service(&json!({"name": "billing"})).unwrap_err();
+        assert!(matches!(err, Error::ValidationError(_)));
+        validator.validate_service(&json!({"name": "billing", "team": "payments"})).unwrap();
+
+        // Other generations are looked up once, falling back to the current schema
+        let minor = json!({"name": "billing", "schema_version": "1.1.0"});
+        validator.validate_service(&minor).unwrap();
+        assert_eq!(validator.check_schema_version("1.1.0"), VersionCompatibility::Compatible);
+        let unknown = json!({"name": "billing", "schema_version": "1.2.0"});
+        assert!(validator.validate_service(&unknown).is_err());
+        assert!(validator.validate_service(&unknown).is_err());
+        assert_eq!(*fetches.lock().unwrap(), ["service/1.0", "service/1.1", "service/1.2"]);
+
+        validator.refresh_remote_schemas();
+        validator.validate_service(&minor).unwrap();
+        validator.validate_service(&json!({"name": "billing", "team": "payments"})).unwrap();
+        assert_eq!(fetches.lock().unwrap()[3..], ["service/1.1", "service/1.0"]);
+    }
 }
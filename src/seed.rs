@@ -0,0 +1,370 @@
+//! Synthetic catalogs for demos, benchmarks and onboarding
+//!
+//! Services are instantiated from the built-in archetypes with realistic names, owners,
+//! endpoints and dependency edges. The same options always yield the same catalog.
+
+use serde_json::json;
+
+use crate::error::{AureaCoreError, Result};
+use crate::registry::BatchSyncReport;
+use crate::schema::service::{Dependency, Endpoint, Interaction, ServiceSchema};
+use crate::templates::TemplateRegistry;
+
+/// Directory of the work directory seeded definitions are written to
+pub const SEED_DIR: &str = "services";
+
+/// Seed used unless another one is given
+pub const DEFAULT_SEED: u64 = 42;
+
+/// Business domains services are named after
+const DOMAINS: &[&str] = &[
+    "accounts",
+    "analytics",
+    "billing",
+    "cart",
+    "catalog",
+    "checkout",
+    "fraud",
+    "fulfillment",
+    "identity",
+    "inventory",
+    "invoicing",
+    "ledger",
+    "loyalty",
+    "media",
+    "messaging",
+    "notifications",
+    "orders",
+    "payments",
+    "pricing",
+    "profiles",
+    "recommendations",
+    "reporting",
+    "returns",
+    "reviews",
+    "search",
+    "shipping",
+    "subscriptions",
+    "tax",
+    "warehouse",
+];
+
+/// Name suffixes and the archetype services named with them follow
+const KINDS: &[(&str, &str)] = &[
+    ("api", "rest"),
+    ("service", "rest"),
+    ("gateway", "rest"),
+    ("rpc", "grpc"),
+    ("worker", "event-driven"),
+    ("events", "event-driven"),
+];
+
+/// Shape of a generated catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedOptions {
+    /// Number of services
+    pub services: usize,
+    /// Length of the longest chain of dependencies
+    pub max_depth: usize,
+    /// Number of dependency cycles, each closed by an optional dependency
+    pub cycles: usize,
+    /// Seed of the random generator
+    pub seed: u64,
+}
+
+impl Default for SeedOptions {
+    fn default() -> Self {
+        Self { services: 50, max_depth: 4, cycles: 0, seed: DEFAULT_SEED }
+    }
+}
+
+/// Outcome of seeding a catalog
+#[derive(Debug, Clone)]
+pub struct SeededCatalog {
+    /// Registration and validation of the generated services
+    pub sync: BatchSyncReport,
+    /// Commit holding the generated files, if committed
+    pub commit: Option<String>,
+}
+
+/// Generates synthetic service definitions
+pub struct CatalogSeeder {
+    options: SeedOptions,
+    templates: TemplateRegistry,
+}
+
+/// A service being generated
+struct Node {
+    name: String,
+    level: usize,
+    definition: ServiceSchema,
+}
+
+impl CatalogSeeder {
+    /// Creates a seeder generating catalogs of the given shape
+    pub fn new(options: SeedOptions) -> Self {
+        Self { options, templates: TemplateRegistry::builtin() }
+    }
+
+    /// Generates the definitions, dependencies before their dependents
+    ///
+    /// Services are spread over `max_depth + 1` levels, each depending on a service of
+    /// the level below and on a few random lower-level ones. Cycles are closed by an
+    /// optional dependency from a service back up to one of its transitive dependents.
+    pub fn generate(&self) -> Result<Vec<ServiceSchema>> {
+        let SeedOptions { services, max_depth, cycles, seed } = self.options;
+        if services == 0 {
+            return Err(AureaCoreError::Config("Cannot seed an empty catalog".to_string()));
+        }
+        if services <= max_depth {
+            return Err(AureaCoreError::Config(format!(
+                "A dependency chain of depth {} needs at least {} services",
+                max_depth,
+                max_depth + 1
+            )));
+        }
+        let mut rng = Rng::new(seed);
+
+        // Every level is populated, so the longest chain is exactly `max_depth` long
+        let mut levels: Vec<usize> = (0..services)
+            .map(|i| if i <= max_depth { i } else { rng.below(max_depth + 1) })
+            .collect();
+        levels.sort_unstable();
+        let cyclable = levels.iter().filter(|level| **level > 0).count();
+        if cycles > cyclable {
+            return Err(AureaCoreError::Config(format!(
+                "Cannot close {} cycles with {} dependent services",
+                cycles, cyclable
+            )));
+        }
+
+        let mut names: Vec<(&'static str, &'static str)> = DOMAINS
+            .iter()
+            .flat_map(|domain| KINDS.iter().map(move |(kind, _)| (*domain, *kind)))
+            .collect();
+        rng.shuffle(&mut names);
+        let mut nodes = Vec::with_capacity(services);
+        for (i, level) in levels.into_iter().enumerate() {
+            let (domain, kind) = names[i % names.len()];
+            let name = match i / names.len() {
+                0 => format!("{}-{}", domain, kind),
+                round => format!("{}-{}-{}", domain, kind, round + 1),
+            };
+            let definition = self.definition(&name, domain, kind, &mut rng)?;
+            nodes.push(Node { name, level, definition });
+        }
+
+        let mut primary = vec![None; services];
+        for i in 0..services {
+            let level = nodes[i].level;
+            if level == 0 {
+                continue;
+            }
+            let below = nodes.iter().position(|node| node.level == level - 1).unwrap_or(0);
+            let lower = nodes.iter().position(|node| node.level == level).unwrap_or(i);
+            let parent = below + rng.below(lower - below);
+            primary[i] = Some(parent);
+            let mut targets = vec![parent];
+            for _ in 0..rng.below(3) {
+                let target = rng.below(lower);
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+            for target in targets {
+                let required = rng.below(5) > 0;
+                let dependency = dependency_on(&nodes[target], required);
+                nodes[i].definition.dependencies.get_or_insert_with(Vec::new).push(dependency);
+            }
+        }
+
+        let mut starts: Vec<usize> = (0..services).filter(|i| nodes[*i].level > 0).collect();
+        rng.shuffle(&mut starts);
+        for start in starts.into_iter().take(cycles) {
+            let mut end = start;
+            for _ in 0..=rng.below(nodes[start].level) {
+                end = primary[end].unwrap_or(end);
+            }
+            let dependency = dependency_on(&nodes[start], false);
+            nodes[end].definition.dependencies.get_or_insert_with(Vec::new).push(dependency);
+        }
+
+        Ok(nodes.into_iter().map(|node| node.definition).collect())
+    }
+
+    /// Instantiates the archetype of a kind of service, fleshed out for its domain
+    fn definition(
+        &self,
+        name: &str,
+        domain: &str,
+        kind: &str,
+        rng: &mut Rng,
+    ) -> Result<ServiceSchema> {
+        let archetype = KINDS.iter().find(|(k, _)| *k == kind).map(|(_, a)| *a).unwrap_or("rest");
+        let template = self.templates.get(archetype).ok_or_else(|| {
+            AureaCoreError::Internal(format!("Missing built-in template '{}'", archetype))
+        })?;
+        let version = format!("{}.{}.{}", 1 + rng.below(3), rng.below(10), rng.below(20));
+        let mut definition = template.instantiate(name, &version);
+        definition.owner = Some(format!("{}-team", domain));
+
+        let endpoint =
+            |name: String, path: String, method: Option<&str>, description: String| Endpoint {
+                name,
+                path,
+                method: method.map(str::to_string),
+                description: Some(description),
+                provides: Vec::new(),
+            };
+        match archetype {
+            "grpc" => {
+                let service = format!("{}.v1.{}Service", domain, pascal_case(domain));
+                definition.description = Some(format!("Serves {} data over gRPC", domain));
+                definition.endpoints.push(endpoint(
+                    format!("get-{}", domain),
+                    format!("{}/Get", service),
+                    None,
+                    format!("Looks up {} by ID", domain),
+                ));
+                definition
+                    .metadata
+                    .insert("proto_files".to_string(), json!([format!("proto/{}.proto", domain)]));
+            }
+            "event-driven" => {
+                definition.description = Some(format!("Processes {} events", domain));
+                definition
+                    .metadata
+                    .insert("topics".to_string(), json!([format!("{}.events", domain)]));
+            }
+            _ => {
+                definition.description = Some(format!("Public {} {}", domain, kind));
+                definition.endpoints.push(endpoint(
+                    format!("list-{}", domain),
+                    format!("/v1/{}", domain),
+                    Some("GET"),
+                    format!("Lists {}", domain),
+                ));
+                definition.endpoints.push(endpoint(
+                    format!("create-{}", domain),
+                    format!("/v1/{}", domain),
+                    Some("POST"),
+                    format!("Creates {}", domain),
+                ));
+            }
+        }
+        Ok(definition)
+    }
+}
+
+/// Declares a dependency compatible with the current version of a service
+fn dependency_on(node: &Node, required: bool) -> Dependency {
+    let mut version = node.definition.version.split('.');
+    let constraint =
+        format!("{}.{}.0", version.next().unwrap_or("1"), version.next().unwrap_or("0"));
+    let interaction = if node.definition.metadata.contains_key("topics") {
+        Interaction::AsyncEvent
+    } else {
+        Interaction::SyncCall
+    };
+    Dependency {
+        service: node.name.clone(),
+        version_constraint: Some(constraint),
+        required,
+        interaction,
+        ..Default::default()
+    }
+}
+
+fn pascal_case(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// Xorshift generator, so a seed always yields the same catalog
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Xorshift never leaves zero
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Picks a number in `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+
+    #[test]
+    fn test_generate_catalog() {
+        let definitions = CatalogSeeder::new(SeedOptions::default()).generate().unwrap();
+        assert_eq!(definitions.len(), 50);
+        let names: HashSet<&str> = definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names.len(), 50);
+        assert!(definitions.iter().all(|d| d.owner.is_some() && d.description.is_some()));
+        let again = CatalogSeeder::new(SeedOptions::default()).generate().unwrap();
+        let render = |d: &[ServiceSchema]| serde_json::to_string(d).unwrap();
+        assert_eq!(render(&definitions), render(&again));
+
+        // Dependencies come first, so the depth of a service follows from earlier ones
+        let mut depths: HashMap<&str, usize> = HashMap::new();
+        for definition in &definitions {
+            let depth = definition
+                .dependencies
+                .iter()
+                .flatten()
+                .map(|dependency| depths[dependency.service.as_str()] + 1)
+                .max()
+                .unwrap_or(0);
+            depths.insert(&definition.name, depth);
+        }
+        assert_eq!(depths.values().max(), Some(&4));
+
+        let options = SeedOptions { services: 200, cycles: 3, seed: 7, ..SeedOptions::default() };
+        let definitions = CatalogSeeder::new(options).generate().unwrap();
+        let position: HashMap<&str, usize> =
+            definitions.iter().enumerate().map(|(i, d)| (d.name.as_str(), i)).collect();
+        let position = &position;
+        let back_edges: Vec<&Dependency> = definitions
+            .iter()
+            .enumerate()
+            .flat_map(|(i, d)| {
+                d.dependencies
+                    .iter()
+                    .flatten()
+                    .filter(move |dep| position[dep.service.as_str()] > i)
+            })
+            .collect();
+        assert_eq!(back_edges.len(), 3);
+        assert!(back_edges.iter().all(|dependency| !dependency.required));
+
+        let invalid = |services, max_depth, cycles| {
+            let options = SeedOptions { services, max_depth, cycles, seed: 1 };
+            CatalogSeeder::new(options).generate().is_err()
+        };
+        assert!(invalid(0, 0, 0));
+        assert!(invalid(3, 3, 0));
+        assert!(invalid(1, 0, 1));
+    }
+}
@@ -0,0 +1,309 @@
+//! Reusable service archetypes and the lint rules that come with them
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AureaCoreError, Result};
+use crate::schema::service::{Endpoint, ServiceSchema, ServiceType};
+
+/// Archetypes shipped with AureaCore, overridable by organization templates
+const BUILTIN_TEMPLATES: &[&str] = &[
+    r#"
+name: rest
+description: Standard REST microservice
+service_type:
+  type: rest
+endpoints:
+  - name: health
+    path: /health
+    method: GET
+    description: Liveness probe
+rules:
+  required_endpoints: [health]
+  require_owner: true
+"#,
+    r#"
+name: grpc
+description: Standard gRPC service
+service_type:
+  type: grpc
+endpoints:
+  - name: health
+    path: grpc.health.v1.Health/Check
+    description: gRPC health checking protocol
+rules:
+  required_endpoints: [health]
+  require_owner: true
+"#,
+    r#"
+name: event-driven
+description: Event consumer or producer without a synchronous API
+service_type:
+  type: eventdriven
+endpoints: []
+rules:
+  require_owner: true
+  require_description: true
+"#,
+];
+
+/// Lint rules a service following an archetype must satisfy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateRules {
+    /// Metadata keys every service must set
+    #[serde(default)]
+    pub required_metadata: Vec<String>,
+    /// Endpoint names every service must declare
+    #[serde(default)]
+    pub required_endpoints: Vec<String>,
+    /// Whether services must declare an owner
+    #[serde(default)]
+    pub require_owner: bool,
+    /// Whether services must have a description
+    #[serde(default)]
+    pub require_description: bool,
+}
+
+/// A reusable service archetype with defaults and lint rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceTemplate {
+    /// Name used to refer to the archetype
+    pub name: String,
+    /// What kind of service the archetype describes
+    pub description: Option<String>,
+    /// Service type of services following the archetype
+    pub service_type: ServiceType,
+    /// Endpoints new services start with
+    #[serde(default)]
+    pub endpoints: Vec<Endpoint>,
+    /// Metadata new services start with
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Lint rules enforced on services following the archetype
+    #[serde(default)]
+    pub rules: TemplateRules,
+}
+
+impl ServiceTemplate {
+    /// Creates a service definition following this archetype
+    pub fn instantiate(&self, name: &str, version: &str) -> ServiceSchema {
+        ServiceSchema {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: None,
+            owner: None,
+            system: None,
+            domain: None,
+            oncall: None,
+            cost_center: None,
+            monthly_cost_estimate: None,
+            documentation_url: None,
+            service_type: self.service_type.clone(),
+            runtime: None,
+            endpoints: self.endpoints.clone(),
+            exposure: None,
+            dependencies: None,
+            consumes: Vec::new(),
+            consumables: Vec::new(),
+            capacity: None,
+            contract_tests: Vec::new(),
+            feature_flags: Vec::new(),
+            sensitive_metadata: Vec::new(),
+            metadata: self.metadata.clone(),
+            archetype: Some(self.name.clone()),
+            ignores: Vec::new(),
+        }
+    }
+
+    /// Checks a service definition against the archetype's lint rules
+    pub fn check(&self, definition: &ServiceSchema) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if serde_json::to_value(&definition.service_type).ok()
+            != serde_json::to_value(&self.service_type).ok()
+        {
+            violations.push(format!("Service type does not match archetype '{}'", self.name));
+        }
+        for endpoint in &self.rules.required_endpoints {
+            if !definition.endpoints.iter().any(|e| &e.name == endpoint) {
+                violations
+                    .push(format!("Archetype '{}' requires endpoint '{}'", self.name, endpoint));
+            }
+        }
+        for key in &self.rules.required_metadata {
+            if !definition.metadata.contains_key(key) {
+                violations.push(format!("Archetype '{}' requires metadata '{}'", self.name, key));
+            }
+        }
+        if self.rules.require_owner && definition.owner.is_none() {
+            violations.push(format!("Archetype '{}' requires an owner", self.name));
+        }
+        if self.rules.require_description && definition.description.is_none() {
+            violations.push(format!("Archetype '{}' requires a description", self.name));
+        }
+
+        violations
+    }
+}
+
+/// Collection of archetypes available to a catalog
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    templates: BTreeMap<String, ServiceTemplate>,
+    require_archetype: bool,
+}
+
+impl TemplateRegistry {
+    /// Creates a registry with the built-in archetypes
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        for source in BUILTIN_TEMPLATES {
+            let template: ServiceTemplate =
+                serde_yaml::from_str(source).expect("built-in templates are valid");
+            registry.insert(template);
+        }
+        registry
+    }
+
+    /// Adds or replaces an archetype
+    pub fn insert(&mut self, template: ServiceTemplate) {
+        self.templates.insert(template.name.clone(), template);
+    }
+
+    /// Loads organization archetypes from YAML or JSON files in a directory
+    ///
+    /// Templates with the same name as an existing archetype replace it.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize> {
+        let mut loaded = 0;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let template: ServiceTemplate = match path.extension().and_then(|e| e.to_str()) {
+                Some("yaml") | Some("yml") => serde_yaml::from_str(&fs::read_to_string(&path)?)
+                    .map_err(|e| {
+                        AureaCoreError::invalid_config(
+                            format!("Invalid template {}", path.display()),
+                            e,
+                        )
+                    })?,
+                Some("json") => serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+                    AureaCoreError::invalid_config(
+                        format!("Invalid template {}", path.display()),
+                        e,
+                    )
+                })?,
+                _ => continue,
+            };
+            self.insert(template);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Requires every service to declare the archetype it follows
+    pub fn require_archetype(mut self, required: bool) -> Self {
+        self.require_archetype = required;
+        self
+    }
+
+    /// Gets an archetype by name
+    pub fn get(&self, name: &str) -> Option<&ServiceTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Finds an archetype describing services of the given type
+    pub fn for_service_type(&self, service_type: &ServiceType) -> Option<&ServiceTemplate> {
+        let wanted = serde_json::to_value(service_type).ok();
+        self.templates.values().find(|t| serde_json::to_value(&t.service_type).ok() == wanted)
+    }
+
+    /// Lists the names of all archetypes
+    pub fn names(&self) -> Vec<String> {
+        self.templates.keys().cloned().collect()
+    }
+
+    /// Checks a service definition against the archetype it declares
+    pub fn check(&self, definition: &ServiceSchema) -> Vec<String> {
+        match &definition.archetype {
+            Some(name) => match self.get(name) {
+                Some(template) => template.check(definition),
+                None => vec![format!("Unknown archetype '{}'", name)],
+            },
+            None if self.require_archetype => {
+                vec!["Service does not declare which archetype it follows".to_string()]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Renders a service definition as YAML, omitting unset fields
+pub fn render_definition(definition: &ServiceSchema) -> Result<String> {
+    let mut value = serde_json::to_value(definition)
+        .map_err(|e| AureaCoreError::Internal(format!("Failed to serialize service: {}", e)))?;
+    if let Some(object) = value.as_object_mut() {
+        // Endpoints are required even when there are none
+        object.retain(|k, v| {
+            k == "endpoints"
+                || !(v.is_null()
+                    || v.as_array().is_some_and(|a| a.is_empty())
+                    || v.as_object().is_some_and(|o| o.is_empty()))
+        });
+    }
+    serde_yaml::to_string(&value)
+        .map_err(|e| AureaCoreError::Internal(format!("Failed to render service: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_instantiate_builtin_template() {
+        let templates = TemplateRegistry::builtin();
+        assert_eq!(templates.names(), vec!["event-driven", "grpc", "rest"]);
+
+        let definition = templates.get("rest").unwrap().instantiate("orders", "0.1.0");
+        assert_eq!(definition.name, "orders");
+        assert_eq!(definition.archetype.as_deref(), Some("rest"));
+        assert_eq!(definition.endpoints[0].name, "health");
+
+        // A fresh instance only lacks what the author must fill in
+        assert_eq!(templates.check(&definition), vec!["Archetype 'rest' requires an owner"]);
+
+        let yaml = render_definition(&definition).unwrap();
+        assert!(yaml.contains("archetype: rest"));
+        assert!(!yaml.contains("null"));
+
+        // The rendered definition parses back into a valid service schema
+        let parsed: ServiceSchema = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.endpoints.len(), 1);
+    }
+
+    #[test]
+    fn test_organization_templates_and_enforcement() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rest.yaml"),
+            "name: rest\nservice_type:\n  type: rest\nrules:\n  required_metadata: [tier]\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "ignored").unwrap();
+
+        let mut templates = TemplateRegistry::builtin().require_archetype(true);
+        assert_eq!(templates.load_dir(temp_dir.path()).unwrap(), 1);
+
+        let mut definition = templates.get("rest").unwrap().instantiate("orders", "0.1.0");
+        assert_eq!(templates.check(&definition), vec!["Archetype 'rest' requires metadata 'tier'"]);
+
+        definition.archetype = Some("lambda".to_string());
+        assert_eq!(templates.check(&definition), vec!["Unknown archetype 'lambda'"]);
+
+        definition.archetype = None;
+        assert_eq!(templates.check(&definition).len(), 1);
+    }
+}
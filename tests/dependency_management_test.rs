@@ -4,7 +4,10 @@ use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
 use aureacore::error::Result;
-use aureacore::registry::{DependencyGraph, DependencyManager, EdgeMetadata, ServiceRegistry};
+use aureacore::registry::{
+    ConstraintStatus, DependencyGraph, DependencyManager, EdgeMetadata, ServiceRegistry,
+    UpgradeAction,
+};
 use aureacore::schema::validation::ValidationService;
 
 // Create a test registry with predefined services and dependencies
@@ -137,6 +140,7 @@ fn test_dependency_graph_creation() -> Result<()> {
                     EdgeMetadata {
                         required: dep.required,
                         version_constraint: dep.version_constraint.clone(),
+                        interaction: dep.interaction,
                     },
                 );
             }
@@ -217,6 +221,7 @@ fn test_dependency_resolution() -> Result<()> {
                     EdgeMetadata {
                         required: dep.required,
                         version_constraint: dep.version_constraint.clone(),
+                        interaction: dep.interaction,
                     },
                 );
             }
@@ -730,17 +735,29 @@ fn test_circular_dependency_detection() -> Result<()> {
     graph.add_edge(
         "service-x".to_string(),
         "service-y".to_string(),
-        EdgeMetadata { required: true, version_constraint: Some("1.0.0".to_string()) },
+        EdgeMetadata {
+            required: true,
+            version_constraint: Some("1.0.0".to_string()),
+            interaction: Default::default(),
+        },
     );
     graph.add_edge(
         "service-y".to_string(),
         "service-z".to_string(),
-        EdgeMetadata { required: true, version_constraint: Some("1.0.0".to_string()) },
+        EdgeMetadata {
+            required: true,
+            version_constraint: Some("1.0.0".to_string()),
+            interaction: Default::default(),
+        },
     );
     graph.add_edge(
         "service-z".to_string(),
         "service-x".to_string(),
-        EdgeMetadata { required: true, version_constraint: Some("1.0.0".to_string()) },
+        EdgeMetadata {
+            required: true,
+            version_constraint: Some("1.0.0".to_string()),
+            interaction: Default::default(),
+        },
     );
 
     // Print the graph structure
@@ -979,13 +996,13 @@ fn test_dependency_resolver() -> Result<()> {
     graph.add_edge(
         "service-a".to_string(),
         "service-b".to_string(),
-        EdgeMetadata { required: true, version_constraint: None },
+        EdgeMetadata { required: true, version_constraint: None, interaction: Default::default() },
     );
 
     graph.add_edge(
         "service-b".to_string(),
         "service-c".to_string(),
-        EdgeMetadata { required: true, version_constraint: None },
+        EdgeMetadata { required: true, version_constraint: None, interaction: Default::default() },
     );
 
     // Create resolver
@@ -1024,3 +1041,55 @@ fn test_dependency_resolver() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_plan_upgrade() -> Result<()> {
+    let registry = create_test_registry();
+    let validation_service = Arc::new(ValidationService::new());
+    let manager = DependencyManager::new(registry.clone(), validation_service);
+
+    // A minor release of service-d is accepted by the caret constraint of service-b
+    let plan = manager.plan_upgrade("service-d", "1.4.0")?;
+    assert!(plan.is_safe());
+    assert!(!plan.breaking_change);
+    assert_eq!(plan.constraints.len(), 1);
+    assert_eq!(plan.constraints[0].status, ConstraintStatus::Satisfied);
+    let order: Vec<&str> = plan.steps.iter().map(|step| step.service.as_str()).collect();
+    assert_eq!(order, vec!["service-d", "service-b", "service-a"]);
+    assert_eq!(
+        plan.steps[0].action,
+        UpgradeAction::Upgrade { from: "1.0.0".to_string(), to: "1.4.0".to_string() }
+    );
+
+    // A major release breaks it
+    let plan = manager.plan_upgrade("service-d", "2.0.0")?;
+    assert!(plan.breaking_change);
+    assert_eq!(plan.breaking().len(), 1);
+    assert_eq!(plan.steps[1].action, UpgradeAction::Adapt { constraint: "1.0.0".to_string() });
+    assert_eq!(plan.steps[2].action, UpgradeAction::Revalidate);
+
+    // A tilde constraint on a minor release can be relaxed
+    {
+        let mut registry = registry.write().unwrap();
+        let config_e = r#"{
+            "name": "service-e",
+            "config_path": "service-e.json",
+            "schema_version": "1.0.0",
+            "dependencies": [
+                { "service": "service-c", "version_constraint": "~1.0.0", "required": true }
+            ]
+        }"#;
+        registry.register_service("service-e", config_e)?;
+    }
+    let plan = manager.plan_upgrade("service-c", "1.1.0")?;
+    assert!(plan.is_safe());
+    let relaxable = plan.relaxable();
+    assert_eq!(relaxable.len(), 1);
+    assert_eq!(relaxable[0].dependent, "service-e");
+    assert_eq!(relaxable[0].status, ConstraintStatus::Relaxable { proposed: "^1.0.0".to_string() });
+
+    assert!(manager.plan_upgrade("service-d", "not-a-version").is_err());
+    assert!(manager.plan_upgrade("nonexistent-service", "1.0.0").is_err());
+
+    Ok(())
+}
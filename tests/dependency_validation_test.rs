@@ -1,213 +1,7 @@
-use std::collections::HashMap;
-
-use aureacore::error::Result;
-use aureacore::registry::{ServiceState, ServiceStatus};
+use aureacore::registry::{InMemoryRegistry, Registry, ServiceState};
 use aureacore::schema::service::Dependency;
 use serde_json::json;
 
-/// Mock implementation of ServiceRegistry for testing without file IO
-struct MockRegistry {
-    services: HashMap<String, aureacore::registry::Service>,
-}
-
-impl MockRegistry {
-    fn new() -> Self {
-        Self { services: HashMap::new() }
-    }
-
-    fn register_service(&mut self, name: &str, config_json: &str) -> Result<()> {
-        // Parse config JSON
-        let config = serde_json::from_str(config_json).map_err(|e| {
-            aureacore::error::AureaCoreError::Config(format!("Invalid config: {}", e))
-        })?;
-
-        // Create service
-        let mut service = aureacore::registry::Service::new(name.to_string(), config);
-
-        // Parse the full JSON to get schema data
-        let schema_data: serde_json::Value = serde_json::from_str(config_json).map_err(|e| {
-            aureacore::error::AureaCoreError::Config(format!("Invalid JSON: {}", e))
-        })?;
-
-        // Set schema data directly
-        service.schema_data = Some(schema_data);
-
-        // Add service to registry
-        self.services.insert(name.to_string(), service);
-
-        Ok(())
-    }
-
-    fn get_service(&self, name: &str) -> Result<&aureacore::registry::Service> {
-        self.services.get(name).ok_or_else(|| {
-            aureacore::error::AureaCoreError::Config(format!("Service '{}' not found", name))
-        })
-    }
-
-    fn validate_all_services(&mut self) -> Result<aureacore::registry::ValidationSummary> {
-        // Create validation summary
-        let mut summary = aureacore::registry::ValidationSummary::new();
-
-        // Build dependency graph
-        let mut dep_graph = aureacore::registry::DependencyGraph::new();
-
-        // Add all services to the graph
-        for service_name in self.services.keys() {
-            dep_graph.add_node(service_name.clone());
-        }
-
-        // Add dependencies as edges
-        for (service_name, service) in &self.services {
-            if let Some(dependencies) = &service.config.dependencies {
-                for dependency in dependencies {
-                    if self.services.contains_key(&dependency.service) {
-                        let metadata = aureacore::registry::EdgeMetadata {
-                            required: dependency.required,
-                            version_constraint: dependency.version_constraint.clone(),
-                        };
-                        dep_graph.add_edge(
-                            service_name.clone(),
-                            dependency.service.clone(),
-                            metadata,
-                        );
-                    }
-                }
-            }
-        }
-
-        // Check for circular dependencies
-        if let Some(cycle) = dep_graph.detect_cycles() {
-            summary.add_warning(
-                "system".to_string(),
-                format!("Circular dependency detected: {}", cycle.description),
-            );
-        }
-
-        // Validate dependencies
-        let mut services_with_errors = Vec::new();
-        let mut dependency_warnings = HashMap::new();
-
-        for (service_name, service) in &self.services {
-            let mut service_warnings = Vec::new();
-            let mut has_critical_error = false;
-            let mut error_message = String::new();
-
-            if let Some(dependencies) = &service.config.dependencies {
-                for dep in dependencies {
-                    let dep_name = &dep.service;
-
-                    // Check if dependency exists
-                    if let Some(dep_service) = self.services.get(dep_name) {
-                        // Skip version check if no constraint provided
-                        if let Some(version_constraint) = &dep.version_constraint {
-                            // Get version from schema data
-                            if let Some(schema) = &dep_service.schema_data {
-                                if let Some(version) =
-                                    schema.get("version").and_then(|v| v.as_str())
-                                {
-                                    // Create a validation service to check version compatibility
-                                    let validation_service =
-                                        aureacore::schema::validation::ValidationService::new();
-                                    let compatibility = validation_service
-                                        .check_version_compatibility(version, version_constraint);
-
-                                    match compatibility {
-                                        aureacore::schema::validation::VersionCompatibility::Compatible => {
-                                            // Compatible - no warning needed
-                                        },
-                                        aureacore::schema::validation::VersionCompatibility::MinorIncompatible => {
-                                            // Add a warning for minor incompatibility
-                                            service_warnings.push(format!(
-                                                "Minor version incompatibility for dependency '{}': expected {} but found {}",
-                                                dep_name, version_constraint, version
-                                            ));
-                                        },
-                                        aureacore::schema::validation::VersionCompatibility::MajorIncompatible => {
-                                            let msg = format!(
-                                                "Major version incompatibility for dependency '{}': expected {} but found {}",
-                                                dep_name, version_constraint, version
-                                            );
-                                            if dep.required {
-                                                // Critical error for required dependency
-                                                has_critical_error = true;
-                                                error_message = msg.clone();
-                                                summary.failed.push((service_name.clone(), msg));
-                                            } else {
-                                                // Warning for optional dependency
-                                                service_warnings.push(format!(
-                                                    "Optional dependency '{}' has incompatible version: {}",
-                                                    dep_name, msg
-                                                ));
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    service_warnings.push(format!(
-                                        "Dependency '{}' has missing or invalid version in schema",
-                                        dep_name
-                                    ));
-                                }
-                            }
-                        }
-                    } else {
-                        // Dependency not found
-                        if dep.required {
-                            let msg = format!("Required dependency '{}' not found", dep_name);
-                            has_critical_error = true;
-                            error_message = msg.clone();
-                            summary.failed.push((service_name.clone(), msg));
-                        } else {
-                            service_warnings
-                                .push(format!("Optional dependency '{}' not found", dep_name));
-                        }
-                    }
-                }
-            }
-
-            // Add warnings for this service if any
-            if !service_warnings.is_empty() {
-                dependency_warnings.insert(service_name.clone(), service_warnings);
-            }
-
-            // Collect services with critical errors for status updates
-            if has_critical_error {
-                services_with_errors.push((service_name.clone(), error_message));
-            } else {
-                // If no errors, mark as successful
-                summary.successful.push(service_name.clone());
-            }
-        }
-
-        // Update service statuses for services with errors
-        for (service_name, error_message) in &services_with_errors {
-            if let Some(service) = self.services.get_mut(service_name) {
-                service.status =
-                    ServiceStatus::new(ServiceState::Error).with_error(error_message.clone());
-            }
-        }
-
-        // Create a HashSet of service names with errors for quick lookups
-        let services_with_errors_set: std::collections::HashSet<String> =
-            services_with_errors.iter().map(|(name, _)| name.clone()).collect();
-
-        // Set remaining services as active
-        for (name, service) in &mut self.services {
-            if !services_with_errors_set.contains(name) {
-                service.status = ServiceStatus::new(ServiceState::Active);
-            }
-        }
-
-        // Merge dependency warnings into the summary
-        for (service_name, warnings) in dependency_warnings {
-            for warning in warnings {
-                summary.add_warning(service_name.clone(), warning);
-            }
-        }
-
-        Ok(summary)
-    }
-}
-
 /// Helper function to create a test service configuration
 fn create_test_config(
     name: &str,
@@ -245,8 +39,8 @@ fn create_test_config(
 
 #[test]
 fn test_registry_dependency_validation() {
-    // Create a mock registry
-    let mut registry = MockRegistry::new();
+    // Create an in-memory registry
+    let mut registry = InMemoryRegistry::new();
 
     // Register services with dependencies
     let service_a = create_test_config(
@@ -257,11 +51,13 @@ fn test_registry_dependency_validation() {
                 service: "service-b".to_string(),
                 version_constraint: Some("1.0.0".to_string()),
                 required: true,
+                ..Default::default()
             },
             Dependency {
                 service: "service-c".to_string(),
                 version_constraint: Some("1.0.0".to_string()),
                 required: false,
+                ..Default::default()
             },
         ]),
     );
@@ -312,8 +108,8 @@ fn test_registry_dependency_validation() {
 
 #[test]
 fn test_registry_missing_required_dependency() {
-    // Create a mock registry
-    let mut registry = MockRegistry::new();
+    // Create an in-memory registry
+    let mut registry = InMemoryRegistry::new();
 
     // Register a service with a required dependency that doesn't exist
     let service_x = create_test_config(
@@ -323,6 +119,7 @@ fn test_registry_missing_required_dependency() {
             service: "missing-service".to_string(),
             version_constraint: Some("1.0.0".to_string()),
             required: true, // Required!
+            ..Default::default()
         }]),
     );
 
@@ -355,8 +152,8 @@ fn test_registry_missing_required_dependency() {
 
 #[test]
 fn test_registry_circular_dependencies() {
-    // Create a mock registry
-    let mut registry = MockRegistry::new();
+    // Create an in-memory registry
+    let mut registry = InMemoryRegistry::new();
 
     // Register services that form a circular dependency: X -> Y -> Z -> X
     let service_x = create_test_config(
@@ -366,6 +163,7 @@ fn test_registry_circular_dependencies() {
             service: "service-y".to_string(),
             version_constraint: Some("1.0.0".to_string()),
             required: true,
+            ..Default::default()
         }]),
     );
 
@@ -376,6 +174,7 @@ fn test_registry_circular_dependencies() {
             service: "service-z".to_string(),
             version_constraint: Some("1.0.0".to_string()),
             required: true,
+            ..Default::default()
         }]),
     );
 
@@ -386,6 +185,7 @@ fn test_registry_circular_dependencies() {
             service: "service-x".to_string(),
             version_constraint: Some("1.0.0".to_string()),
             required: true,
+            ..Default::default()
         }]),
     );
 